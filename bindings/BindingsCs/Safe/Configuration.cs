@@ -76,6 +76,19 @@ public class ConfigurationBuilder : IDisposable
         return this;
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.builder_with_equipment_visibility"/>
+    public ConfigurationBuilder WithEquipment(EquipmentConfig equipmentConfig, ObstructionTier visibility)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        lock (_lockMut)
+        {
+            Inner = Unsafe.NativeMethods.builder_with_equipment_visibility(Inner, equipmentConfig.InnerConfig,
+                visibility.ToNative());
+        }
+
+        return this;
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.builder_with_retract"/>
     public ConfigurationBuilder WithRetract(Id id, RetractConfig retractConfig,
         ResolverRetractConfig resolverRetractConfig)
@@ -90,6 +103,42 @@ public class ConfigurationBuilder : IDisposable
         return this;
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.builder_with_stage_home"/>
+    public ConfigurationBuilder WithStageHome(SixAxis home)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        lock (_lockMut)
+        {
+            Inner = Unsafe.NativeMethods.builder_with_stage_home(Inner, home.Inner);
+        }
+
+        return this;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.builder_with_retract_home"/>
+    public ConfigurationBuilder WithRetractHome(Id id, LinearState home)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        lock (_lockMut)
+        {
+            Inner = Unsafe.NativeMethods.builder_with_retract_home(Inner, id.Inner, home.Inner);
+        }
+
+        return this;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.builder_with_stage_contact"/>
+    public ConfigurationBuilder WithStageContact(StagePart part, ObstaclePart obstacle)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        lock (_lockMut)
+        {
+            Inner = Unsafe.NativeMethods.builder_with_stage_contact(Inner, part.ToNative(), obstacle.InnerPart);
+        }
+
+        return this;
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.builder_build"/>
     public Configuration Build()
     {
@@ -109,7 +158,11 @@ public class ConfigurationBuilder : IDisposable
                     Unsafe.ConfigBuilderResult.MissingChamber => throw new InvalidOperationException(
                         "Missing chamber config"),
                     Unsafe.ConfigBuilderResult.MissingStage => throw new InvalidOperationException(
-                        "Missing stage config")
+                        "Missing stage config"),
+                    Unsafe.ConfigBuilderResult.DuplicateRetractId => throw new InvalidOperationException(
+                        "Duplicate retract id"),
+                    Unsafe.ConfigBuilderResult.UnknownRetractId => throw new InvalidOperationException(
+                        "Home of an unknown retract id")
                 };
 #pragma warning restore
             }
@@ -0,0 +1,31 @@
+using BindingsCs.Safe.Types;
+
+namespace BindingsCs.Safe.Configurations;
+
+public class ObstaclePart
+{
+    internal readonly Unsafe.ObstaclePart InnerPart;
+
+    private ObstaclePart(Unsafe.ObstaclePart innerPart)
+    {
+        InnerPart = innerPart;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.obstacle_chamber"/>
+    public static ObstaclePart Chamber()
+    {
+        return new ObstaclePart(Unsafe.NativeMethods.obstacle_chamber());
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.obstacle_equipment"/>
+    public static ObstaclePart Equipment(int index)
+    {
+        return new ObstaclePart(Unsafe.NativeMethods.obstacle_equipment((nuint)index));
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.obstacle_retract"/>
+    public static ObstaclePart Retract(Id id)
+    {
+        return new ObstaclePart(Unsafe.NativeMethods.obstacle_retract(id.Inner));
+    }
+}
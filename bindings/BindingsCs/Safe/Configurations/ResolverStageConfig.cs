@@ -17,6 +17,18 @@ public class ResolverStageConfig
         return new ResolverStageConfig(Unsafe.NativeMethods.stage_linear_resolver(step.Inner));
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.stage_bidirectional_resolver"/>
+    public static ResolverStageConfig Bidirectional(SixAxis step, double connectDistance)
+    {
+        return new ResolverStageConfig(Unsafe.NativeMethods.stage_bidirectional_resolver(step.Inner, connectDistance));
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.stage_planar_resolver"/>
+    public static ResolverStageConfig Planar(SixAxis step, double connectDistance)
+    {
+        return new ResolverStageConfig(Unsafe.NativeMethods.stage_planar_resolver(step.Inner, connectDistance));
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.down_rotate_find_resolver"/>
     public static ResolverStageConfig DownRotateFind(Vector3 downPoint, SixAxis downStep, Vector3 moveSpeed,
         Vector3 sampleMin, Vector3 sampleMax, Vector3 sampleStep, Vector3 sampleEpsilon, Vector3 losStep,
@@ -1,3 +1,4 @@
+using System.Runtime.InteropServices;
 using BindingsCs.Safe.Configurations;
 using BindingsCs.Safe.Types;
 
@@ -30,6 +31,73 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_replay"/>
+    public static Microscope Replay(Configuration configuration, MicroscopeEventLog log)
+    {
+        lock (configuration)
+        {
+            unsafe
+            {
+                fixed (Unsafe.Configuration* innerPtr = &configuration.Inner)
+                fixed (Unsafe.MicroscopeEventLog* logPtr = &log.Inner)
+                {
+                    return new Microscope(Unsafe.NativeMethods.microscope_replay(innerPtr, logPtr));
+                }
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_with_config"/>
+    /// <param name="statesValid">Whether the resolvers were updated with the carried over states.</param>
+    public Microscope WithConfiguration(Configuration configuration, out bool statesValid)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        lock (configuration)
+        {
+            unsafe
+            {
+                fixed (Unsafe.Microscope* microscope = &_microscope)
+                fixed (Unsafe.Configuration* innerPtr = &configuration.Inner)
+                {
+                    var update = Unsafe.StateUpdateError.Ok;
+                    var rebuilt = new Microscope(Unsafe.NativeMethods.microscope_with_config(microscope, innerPtr,
+                        &update));
+                    statesValid = update == Unsafe.StateUpdateError.Ok;
+                    return rebuilt;
+                }
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_start_recording"/>
+    public void StartRecording()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                Unsafe.NativeMethods.microscope_start_recording(microscope);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_stop_recording"/>
+    public MicroscopeEventLog StopRecording()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return new MicroscopeEventLog(Unsafe.NativeMethods.microscope_stop_recording(microscope));
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_update_holder"/>
     public void UpdateHolder(HolderConfig holder)
     {
@@ -59,6 +127,49 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_set_sample_rotation"/>
+    public void SetSampleRotation(Vector3 rotation)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                Unsafe.NativeMethods.microscope_set_sample_rotation(microscope, &rotation.Inner);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_add_equipment"/>
+    public void AddEquipment(EquipmentConfig equipment)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.EquipmentConfig* equipmentConfig = &equipment.InnerConfig)
+            {
+                Unsafe.NativeMethods.microscope_add_equipment(microscope, equipmentConfig);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_remove_equipment"/>
+    public bool RemoveEquipment(int index)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return Unsafe.NativeMethods.microscope_remove_equipment(microscope, (nuint)index);
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_update_sample_height_map"/>
     public void UpdateSampleHeightMap(double[] heightMap, nuint sizeX, nuint sizeY, double realX, double realY)
     {
@@ -103,6 +214,82 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_stage_state"/>
+    public SixAxis StageState()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return new SixAxis(Unsafe.NativeMethods.microscope_stage_state(microscope));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_snapshot"/>
+    public MicroscopeState Snapshot()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return new MicroscopeState(Unsafe.NativeMethods.microscope_snapshot(microscope));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_retract_state"/>
+    public LinearState? RetractState(Id id)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var state = new Unsafe.CLinearState();
+                if (!Unsafe.NativeMethods.microscope_retract_state(microscope, id.Inner, &state))
+                    return null;
+                return new LinearState(state);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_snap_stage_to_grid"/>
+    public SixAxis SnapStageToGrid(SixAxis state)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return new SixAxis(Unsafe.NativeMethods.microscope_snap_stage_to_grid(microscope, &state.Inner));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_snap_retract_to_grid"/>
+    public LinearState? SnapRetractToGrid(Id id, LinearState state)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var snapped = new Unsafe.CLinearState();
+                if (!Unsafe.NativeMethods.microscope_snap_retract_to_grid(microscope, id.Inner, &state.Inner, &snapped))
+                    return null;
+                return new LinearState(snapped);
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_update_retract_state"/>
     public void UpdateRetractState(Id id, LinearState state)
     {
@@ -134,6 +321,51 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_home"/>
+    public void Home()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var result = Unsafe.NativeMethods.microscope_home(microscope);
+                ThrowIfStateUpdateError(result);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_prepare"/>
+    public bool Prepare()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var collision = new Unsafe.StaticCollision();
+                return Unsafe.NativeMethods.microscope_prepare(microscope, &collision);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_validate_static_configuration"/>
+    public bool ValidateStaticConfiguration()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var collision = new Unsafe.StaticCollision();
+                return Unsafe.NativeMethods.microscope_validate_static_configuration(microscope, &collision);
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_find_stage_path"/>
     public PathResult<SixAxis> FindStagePath(SixAxis target)
     {
@@ -149,6 +381,346 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_find_stage_path_cancellable"/>
+    public PathResult<SixAxis> FindStagePathCancellable(SixAxis target, CancelToken cancel)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CCancelToken* token = &cancel.Inner)
+            {
+                return PathResult<SixAxis>.FromNative(
+                    Unsafe.NativeMethods.microscope_find_stage_path_cancellable(microscope, &target.Inner, token));
+            }
+        }
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    private delegate void ProgressCallback(float progress);
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_find_stage_path_with_progress"/>
+    public PathResult<SixAxis> FindStagePathWithProgress(SixAxis target, CancelToken cancel, Action<float> progress)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        ProgressCallback callback = f => progress(f);
+        var callbackPtr = Marshal.GetFunctionPointerForDelegate(callback);
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CCancelToken* token = &cancel.Inner)
+            {
+                var result = Unsafe.NativeMethods.microscope_find_stage_path_with_progress(microscope, &target.Inner,
+                    token, (delegate* unmanaged[Cdecl]<float, void>)callbackPtr);
+                GC.KeepAlive(callback);
+                return PathResult<SixAxis>.FromNative(result);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_replan_from"/>
+    public PathResult<SixAxis> ReplanFrom(SixAxis current, SixAxis goal, SixAxis[] previous, CancelToken cancel)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = previous.Select(p => p.Inner).ToArray();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            fixed (Unsafe.CCancelToken* token = &cancel.Inner)
+            {
+                return PathResult<SixAxis>.FromNative(
+                    Unsafe.NativeMethods.microscope_replan_from(microscope, &current.Inner, &goal.Inner, nodesPtr,
+                        (nuint)nodes.Length, token));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_nudge_to_free"/>
+    public SixAxis? NudgeToFree(SixAxis state, double maxDistance)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var nudged = new Unsafe.CSixAxis();
+                if (!Unsafe.NativeMethods.microscope_nudge_to_free(microscope, &state.Inner, maxDistance, &nudged))
+                    return null;
+                return new SixAxis(nudged);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_separation_vector"/>
+    public Vector3? SeparationVector(SixAxis state)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var separation = new Unsafe.CVector3();
+                if (!Unsafe.NativeMethods.microscope_separation_vector(microscope, &state.Inner, &separation))
+                    return null;
+                return new Vector3(separation);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_approach_direction"/>
+    /// <returns>The first collision-free candidate, or null when every approach collides.</returns>
+    public Vector3? ApproachDirection(SixAxis target, Vector3[] candidates, double distance)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var directions = candidates.Select(c => c.Inner).ToArray();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CVector3* directionsPtr = directions)
+            {
+                var direction = new Unsafe.CVector3();
+                if (!Unsafe.NativeMethods.microscope_approach_direction(microscope, &target.Inner, directionsPtr,
+                        (nuint)directions.Length, distance, &direction))
+                    return null;
+                return new Vector3(direction);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_advance_toward"/>
+    public SixAxis? AdvanceToward(SixAxis from, SixAxis goal)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var reached = new Unsafe.CSixAxis();
+                if (!Unsafe.NativeMethods.microscope_advance_toward(microscope, &from.Inner, &goal.Inner, &reached))
+                    return null;
+                return new SixAxis(reached);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_estimate_difficulty"/>
+    public Difficulty EstimateDifficulty(SixAxis from, SixAxis to)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var difficulty = Unsafe.NativeMethods.microscope_estimate_difficulty(microscope, &from.Inner, &to.Inner);
+                return DifficultyExtension.FromNative(difficulty);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_nearest_valid_pose"/>
+    public SixAxis? NearestValidPose(SixAxis desired, SixAxis searchRadius)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var nearest = new Unsafe.CSixAxis();
+                if (!Unsafe.NativeMethods.microscope_nearest_valid_pose(microscope, &desired.Inner,
+                        &searchRadius.Inner, &nearest))
+                    return null;
+                return new SixAxis(nearest);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_rotate_to_free"/>
+    public SixAxis? RotateToFree(SixAxis state, Axis axis, double maxAngle)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var rotated = new Unsafe.CSixAxis();
+                if (!Unsafe.NativeMethods.microscope_rotate_to_free(microscope, &state.Inner, axis.ToNative(), maxAngle,
+                        &rotated))
+                    return null;
+                return new SixAxis(rotated);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_reachable_extents"/>
+    public (SixAxis Min, SixAxis Max) ReachableExtents()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var min = new Unsafe.CSixAxis();
+                var max = new Unsafe.CSixAxis();
+                Unsafe.NativeMethods.microscope_reachable_extents(microscope, &min, &max);
+                return (new SixAxis(min), new SixAxis(max));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_path_min_clearance"/>
+    public double PathMinClearance(SixAxis[] path)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = path.Select(p => p.Inner).ToArray();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            {
+                return Unsafe.NativeMethods.microscope_path_min_clearance(microscope, nodesPtr, (nuint)nodes.Length);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_validate_stage_path"/>
+    /// <returns>Index of the first colliding segment, or null when the whole path is free.</returns>
+    public int? ValidateStagePath(SixAxis[] path)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = path.Select(p => p.Inner).ToArray();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            {
+                nuint segment = 0;
+                if (Unsafe.NativeMethods.microscope_validate_stage_path(microscope, nodesPtr, (nuint)nodes.Length,
+                        &segment))
+                    return null;
+                return (int)segment;
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_swept_volume_bounds"/>
+    public (Vector3 Min, Vector3 Max)[] SweptVolumeBounds(SixAxis[] path)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = path.Select(p => p.Inner).ToArray();
+        var min = new Unsafe.CVector3[Math.Max(nodes.Length - 1, 0)];
+        var max = new Unsafe.CVector3[min.Length];
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            fixed (Unsafe.CVector3* minPtr = min)
+            fixed (Unsafe.CVector3* maxPtr = max)
+            {
+                var written = Unsafe.NativeMethods.microscope_swept_volume_bounds(microscope, nodesPtr,
+                    (nuint)nodes.Length, minPtr, maxPtr);
+                return Enumerable.Range(0, (int)written)
+                    .Select(i => (new Vector3(min[i]), new Vector3(max[i])))
+                    .ToArray();
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_moving_pair_collides"/>
+    public bool? MovingPairCollides(SixAxis stageFrom, SixAxis stageTo, Id retractId, LinearState retractFrom,
+        LinearState retractTo)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var collides = false;
+                if (!Unsafe.NativeMethods.microscope_moving_pair_collides(microscope, &stageFrom.Inner,
+                        &stageTo.Inner, retractId.Inner, &retractFrom.Inner, &retractTo.Inner, &collides))
+                    return null;
+                return collides;
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_order_targets"/>
+    /// <returns>Indices into <paramref name="targets"/> in the order to visit them.</returns>
+    public int[] OrderTargets(SixAxis start, SixAxis[] targets, SixAxis speed)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = targets.Select(t => t.Inner).ToArray();
+        var order = new nuint[nodes.Length];
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            fixed (nuint* orderPtr = order)
+            {
+                Unsafe.NativeMethods.microscope_order_targets(microscope, &start.Inner, nodesPtr,
+                    (nuint)nodes.Length, &speed.Inner, orderPtr);
+            }
+        }
+
+        return order.Select(i => (int)i).ToArray();
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_free_fraction"/>
+    public double? FreeFraction(SixAxis grid, SixAxis min, SixAxis max)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var fraction = 0.0;
+                if (!Unsafe.NativeMethods.microscope_free_fraction(microscope, &grid.Inner, &min.Inner,
+                        &max.Inner, &fraction))
+                    return null;
+                return fraction;
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_filter_valid_poses"/>
+    /// <returns><c>true</c> at the index of each collision-free candidate.</returns>
+    public bool[] FilterValidPoses(SixAxis[] candidates)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockMut();
+        var nodes = candidates.Select(c => c.Inner).ToArray();
+        var valid = new bool[nodes.Length];
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CSixAxis* nodesPtr = nodes)
+            fixed (bool* validPtr = valid)
+            {
+                Unsafe.NativeMethods.microscope_filter_valid_poses(microscope, nodesPtr, (nuint)nodes.Length,
+                    validPtr);
+            }
+        }
+
+        return valid;
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_find_retract_path"/>
     public PathResult<LinearState> FindRetractPath(Id id, LinearState target)
     {
@@ -164,6 +736,20 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_suggest_step_size"/>
+    public SixAxis SuggestStepSize()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return new SixAxis(Unsafe.NativeMethods.microscope_suggest_step_size(microscope));
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_full"/>
     public List<TriangleBuffer> PresentStaticFull()
     {
@@ -178,6 +764,90 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_full_chunk_count"/>
+    public int PresentStaticFullChunkCount(int chunkSize)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return (int)Unsafe.NativeMethods.microscope_present_static_full_chunk_count(microscope,
+                    (nuint)chunkSize);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_full_chunk"/>
+    public Vector3[] PresentStaticFullChunk(int chunkIndex, int chunkSize)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        var vertices = new Unsafe.CVector3[chunkSize * 3];
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            fixed (Unsafe.CVector3* verticesPtr = vertices)
+            {
+                var triangles = (int)Unsafe.NativeMethods.microscope_present_static_full_chunk(microscope,
+                    (nuint)chunkIndex, (nuint)chunkSize, verticesPtr);
+                return vertices.Take(triangles * 3).Select(v => new Vector3(v)).ToArray();
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_boxes"/>
+    public List<(Vector3 Min, Vector3 Max)> PresentStaticBoxes()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var buffer = Unsafe.NativeMethods.microscope_present_static_boxes(microscope);
+                var list = new List<(Vector3 Min, Vector3 Max)>();
+                for (uint i = 0; i + 1 < buffer.len; i += 2)
+                    list.Add((new Vector3(buffer.data[i]), new Vector3(buffer.data[i + 1])));
+
+                Unsafe.NativeMethods.boundingboxbuffer_drop(buffer);
+                return list;
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_full_rotated"/>
+    public List<TriangleBuffer> PresentStaticFullRotated(SixAxis rotation, Vector3 pivot)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return TriangleBuffer.FromNativeVec(
+                    Unsafe.NativeMethods.microscope_present_static_full_rotated(microscope, &rotation.Inner,
+                        &pivot.Inner));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_full_with_normals"/>
+    public List<InterleavedBuffer> PresentStaticFullWithNormals()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                return InterleavedBuffer.FromNativeVec(
+                    Unsafe.NativeMethods.microscope_present_static_full_with_normals(microscope));
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_static_less_obstructive"/>
     public List<TriangleBuffer> PresentStaticLessObstructive()
     {
@@ -237,6 +907,58 @@ public class Microscope : IDisposable
         }
     }
 
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_stage_bounds_at"/>
+    public (Vector3 Min, Vector3 Max) StageBoundsAt(SixAxis state)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var min = new Unsafe.CVector3();
+                var max = new Unsafe.CVector3();
+                Unsafe.NativeMethods.microscope_stage_bounds_at(microscope, &state.Inner, &min, &max);
+                return (new Vector3(min), new Vector3(max));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_dynamic_bounds"/>
+    public (Vector3 Min, Vector3 Max) DynamicBounds()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var min = new Unsafe.CVector3();
+                var max = new Unsafe.CVector3();
+                Unsafe.NativeMethods.microscope_dynamic_bounds(microscope, &min, &max);
+                return (new Vector3(min), new Vector3(max));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_stage_principal_axes"/>
+    public (Vector3 First, Vector3 Second, Vector3 Third) StagePrincipalAxes()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        using var guard = _lock.LockRef();
+        unsafe
+        {
+            fixed (Unsafe.Microscope* microscope = &_microscope)
+            {
+                var first = new Unsafe.CVector3();
+                var second = new Unsafe.CVector3();
+                var third = new Unsafe.CVector3();
+                Unsafe.NativeMethods.microscope_stage_principal_axes(microscope, &first, &second, &third);
+                return (new Vector3(first), new Vector3(second), new Vector3(third));
+            }
+        }
+    }
+
     /// <inheritdoc cref="Unsafe.NativeMethods.microscope_present_retract"/>
     public List<TriangleBuffer> PresentRetract(Id id)
     {
@@ -0,0 +1,37 @@
+namespace BindingsCs.Safe;
+
+public class MicroscopeEventLog : IDisposable
+{
+    internal readonly Unsafe.MicroscopeEventLog Inner;
+
+    private bool _disposed;
+
+    internal MicroscopeEventLog(Unsafe.MicroscopeEventLog inner)
+    {
+        Inner = inner;
+        _disposed = false;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_event_log_len"/>
+    public int Count()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        unsafe
+        {
+            fixed (Unsafe.MicroscopeEventLog* log = &Inner)
+            {
+                return (int)Unsafe.NativeMethods.microscope_event_log_len(log);
+            }
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        lock (this)
+        {
+            Unsafe.NativeMethods.microscope_event_log_drop(Inner);
+            _disposed = true;
+        }
+    }
+}
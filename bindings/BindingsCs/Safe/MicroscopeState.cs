@@ -0,0 +1,70 @@
+using BindingsCs.Safe.Types;
+
+namespace BindingsCs.Safe;
+
+public class MicroscopeState : IDisposable
+{
+    internal readonly Unsafe.MicroscopeState Inner;
+
+    private bool _disposed;
+
+    internal MicroscopeState(Unsafe.MicroscopeState inner)
+    {
+        Inner = inner;
+        _disposed = false;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_state_stage"/>
+    public SixAxis Stage()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        unsafe
+        {
+            fixed (Unsafe.MicroscopeState* state = &Inner)
+            {
+                return new SixAxis(Unsafe.NativeMethods.microscope_state_stage(state));
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_state_retract"/>
+    public LinearState? Retract(Id id)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        unsafe
+        {
+            fixed (Unsafe.MicroscopeState* snapshot = &Inner)
+            {
+                var state = new Unsafe.CLinearState();
+                if (!Unsafe.NativeMethods.microscope_state_retract(snapshot, id.Inner, &state))
+                    return null;
+                return new LinearState(state);
+            }
+        }
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.microscope_state_lerp"/>
+    public static MicroscopeState Lerp(MicroscopeState a, MicroscopeState b, double t)
+    {
+        ObjectDisposedException.ThrowIf(a._disposed, a);
+        ObjectDisposedException.ThrowIf(b._disposed, b);
+        unsafe
+        {
+            fixed (Unsafe.MicroscopeState* from = &a.Inner)
+            fixed (Unsafe.MicroscopeState* to = &b.Inner)
+            {
+                return new MicroscopeState(Unsafe.NativeMethods.microscope_state_lerp(from, to, t));
+            }
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        lock (this)
+        {
+            Unsafe.NativeMethods.microscope_state_drop(Inner);
+            _disposed = true;
+        }
+    }
+}
@@ -0,0 +1,24 @@
+namespace BindingsCs.Safe.Types;
+
+/// <inheritdoc cref="Unsafe.CAxis"/>
+public enum Axis
+{
+    X,
+    Y,
+    Z
+}
+
+internal static class AxisExtension
+{
+    internal static Unsafe.CAxis ToNative(this Axis axis)
+    {
+#pragma warning disable CS8524
+        return axis switch
+        {
+            Axis.X => Unsafe.CAxis.X,
+            Axis.Y => Unsafe.CAxis.Y,
+            Axis.Z => Unsafe.CAxis.Z
+        };
+#pragma warning restore
+    }
+}
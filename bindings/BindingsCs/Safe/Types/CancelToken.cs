@@ -0,0 +1,38 @@
+namespace BindingsCs.Safe.Types;
+
+public class CancelToken : IDisposable
+{
+    internal Unsafe.CCancelToken Inner;
+
+    private bool _disposed;
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.ccanceltoken_new"/>
+    public CancelToken()
+    {
+        Inner = Unsafe.NativeMethods.ccanceltoken_new();
+        _disposed = false;
+    }
+
+    /// <inheritdoc cref="Unsafe.NativeMethods.ccanceltoken_cancel"/>
+    public void Cancel()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        unsafe
+        {
+            fixed (Unsafe.CCancelToken* token = &Inner)
+            {
+                Unsafe.NativeMethods.ccanceltoken_cancel(token);
+            }
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        lock (this)
+        {
+            Unsafe.NativeMethods.ccanceltoken_drop(Inner);
+            _disposed = true;
+        }
+    }
+}
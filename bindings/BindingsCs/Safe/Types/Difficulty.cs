@@ -0,0 +1,24 @@
+namespace BindingsCs.Safe.Types;
+
+/// <inheritdoc cref="Unsafe.Difficulty"/>
+public enum Difficulty
+{
+    Trivial,
+    Moderate,
+    Hard
+}
+
+internal static class DifficultyExtension
+{
+    internal static Difficulty FromNative(Unsafe.Difficulty difficulty)
+    {
+#pragma warning disable CS8524
+        return difficulty switch
+        {
+            Unsafe.Difficulty.Trivial => Difficulty.Trivial,
+            Unsafe.Difficulty.Moderate => Difficulty.Moderate,
+            Unsafe.Difficulty.Hard => Difficulty.Hard
+        };
+#pragma warning restore
+    }
+}
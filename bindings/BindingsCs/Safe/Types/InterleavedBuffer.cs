@@ -0,0 +1,33 @@
+namespace BindingsCs.Safe.Types;
+
+public class InterleavedBuffer
+{
+    public float[] Buffer { get; }
+
+    public InterleavedBuffer(float[] buffer)
+    {
+        Buffer = buffer;
+    }
+
+    internal static List<InterleavedBuffer> FromNativeVec(Unsafe.InterleavedBufferVec vec)
+    {
+        var list = new List<InterleavedBuffer>();
+        unsafe
+        {
+            for (uint i = 0; i < vec.len; i++) list.Add(new InterleavedBuffer(vec.data[i]));
+        }
+
+        return list;
+    }
+
+    internal InterleavedBuffer(Unsafe.InterleavedBuffer nativeBuffer)
+    {
+        Buffer = new float[nativeBuffer.len];
+        unsafe
+        {
+            for (uint i = 0; i < nativeBuffer.len; i++) Buffer[i] = nativeBuffer.data[i];
+        }
+
+        Unsafe.NativeMethods.interleavedbuffer_drop(nativeBuffer);
+    }
+}
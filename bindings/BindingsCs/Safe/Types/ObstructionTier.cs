@@ -0,0 +1,24 @@
+namespace BindingsCs.Safe.Types;
+
+/// <inheritdoc cref="Unsafe.ObstructionTier"/>
+public enum ObstructionTier
+{
+    Full,
+    LessObstructive,
+    NonObstructive
+}
+
+internal static class ObstructionTierExtension
+{
+    internal static Unsafe.ObstructionTier ToNative(this ObstructionTier tier)
+    {
+#pragma warning disable CS8524
+        return tier switch
+        {
+            ObstructionTier.Full => Unsafe.ObstructionTier.Full,
+            ObstructionTier.LessObstructive => Unsafe.ObstructionTier.LessObstructive,
+            ObstructionTier.NonObstructive => Unsafe.ObstructionTier.NonObstructive
+        };
+#pragma warning restore
+    }
+}
@@ -4,7 +4,9 @@ public enum PathResultState
 {
     Success,
     InvalidStart,
-    UnreachableEnd
+    UnreachableEnd,
+    Cancelled,
+    InvalidId
 }
 
 internal static class PathResultExtension
@@ -16,7 +18,9 @@ internal static class PathResultExtension
         {
             Unsafe.PathResultState.Path => PathResultState.Success,
             Unsafe.PathResultState.InvalidStart => PathResultState.InvalidStart,
-            Unsafe.PathResultState.UnreachableEnd => PathResultState.UnreachableEnd
+            Unsafe.PathResultState.UnreachableEnd => PathResultState.UnreachableEnd,
+            Unsafe.PathResultState.Cancelled => PathResultState.Cancelled,
+            Unsafe.PathResultState.InvalidId => PathResultState.InvalidId
         };
 #pragma warning restore
     }
@@ -0,0 +1,24 @@
+namespace BindingsCs.Safe.Types;
+
+/// <inheritdoc cref="Unsafe.StagePart"/>
+public enum StagePart
+{
+    Stage,
+    Holder,
+    Sample
+}
+
+internal static class StagePartExtension
+{
+    internal static Unsafe.StagePart ToNative(this StagePart part)
+    {
+#pragma warning disable CS8524
+        return part switch
+        {
+            StagePart.Stage => Unsafe.StagePart.Stage,
+            StagePart.Holder => Unsafe.StagePart.Holder,
+            StagePart.Sample => Unsafe.StagePart.Sample
+        };
+#pragma warning restore
+    }
+}
@@ -3,6 +3,8 @@
 
 mod bounded;
 mod collides;
+mod contact;
+mod distance;
 mod projectable;
 mod rotation;
 mod transform;
@@ -11,6 +13,8 @@ mod treeable;
 
 pub use bounded::Bounded;
 pub use collides::Collides;
+pub use contact::Contact;
+pub use distance::Distance;
 pub use projectable::Projectable;
 pub use rotation::Rotation;
 pub use transform::Transformation;
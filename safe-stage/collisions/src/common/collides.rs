@@ -4,3 +4,145 @@ pub trait Collides<T> {
     /// Returns true if the collider collides with the other collider.
     fn collides_with(&self, other: &T) -> bool;
 }
+
+/// Implements [Collides] in the reverse direction for each `implemented => reversed` pair,
+/// delegating to the `impl Collides<reversed> for implemented`,
+/// so the two directions can never diverge.
+///
+/// ## Example
+/// ```
+/// use collisions::collides_reverse_impl;
+/// use collisions::common::Collides;
+///
+/// struct Big;
+/// struct Small;
+///
+/// impl Collides<Small> for Big {
+///     fn collides_with(&self, _: &Small) -> bool {
+///         true
+///     }
+/// }
+///
+/// collides_reverse_impl!(Big => Small);
+///
+/// assert!(Small.collides_with(&Big));
+/// ```
+#[macro_export]
+macro_rules! collides_reverse_impl {
+    ($($t1:ty => $t2:ty),* $(,)?) => ($(
+        impl $crate::common::Collides<$t1> for $t2 {
+            #[inline]
+            fn collides_with(&self, other: &$t1) -> bool {
+                other.collides_with(self)
+            }
+        }
+    )*)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::{
+        AlignedBoxCollider, Collider, ConvexHullCollider, OrientedBoxCollider, PointCollider,
+        SphereCollider, TriangleCollider,
+    };
+    use maths::{Quaternion, Vector3};
+
+    fn offsets() -> Vec<Vector3> {
+        let steps = [-1.5, -0.6, 0.0, 0.4, 1.2];
+        steps
+            .iter()
+            .flat_map(|&x| {
+                steps
+                    .iter()
+                    .map(move |&y| Vector3::new(x, y, 0.3 * x - 0.2 * y))
+            })
+            .collect()
+    }
+
+    fn colliders() -> Vec<Collider> {
+        let rotation = Quaternion::from_euler(&Vector3::new(0.3, 0.5, 0.7));
+        offsets()
+            .into_iter()
+            .flat_map(|o| {
+                [
+                    Collider::Point(PointCollider::new(o)),
+                    Collider::Sphere(SphereCollider::new(o, 0.5)),
+                    Collider::AlignedBox(AlignedBoxCollider::new(o, Vector3::new(1.0, 0.6, 0.8))),
+                    Collider::OrientedBox(OrientedBoxCollider::new(
+                        o,
+                        Vector3::new(1.0, 0.6, 0.8),
+                        rotation,
+                    )),
+                ]
+            })
+            .collect()
+    }
+
+    fn triangles() -> Vec<TriangleCollider> {
+        offsets()
+            .into_iter()
+            .map(|o| {
+                TriangleCollider::new(
+                    o + Vector3::new(-0.5, 0.0, 0.0),
+                    o + Vector3::new(0.5, -0.2, 0.3),
+                    o + Vector3::new(0.0, 0.6, -0.4),
+                )
+            })
+            .collect()
+    }
+
+    fn hulls() -> Vec<ConvexHullCollider> {
+        offsets()
+            .into_iter()
+            .map(|o| {
+                ConvexHullCollider::new(vec![
+                    o + Vector3::new(0.0, 0.0, 0.5),
+                    o + Vector3::new(0.4, 0.0, -0.3),
+                    o + Vector3::new(-0.4, 0.3, -0.3),
+                    o + Vector3::new(-0.4, -0.3, -0.3),
+                ])
+            })
+            .collect()
+    }
+
+    /// Checks both directions agree for every pair, returns how many pairs collide.
+    fn assert_symmetric<A: Collides<B>, B: Collides<A>>(a: &[A], b: &[B]) -> usize {
+        let mut colliding = 0;
+        for (i, a) in a.iter().enumerate() {
+            for (j, b) in b.iter().enumerate() {
+                let forward = a.collides_with(b);
+                assert_eq!(forward, b.collides_with(a), "pair {i}, {j}");
+                colliding += forward as usize;
+            }
+        }
+        colliding
+    }
+
+    #[test]
+    fn collision_is_symmetric_for_all_primitive_pairs() {
+        let colliders = colliders();
+        let triangles = triangles();
+        let hulls = hulls();
+        let oriented = colliders
+            .iter()
+            .filter_map(|c| match c {
+                Collider::OrientedBox(b) => Some(b.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let counts = [
+            assert_symmetric(&colliders, &colliders),
+            assert_symmetric(&oriented, &triangles),
+            assert_symmetric(&triangles, &triangles),
+            assert_symmetric(&triangles, &hulls),
+            assert_symmetric(&hulls, &hulls),
+        ];
+
+        for count in counts {
+            assert!(count > 0);
+        }
+        assert!(counts[0] < colliders.len() * colliders.len());
+    }
+}
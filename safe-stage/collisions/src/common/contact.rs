@@ -0,0 +1,11 @@
+/// # Contact classification
+/// Finer result of a collision check than a plain `bool`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Contact {
+    /// Colliders are apart by `distance`.
+    Clear { distance: f64 },
+    /// Colliders share only a boundary.
+    Touching,
+    /// Colliders overlap, `depth` is the smallest translation separating them.
+    Penetrating { depth: f64 },
+}
@@ -0,0 +1,7 @@
+/// # Object distance
+/// Separation distance between colliders, used for continuous collision detection.
+pub trait Distance<T> {
+    /// Returns the smallest distance between the collider and the other collider,
+    /// or `0.0` if they collide.
+    fn distance_to(&self, other: &T) -> f64;
+}
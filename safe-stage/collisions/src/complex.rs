@@ -6,6 +6,8 @@
 //!  - [BvhRecursive] - generic bounding volume hierarchy, recommended to use with [AlignedBoxCollider]
 //!  - [BvhSphereLinear] - bounding sphere hierarchy, implemented with Vec as node storage, very fast transformations slower collision detection
 //!  - [BvhSphereRecursive] - bounding sphere hierarchy, variant of [BvhRecursive] but optimized for [SphereCollider] as the bounding shape
+//!  - [HybridCollider] - either a box proxy or a [BvhRecursive] mesh, for mixing both in one caller-built group
+//!  - [VoxelGrid] - occupied cells of a triangle mesh, fast boolean collision, conservative near the surface
 
 use crate::collides_group_impl;
 use crate::complex::bvh_recursive::BvhRecursive;
@@ -15,8 +17,14 @@ pub mod bvh;
 pub mod bvh_recursive;
 pub mod bvh_sphere_linear;
 pub mod bvh_sphere_recursive;
+pub mod convex_decomposition;
 pub mod group;
+pub mod hybrid;
+pub mod morton;
+pub mod self_intersection;
+pub mod spatial_hash;
 pub(crate) mod tree;
+pub mod voxel;
 
 collides_group_impl!(
     BvhRecursive<AlignedBoxCollider>,
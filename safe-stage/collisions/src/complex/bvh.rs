@@ -1,8 +1,8 @@
 use crate::collides_group_impl;
 use crate::common::{Bounded, Collides, Rotation, Transformation, Translation};
-use crate::primitive::Collider;
-use itertools::Itertools;
-use maths::{Axis, Quaternion, Vector3};
+use crate::complex::morton::morton_sorted_indices;
+use crate::primitive::{AlignedBoxCollider, Collider};
+use maths::{bounds_of, dominant_axis, Axis, Quaternion, Vector3};
 
 /// # Bounding Volume Hierarchy
 /// A tree structure composed of primitive colliders
@@ -42,6 +42,42 @@ impl BvhTree {
         ))
     }
 
+    /// Builds the BVH tree from a list of colliders ordered along the Morton curve,
+    /// see [morton_sorted_indices].
+    ///
+    /// Splits the ordered list in halves instead of sorting every level by the longest axis.
+    pub fn build_morton(objects: &[Collider]) -> Option<BvhTree> {
+        let boxes = objects
+            .iter()
+            .map(|c| AlignedBoxCollider::from_min_max(c.min(), c.max()))
+            .collect::<Vec<_>>();
+        let ordered = morton_sorted_indices(&boxes)
+            .into_iter()
+            .map(|i| objects[i].clone())
+            .collect::<Vec<_>>();
+        Self::build_ordered(&ordered)
+    }
+
+    fn build_ordered(objects: &[Collider]) -> Option<BvhTree> {
+        if objects.is_empty() {
+            return None;
+        }
+
+        if objects.len() == 1 {
+            return Some(BvhTree::Leaf(objects[0].clone()));
+        }
+
+        let half = objects.len() / 2;
+        let left = Self::build_ordered(&objects[..half]);
+        let right = Self::build_ordered(&objects[half..]);
+
+        Some(BvhTree::Branch(
+            Self::colliders_to_bounding(objects),
+            left.map(Box::new),
+            right.map(Box::new),
+        ))
+    }
+
     /// Concatenates two BVH trees into a new one.
     pub fn concat(self, other: BvhTree) -> BvhTree {
         let first = match &self {
@@ -58,22 +94,46 @@ impl BvhTree {
         Self::Branch(bound, Some(Box::new(self)), Some(Box::new(other)))
     }
 
-    fn longest_axis(colliders: &[Collider]) -> Axis {
-        let (min, max) = colliders
+    /// Returns the depth of the tree, a single leaf has depth `0`.
+    pub fn depth(&self) -> usize {
+        self.leaf_depths().1
+    }
+
+    /// Returns the ratio of the shallowest to the deepest leaf depth,
+    /// `1.0` for a perfectly balanced tree and a single leaf.
+    ///
+    /// Useful for comparing the quality of [BvhTree::build] and [BvhTree::build_morton].
+    pub fn balance_factor(&self) -> f64 {
+        match self.leaf_depths() {
+            (_, 0) => 1.0,
+            (min, max) => min as f64 / max as f64,
+        }
+    }
+
+    /// Returns the depths of the shallowest and the deepest leaf.
+    fn leaf_depths(&self) -> (usize, usize) {
+        let Self::Branch(_, left, right) = self else {
+            return (0, 0);
+        };
+        let children = [left, right]
+            .into_iter()
+            .flatten()
+            .map(|child| child.leaf_depths())
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)));
+        match children {
+            Some((min, max)) => (min + 1, max + 1),
+            None => (0, 0),
+        }
+    }
+
+    pub(super) fn longest_axis(colliders: &[Collider]) -> Axis {
+        let points = colliders
             .iter()
             .map(Self::collider_to_point)
-            .minmax_by(|a, b| a.partial_cmp(b).unwrap())
-            .into_option()
-            .unwrap();
+            .collect::<Vec<_>>();
+        let (min, max) = bounds_of(&points).unwrap();
 
-        let diff = max - min;
-        if diff.x() >= diff.y() && diff.x() >= diff.z() {
-            Axis::X
-        } else if diff.y() >= diff.x() && diff.y() >= diff.z() {
-            Axis::Y
-        } else {
-            Axis::Z
-        }
+        dominant_axis(max - min)
     }
 
     fn order_by_axis(colliders: &[Collider], axis: Axis) -> Vec<Collider> {
@@ -82,7 +142,7 @@ impl BvhTree {
             .map(|c| (c, Self::collider_to_point(c).get(axis)))
             .collect::<Vec<(&Collider, f64)>>();
 
-        ordered.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        ordered.sort_by(|a, b| a.1.total_cmp(&b.1));
 
         ordered
             .into_iter()
@@ -231,9 +291,20 @@ mod tests {
         Collider::point(x, y, z)
     }
 
+    #[test]
+    fn longest_axis_uses_spatial_extent() {
+        let objects = [
+            cpoint(0.0, 0.0, 0.0),
+            cpoint(0.0, 10.0, 0.0),
+            cpoint(1.0, 0.0, 0.0),
+        ];
+
+        assert_eq!(Axis::Y, BvhTree::longest_axis(&objects));
+    }
+
     #[test]
     fn build_bhv_correct() {
-        let objects = vec![
+        let objects = [
             cpoint(1.0, 1.0, 1.0),
             cpoint(2.0, 2.0, 2.0),
             cpoint(-3.0, 3.0, 3.0),
@@ -253,8 +324,37 @@ mod tests {
                 ))),
                 Some(Box::new(BvhTree::Branch(
                     cbox(3.0, -1.0, 3.0, 2.0, 6.0, 2.0),
-                    Some(Box::new(BvhTree::Leaf(cpoint(2.0, 2.0, 2.0)))),
                     Some(Box::new(BvhTree::Leaf(cpoint(4.0, -4.0, 4.0)))),
+                    Some(Box::new(BvhTree::Leaf(cpoint(2.0, 2.0, 2.0)))),
+                ))),
+            ),
+            tree.unwrap()
+        );
+    }
+
+    #[test]
+    fn build_morton_groups_neighbors() {
+        let objects = [
+            cpoint(0.0, 0.0, 0.0),
+            cpoint(5.0, 0.0, 0.0),
+            cpoint(1.0, 0.0, 0.0),
+            cpoint(6.0, 0.0, 0.0),
+        ];
+
+        let tree = BvhTree::build_morton(&objects[..]);
+
+        assert_eq!(
+            BvhTree::Branch(
+                cbox(3.0, 0.0, 0.0, 6.0, 0.0, 0.0),
+                Some(Box::new(BvhTree::Branch(
+                    cbox(0.5, 0.0, 0.0, 1.0, 0.0, 0.0),
+                    Some(Box::new(BvhTree::Leaf(cpoint(0.0, 0.0, 0.0)))),
+                    Some(Box::new(BvhTree::Leaf(cpoint(1.0, 0.0, 0.0)))),
+                ))),
+                Some(Box::new(BvhTree::Branch(
+                    cbox(5.5, 0.0, 0.0, 1.0, 0.0, 0.0),
+                    Some(Box::new(BvhTree::Leaf(cpoint(5.0, 0.0, 0.0)))),
+                    Some(Box::new(BvhTree::Leaf(cpoint(6.0, 0.0, 0.0)))),
                 ))),
             ),
             tree.unwrap()
@@ -263,7 +363,7 @@ mod tests {
 
     #[test]
     fn build_bhv_complex() {
-        let objects = vec![
+        let objects = [
             cbox(0.0, 0.0, 0.0, 1.0, 1.0, 1.0),
             cbox(2.0, 0.0, 0.0, 1.0, 1.0, 1.0),
             cbox(0.0, 2.0, 0.0, 1.0, 1.0, 1.0),
@@ -313,7 +413,7 @@ mod tests {
 
     #[test]
     fn longest_axis() {
-        let objects = vec![
+        let objects = [
             cpoint(1.0, 1.0, 1.0),
             cpoint(2.0, 2.0, 2.0),
             cpoint(3.0, 3.0, 3.0),
@@ -325,7 +425,7 @@ mod tests {
 
     #[test]
     fn order_by_axis() {
-        let objects = vec![
+        let objects = [
             cpoint(1.0, 1.0, 1.0),
             cpoint(2.0, 2.0, 2.0),
             cpoint(3.0, 3.0, 3.0),
@@ -350,7 +450,7 @@ mod tests {
 
     #[test]
     fn colliders_to_bounding() {
-        let colliders = vec![
+        let colliders = [
             cbox(1.0, 1.0, 1.0, 2.0, 2.0, 2.0),
             cbox(2.0, 2.0, 2.0, 3.0, 3.0, 3.0),
             cbox(3.0, 3.0, 3.0, 4.0, 4.0, 4.0),
@@ -561,4 +661,23 @@ mod tests {
             _ => panic!("Expected branch"),
         }
     }
+
+    #[test]
+    fn depth_and_balance_of_built_trees() {
+        let objects = (0..4)
+            .map(|i| cbox(i as f64 * 2.0, 0.0, 0.0, 1.0, 1.0, 1.0))
+            .collect::<Vec<_>>();
+
+        let balanced = BvhTree::build(&objects).unwrap();
+        assert_eq!(2, balanced.depth());
+        assert_eq!(1.0, balanced.balance_factor());
+
+        let uneven = BvhTree::build_morton(&objects[..3]).unwrap();
+        assert_eq!(2, uneven.depth());
+        assert_eq!(0.5, uneven.balance_factor());
+
+        let leaf = BvhTree::Leaf(cpoint(0.0, 0.0, 0.0));
+        assert_eq!(0, leaf.depth());
+        assert_eq!(1.0, leaf.balance_factor());
+    }
 }
@@ -1,8 +1,14 @@
-use crate::common::{Bounded, Collides, Rotation, Transformation, Translation, Treeable};
+use crate::common::{Bounded, Collides, Distance, Rotation, Transformation, Translation, Treeable};
 use crate::complex::tree::RecursiveTree;
-use crate::primitive::TriangleCollider;
+use crate::primitive::algo::distance::closest_point_on_triangle;
+use crate::primitive::algo::obb::fit_obb;
+use crate::primitive::algo::ray::{ray_box, ray_triangle};
+use crate::primitive::algo::sphere::ritter_sphere;
+use crate::primitive::{
+    AlignedBoxCollider, ConvexHullCollider, OrientedBoxCollider, TriangleCollider,
+};
 use itertools::Itertools;
-use maths::{Axis, Quaternion, Vector3};
+use maths::{bounds_of, dominant_axis, Axis, Quaternion, Vector3};
 use std::sync::Arc;
 
 type Tree<T> = RecursiveTree<T, TriangleCollider>;
@@ -48,10 +54,134 @@ where
         BvhRecursive(Arc::new(node))
     }
 
+    /// Appends triangles by building a BVH of only them and concatenating it,
+    /// the existing tree is not rebuilt.
+    pub fn append_triangles(self, triangles: &[TriangleCollider]) -> BvhRecursive<T> {
+        if triangles.is_empty() {
+            return self;
+        }
+
+        self.concat(BvhRecursive::build(triangles))
+    }
+
     /// Returns a list of triangles in the BVH.
     pub fn triangle_buffer(&self) -> Vec<Vector3> {
         node_triangles(&self.0)
     }
+
+    /// Returns the number of triangles in the BVH.
+    pub fn triangle_count(&self) -> usize {
+        node_triangle_count(&self.0)
+    }
+
+    /// Returns up to `count` triangles starting at the `start` triangle,
+    /// in the same order as [BvhRecursive::triangle_buffer].
+    pub fn triangle_range(&self, start: usize, count: usize) -> Vec<Vector3> {
+        let mut out = Vec::with_capacity(count.min(self.triangle_count()) * 3);
+        let mut skip = start;
+        node_triangle_range(&self.0, &mut skip, count, &mut out);
+        out
+    }
+
+    /// Returns the center and radius of a sphere around all the triangles
+    /// using Ritter's approximation.
+    ///
+    /// The radius is usually within 5 % of the minimal enclosing sphere
+    /// and never more than twice as large.
+    pub fn bounding_sphere(&self) -> (Vector3, f64) {
+        ritter_sphere(&self.triangle_buffer())
+    }
+
+    /// Returns an oriented box around all the triangles,
+    /// usually tighter than the root bounds for meshes not aligned with the axes.
+    ///
+    /// Vertices shared by multiple triangles are counted once,
+    /// so the axes do not lean towards densely connected vertices.
+    pub fn oriented_bounds(&self) -> OrientedBoxCollider {
+        let mut vertices = self.triangle_buffer();
+        vertices.sort_by(|a, b| a.partial_cmp(b).expect("incomparable values"));
+        vertices.dedup();
+        fit_obb(&vertices)
+    }
+
+    /// Returns the size of the smallest triangle in the BVH,
+    /// measured as the longest edge of its bounding box.
+    pub fn smallest_feature_size(&self) -> f64 {
+        node_smallest_feature(&self.0)
+    }
+
+    /// Returns the signed distance from the `query` point to the mesh,
+    /// negative inside and positive outside.
+    ///
+    /// **Expects a closed mesh**, inside is determined by the parity of ray crossings,
+    /// so holes or duplicated faces make the sign unreliable.
+    pub fn signed_distance(&self, query: Vector3) -> f64 {
+        let distance = self.unsigned_distance(query);
+        if self.contains_point(query) {
+            -distance
+        } else {
+            distance
+        }
+    }
+
+    fn unsigned_distance(&self, query: Vector3) -> f64 {
+        let mut best = f64::INFINITY;
+        let mut stack = vec![self.0.as_ref()];
+        while let Some(node) = stack.pop() {
+            let key = node.key();
+            let gap = (key.min() - query).maximized(&(query - key.max()));
+            if gap.maximized(&Vector3::ZERO).len() >= best {
+                continue;
+            }
+
+            match node {
+                RecursiveTree::Leaf(_, t) => {
+                    let (a, b, c) = t.points();
+                    let closest = closest_point_on_triangle(query, [*a, *b, *c]);
+                    best = best.min((closest - query).len());
+                }
+                RecursiveTree::Branch(_, l, r) => {
+                    stack.push(r);
+                    stack.push(l);
+                }
+            }
+        }
+
+        best
+    }
+
+    fn contains_point(&self, query: Vector3) -> bool {
+        //  Skewed direction, so the ray does not run along edges of axis aligned meshes
+        let direction = Vector3::new(0.3711, 0.5229, 0.7675);
+        let mut crossings = 0;
+        let mut stack = vec![self.0.as_ref()];
+        while let Some(node) = stack.pop() {
+            let key = node.key();
+            if !ray_box(query, direction, key.min(), key.max()) {
+                continue;
+            }
+
+            match node {
+                RecursiveTree::Leaf(_, t) => {
+                    let (a, b, c) = t.points();
+                    if ray_triangle(query, direction, [*a, *b, *c]).is_some() {
+                        crossings += 1;
+                    }
+                }
+                RecursiveTree::Branch(_, l, r) => {
+                    stack.push(r);
+                    stack.push(l);
+                }
+            }
+        }
+
+        crossings % 2 == 1
+    }
+
+    /// Returns a list of triangles in the BVH, each vertex paired with its face normal.
+    pub fn triangle_buffer_with_normals(&self) -> Vec<(Vector3, Vector3)> {
+        node_triangles_with_normals(&self.0)
+    }
 }
 
 fn node_triangles<T>(node: &Tree<T>) -> Vec<Vector3> {
@@ -68,6 +198,73 @@ fn node_triangles<T>(node: &Tree<T>) -> Vec<Vector3> {
     }
 }
 
+fn node_smallest_feature<T>(node: &Tree<T>) -> f64 {
+    match node {
+        RecursiveTree::Leaf(_, t) => {
+            let (a, b, c) = t.points();
+            let size = a.maximized(b).maximized(c) - a.minimized(b).minimized(c);
+            size.x().max(size.y()).max(size.z())
+        }
+        RecursiveTree::Branch(_, l, r) => node_smallest_feature(l).min(node_smallest_feature(r)),
+    }
+}
+
+fn node_triangle_count<T>(node: &Tree<T>) -> usize {
+    match node {
+        RecursiveTree::Leaf(_, _) => 1,
+        RecursiveTree::Branch(_, l, r) => node_triangle_count(l) + node_triangle_count(r),
+    }
+}
+
+fn node_triangle_range<T>(node: &Tree<T>, skip: &mut usize, count: usize, out: &mut Vec<Vector3>) {
+    if out.len() >= count.saturating_mul(3) {
+        return;
+    }
+
+    match node {
+        RecursiveTree::Leaf(_, t) => {
+            if *skip > 0 {
+                *skip -= 1;
+            } else {
+                let (a, b, c) = t.points();
+                out.extend([*a, *b, *c]);
+            }
+        }
+        RecursiveTree::Branch(_, l, r) => {
+            node_triangle_range(l, skip, count, out);
+            node_triangle_range(r, skip, count, out);
+        }
+    }
+}
+
+fn node_triangles_with_normals<T>(node: &Tree<T>) -> Vec<(Vector3, Vector3)> {
+    match node {
+        RecursiveTree::Leaf(_, t) => {
+            let (a, b, c) = t.points();
+            let normal = t.normal();
+            vec![(*a, normal), (*b, normal), (*c, normal)]
+        }
+        RecursiveTree::Branch(_, l, r) => {
+            let mut triangles = node_triangles_with_normals(l);
+            triangles.extend(node_triangles_with_normals(r));
+            triangles
+        }
+    }
+}
+
+impl<T> Bounded for BvhRecursive<T>
+where
+    T: Bounded,
+{
+    fn min(&self) -> Vector3 {
+        self.0.key().min()
+    }
+
+    fn max(&self) -> Vector3 {
+        self.0.key().max()
+    }
+}
+
 impl<T> Collides<Self> for BvhRecursive<T>
 where
     T: Collides<T>,
@@ -97,6 +294,284 @@ where
     }
 }
 
+impl<T> BvhRecursive<T>
+where
+    T: Clone + Treeable + Bounded + Collides<T>,
+{
+    /// Returns how severe the collision with `other` is, `0` when they do not collide,
+    /// otherwise in `(0, 1]` growing with the penetration depth.
+    ///
+    /// The depth is the deepest vertex of the colliding triangle pairs inside the other mesh,
+    /// relative to the smallest extent of the bounds, and saturates at `1`.
+    ///
+    /// **Expects closed meshes**, see [BvhRecursive::signed_distance].
+    pub fn collision_severity(&self, other: &Self) -> f64 {
+        if !self.collides_with(other) {
+            return 0.0;
+        }
+
+        let smallest = |v: Vector3| v.x().min(v.y()).min(v.z());
+        let reference = smallest((self.max() - self.min()).minimized(&(other.max() - other.min())));
+        if reference <= 0.0 {
+            return 1.0;
+        }
+
+        (self.penetration_depth(other) / reference).clamp(f64::EPSILON, 1.0)
+    }
+
+    /// Returns the deepest penetration of the colliding triangle pairs,
+    /// as the unit direction to translate `other` and the depth, or `None` if they do not collide.
+    ///
+    /// Every pair is separated on its own by [TriangleCollider::penetration],
+    /// so other pairs may still collide after translating `other` by the result.
+    pub fn deepest_penetration(&self, other: &Self) -> Option<(Vector3, f64)> {
+        let mut deepest: Option<(Vector3, f64)> = None;
+        self.for_each_colliding_pair(other, |t1, t2| {
+            if let Some((direction, depth)) = t1.penetration(t2) {
+                if deepest.is_none_or(|(_, d)| depth > d) {
+                    deepest = Some((direction, depth));
+                }
+            }
+        });
+        deepest
+    }
+
+    fn penetration_depth(&self, other: &Self) -> f64 {
+        let depth_in = |mesh: &Self, t: &TriangleCollider| {
+            let (a, b, c) = t.points();
+            [a, b, c]
+                .into_iter()
+                .map(|v| -mesh.signed_distance(*v))
+                .fold(0.0, f64::max)
+        };
+
+        let mut depth = 0.0_f64;
+        self.for_each_colliding_pair(other, |t1, t2| {
+            depth = depth.max(depth_in(other, t1)).max(depth_in(self, t2));
+        });
+        depth
+    }
+
+    /// Calls `f` with every colliding pair of triangles of this and the `other` tree.
+    fn for_each_colliding_pair<F>(&self, other: &Self, mut f: F)
+    where
+        F: FnMut(&TriangleCollider, &TriangleCollider),
+    {
+        let mut stack: Vec<(&Tree<T>, &Tree<T>)> = vec![(&self.0, &other.0)];
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (RecursiveTree::Leaf(_, t1), RecursiveTree::Leaf(_, t2)) => {
+                    if t1.collides_with(t2) {
+                        f(t1, t2);
+                    }
+                }
+                (leaf @ RecursiveTree::Leaf(s1, _), RecursiveTree::Branch(s2, l, r)) => {
+                    if s1.collides_with(s2) {
+                        stack.push((leaf, r));
+                        stack.push((leaf, l));
+                    }
+                }
+                (RecursiveTree::Branch(s1, l, r), leaf @ RecursiveTree::Leaf(s2, _)) => {
+                    if s1.collides_with(s2) {
+                        stack.push((r, leaf));
+                        stack.push((l, leaf));
+                    }
+                }
+                (RecursiveTree::Branch(s1, l1, r1), RecursiveTree::Branch(s2, l2, r2)) => {
+                    if s1.collides_with(s2) {
+                        stack.push((r1, r2));
+                        stack.push((r1, l2));
+                        stack.push((l1, r2));
+                        stack.push((l1, l2));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> BvhRecursive<T>
+where
+    T: Collides<T>,
+{
+    /// Same as [Collides::collides_with], but also returns the number of bounding box tests
+    /// and triangle tests performed, e.g. for tuning the tree construction.
+    pub fn collides_with_counted(&self, other: &Self) -> (bool, usize, usize) {
+        let (mut boxes, mut triangles) = (0, 0);
+        let mut stack: Vec<(&Tree<T>, &Tree<T>)> = vec![(&self.0, &other.0)];
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (RecursiveTree::Leaf(_, t1), RecursiveTree::Leaf(_, t2)) => {
+                    triangles += 1;
+                    if t1.collides_with(t2) {
+                        return (true, boxes, triangles);
+                    }
+                }
+                (leaf @ RecursiveTree::Leaf(s1, _), RecursiveTree::Branch(s2, l, r)) => {
+                    boxes += 1;
+                    if s1.collides_with(s2) {
+                        stack.push((leaf, r));
+                        stack.push((leaf, l));
+                    }
+                }
+                (RecursiveTree::Branch(s1, l, r), leaf @ RecursiveTree::Leaf(s2, _)) => {
+                    boxes += 1;
+                    if s1.collides_with(s2) {
+                        stack.push((r, leaf));
+                        stack.push((l, leaf));
+                    }
+                }
+                (RecursiveTree::Branch(s1, l1, r1), RecursiveTree::Branch(s2, l2, r2)) => {
+                    boxes += 1;
+                    if s1.collides_with(s2) {
+                        stack.push((r1, r2));
+                        stack.push((r1, l2));
+                        stack.push((l1, r2));
+                        stack.push((l1, l2));
+                    }
+                }
+            }
+        }
+        (false, boxes, triangles)
+    }
+}
+
+impl<T> Collides<OrientedBoxCollider> for BvhRecursive<T>
+where
+    T: Collides<OrientedBoxCollider>,
+{
+    fn collides_with(&self, other: &OrientedBoxCollider) -> bool {
+        collides_with_box(&self.0, other)
+    }
+}
+
+fn collides_with_box<T>(node: &Tree<T>, other: &OrientedBoxCollider) -> bool
+where
+    T: Collides<OrientedBoxCollider>,
+{
+    match node {
+        RecursiveTree::Leaf(s, t) => s.collides_with(other) && t.collides_with(other),
+        RecursiveTree::Branch(s, l, r) => {
+            s.collides_with(other) && (collides_with_box(l, other) || collides_with_box(r, other))
+        }
+    }
+}
+
+impl<T> Collides<ConvexHullCollider> for BvhRecursive<T>
+where
+    T: Collides<AlignedBoxCollider>,
+{
+    /// Prunes the tree by the bounds of the hull,
+    /// so a triangle fully inside the hull collides with it.
+    fn collides_with(&self, other: &ConvexHullCollider) -> bool {
+        let bounds = AlignedBoxCollider::from_min_max(other.min(), other.max());
+        collides_with_hull(&self.0, other, &bounds)
+    }
+}
+
+fn collides_with_hull<T>(
+    node: &Tree<T>,
+    other: &ConvexHullCollider,
+    bounds: &AlignedBoxCollider,
+) -> bool
+where
+    T: Collides<AlignedBoxCollider>,
+{
+    match node {
+        RecursiveTree::Leaf(s, t) => s.collides_with(bounds) && t.collides_with(other),
+        RecursiveTree::Branch(s, l, r) => {
+            s.collides_with(bounds)
+                && (collides_with_hull(l, other, bounds) || collides_with_hull(r, other, bounds))
+        }
+    }
+}
+
+impl<T> BvhRecursive<T>
+where
+    T: Collides<T>,
+{
+    /// Checks collision with the `other` BVH without recursion.
+    ///
+    /// Same result as [Collides::collides_with], but safe for very deep trees.
+    pub fn collides_with_iter(&self, other: &Self) -> bool {
+        let mut stack: Vec<(&Tree<T>, &Tree<T>)> = vec![(&self.0, &other.0)];
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (RecursiveTree::Leaf(_, t1), RecursiveTree::Leaf(_, t2)) => {
+                    if t1.collides_with(t2) {
+                        return true;
+                    }
+                }
+                (leaf @ RecursiveTree::Leaf(s1, _), RecursiveTree::Branch(s2, l, r))
+                | (RecursiveTree::Branch(s1, l, r), leaf @ RecursiveTree::Leaf(s2, _)) => {
+                    if s1.collides_with(s2) {
+                        stack.push((r, leaf));
+                        stack.push((l, leaf));
+                    }
+                }
+                (RecursiveTree::Branch(s1, l1, r1), RecursiveTree::Branch(s2, l2, r2)) => {
+                    if s1.collides_with(s2) {
+                        stack.push((r1, r2));
+                        stack.push((r1, l2));
+                        stack.push((l1, r2));
+                        stack.push((l1, l2));
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl<T> Distance<Self> for BvhRecursive<T>
+where
+    T: Bounded,
+{
+    /// Branch and bound search over both trees,
+    /// skipping node pairs whose bounds are further apart than the best distance found.
+    fn distance_to(&self, other: &Self) -> f64 {
+        let mut best = f64::INFINITY;
+        let mut stack: Vec<(&Tree<T>, &Tree<T>)> = vec![(&self.0, &other.0)];
+        while let Some((left, right)) = stack.pop() {
+            if bounds_distance(left.key(), right.key()) >= best {
+                continue;
+            }
+
+            match (left, right) {
+                (RecursiveTree::Leaf(_, t1), RecursiveTree::Leaf(_, t2)) => {
+                    best = best.min(t1.distance_to(t2));
+                    if best <= 0.0 {
+                        return 0.0;
+                    }
+                }
+                (leaf @ RecursiveTree::Leaf(_, _), RecursiveTree::Branch(_, l, r)) => {
+                    stack.push((leaf, r));
+                    stack.push((leaf, l));
+                }
+                (RecursiveTree::Branch(_, l, r), leaf @ RecursiveTree::Leaf(_, _)) => {
+                    stack.push((r, leaf));
+                    stack.push((l, leaf));
+                }
+                (RecursiveTree::Branch(_, l1, r1), RecursiveTree::Branch(_, l2, r2)) => {
+                    stack.push((r1, r2));
+                    stack.push((r1, l2));
+                    stack.push((l1, r2));
+                    stack.push((l1, l2));
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/// Lower bound of the distance between anything inside the two bounding volumes.
+fn bounds_distance<T: Bounded>(a: &T, b: &T) -> f64 {
+    let gap = (a.min() - b.max()).maximized(&(b.min() - a.max()));
+    gap.maximized(&Vector3::ZERO).len()
+}
+
 impl<T> Rotation for BvhRecursive<T>
 where
     T: Treeable + Bounded,
@@ -243,7 +718,7 @@ where
         .map(|c| (c, c.key().center().get(axis)))
         .collect::<Vec<_>>();
 
-    ordered.sort_by(|(_, p1), (_, p2)| p1.partial_cmp(p2).expect("incomparable values"));
+    ordered.sort_by(|(_, p1), (_, p2)| p1.total_cmp(p2));
 
     let half = ordered.len().div_ceil(2);
 
@@ -258,19 +733,352 @@ fn longest_axis<T>(leaves: &[Tree<T>]) -> Axis
 where
     T: Bounded,
 {
-    let (min, max) = leaves
+    let centers = leaves.iter().map(|l| l.key().center()).collect::<Vec<_>>();
+    let (min, max) = bounds_of(&centers).expect("provided no colliders");
+
+    dominant_axis(max - min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::AlignedBoxCollider;
+    use maths::asserts::assert_vectors;
+
+    fn collinear_chain(count: usize) -> BvhRecursive<AlignedBoxCollider> {
+        (0..count)
+            .map(|i| {
+                let x = i as f64 * 2.0;
+                BvhRecursive::build(&[TriangleCollider::new(
+                    Vector3::new(x, 0.0, 0.0),
+                    Vector3::new(x + 1.0, 0.0, 0.0),
+                    Vector3::new(x, 1.0, 0.0),
+                )])
+            })
+            .reduce(|tree, next| tree.concat(next))
+            .unwrap()
+    }
+
+    #[test]
+    fn appended_triangles_collide() {
+        let scene = collinear_chain(50);
+        let probe = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 10.5, -1.0),
+            Vector3::new(0.5, 10.5, 1.0),
+            Vector3::new(0.1, 10.1, 1.0),
+        )]);
+        assert!(!scene.collides_with(&probe));
+
+        let scene = scene.append_triangles(&[]);
+        assert_eq!(50, scene.triangle_count());
+
+        let appended = scene.append_triangles(&[
+            TriangleCollider::new(
+                Vector3::new(0.0, 10.0, 0.0),
+                Vector3::new(1.0, 10.0, 0.0),
+                Vector3::new(0.0, 11.0, 0.0),
+            ),
+            TriangleCollider::new(
+                Vector3::new(4.0, 10.0, 0.0),
+                Vector3::new(5.0, 10.0, 0.0),
+                Vector3::new(4.0, 11.0, 0.0),
+            ),
+        ]);
+        assert_eq!(52, appended.triangle_count());
+        assert!(appended.collides_with(&probe));
+    }
+
+    #[test]
+    fn triangles_inside_hull_collide() {
+        let chain = collinear_chain(8);
+        let hull = |min: Vector3, max: Vector3| {
+            ConvexHullCollider::new(
+                AlignedBoxCollider::from_min_max(min, max)
+                    .corners()
+                    .to_vec(),
+            )
+        };
+
+        let around_third = hull(Vector3::new(3.5, -1.0, -1.0), Vector3::new(5.5, 2.0, 1.0));
+        let between = hull(Vector3::new(1.2, -1.0, -1.0), Vector3::new(1.8, 2.0, 1.0));
+        assert!(chain.collides_with(&around_third));
+        assert!(!chain.collides_with(&between));
+    }
+
+    #[test]
+    fn iterative_matches_recursive_on_deep_tree() {
+        let chain = collinear_chain(1000);
+        assert_eq!(999, chain.0.depth());
+
+        let deepest = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 0.5, -1.0),
+            Vector3::new(0.5, 0.5, 1.0),
+            Vector3::new(0.1, 0.1, 1.0),
+        )]);
+        assert!(chain.collides_with(&deepest));
+        assert!(chain.collides_with_iter(&deepest));
+        assert!(deepest.collides_with_iter(&chain));
+
+        let outside = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 5.0, -1.0),
+            Vector3::new(0.5, 5.0, 1.0),
+            Vector3::new(0.1, 5.0, 1.0),
+        )]);
+        assert!(!chain.collides_with(&outside));
+        assert!(!chain.collides_with_iter(&outside));
+    }
+
+    #[test]
+    fn triangle_ranges_concatenate_to_buffer() {
+        let chain = collinear_chain(23);
+        assert_eq!(23, chain.triangle_count());
+
+        let ranges = (0..5)
+            .flat_map(|i| chain.triangle_range(i * 5, 5))
+            .collect::<Vec<_>>();
+        assert_eq!(chain.triangle_buffer(), ranges);
+        assert!(chain.triangle_range(23, 5).is_empty());
+    }
+
+    #[test]
+    fn counted_collision_matches_and_counts_tests() {
+        let chain = collinear_chain(64);
+        let crossing = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 0.5, -1.0),
+            Vector3::new(0.5, 0.5, 1.0),
+            Vector3::new(0.1, 0.1, 1.0),
+        )]);
+        let separated = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(60.5, 5.0, -1.0),
+            Vector3::new(60.5, 5.0, 1.0),
+            Vector3::new(60.1, 5.0, 1.0),
+        )]);
+
+        let (collides, boxes, triangles) = chain.collides_with_counted(&crossing);
+        assert!(collides);
+        assert_eq!(chain.collides_with(&crossing), collides);
+        assert!(boxes > 0);
+        assert!(triangles > 0);
+
+        let (collides, separated_boxes, separated_triangles) =
+            chain.collides_with_counted(&separated);
+        assert!(!collides);
+        assert!(separated_boxes > 0);
+        assert!(separated_triangles < triangles);
+    }
+
+    #[test]
+    fn bounding_sphere_of_cube() {
+        let cube = BvhRecursive::<AlignedBoxCollider>::build(&crate::scene::box_triangles(
+            Vector3::new(-1.0, 2.0, 3.0),
+            Vector3::new(1.0, 4.0, 5.0),
+        ));
+        let minimal = 3.0_f64.sqrt();
+
+        let (center, radius) = cube.bounding_sphere();
+        for v in cube.triangle_buffer() {
+            assert!((v - center).len() <= radius + 1e-9);
+        }
+        assert!(radius >= minimal - 1e-9);
+        assert!(radius <= minimal * 1.05);
+    }
+
+    #[test]
+    fn distance_to_nearest_leaf() {
+        let chain = collinear_chain(50);
+        let above = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(40.0, 0.0, 3.0),
+            Vector3::new(41.0, 0.0, 3.0),
+            Vector3::new(40.0, 1.0, 3.0),
+        )]);
+        let touching = BvhRecursive::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 0.5, -1.0),
+            Vector3::new(0.5, 0.5, 1.0),
+            Vector3::new(0.1, 0.1, 1.0),
+        )]);
+
+        assert_eq!(3.0, chain.distance_to(&above));
+        assert_eq!(3.0, above.distance_to(&chain));
+        assert_eq!(0.0, chain.distance_to(&touching));
+    }
+
+    #[test]
+    fn smallest_feature_size_of_leaves() {
+        let bvh = BvhRecursive::<AlignedBoxCollider>::build(&[
+            TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(4.0, 0.0, 0.0),
+                Vector3::new(0.0, 2.0, 0.0),
+            ),
+            TriangleCollider::new(
+                Vector3::new(10.0, 0.0, 0.0),
+                Vector3::new(10.5, 0.0, 0.0),
+                Vector3::new(10.0, 0.0, 0.25),
+            ),
+        ]);
+
+        assert_eq!(0.5, bvh.smallest_feature_size());
+    }
+
+    #[test]
+    fn triangle_buffer_with_face_normals() {
+        let triangle = TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(0.0, 1.0, 1.0),
+        );
+        let bvh = BvhRecursive::<AlignedBoxCollider>::build(&[triangle]);
+
+        let buffer = bvh.triangle_buffer_with_normals();
+
+        assert_eq!(3, buffer.len());
+        assert_vectors(Vector3::new(0.0, 0.0, 1.0), buffer[0].0);
+        assert_vectors(Vector3::new(1.0, 0.0, 1.0), buffer[1].0);
+        assert_vectors(Vector3::new(0.0, 1.0, 1.0), buffer[2].0);
+        for (_, normal) in buffer {
+            assert_vectors(Vector3::new(0.0, 0.0, 1.0), normal);
+        }
+    }
+
+    #[test]
+    fn collision_severity_grows_with_depth() {
+        let block = |min: Vector3, max: Vector3| crate::scene::box_triangles(min, max);
+        //  U shape, the bounds of the probe are inside its bounds in every position
+        let mut walls = block(Vector3::new(0.0, 0.0, 0.0), Vector3::new(10.0, 10.0, 1.0));
+        walls.extend(block(
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(3.0, 10.0, 5.0),
+        ));
+        walls.extend(block(
+            Vector3::new(7.0, 0.0, 1.0),
+            Vector3::new(10.0, 10.0, 5.0),
+        ));
+        let walls = BvhRecursive::<AlignedBoxCollider>::build(&walls);
+        let probe = |x: f64| {
+            BvhRecursive::<AlignedBoxCollider>::build(&block(
+                Vector3::new(x, 4.0, 2.0),
+                Vector3::new(x + 1.0, 5.0, 3.0),
+            ))
+        };
+
+        assert_eq!(0.0, walls.collision_severity(&probe(4.0)));
+
+        let touching = walls.collision_severity(&probe(2.999));
+        assert!(touching > 0.0 && touching < 0.01);
+
+        let shallow = walls.collision_severity(&probe(2.5));
+        let deep = walls.collision_severity(&probe(2.05));
+        assert!(touching < shallow && shallow < deep);
+        assert!(deep > 0.9 && deep <= 1.0);
+        assert_eq!(deep, probe(2.05).collision_severity(&walls));
+    }
+
+    #[test]
+    fn deepest_penetration_lifts_sunk_block() {
+        let block = |min: Vector3, max: Vector3| {
+            BvhRecursive::<AlignedBoxCollider>::build(&crate::scene::box_triangles(min, max))
+        };
+        let floor = block(Vector3::new(0.0, 0.0, 0.0), Vector3::new(10.0, 10.0, 1.0));
+        let sunk = block(Vector3::new(6.0, 1.0, 0.9), Vector3::new(7.0, 2.0, 1.9));
+        let above = block(Vector3::new(6.0, 1.0, 1.5), Vector3::new(7.0, 2.0, 2.5));
+
+        let (direction, depth) = floor.deepest_penetration(&sunk).unwrap();
+        assert!((depth - 0.1).abs() < 1e-9);
+        assert!((direction.z() - 1.0).abs() < 1e-9);
+        assert_eq!(None, floor.deepest_penetration(&above));
+    }
+
+    #[test]
+    fn signed_distance_of_cube() {
+        let cube = BvhRecursive::<AlignedBoxCollider>::build(&crate::scene::box_triangles(
+            Vector3::new(-1.0, -1.0, -1.0),
+            Vector3::new(1.0, 1.0, 1.0),
+        ));
+
+        assert!((cube.signed_distance(Vector3::ZERO) + 1.0).abs() < 1e-12);
+        assert!((cube.signed_distance(Vector3::new(0.5, 0.0, 0.0)) + 0.5).abs() < 1e-12);
+        assert!((cube.signed_distance(Vector3::new(3.0, 0.0, 0.0)) - 2.0).abs() < 1e-12);
+        assert!((cube.signed_distance(Vector3::new(2.0, 2.0, 2.0)) - 3_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn longest_axis_matches_bvh_tree_on_ties() {
+        use crate::complex::bvh::BvhTree;
+        use crate::primitive::Collider;
+
+        let triangle_at = |c: Vector3| {
+            TriangleCollider::new(
+                c + Vector3::new(-0.1, -0.1, -0.1),
+                c + Vector3::new(0.1, 0.1, 0.1),
+                c + Vector3::new(0.1, -0.1, 0.1),
+            )
+        };
+
+        for far in [Vector3::new(2.0, 2.0, 2.0), Vector3::new(1.0, 2.0, 2.0)] {
+            let centers = [Vector3::ZERO, far];
+            let triangles = centers.map(triangle_at);
+            let leaves = into_leaves::<AlignedBoxCollider>(&triangles);
+            let colliders =
+                centers.map(|c| Collider::aligned_box(c.x(), c.y(), c.z(), 0.2, 0.2, 0.2));
+
+            let axis = longest_axis(&leaves);
+            assert_eq!(BvhTree::longest_axis(&colliders), axis);
+            assert_eq!(dominant_axis(far), axis);
+        }
+    }
+
+    #[test]
+    fn longest_axis_uses_spatial_extent() {
+        let triangle_at = |c: Vector3| {
+            TriangleCollider::new(
+                c + Vector3::new(-0.1, -0.1, -0.1),
+                c + Vector3::new(0.1, 0.1, 0.1),
+                c + Vector3::new(0.1, -0.1, 0.1),
+            )
+        };
+
+        //  Lexicographic extremes are (0, 0, 0) and (1, 0, 0), spanning only the X axis
+        let centers = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 10.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+        ];
+        let leaves = into_leaves::<AlignedBoxCollider>(&centers.map(triangle_at));
+        assert_eq!(Axis::Y, longest_axis(&leaves));
+
+        //  Equal leading components, ordered by the split axis only
+        let centers = [
+            Vector3::new(0.0, 0.0, 3.0),
+            Vector3::new(0.0, 0.0, -3.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+        ];
+        let leaves = into_leaves::<AlignedBoxCollider>(&centers.map(triangle_at));
+        assert_eq!(Axis::Z, longest_axis(&leaves));
+        let (low, high) = split_by_axis(&leaves, Axis::Z);
+        let z = |l: &Tree<AlignedBoxCollider>| l.key().center().z();
+        assert_eq!(vec![-3.0, -1.0], low.iter().map(z).collect::<Vec<_>>());
+        assert_eq!(vec![1.0, 3.0], high.iter().map(z).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn oriented_bounds_tighter_than_aligned() {
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.3, 45_f64.to_radians()));
+        let triangles = crate::scene::box_triangles(
+            Vector3::new(-5.0, -1.0, -0.25),
+            Vector3::new(5.0, 1.0, 0.25),
+        )
         .iter()
-        .map(|l| l.key().center())
-        .minmax_by(|a, b| a.partial_cmp(b).expect("incomparable values"))
-        .into_option()
-        .expect("provided no colliders");
-
-    let diff = max - min;
-    if diff.x() >= diff.y() && diff.x() >= diff.z() {
-        Axis::X
-    } else if diff.y() >= diff.z() {
-        Axis::Y
-    } else {
-        Axis::Z
+        .map(|t| t.rotate_around(&rotation, &Vector3::ZERO))
+        .collect::<Vec<_>>();
+        let tree = BvhRecursive::<AlignedBoxCollider>::build(&triangles);
+
+        let obb = tree.oriented_bounds();
+        let obb_volume = obb.size().x() * obb.size().y() * obb.size().z();
+        let aabb = tree.max() - tree.min();
+        let aabb_volume = aabb.x() * aabb.y() * aabb.z();
+
+        assert!((obb_volume - 10.0).abs() < 1e-6, "{obb_volume}");
+        assert!(obb_volume < aabb_volume / 4.0);
     }
 }
@@ -3,7 +3,7 @@ use crate::common::{Collides, Rotation, Transformation, Translation, Treeable};
 use crate::complex::tree::RecursiveTree;
 use crate::primitive::{SphereCollider, TriangleCollider};
 use itertools::Itertools;
-use maths::{Axis, Quaternion, Vector3};
+use maths::{bounds_of, dominant_axis, Axis, Quaternion, Vector3};
 use std::sync::Arc;
 
 type Node = RecursiveTree<SphereCollider, TriangleCollider>;
@@ -210,7 +210,7 @@ fn split_by_axis(leaves: &[Node], axis: Axis) -> (Vec<Node>, Vec<Node>) {
         .map(|c| (c, c.key().center().get(axis)))
         .collect::<Vec<_>>();
 
-    ordered.sort_by(|(_, p1), (_, p2)| p1.partial_cmp(p2).expect("incomparable values"));
+    ordered.sort_by(|(_, p1), (_, p2)| p1.total_cmp(p2));
 
     let half = ordered.len().div_ceil(2);
 
@@ -222,19 +222,8 @@ fn split_by_axis(leaves: &[Node], axis: Axis) -> (Vec<Node>, Vec<Node>) {
 }
 
 fn longest_axis(leaves: &[Node]) -> Axis {
-    let (min, max) = leaves
-        .iter()
-        .map(|l| l.key().center())
-        .minmax_by(|a, b| a.partial_cmp(b).expect("incomparable values"))
-        .into_option()
-        .expect("provided no colliders");
-
-    let diff = max - min;
-    if diff.x() >= diff.y() && diff.x() >= diff.z() {
-        Axis::X
-    } else if diff.y() >= diff.z() {
-        Axis::Y
-    } else {
-        Axis::Z
-    }
+    let centers = leaves.iter().map(|l| l.key().center()).collect::<Vec<_>>();
+    let (min, max) = bounds_of(&centers).expect("provided no colliders");
+
+    dominant_axis(max - min)
 }
@@ -0,0 +1,138 @@
+use crate::primitive::{ConvexHullCollider, TriangleCollider};
+use maths::{bounds_of, Axis, Vector3};
+
+/// Concavity under which a cluster of triangles is considered convex.
+const CONVEX_TOLERANCE: f64 = 1e-9;
+
+/// Splits a mesh into at most `max_pieces` convex hulls covering all of its triangles.
+///
+/// Triangles are clustered by their centroids,
+/// the most concave cluster is repeatedly halved along its longest axis
+/// until all clusters are convex or the limit of pieces is reached.
+///
+/// Returns no pieces for an empty list of triangles or zero `max_pieces`.
+pub fn approximate_convex_decomposition(
+    triangles: &[TriangleCollider],
+    max_pieces: usize,
+) -> Vec<ConvexHullCollider> {
+    if triangles.is_empty() || max_pieces == 0 {
+        return Vec::new();
+    }
+
+    let mut clusters = vec![triangles.to_vec()];
+    while clusters.len() < max_pieces {
+        let most_concave = clusters
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.len() > 1)
+            .map(|(i, c)| (i, concavity(c)))
+            .filter(|(_, concavity)| *concavity > CONVEX_TOLERANCE)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let Some((index, _)) = most_concave else {
+            break;
+        };
+
+        let cluster = clusters.swap_remove(index);
+        let (left, right) = split_cluster(cluster);
+        clusters.push(left);
+        clusters.push(right);
+    }
+
+    clusters
+        .iter()
+        .map(|c| ConvexHullCollider::from_triangles(c))
+        .collect()
+}
+
+/// Largest depth of a vertex behind a triangle plane, when the triangle has vertices on both sides.
+///
+/// Zero for a set of triangles lying on the boundary of their convex hull.
+fn concavity(triangles: &[TriangleCollider]) -> f64 {
+    let points = triangles
+        .iter()
+        .flat_map(|t| {
+            let (a, b, c) = t.points();
+            [*a, *b, *c]
+        })
+        .collect::<Vec<_>>();
+
+    triangles
+        .iter()
+        .map(|t| {
+            let (a, _, _) = t.points();
+            let normal = t.normal();
+            let (below, above) = points.iter().fold((0.0_f64, 0.0_f64), |(lo, hi), p| {
+                let d = normal.dot(&(*p - a));
+                (lo.min(d), hi.max(d))
+            });
+            (-below).min(above)
+        })
+        .fold(0.0, f64::max)
+}
+
+fn split_cluster(
+    mut cluster: Vec<TriangleCollider>,
+) -> (Vec<TriangleCollider>, Vec<TriangleCollider>) {
+    let centroid = |t: &TriangleCollider| {
+        let (a, b, c) = t.points();
+        (*a + b + c) / 3.0
+    };
+
+    let centroids = cluster.iter().map(centroid).collect::<Vec<_>>();
+    let size = bounds_of(&centroids).map_or(Vector3::ZERO, |(min, max)| max - min);
+    let axis = if size.x() >= size.y() && size.x() >= size.z() {
+        Axis::X
+    } else if size.y() >= size.z() {
+        Axis::Y
+    } else {
+        Axis::Z
+    };
+
+    cluster.sort_by(|a, b| centroid(a).get(axis).total_cmp(&centroid(b).get(axis)));
+    let right = cluster.split_off(cluster.len() / 2);
+    (cluster, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::box_triangles;
+
+    #[test]
+    fn single_box_is_convex() {
+        let triangles = box_triangles(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0));
+        let pieces = approximate_convex_decomposition(&triangles, 8);
+        assert_eq!(1, pieces.len());
+    }
+
+    #[test]
+    fn empty_input_has_no_pieces() {
+        let triangles = box_triangles(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0));
+        assert!(approximate_convex_decomposition(&[], 8).is_empty());
+        assert!(approximate_convex_decomposition(&triangles, 0).is_empty());
+    }
+
+    #[test]
+    fn l_shape_decomposes_into_covering_hulls() {
+        let mut triangles = box_triangles(Vector3::ZERO, Vector3::new(3.0, 1.0, 1.0));
+        triangles.extend(box_triangles(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 3.0, 1.0),
+        ));
+        assert!(concavity(&triangles) > CONVEX_TOLERANCE);
+
+        let pieces = approximate_convex_decomposition(&triangles, 8);
+
+        assert!(pieces.len() >= 2 && pieces.len() <= 8);
+        for triangle in &triangles {
+            let (a, b, c) = triangle.points();
+            assert!(pieces
+                .iter()
+                .any(|p| p.contains(a) && p.contains(b) && p.contains(c)));
+        }
+        //  The inner corner of the L is not part of the original mesh
+        let inner_corner = Vector3::new(2.0, 2.0, 0.5);
+        assert!(pieces.iter().all(|p| !p.contains(&inner_corner)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::common::Collides;
+use crate::common::{Collides, Distance};
 
 #[cfg(feature = "rayon-group")]
 use rayon::prelude::*;
@@ -10,6 +10,8 @@ macro_rules! collider_group {
     }
 }
 
+use crate::primitive::algo::obb::principal_components;
+use crate::primitive::TriangleCollider;
 use crate::PrimaryCollider;
 pub use collider_group;
 use maths::Vector3;
@@ -34,6 +36,20 @@ impl<T> ColliderGroup<T> {
     pub fn extend(&mut self, other: ColliderGroup<T>) {
         self.0.extend(other.0);
     }
+
+    /// Combines two groups, e.g. precomputed static parts with the current retracts.
+    ///
+    /// Items of `self` come first, followed by the items of `other`.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.merge_in_place(other);
+        self
+    }
+
+    /// Same as [ColliderGroup::merge], without taking ownership of `self`.
+    pub fn merge_in_place(&mut self, other: Self) {
+        self.0.reserve(other.0.len());
+        self.0.extend(other.0);
+    }
 }
 
 impl ColliderGroup<PrimaryCollider> {
@@ -63,6 +79,51 @@ impl ColliderGroup<PrimaryCollider> {
             .expect("ColliderGroup cannot be empty")
     }
 
+    /// Returns the principal axes of the group vertices, see [principal_components],
+    /// world axes for a group without triangles.
+    ///
+    /// Vertices shared by multiple triangles are counted once,
+    /// same as for [PrimaryCollider::oriented_bounds].
+    pub fn principal_axes(&self) -> [Vector3; 3] {
+        let mut vertices = self
+            .0
+            .iter()
+            .flat_map(|bvh| bvh.triangle_buffer())
+            .collect::<Vec<_>>();
+        if vertices.is_empty() {
+            return [
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+                Vector3::new(0.0, 0.0, 1.0),
+            ];
+        }
+
+        vertices.sort_by(|a, b| a.partial_cmp(b).expect("incomparable values"));
+        vertices.dedup();
+        principal_components(&vertices)
+    }
+
+    /// Returns the group reflected across the plane given by `plane_normal` and `plane_point`,
+    /// e.g. the opposite detector of a symmetric pair.
+    pub fn mirrored(&self, plane_normal: Vector3, plane_point: Vector3) -> Self {
+        ColliderGroup(
+            self.0
+                .iter()
+                .map(|bvh| {
+                    let triangles = bvh
+                        .triangle_buffer()
+                        .chunks_exact(3)
+                        .map(|t| {
+                            TriangleCollider::new(t[0], t[1], t[2])
+                                .mirror(plane_normal, plane_point)
+                        })
+                        .collect::<Vec<_>>();
+                    PrimaryCollider::build(&triangles)
+                })
+                .collect(),
+        )
+    }
+
     /// Returns a buffer of all the triangles in the group.
     pub fn triangle_buffer_per_item<T, M>(&self, mapper: M) -> Vec<Vec<T>>
     where
@@ -79,6 +140,28 @@ impl ColliderGroup<PrimaryCollider> {
             .map(|t| t.triangle_buffer().into_iter().map(&mapper).collect())
             .collect()
     }
+
+    /// Returns a buffer of all the triangles in the group with per-vertex face normals.
+    pub fn triangle_buffer_with_normals_per_item<T, M>(&self, mapper: M) -> Vec<Vec<T>>
+    where
+        T: Send,
+        M: (Fn(Vector3, Vector3) -> T) + Send + Sync,
+    {
+        #[cfg(feature = "rayon-group")]
+        let data_iter = self.0.par_iter();
+
+        #[cfg(not(feature = "rayon-group"))]
+        let data_iter = self.0.iter();
+
+        data_iter
+            .map(|t| {
+                t.triangle_buffer_with_normals()
+                    .into_iter()
+                    .map(|(v, n)| mapper(v, n))
+                    .collect()
+            })
+            .collect()
+    }
 }
 
 #[cfg(feature = "rayon-group")]
@@ -106,6 +189,19 @@ where
     }
 }
 
+impl<A, B> Distance<ColliderGroup<A>> for ColliderGroup<B>
+where
+    B: Distance<A>,
+{
+    /// Returns infinity if either of the groups is empty.
+    fn distance_to(&self, a: &ColliderGroup<A>) -> f64 {
+        self.0
+            .iter()
+            .flat_map(|b| a.0.iter().map(move |a| b.distance_to(a)))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
 #[cfg(feature = "rayon-group")]
 #[macro_export]
 macro_rules! collides_group_impl {
@@ -120,12 +216,7 @@ macro_rules! collides_group_impl {
                     self.0.par_iter().any(|item| item.collides_with(other))
                 }
             }
-            impl $crate::common::Collides<$crate::complex::group::ColliderGroup<$t2>> for $t1 {
-                #[inline]
-                fn collides_with(&self, other: &$crate::complex::group::ColliderGroup<$t2>) -> bool {
-                    other.collides_with(self)
-                }
-            }
+            $crate::collides_reverse_impl!($crate::complex::group::ColliderGroup<$t2> => $t1);
         )*
     )
 }
@@ -140,20 +231,18 @@ macro_rules! collides_group_impl {
                 self.0.iter().any(|item| item.collides_with(other))
             }
         }
-        impl $crate::common::Collides<$crate::complex::group::ColliderGroup<$t2>> for $t1 {
-            #[inline]
-            fn collides_with(&self, other: &$crate::complex::group::ColliderGroup<$t2>) -> bool {
-                other.collides_with(self)
-            }
-        }
+        $crate::collides_reverse_impl!($crate::complex::group::ColliderGroup<$t2> => $t1);
     )*)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::Rotation;
     use crate::primitive::TriangleCollider;
+    use crate::scene::box_triangles;
     use crate::PrimaryCollider;
+    use maths::Quaternion;
 
     struct Col;
     impl Collides<Col> for Col {
@@ -174,6 +263,62 @@ mod tests {
         assert!(group.collides_with(&Col));
     }
 
+    fn triangle_at(x: f64) -> PrimaryCollider {
+        PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(x, 0.0, 0.0),
+            Vector3::new(x + 1.0, 0.0, 0.0),
+            Vector3::new(x, 1.0, 0.0),
+        )])
+    }
+
+    #[test]
+    fn merged_collides_as_extended() {
+        let mut extended = collider_group!();
+        for x in [0.0, 5.0, 10.0, 15.0] {
+            extended.extend(collider_group!(triangle_at(x)));
+        }
+        let merged = collider_group!(triangle_at(0.0), triangle_at(5.0))
+            .merge(collider_group!(triangle_at(10.0), triangle_at(15.0)));
+        let mut merged_in_place = collider_group!(triangle_at(0.0));
+        merged_in_place.merge_in_place(collider_group!(
+            triangle_at(5.0),
+            triangle_at(10.0),
+            triangle_at(15.0)
+        ));
+
+        assert_eq!(4, merged.0.len());
+        assert_eq!(4, merged_in_place.0.len());
+        for x in [0.2, 3.0, 5.2, 10.2, 12.5, 15.2, 20.0] {
+            let probe = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+                Vector3::new(x, 0.2, -1.0),
+                Vector3::new(x, 0.2, 1.0),
+                Vector3::new(x + 0.1, 0.1, 1.0),
+            )]));
+            let expected = extended.collides_with(&probe);
+            assert_eq!(expected, merged.collides_with(&probe), "at {x}");
+            assert_eq!(expected, merged_in_place.collides_with(&probe), "at {x}");
+        }
+    }
+
+    #[test]
+    fn mirrored_collides_on_opposite_side() {
+        let group = collider_group!(triangle_at(5.0));
+        let mirrored = group.mirrored(Vector3::new(1.0, 0.0, 0.0), Vector3::ZERO);
+        let probe_at = |x: f64| {
+            collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+                Vector3::new(x, 0.2, -1.0),
+                Vector3::new(x, 0.2, 1.0),
+                Vector3::new(x - 0.1, 0.1, 1.0),
+            )]))
+        };
+
+        assert_eq!(1, mirrored.0.len());
+        assert!(group.collides_with(&probe_at(5.2)));
+        assert!(!group.collides_with(&probe_at(-5.2)));
+        assert!(mirrored.collides_with(&probe_at(-5.2)));
+        assert!(!mirrored.collides_with(&probe_at(5.2)));
+    }
+
     #[test]
     fn vertices_keep_ordering() {
         let collider = collider_group!(
@@ -228,4 +373,28 @@ mod tests {
             assert_eq!(c, actual[first + 2]);
         }
     }
+
+    #[test]
+    fn principal_axes_follow_elongated_group() {
+        let elongated = PrimaryCollider::build(&box_triangles(
+            Vector3::new(-0.005, -0.04, -0.002),
+            Vector3::new(0.005, 0.04, 0.002),
+        ));
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 0.3));
+        let group = collider_group!(elongated.rotate(&rotation));
+
+        let [first, second, third] = group.principal_axes();
+
+        let long = Vector3::new(0.0, 1.0, 0.0).rotate(&rotation);
+        assert!((first.dot(&long).abs() - 1.0).abs() < 1e-6);
+        assert!(
+            (second
+                .dot(&Vector3::new(1.0, 0.0, 0.0).rotate(&rotation))
+                .abs()
+                - 1.0)
+                .abs()
+                < 1e-6
+        );
+        assert!((third.z().abs() - 1.0).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,158 @@
+use crate::common::{Bounded, Collides, Rotation, Transformation, Translation};
+use crate::primitive::{AlignedBoxCollider, OrientedBoxCollider};
+use crate::PrimaryCollider;
+use maths::{Quaternion, Vector3};
+
+/// # Hybrid Collider
+/// Either a coarse box proxy or a full triangle mesh.
+///
+/// Allows mixing cheap approximations of parts with triangle-accurate ones
+/// in a single group of colliders built by the caller.
+/// The microscope models are groups of meshes only and do not use it,
+/// box proxies are checked against them with `models::immovable::proxy` instead.
+#[derive(Debug, Clone)]
+pub enum HybridCollider {
+    Proxy(OrientedBoxCollider),
+    Mesh(PrimaryCollider),
+}
+
+impl From<OrientedBoxCollider> for HybridCollider {
+    fn from(value: OrientedBoxCollider) -> Self {
+        Self::Proxy(value)
+    }
+}
+
+impl From<AlignedBoxCollider> for HybridCollider {
+    fn from(value: AlignedBoxCollider) -> Self {
+        Self::Proxy(OrientedBoxCollider::from(&value))
+    }
+}
+
+impl From<PrimaryCollider> for HybridCollider {
+    fn from(value: PrimaryCollider) -> Self {
+        Self::Mesh(value)
+    }
+}
+
+impl Bounded for HybridCollider {
+    fn min(&self) -> Vector3 {
+        match self {
+            HybridCollider::Proxy(b) => b.min(),
+            HybridCollider::Mesh(m) => m.min(),
+        }
+    }
+
+    fn max(&self) -> Vector3 {
+        match self {
+            HybridCollider::Proxy(b) => b.max(),
+            HybridCollider::Mesh(m) => m.max(),
+        }
+    }
+}
+
+impl Collides<Self> for HybridCollider {
+    fn collides_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HybridCollider::Proxy(b1), HybridCollider::Proxy(b2)) => b1.collides_with(b2),
+            (HybridCollider::Proxy(b), HybridCollider::Mesh(m))
+            | (HybridCollider::Mesh(m), HybridCollider::Proxy(b)) => m.collides_with(b),
+            (HybridCollider::Mesh(m1), HybridCollider::Mesh(m2)) => m1.collides_with(m2),
+        }
+    }
+}
+
+impl Rotation for HybridCollider {
+    fn rotate(&self, rotation: &Quaternion) -> Self {
+        match self {
+            HybridCollider::Proxy(b) => HybridCollider::Proxy(b.rotate(rotation)),
+            HybridCollider::Mesh(m) => HybridCollider::Mesh(m.rotate(rotation)),
+        }
+    }
+
+    fn rotate_around(&self, rotation: &Quaternion, pivot: &Vector3) -> Self {
+        match self {
+            HybridCollider::Proxy(b) => HybridCollider::Proxy(b.rotate_around(rotation, pivot)),
+            HybridCollider::Mesh(m) => HybridCollider::Mesh(m.rotate_around(rotation, pivot)),
+        }
+    }
+}
+
+impl Translation for HybridCollider {
+    fn translate(&self, translation: &Vector3) -> Self {
+        match self {
+            HybridCollider::Proxy(b) => HybridCollider::Proxy(b.translate(translation)),
+            HybridCollider::Mesh(m) => HybridCollider::Mesh(m.translate(translation)),
+        }
+    }
+}
+
+impl Transformation for HybridCollider {
+    fn transform(&self, rotation: &Quaternion, pivot: &Vector3, translation: &Vector3) -> Self {
+        match self {
+            HybridCollider::Proxy(b) => {
+                HybridCollider::Proxy(b.transform(rotation, pivot, translation))
+            }
+            HybridCollider::Mesh(m) => {
+                HybridCollider::Mesh(m.transform(rotation, pivot, translation))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collider_group;
+    use crate::primitive::TriangleCollider;
+
+    fn mesh() -> HybridCollider {
+        HybridCollider::from(PrimaryCollider::build(&[
+            TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(4.0, 0.0, 0.0),
+                Vector3::new(0.0, 4.0, 0.0),
+            ),
+            TriangleCollider::new(
+                Vector3::new(4.0, 0.0, 0.0),
+                Vector3::new(4.0, 4.0, 0.0),
+                Vector3::new(0.0, 4.0, 0.0),
+            ),
+        ]))
+    }
+
+    fn proxy(center: Vector3, rotation: Vector3) -> HybridCollider {
+        HybridCollider::from(OrientedBoxCollider::new(
+            center,
+            Vector3::new(1.0, 1.0, 1.0),
+            Quaternion::from_euler(&rotation),
+        ))
+    }
+
+    #[test]
+    fn box_proxy_against_triangle_mesh() {
+        let mesh = mesh();
+        let touching = proxy(Vector3::new(2.0, 2.0, 0.4), Vector3::ZERO);
+        let above = proxy(Vector3::new(2.0, 2.0, 0.6), Vector3::ZERO);
+        let tilted = proxy(
+            Vector3::new(2.0, 2.0, 0.6),
+            Vector3::new(45_f64.to_radians(), 0.0, 0.0),
+        );
+        let beside = proxy(Vector3::new(5.0, 5.0, 0.0), Vector3::ZERO);
+
+        assert!(mesh.collides_with(&touching));
+        assert!(touching.collides_with(&mesh));
+        assert!(!mesh.collides_with(&above));
+        assert!(mesh.collides_with(&tilted));
+        assert!(!mesh.collides_with(&beside));
+    }
+
+    #[test]
+    fn mixed_group() {
+        let group = collider_group!(mesh(), proxy(Vector3::new(10.0, 0.0, 0.0), Vector3::ZERO));
+        let probe = collider_group!(proxy(Vector3::new(10.5, 0.5, 0.0), Vector3::ZERO));
+        let outside = collider_group!(proxy(Vector3::new(20.0, 0.0, 0.0), Vector3::ZERO));
+
+        assert!(group.collides_with(&probe));
+        assert!(!group.collides_with(&outside));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::common::Bounded;
+use crate::primitive::AlignedBoxCollider;
+use maths::{bounds_of, Vector3};
+
+/// Bits per axis of the quantized center, three axes fit into 63 bits.
+const MORTON_BITS: u32 = 21;
+
+/// Returns the indices of `boxes` ordered along the 3D Morton (Z-order) curve.
+///
+/// Box centers are quantized within the bounds of all centers,
+/// boxes with equal codes keep their original order.
+pub fn morton_sorted_indices(boxes: &[AlignedBoxCollider]) -> Vec<usize> {
+    let centers = boxes.iter().map(center).collect::<Vec<_>>();
+    let Some((min, max)) = bounds_of(&centers) else {
+        return vec![];
+    };
+
+    let extent = max - min;
+    let scale = ((1u64 << MORTON_BITS) - 1) as f64;
+    let quantize = |value: f64, extent: f64| {
+        if extent > 0.0 {
+            ((value / extent) * scale) as u64
+        } else {
+            0
+        }
+    };
+
+    let mut indexed = centers
+        .iter()
+        .map(|c| {
+            let d = *c - min;
+            morton_code(
+                quantize(d.x(), extent.x()),
+                quantize(d.y(), extent.y()),
+                quantize(d.z(), extent.z()),
+            )
+        })
+        .enumerate()
+        .collect::<Vec<_>>();
+    indexed.sort_by_key(|(_, code)| *code);
+    indexed.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Interleaves the lowest 21 bits of each coordinate, `x` in the least significant position.
+pub fn morton_code(x: u64, y: u64, z: u64) -> u64 {
+    spread_bits(x) | (spread_bits(y) << 1) | (spread_bits(z) << 2)
+}
+
+fn spread_bits(value: u64) -> u64 {
+    let mut v = value & 0x1f_ffff;
+    v = (v | (v << 32)) & 0x1f_0000_0000_ffff;
+    v = (v | (v << 16)) & 0x1f_0000_ff00_00ff;
+    v = (v | (v << 8)) & 0x100f_00f0_0f00_f00f;
+    v = (v | (v << 4)) & 0x10c3_0c30_c30c_30c3;
+    v = (v | (v << 2)) & 0x1249_2492_4924_9249;
+    v
+}
+
+fn center(b: &AlignedBoxCollider) -> Vector3 {
+    b.min() + (b.max() - b.min()) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn morton_code_interleaves() {
+        assert_eq!(0b111, morton_code(1, 1, 1));
+        assert_eq!(0b100_000, morton_code(0, 0, 2));
+        assert_eq!(0b001_001, morton_code(3, 0, 0));
+    }
+
+    #[test]
+    fn nearby_boxes_are_adjacent() {
+        let mut boxes = vec![];
+        for y in 0..4 {
+            for x in 0..4 {
+                boxes.push(AlignedBoxCollider::new(
+                    Vector3::new(x as f64, y as f64, 0.0),
+                    Vector3::new(0.5, 0.5, 0.5),
+                ));
+            }
+        }
+
+        let order = morton_sorted_indices(&boxes);
+
+        assert_eq!(boxes.len(), order.len());
+        //  Every run of four forms a 2x2 quadrant of the grid
+        for quad in order.chunks(4) {
+            let centers = quad.iter().map(|i| center(&boxes[*i])).collect::<Vec<_>>();
+            let min = centers
+                .iter()
+                .copied()
+                .reduce(|a, b| a.minimized(&b))
+                .unwrap();
+            let max = centers
+                .iter()
+                .copied()
+                .reduce(|a, b| a.maximized(&b))
+                .unwrap();
+            assert_eq!(Vector3::new(1.0, 1.0, 0.0), max - min);
+        }
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(morton_sorted_indices(&[]).is_empty());
+    }
+}
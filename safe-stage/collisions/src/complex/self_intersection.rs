@@ -0,0 +1,79 @@
+use crate::common::{Bounded, Collides};
+use crate::complex::bvh_recursive::BvhRecursive;
+use crate::primitive::AlignedBoxCollider;
+use maths::Vector3;
+
+/// Returns all pairs of `groups` whose geometry intersects.
+///
+/// Candidates are found by sweep-and-prune over the root bounds along the X axis,
+/// only pairs with overlapping bounds are tested tree against tree.
+/// Each pair is reported once as `(i, j)` with `i < j`, sorted ascending.
+pub fn self_intersections(groups: &[BvhRecursive<AlignedBoxCollider>]) -> Vec<(usize, usize)> {
+    let bounds = groups
+        .iter()
+        .map(|g| (g.min(), g.max()))
+        .collect::<Vec<_>>();
+
+    let mut order = (0..groups.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| bounds[a].0.x().total_cmp(&bounds[b].0.x()));
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut pairs = Vec::new();
+    for &current in &order {
+        let (min, max) = bounds[current];
+        active.retain(|&other| bounds[other].1.x() >= min.x());
+
+        for &other in &active {
+            let (other_min, other_max) = bounds[other];
+            if overlaps(min, max, other_min, other_max)
+                && groups[current].collides_with(&groups[other])
+            {
+                pairs.push((current.min(other), current.max(other)));
+            }
+        }
+
+        active.push(current);
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+fn overlaps(a_min: Vector3, a_max: Vector3, b_min: Vector3, b_max: Vector3) -> bool {
+    a_min.y() <= b_max.y()
+        && b_min.y() <= a_max.y()
+        && a_min.z() <= b_max.z()
+        && b_min.z() <= a_max.z()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::box_triangles;
+
+    fn part(min: Vector3, max: Vector3) -> BvhRecursive<AlignedBoxCollider> {
+        BvhRecursive::build(&box_triangles(min, max))
+    }
+
+    #[test]
+    fn reports_only_overlapping_pair() {
+        let groups = [
+            part(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0)),
+            part(Vector3::new(5.0, 0.0, 0.0), Vector3::new(6.0, 1.0, 1.0)),
+            part(Vector3::new(0.5, 0.5, 0.5), Vector3::new(1.5, 1.5, 1.5)),
+        ];
+
+        assert_eq!(vec![(0, 2)], self_intersections(&groups));
+    }
+
+    #[test]
+    fn separated_in_other_axis_is_pruned() {
+        let groups = [
+            part(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0)),
+            part(Vector3::new(0.0, 3.0, 0.0), Vector3::new(1.0, 4.0, 1.0)),
+        ];
+
+        assert!(self_intersections(&groups).is_empty());
+        assert!(self_intersections(&[]).is_empty());
+    }
+}
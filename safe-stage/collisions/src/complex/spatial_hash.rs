@@ -0,0 +1,122 @@
+use crate::common::Bounded;
+use crate::primitive::AlignedBoxCollider;
+use maths::Vector3;
+use std::collections::HashMap;
+
+type Cell = (i64, i64, i64);
+
+/// # Spatial Hash Grid
+/// Uniform grid of cells mapping to indices of the boxes overlapping them.
+///
+/// Broad phase alternative to a BVH for many similarly sized boxes,
+/// the cell size should be close to the size of a typical box.
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid {
+    cell_size: f64,
+    cells: HashMap<Cell, Vec<usize>>,
+}
+
+impl SpatialHashGrid {
+    /// Creates an empty grid with cubic cells of the given size.
+    pub fn new(cell_size: f64) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "cell size must be positive"
+        );
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Registers the box with index `idx` in all the cells it overlaps.
+    pub fn insert(&mut self, idx: usize, aabb: &AlignedBoxCollider) {
+        for cell in self.cells_of(aabb) {
+            self.cells.entry(cell).or_default().push(idx);
+        }
+    }
+
+    /// Returns the indices of all the boxes sharing a cell with `aabb`,
+    /// sorted and without duplicates.
+    ///
+    /// Boxes are only candidates, they do not have to overlap `aabb` itself.
+    pub fn query(&self, aabb: &AlignedBoxCollider) -> Vec<usize> {
+        let mut found = self
+            .cells_of(aabb)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Removes all the boxes from the grid.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    fn cell_of(&self, point: Vector3) -> Cell {
+        (
+            (point.x() / self.cell_size).floor() as i64,
+            (point.y() / self.cell_size).floor() as i64,
+            (point.z() / self.cell_size).floor() as i64,
+        )
+    }
+
+    fn cells_of(&self, aabb: &AlignedBoxCollider) -> impl Iterator<Item = Cell> {
+        let (min_x, min_y, min_z) = self.cell_of(aabb.min());
+        let (max_x, max_y, max_z) = self.cell_of(aabb.max());
+        (min_x..=max_x).flat_map(move |x| {
+            (min_y..=max_y).flat_map(move |y| (min_z..=max_z).map(move |z| (x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(x: f64, y: f64, z: f64, size: f64) -> AlignedBoxCollider {
+        AlignedBoxCollider::from_min_max(
+            Vector3::new(x, y, z),
+            Vector3::new(x + size, y + size, z + size),
+        )
+    }
+
+    #[test]
+    fn query_returns_boxes_in_overlapping_cells() {
+        let mut grid = SpatialHashGrid::new(1.0);
+        let mut index = 0;
+        let mut expected = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                grid.insert(index, &cube(x as f64 + 0.25, y as f64 + 0.25, 0.25, 0.5));
+                if (1..=2).contains(&x) && y == 1 {
+                    expected.push(index);
+                }
+                index += 1;
+            }
+        }
+
+        let query = AlignedBoxCollider::from_min_max(
+            Vector3::new(1.9, 1.1, 0.1),
+            Vector3::new(2.1, 1.9, 0.9),
+        );
+        assert_eq!(expected, grid.query(&query));
+    }
+
+    #[test]
+    fn box_spanning_cells_is_reported_once() {
+        let mut grid = SpatialHashGrid::new(1.0);
+        grid.insert(7, &cube(-0.5, -0.5, -0.5, 2.0));
+
+        assert_eq!(vec![7], grid.query(&cube(-1.0, -1.0, -1.0, 3.0)));
+        assert_eq!(vec![7], grid.query(&cube(1.2, 1.2, 1.2, 0.1)));
+        assert!(grid.query(&cube(5.0, 5.0, 5.0, 0.1)).is_empty());
+
+        grid.clear();
+        assert!(grid.query(&cube(0.0, 0.0, 0.0, 0.1)).is_empty());
+    }
+}
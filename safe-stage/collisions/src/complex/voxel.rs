@@ -0,0 +1,244 @@
+use crate::collides_group_impl;
+use crate::collides_reverse_impl;
+use crate::common::{Bounded, Collides};
+use crate::primitive::{
+    AlignedBoxCollider, OrientedBoxCollider, PointCollider, SphereCollider, TriangleCollider,
+};
+use maths::{bounds_of, Vector3};
+use std::collections::HashSet;
+
+type Cell = (i64, i64, i64);
+
+/// Relative inflation of the cells during rasterization.
+const RASTERIZATION_MARGIN: f64 = 1e-6;
+
+/// # Voxel Grid
+/// Uniform grid of cubic cells marking the cells touched by a triangle mesh.
+///
+/// Faster than a triangle BVH for boolean collision against very complex meshes,
+/// but **conservative**: a cell is occupied if any part of it touches a triangle,
+/// so queries near the surface may report collisions up to one cell away from the mesh.
+/// Collisions with the mesh itself are never missed.
+///
+/// Only the surface is voxelized, the same as for a triangle BVH,
+/// colliders fully inside a closed mesh do not collide with it.
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    cell_size: f64,
+    occupied: HashSet<Cell>,
+}
+
+impl VoxelGrid {
+    /// Voxelizes the triangles into cubic cells of the given size.
+    ///
+    /// Every cell overlapping a triangle is marked occupied (conservative rasterization).
+    pub fn from_triangles(triangles: &[TriangleCollider], cell_size: f64) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "cell size must be positive"
+        );
+
+        let mut grid = Self {
+            cell_size,
+            occupied: HashSet::new(),
+        };
+        //  cells only touching a triangle on their boundary must be occupied as well
+        let margin = Vector3::new(1.0, 1.0, 1.0) * (cell_size * RASTERIZATION_MARGIN);
+        for triangle in triangles {
+            let (a, b, c) = triangle.points();
+            let (min, max) = bounds_of(&[*a, *b, *c]).expect("triangle has points");
+            let touched = grid
+                .cells_between(min - margin, max + margin)
+                .filter(|cell| {
+                    let cell = grid.cell_box(cell);
+                    let inflated =
+                        AlignedBoxCollider::from_min_max(cell.min() - margin, cell.max() + margin);
+                    OrientedBoxCollider::from(&inflated).collides_with(triangle)
+                })
+                .collect::<Vec<_>>();
+            grid.occupied.extend(touched);
+        }
+
+        grid
+    }
+
+    /// Returns the size of a single cell.
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// Returns the number of occupied cells.
+    pub fn occupied_count(&self) -> usize {
+        self.occupied.len()
+    }
+
+    fn cell_of(&self, point: Vector3) -> Cell {
+        (
+            (point.x() / self.cell_size).floor() as i64,
+            (point.y() / self.cell_size).floor() as i64,
+            (point.z() / self.cell_size).floor() as i64,
+        )
+    }
+
+    fn cell_box(&self, (x, y, z): &Cell) -> AlignedBoxCollider {
+        let min = Vector3::new(*x as f64, *y as f64, *z as f64) * self.cell_size;
+        AlignedBoxCollider::from_min_max(min, min + Vector3::new(1.0, 1.0, 1.0) * self.cell_size)
+    }
+
+    fn cells_between(&self, min: Vector3, max: Vector3) -> impl Iterator<Item = Cell> {
+        let (min_x, min_y, min_z) = self.cell_of(min);
+        let (max_x, max_y, max_z) = self.cell_of(max);
+        (min_x..=max_x).flat_map(move |x| {
+            (min_y..=max_y).flat_map(move |y| (min_z..=max_z).map(move |z| (x, y, z)))
+        })
+    }
+
+    fn occupied_cells_of<'a>(
+        &'a self,
+        bounded: &impl Bounded,
+    ) -> impl Iterator<Item = AlignedBoxCollider> + 'a {
+        let (min, max) = (bounded.min(), bounded.max());
+        let (low, high) = (self.cell_of(min), self.cell_of(max));
+        let span = |from: i64, to: i64| (i128::from(to) - i128::from(from) + 1).max(0) as u128;
+        let range = span(low.0, high.0)
+            .saturating_mul(span(low.1, high.1))
+            .saturating_mul(span(low.2, high.2));
+
+        //  Ranges with more cells than are occupied are cheaper to check against the occupied cells
+        let (by_range, by_occupied) = if range > self.occupied.len() as u128 {
+            let in_range = move |(x, y, z): &Cell| {
+                (low.0..=high.0).contains(x)
+                    && (low.1..=high.1).contains(y)
+                    && (low.2..=high.2).contains(z)
+            };
+            (None, Some(self.occupied.iter().copied().filter(in_range)))
+        } else {
+            let cells = self
+                .cells_between(min, max)
+                .filter(|cell| self.occupied.contains(cell));
+            (Some(cells), None)
+        };
+
+        by_range
+            .into_iter()
+            .flatten()
+            .chain(by_occupied.into_iter().flatten())
+            .map(|cell| self.cell_box(&cell))
+    }
+}
+
+impl Collides<AlignedBoxCollider> for VoxelGrid {
+    fn collides_with(&self, other: &AlignedBoxCollider) -> bool {
+        self.occupied_cells_of(other).next().is_some()
+    }
+}
+
+impl Collides<OrientedBoxCollider> for VoxelGrid {
+    fn collides_with(&self, other: &OrientedBoxCollider) -> bool {
+        self.occupied_cells_of(other)
+            .any(|cell| other.collides_with(&cell))
+    }
+}
+
+impl Collides<SphereCollider> for VoxelGrid {
+    fn collides_with(&self, other: &SphereCollider) -> bool {
+        self.occupied_cells_of(other)
+            .any(|cell| cell.collides_with(other))
+    }
+}
+
+impl Collides<PointCollider> for VoxelGrid {
+    fn collides_with(&self, other: &PointCollider) -> bool {
+        self.occupied.contains(&self.cell_of(other.position()))
+    }
+}
+
+collides_reverse_impl!(
+    VoxelGrid => AlignedBoxCollider,
+    VoxelGrid => OrientedBoxCollider,
+    VoxelGrid => SphereCollider,
+    VoxelGrid => PointCollider,
+);
+
+collides_group_impl!(
+    VoxelGrid, AlignedBoxCollider
+    VoxelGrid, OrientedBoxCollider
+    VoxelGrid, SphereCollider
+    VoxelGrid, PointCollider
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::box_triangles;
+
+    fn voxelized_cube() -> VoxelGrid {
+        VoxelGrid::from_triangles(
+            &box_triangles(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0)),
+            0.25,
+        )
+    }
+
+    fn point(x: f64, y: f64, z: f64) -> PointCollider {
+        PointCollider::new(Vector3::new(x, y, z))
+    }
+
+    #[test]
+    fn voxelized_cube_is_conservative() {
+        let grid = voxelized_cube();
+
+        //  on the surface
+        assert!(grid.collides_with(&point(1.0, 0.3, -0.6)));
+        assert!(grid.collides_with(&SphereCollider::new(Vector3::new(0.0, 0.0, 1.0), 0.01)));
+        //  interior and exterior away from the surface
+        assert!(!grid.collides_with(&point(0.0, 0.0, 0.0)));
+        assert!(!grid.collides_with(&point(2.0, 0.0, 0.0)));
+        assert!(!grid.collides_with(&AlignedBoxCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0)
+        )));
+        //  near the surface, over-reported within a cell
+        assert!(grid.collides_with(&point(1.1, 0.3, -0.6)));
+        assert!(grid.collides_with(&point(0.9, 0.3, -0.6)));
+        assert!(!grid.collides_with(&point(1.3, 0.3, -0.6)));
+        //  crossing the surface
+        assert!(AlignedBoxCollider::new(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.05, 0.05, 0.05)
+        )
+        .collides_with(&grid));
+    }
+
+    #[test]
+    fn voxelized_cube_never_misses_mesh_collisions() {
+        let triangles = box_triangles(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let grid = VoxelGrid::from_triangles(&triangles, 0.25);
+
+        for i in -10..=10 {
+            for j in -10..=10 {
+                let center = Vector3::new(i as f64 * 0.13, j as f64 * 0.11, 0.97);
+                let probe = OrientedBoxCollider::from(&AlignedBoxCollider::new(
+                    center,
+                    Vector3::new(0.1, 0.1, 0.1),
+                ));
+                if triangles.iter().any(|t| probe.collides_with(t)) {
+                    assert!(grid.collides_with(&probe), "missed at {center:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_query_checks_occupied_cells() {
+        let grid = voxelized_cube();
+        let around = AlignedBoxCollider::new(Vector3::ZERO, Vector3::new(1e9, 1e9, 1e9));
+        let aside = AlignedBoxCollider::new(
+            Vector3::new(1e9, 0.0, 0.0),
+            Vector3::new(1e9 - 2.0, 1e9, 1e9),
+        );
+
+        assert!(grid.collides_with(&around));
+        assert!(!grid.collides_with(&aside));
+        assert!(grid.collides_with(&SphereCollider::new(Vector3::ZERO, 1e9)));
+    }
+}
@@ -6,6 +6,10 @@ use crate::primitive::AlignedBoxCollider;
 pub mod common;
 pub mod complex;
 pub mod primitive;
+#[cfg(any(test, feature = "scene"))]
+pub mod scene;
+#[cfg(feature = "serde")]
+pub mod versioned;
 
 ///  BVH with AABB is currently the most performant implementation for this use-case.
 pub type PrimaryCollider = complex::bvh_recursive::BvhRecursive<AlignedBoxCollider>;
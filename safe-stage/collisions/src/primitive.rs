@@ -11,10 +11,14 @@
 //! - implementations are sorted based on this hierarchy in ascending order
 //! - every collider implements collision detection for itself and for all colliders with same or lower complexity
 //! - every collider takes collision detection for all colliders with higher complexity
+//!   through [crate::collides_reverse_impl]
 
-mod algo;
+pub(crate) mod algo;
 mod aligned_box;
+#[cfg(feature = "simd")]
+mod batch;
 mod collider;
+mod convex_hull;
 mod oriented_box;
 mod point;
 mod sphere;
@@ -23,6 +27,9 @@ mod triangle;
 pub use collider::Collider;
 
 pub use aligned_box::AlignedBoxCollider;
+#[cfg(feature = "simd")]
+pub use batch::collides_batch;
+pub use convex_hull::ConvexHullCollider;
 pub use oriented_box::OrientedBoxCollider;
 pub use point::PointCollider;
 pub use sphere::SphereCollider;
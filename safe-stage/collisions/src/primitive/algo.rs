@@ -1,4 +1,10 @@
+pub mod distance;
+pub mod epa;
+pub mod gjk;
 pub mod guigue_2003;
+pub mod obb;
+pub mod ray;
+pub mod sphere;
 
 #[cfg(test)]
 mod tri_dataset;
@@ -0,0 +1,184 @@
+//! Closest point queries between triangles,
+//! based on Real-Time Collision Detection by Christer Ericson.
+
+use maths::Vector3;
+
+/// Returns the smallest distance between two non-intersecting triangles.
+///
+/// For non-intersecting triangles, the closest pair of points lies either
+/// on a vertex and a face, or on two edges.
+pub fn tri_tri_distance(t1: [Vector3; 3], t2: [Vector3; 3]) -> f64 {
+    let mut best = f64::INFINITY;
+    for p in t1 {
+        best = best.min((p - closest_point_on_triangle(p, t2)).len());
+    }
+    for p in t2 {
+        best = best.min((p - closest_point_on_triangle(p, t1)).len());
+    }
+    for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+        for (k, l) in [(0, 1), (1, 2), (2, 0)] {
+            best = best.min(segment_segment_distance(t1[i], t1[j], t2[k], t2[l]));
+        }
+    }
+
+    best
+}
+
+/// Returns the point on the triangle closest to the point `p`.
+pub fn closest_point_on_triangle(p: Vector3, [a, b, c]: [Vector3; 3]) -> Vector3 {
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Squared length under which a segment is treated as a point.
+const DEGENERATE_EPSILON: f64 = 1e-24;
+
+/// Returns the smallest distance between segments `p1q1` and `p2q2`.
+///
+/// Segments of zero length are treated as points.
+pub fn segment_segment_distance(p1: Vector3, q1: Vector3, p2: Vector3, q2: Vector3) -> f64 {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.len2();
+    let e = d2.len2();
+    let f = d2.dot(&r);
+
+    if a <= DEGENERATE_EPSILON && e <= DEGENERATE_EPSILON {
+        return r.len();
+    }
+    if a <= DEGENERATE_EPSILON {
+        let t = (f / e).clamp(0.0, 1.0);
+        return (p1 - (p2 + d2 * t)).len();
+    }
+
+    let c = d1.dot(&r);
+    if e <= DEGENERATE_EPSILON {
+        let s = (-c / a).clamp(0.0, 1.0);
+        return ((p1 + d1 * s) - p2).len();
+    }
+
+    let b = d1.dot(&d2);
+    let denom = a * e - b * b;
+    let mut s = if denom > 0.0 {
+        ((b * f - c * e) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+
+    ((p1 + d1 * s) - (p2 + d2 * t)).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::*;
+
+    const TRIANGLE: [Vector3; 3] = [
+        Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+    ];
+
+    #[test]
+    fn closest_point_regions() {
+        let cases = [
+            (Vector3::new(0.2, 0.2, 3.0), Vector3::new(0.2, 0.2, 0.0)),
+            (Vector3::new(-1.0, -1.0, 0.0), Vector3::new(0.0, 0.0, 0.0)),
+            (Vector3::new(0.5, -1.0, 1.0), Vector3::new(0.5, 0.0, 0.0)),
+            (Vector3::new(1.0, 1.0, 0.0), Vector3::new(0.5, 0.5, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            let actual = closest_point_on_triangle(point, TRIANGLE);
+            assert_float_absolute_eq!(0.0, (expected - actual).len());
+        }
+    }
+
+    #[test]
+    fn crossing_segments() {
+        let distance = segment_segment_distance(
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, -1.0, 2.0),
+            Vector3::new(0.0, 1.0, 2.0),
+        );
+        assert_float_absolute_eq!(2.0, distance);
+    }
+
+    #[test]
+    fn zero_length_segments_are_points() {
+        let point = Vector3::new(0.5, 2.0, 0.0);
+        let (p, q) = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+
+        assert_float_absolute_eq!(2.0, segment_segment_distance(point, point, p, q));
+        assert_float_absolute_eq!(2.0, segment_segment_distance(p, q, point, point));
+        assert_float_absolute_eq!(
+            (point - p).len(),
+            segment_segment_distance(point, point, p, p)
+        );
+    }
+
+    #[test]
+    fn parallel_triangles() {
+        let other = TRIANGLE.map(|v| v + Vector3::new(0.0, 0.0, 1.5));
+        assert_float_absolute_eq!(1.5, tri_tri_distance(TRIANGLE, other));
+    }
+
+    #[test]
+    fn edge_to_edge_triangles() {
+        let other = [
+            Vector3::new(1.0, 1.0, -1.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(3.0, 3.0, 0.0),
+        ];
+        assert_float_absolute_eq!(0.5_f64.sqrt(), tri_tri_distance(TRIANGLE, other));
+    }
+}
@@ -0,0 +1,258 @@
+//! Penetration of two intersecting convex shapes
+//! by the Expanding Polytope Algorithm.
+//!
+//! Starts from the final [GJK](super::gjk) simplex and expands it towards the boundary
+//! of the Minkowski difference, the nearest boundary face gives the penetration.
+
+use crate::primitive::algo::gjk::gjk_simplex;
+use maths::Vector3;
+
+/// Upper limit of polytope expansions, the nearest face found so far is returned when reached.
+const EPA_MAX_ITERATIONS: usize = 64;
+
+/// Distance under which the expanded face is considered to lie on the boundary.
+const EPA_TOLERANCE: f64 = 1e-12;
+
+/// Squared length under which a vector is considered degenerate.
+const EPA_EPSILON: f64 = 1e-24;
+
+/// Returns the shortest translation of the first shape after which the shapes only touch,
+/// as a unit direction and a depth, or `None` if the shapes do not intersect.
+///
+/// Flat shapes with a flat Minkowski difference, e.g. coplanar triangles,
+/// are separated by any translation out of their plane, the depth is `0`.
+/// Shapes degenerated into a segment or a point have no defined direction and result in `None`.
+pub fn epa_penetration<A, B>(support_a: A, support_b: B) -> Option<(Vector3, f64)>
+where
+    A: Fn(&Vector3) -> Vector3,
+    B: Fn(&Vector3) -> Vector3,
+{
+    let support = |d: &Vector3| support_a(d) - support_b(&-d);
+    let simplex = gjk_simplex(support)?;
+
+    let mut vertices = match enclose_origin(simplex, &support) {
+        Ok(tetrahedron) => tetrahedron,
+        Err(Some(normal)) => return Some((normal, 0.0)),
+        Err(None) => return None,
+    };
+
+    let mut faces = Vec::new();
+    for (a, b, c, opposite) in [(0, 1, 2, 3), (0, 3, 1, 2), (0, 2, 3, 1), (1, 3, 2, 0)] {
+        let normal = (vertices[b] - vertices[a]).cross(&(vertices[c] - vertices[a]));
+        if normal.dot(&(vertices[opposite] - vertices[a])) > 0.0 {
+            faces.push([a, c, b]);
+        } else {
+            faces.push([a, b, c]);
+        }
+    }
+
+    let mut nearest = (Vector3::ZERO, f64::INFINITY);
+    for _ in 0..EPA_MAX_ITERATIONS {
+        nearest = faces
+            .iter()
+            .filter_map(|f| face_plane(&vertices, f))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        let (normal, distance) = nearest;
+        let point = support(&normal);
+        if point.dot(&normal) - distance <= EPA_TOLERANCE {
+            break;
+        }
+
+        //  Faces seen from the new point are replaced by faces connecting it to their horizon
+        let (visible, kept): (Vec<_>, Vec<_>) = faces
+            .into_iter()
+            .partition(|f| face_plane(&vertices, f).is_none_or(|(n, d)| n.dot(&point) - d > 0.0));
+        let edges = visible
+            .iter()
+            .flat_map(|f| [(f[0], f[1]), (f[1], f[2]), (f[2], f[0])])
+            .collect::<Vec<_>>();
+        let horizon = edges
+            .iter()
+            .filter(|(a, b)| !edges.contains(&(*b, *a)))
+            .copied()
+            .collect::<Vec<_>>();
+
+        vertices.push(point);
+        let new = vertices.len() - 1;
+        faces = kept;
+        faces.extend(horizon.into_iter().map(|(a, b)| [a, b, new]));
+    }
+
+    let (normal, distance) = nearest;
+    Some((-normal, distance.max(0.0)))
+}
+
+/// Outward unit normal and the distance from the origin of the plane of the `face`,
+/// `None` for a degenerate face.
+fn face_plane(vertices: &[Vector3], face: &[usize; 3]) -> Option<(Vector3, f64)> {
+    let [a, b, c] = face.map(|i| vertices[i]);
+    let normal = (b - a).cross(&(c - a));
+    if normal.len2() < EPA_EPSILON {
+        return None;
+    }
+
+    let normal = normal.normalize();
+    Some((normal, normal.dot(&a)))
+}
+
+/// Extends the GJK `simplex` into a tetrahedron containing the origin.
+///
+/// Fails with the normal of the Minkowski difference when it is flat,
+/// or with `None` when it is a segment or a point.
+fn enclose_origin<S>(
+    mut simplex: Vec<Vector3>,
+    support: &S,
+) -> Result<Vec<Vector3>, Option<Vector3>>
+where
+    S: Fn(&Vector3) -> Vector3,
+{
+    let axes = [
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(0.0, 0.0, 1.0),
+    ];
+
+    //  The origin lies on the simplex, so it stays inside when points are only added
+    if simplex.len() == 1 {
+        let first = simplex[0];
+        let point = axes
+            .iter()
+            .flat_map(|a| [support(a), support(&-a)])
+            .max_by(|a, b| (a - first).len2().total_cmp(&(b - first).len2()))
+            .expect("axes are not empty");
+        if (point - first).len2() < EPA_EPSILON {
+            return Err(None);
+        }
+        simplex.push(point);
+    }
+
+    if simplex.len() == 2 {
+        let line = simplex[1] - simplex[0];
+        let point = axes
+            .iter()
+            .map(|a| line.cross(a))
+            .filter(|d| d.len2() >= EPA_EPSILON)
+            .flat_map(|d| [support(&d), support(&-d)])
+            .max_by(|a, b| {
+                let distance = |p: &Vector3| line.cross(&(p - simplex[0])).len2();
+                distance(a).total_cmp(&distance(b))
+            })
+            .expect("a segment is perpendicular to at least two axes");
+        if line.cross(&(point - simplex[0])).len2() < EPA_EPSILON {
+            return Err(None);
+        }
+        simplex.push(point);
+    }
+
+    if simplex.len() == 3 {
+        let normal = (simplex[1] - simplex[0]).cross(&(simplex[2] - simplex[0]));
+        if normal.len2() < EPA_EPSILON {
+            return Err(None);
+        }
+        let normal = normal.normalize();
+        let point = [support(&normal), support(&-normal)]
+            .into_iter()
+            .max_by(|a, b| {
+                let distance = |p: &Vector3| normal.dot(&(p - simplex[0])).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .expect("two candidates");
+        if normal.dot(&(point - simplex[0])).abs() < EPA_TOLERANCE {
+            return Err(Some(normal));
+        }
+        simplex.push(point);
+    }
+
+    Ok(simplex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(center: Vector3) -> impl Fn(&Vector3) -> Vector3 {
+        move |d: &Vector3| {
+            center
+                + Vector3::new(
+                    0.5_f64.copysign(d.x()),
+                    0.5_f64.copysign(d.y()),
+                    0.5_f64.copysign(d.z()),
+                )
+        }
+    }
+
+    fn triangle(points: [Vector3; 3]) -> impl Fn(&Vector3) -> Vector3 {
+        move |d: &Vector3| {
+            *points
+                .iter()
+                .max_by(|a, b| a.dot(d).total_cmp(&b.dot(d)))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn separated_cubes_have_no_penetration() {
+        assert_eq!(
+            None,
+            epa_penetration(cube(Vector3::ZERO), cube(Vector3::new(1.5, 0.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn overlapping_cubes_separate_along_shallowest_axis() {
+        let (direction, depth) =
+            epa_penetration(cube(Vector3::ZERO), cube(Vector3::new(0.8, 0.3, -0.1))).unwrap();
+
+        assert!((depth - 0.2).abs() < 1e-9);
+        assert!((direction.x() + 1.0).abs() < 1e-9);
+        assert!(direction.y().abs() < 1e-9);
+        assert!(direction.z().abs() < 1e-9);
+    }
+
+    #[test]
+    fn touching_cubes_have_zero_depth() {
+        let (_, depth) =
+            epa_penetration(cube(Vector3::ZERO), cube(Vector3::new(1.0, 0.0, 0.0))).unwrap();
+
+        assert!(depth.abs() < 1e-9);
+    }
+
+    #[test]
+    fn crossing_triangle_is_pushed_out_of_the_plane() {
+        let floor = triangle([
+            Vector3::new(-5.0, -5.0, 0.0),
+            Vector3::new(5.0, -5.0, 0.0),
+            Vector3::new(0.0, 5.0, 0.0),
+        ]);
+        let wall = triangle([
+            Vector3::new(-1.0, 0.0, -0.01),
+            Vector3::new(1.0, 0.0, -0.01),
+            Vector3::new(0.0, 0.0, 2.0),
+        ]);
+
+        let (direction, depth) = epa_penetration(wall, floor).unwrap();
+
+        assert!((depth - 0.01).abs() < 1e-9);
+        assert!((direction.z() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn coplanar_triangles_separate_out_of_plane() {
+        let first = triangle([
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        ]);
+        let second = triangle([
+            Vector3::new(0.2, 0.2, 0.0),
+            Vector3::new(1.2, 0.2, 0.0),
+            Vector3::new(0.2, 1.2, 0.0),
+        ]);
+
+        let (direction, depth) = epa_penetration(first, second).unwrap();
+
+        assert_eq!(0.0, depth);
+        assert!((direction.z().abs() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,186 @@
+//! Boolean intersection test of two convex shapes
+//! by the Gilbert-Johnson-Keerthi algorithm.
+//!
+//! Shapes are given only by their support functions,
+//! returning the furthest point of the shape in a given direction.
+
+use maths::Vector3;
+
+/// Upper limit of iterations, the shapes are reported as intersecting when reached.
+const GJK_MAX_ITERATIONS: usize = 64;
+
+/// Squared length under which the search direction is considered degenerate,
+/// meaning the origin lies on the current simplex.
+const GJK_EPSILON: f64 = 1e-18;
+
+/// Returns true if the convex shapes given by their support functions intersect.
+///
+/// Touching shapes are reported as intersecting.
+pub fn gjk_intersects<A, B>(support_a: A, support_b: B) -> bool
+where
+    A: Fn(&Vector3) -> Vector3,
+    B: Fn(&Vector3) -> Vector3,
+{
+    gjk_simplex(|d| support_a(d) - support_b(&-d)).is_some()
+}
+
+/// Returns the final simplex of the Minkowski difference given by its `support` function
+/// if it contains the origin, meaning the shapes intersect.
+///
+/// The origin lies on the boundary of the simplex when it has less than 4 points.
+pub(crate) fn gjk_simplex<S>(support: S) -> Option<Vec<Vector3>>
+where
+    S: Fn(&Vector3) -> Vector3,
+{
+    let first = support(&Vector3::new(1.0, 0.0, 0.0));
+    let mut simplex = vec![first];
+    let mut direction = -first;
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        if direction.len2() < GJK_EPSILON {
+            return Some(simplex);
+        }
+
+        let point = support(&direction);
+        if point.dot(&direction) < 0.0 {
+            return None;
+        }
+
+        simplex.push(point);
+        match nearest_simplex(&mut simplex) {
+            Some(d) => direction = d,
+            None => return Some(simplex),
+        }
+    }
+
+    Some(simplex)
+}
+
+/// Reduces the simplex to the feature nearest to the origin, the newest point is the last one.
+///
+/// Returns the next search direction or `None` if the simplex contains the origin.
+fn nearest_simplex(simplex: &mut Vec<Vector3>) -> Option<Vector3> {
+    match simplex.len() {
+        2 => Some(line(simplex)),
+        3 => triangle(simplex),
+        4 => tetrahedron(simplex),
+        _ => unreachable!("simplex has between 2 and 4 points"),
+    }
+}
+
+fn line(simplex: &mut Vec<Vector3>) -> Vector3 {
+    let (b, a) = (simplex[0], simplex[1]);
+    let ab = b - a;
+    let ao = -a;
+    if ab.dot(&ao) > 0.0 {
+        ab.cross(&ao).cross(&ab)
+    } else {
+        *simplex = vec![a];
+        ao
+    }
+}
+
+fn triangle(simplex: &mut Vec<Vector3>) -> Option<Vector3> {
+    let (c, b, a) = (simplex[0], simplex[1], simplex[2]);
+    let ab = b - a;
+    let ac = c - a;
+    let ao = -a;
+    let abc = ab.cross(&ac);
+
+    if abc.cross(&ac).dot(&ao) > 0.0 {
+        if ac.dot(&ao) > 0.0 {
+            *simplex = vec![c, a];
+            return Some(ac.cross(&ao).cross(&ac));
+        }
+        *simplex = vec![b, a];
+        return Some(line(simplex));
+    }
+
+    if ab.cross(&abc).dot(&ao) > 0.0 {
+        *simplex = vec![b, a];
+        return Some(line(simplex));
+    }
+
+    let side = abc.dot(&ao);
+    if side > 0.0 {
+        Some(abc)
+    } else if side < 0.0 {
+        Some(-abc)
+    } else {
+        None
+    }
+}
+
+fn tetrahedron(simplex: &mut Vec<Vector3>) -> Option<Vector3> {
+    let (d, c, b, a) = (simplex[0], simplex[1], simplex[2], simplex[3]);
+    let ao = -a;
+
+    for (p, q, opposite) in [(b, c, d), (c, d, b), (d, b, c)] {
+        let mut normal = (p - a).cross(&(q - a));
+        if normal.dot(&(opposite - a)) > 0.0 {
+            normal = -normal;
+        }
+        if normal.dot(&ao) > 0.0 {
+            *simplex = vec![q, p, a];
+            return triangle(simplex);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(center: Vector3) -> impl Fn(&Vector3) -> Vector3 {
+        move |d: &Vector3| {
+            center
+                + Vector3::new(
+                    0.5_f64.copysign(d.x()),
+                    0.5_f64.copysign(d.y()),
+                    0.5_f64.copysign(d.z()),
+                )
+        }
+    }
+
+    #[test]
+    fn overlapping_cubes() {
+        assert!(gjk_intersects(
+            cube(Vector3::ZERO),
+            cube(Vector3::new(0.5, 0.5, 0.5))
+        ));
+    }
+
+    #[test]
+    fn separated_cubes() {
+        assert!(!gjk_intersects(
+            cube(Vector3::ZERO),
+            cube(Vector3::new(1.5, 0.2, 0.0))
+        ));
+        assert!(!gjk_intersects(
+            cube(Vector3::ZERO),
+            cube(Vector3::new(1.1, 1.1, 1.1))
+        ));
+    }
+
+    #[test]
+    fn point_inside_cube() {
+        let point = |_: &Vector3| Vector3::new(0.1, -0.2, 0.3);
+        assert!(gjk_intersects(cube(Vector3::ZERO), point));
+    }
+
+    #[test]
+    fn matches_box_overlap() {
+        let offsets: [f64; 7] = [-1.3, -0.9, -0.4, 0.0, 0.35, 0.8, 1.2];
+        for x in offsets {
+            for y in offsets {
+                for z in offsets {
+                    let expected = x.abs() < 1.0 && y.abs() < 1.0 && z.abs() < 1.0;
+                    let actual = gjk_intersects(cube(Vector3::ZERO), cube(Vector3::new(x, y, z)));
+                    assert_eq!(expected, actual, "offset {x}, {y}, {z}");
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,10 @@ use maths::{Vector2, Vector3};
 
 const COPLANAR_EPSILON: f64 = 1e-16;
 
+/// Coplanarity tolerance relative to the magnitude of the orientation values,
+/// which are products of two edges of one triangle and an offset to the other triangle.
+const RELATIVE_COPLANAR_EPSILON: f64 = 1e-15;
+
 pub fn tri_tri_overlap_test_3d(
     p1: Vector3,
     q1: Vector3,
@@ -19,6 +23,51 @@ pub fn tri_tri_overlap_test_3d(
     p2: Vector3,
     q2: Vector3,
     r2: Vector3,
+) -> bool {
+    tri_tri_overlap_test_3d_eps(p1, q1, r1, p2, q2, r2, COPLANAR_EPSILON, COPLANAR_EPSILON)
+}
+
+/// Variant of [tri_tri_overlap_test_3d] with the coplanarity tolerance
+/// scaled by the size of the triangles and the offsets between them.
+///
+/// More robust for large triangles, independent of the distance from the origin.
+pub fn tri_tri_overlap_test_3d_relative(
+    p1: Vector3,
+    q1: Vector3,
+    r1: Vector3,
+    p2: Vector3,
+    q2: Vector3,
+    r2: Vector3,
+) -> bool {
+    let eps1 = relative_tolerance([p2, q2, r2], [p1, q1, r1]);
+    let eps2 = relative_tolerance([p1, q1, r1], [p2, q2, r2]);
+    tri_tri_overlap_test_3d_eps(p1, q1, r1, p2, q2, r2, eps1, eps2)
+}
+
+/// Tolerance of the orientation values of `points` against the plane of `triangle`,
+/// computed as `(point - triangle[2]) · (edge × edge)`.
+fn relative_tolerance(triangle: [Vector3; 3], points: [Vector3; 3]) -> f64 {
+    let [p, q, r] = triangle;
+    let edge = [q - p, r - q, p - r]
+        .iter()
+        .map(|e| e.len2())
+        .fold(0.0_f64, f64::max);
+    let offset = points
+        .iter()
+        .map(|point| (point - r).len())
+        .fold(0.0_f64, f64::max);
+    COPLANAR_EPSILON.max(RELATIVE_COPLANAR_EPSILON * edge * offset)
+}
+
+fn tri_tri_overlap_test_3d_eps(
+    p1: Vector3,
+    q1: Vector3,
+    r1: Vector3,
+    p2: Vector3,
+    q2: Vector3,
+    r2: Vector3,
+    eps1: f64,
+    eps2: f64,
 ) -> bool {
     let v1 = p2 - r2;
     let v2 = q2 - r2;
@@ -31,21 +80,9 @@ pub fn tri_tri_overlap_test_3d(
     let v1 = r1 - r2;
     let dr1 = v1.dot(&n2);
 
-    let dp1 = if dp1.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dp1
-    };
-    let dq1 = if dq1.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dq1
-    };
-    let dr1 = if dr1.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dr1
-    };
+    let dp1 = if dp1.abs() < eps1 { 0.0 } else { dp1 };
+    let dq1 = if dq1.abs() < eps1 { 0.0 } else { dq1 };
+    let dr1 = if dr1.abs() < eps1 { 0.0 } else { dr1 };
 
     if (dp1 * dq1) > 0.0 && (dp1 * dr1) > 0.0 {
         return false;
@@ -62,21 +99,9 @@ pub fn tri_tri_overlap_test_3d(
     let v1 = r2 - r1;
     let dr2 = v1.dot(&n1);
 
-    let dp2 = if dp2.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dp2
-    };
-    let dq2 = if dq2.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dq2
-    };
-    let dr2 = if dr2.abs() < COPLANAR_EPSILON {
-        0.0
-    } else {
-        dr2
-    };
+    let dp2 = if dp2.abs() < eps2 { 0.0 } else { dp2 };
+    let dq2 = if dq2.abs() < eps2 { 0.0 } else { dq2 };
+    let dr2 = if dr2.abs() < eps2 { 0.0 } else { dr2 };
 
     if (dp2 * dq2) > 0.0 && (dp2 * dr2) > 0.0 {
         return false;
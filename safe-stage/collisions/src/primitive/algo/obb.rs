@@ -0,0 +1,167 @@
+//! Fitting of oriented bounding boxes to point sets.
+
+use crate::primitive::OrientedBoxCollider;
+use maths::{Quaternion, Vector3};
+
+/// Sweeps of the Jacobi eigenvalue iteration, 3x3 matrices converge in a handful.
+const JACOBI_SWEEPS: usize = 32;
+
+/// Returns an oriented box containing all the `points`,
+/// with axes given by the principal components of the points.
+///
+/// Panics when `points` is empty.
+pub fn fit_obb(points: &[Vector3]) -> OrientedBoxCollider {
+    assert!(!points.is_empty(), "cannot fit a box to no points");
+
+    let (mean, covariance) = mean_and_covariance(points);
+    let [x, y, _] = principal_axes(covariance);
+    let z = x.cross(&y).normalize();
+    let axes = [x, y, z];
+
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for p in points {
+        let d = *p - mean;
+        for (i, axis) in axes.iter().enumerate() {
+            let projected = d.dot(axis);
+            min[i] = min[i].min(projected);
+            max[i] = max[i].max(projected);
+        }
+    }
+
+    let center = axes.iter().enumerate().fold(mean, |acc, (i, axis)| {
+        acc + axis * ((min[i] + max[i]) / 2.0)
+    });
+    let size = Vector3::new(max[0] - min[0], max[1] - min[1], max[2] - min[2]);
+    let rotation = Quaternion::from_basis(&x, &y, &z);
+
+    OrientedBoxCollider::new(center, size, rotation)
+}
+
+/// Returns the principal components of the `points` as unit vectors,
+/// ordered by decreasing variance along them and forming a right-handed basis.
+///
+/// Panics when `points` is empty.
+pub fn principal_components(points: &[Vector3]) -> [Vector3; 3] {
+    assert!(!points.is_empty(), "cannot analyze no points");
+
+    let (_, covariance) = mean_and_covariance(points);
+    let variance = |axis: &Vector3| {
+        let axis = [axis.x(), axis.y(), axis.z()];
+        (0..3)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .map(|(i, j)| axis[i] * covariance[i][j] * axis[j])
+            .sum::<f64>()
+    };
+
+    let mut axes = principal_axes(covariance);
+    axes.sort_by(|a, b| variance(b).total_cmp(&variance(a)));
+    axes[2] = axes[0].cross(&axes[1]).normalize();
+    axes
+}
+
+fn mean_and_covariance(points: &[Vector3]) -> (Vector3, [[f64; 3]; 3]) {
+    let count = points.len() as f64;
+    let mean = points.iter().fold(Vector3::ZERO, |acc, p| acc + p) / count;
+
+    let mut covariance = [[0.0; 3]; 3];
+    for p in points {
+        let d = *p - mean;
+        let d = [d.x(), d.y(), d.z()];
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value += d[i] * d[j] / count;
+            }
+        }
+    }
+
+    (mean, covariance)
+}
+
+/// Eigenvectors of a symmetric matrix by the cyclic Jacobi method, as unit vectors.
+fn principal_axes(mut a: [[f64; 3]; 3]) -> [Vector3; 3] {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..JACOBI_SWEEPS {
+        let off = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        if off < 1e-30 {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1e-300 {
+                continue;
+            }
+
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                let (rp, rq) = (row[p], row[q]);
+                row[p] = c * rp - s * rq;
+                row[q] = s * rp + c * rq;
+            }
+            let (rp, rq) = (a[p], a[q]);
+            a[p] = [0, 1, 2].map(|k| c * rp[k] - s * rq[k]);
+            a[q] = [0, 1, 2].map(|k| s * rp[k] + c * rq[k]);
+        }
+    }
+
+    [0, 1, 2].map(|i| Vector3::new(v[0][i], v[1][i], v[2][i]).normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maths::asserts::assert_vectors;
+    use maths::World;
+
+    #[test]
+    fn principal_axes_of_diagonal() {
+        let axes = principal_axes([[1.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 2.0]]);
+        assert_vectors(Vector3::new(1.0, 0.0, 0.0), axes[0].abs());
+        assert_vectors(Vector3::new(0.0, 1.0, 0.0), axes[1].abs());
+        assert_vectors(Vector3::new(0.0, 0.0, 1.0), axes[2].abs());
+    }
+
+    #[test]
+    fn principal_components_ordered_by_variance() {
+        let points = (0..=20)
+            .flat_map(|i| {
+                let t = i as f64 * 0.5;
+                [-1.0, 1.0].map(|s| Vector3::new(t + s * 0.5, t - s * 0.5, s * 0.2))
+            })
+            .collect::<Vec<_>>();
+
+        let [first, second, third] = principal_components(&points);
+
+        let diagonal = Vector3::new(1.0, 1.0, 0.0).normalize();
+        assert!((first.dot(&diagonal).abs() - 1.0).abs() < 1e-6);
+        assert!(second.dot(&diagonal).abs() < 1e-6);
+        assert_vectors(third, first.cross(&second));
+    }
+
+    #[test]
+    fn fit_contains_points() {
+        let points = (0..=10)
+            .map(|i| Vector3::new(i as f64, i as f64, 0.0))
+            .chain([Vector3::new(5.5, 4.5, 0.0), Vector3::new(5.0, 5.0, 0.5)])
+            .collect::<Vec<_>>();
+
+        let obb = fit_obb(&points);
+
+        let halfs = obb.size() / 2.0;
+        for p in points {
+            let local = World::new(p).to_local(&obb.center(), &obb.rotation());
+            let local = local.vector().abs();
+            assert!(local.x() <= halfs.x() + 1e-9);
+            assert!(local.y() <= halfs.y() + 1e-9);
+            assert!(local.z() <= halfs.z() + 1e-9);
+        }
+
+        let volume = obb.size().x() * obb.size().y() * obb.size().z();
+        assert!(volume < 0.2 * 10.0 * 10.0 * 0.5);
+    }
+}
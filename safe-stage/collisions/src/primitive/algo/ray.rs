@@ -0,0 +1,120 @@
+//! Ray queries used for inside and outside tests of closed meshes.
+
+use maths::Vector3;
+
+/// Returns the distance along the ray to the intersection with the triangle,
+/// based on the Möller–Trumbore algorithm.
+///
+/// Returns `None` for rays parallel with the triangle or intersections behind the origin.
+pub fn ray_triangle(origin: Vector3, direction: Vector3, [a, b, c]: [Vector3; 3]) -> Option<f64> {
+    const EPSILON: f64 = 1e-12;
+
+    let ab = b - a;
+    let ac = c - a;
+    let p = direction.cross(&ac);
+    let det = ab.dot(&p);
+    if det.abs() < EPSILON {
+        return None;
+    }
+
+    let inv = 1.0 / det;
+    let ao = origin - a;
+    let u = ao.dot(&p) * inv;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = ao.cross(&ab);
+    let v = direction.dot(&q) * inv;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = ac.dot(&q) * inv;
+    (t > EPSILON).then_some(t)
+}
+
+/// Returns `true` if the ray hits the box between `min` and `max`, slab method.
+pub fn ray_box(origin: Vector3, direction: Vector3, min: Vector3, max: Vector3) -> bool {
+    let mut near = 0.0_f64;
+    let mut far = f64::INFINITY;
+    for (o, d, lo, hi) in [
+        (origin.x(), direction.x(), min.x(), max.x()),
+        (origin.y(), direction.y(), min.y(), max.y()),
+        (origin.z(), direction.z(), min.z(), max.z()),
+    ] {
+        if d == 0.0 {
+            if o < lo || o > hi {
+                return false;
+            }
+            continue;
+        }
+
+        let (t1, t2) = ((lo - o) / d, (hi - o) / d);
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+        if near > far {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: [Vector3; 3] = [
+        Vector3::new(0.0, 0.0, 1.0),
+        Vector3::new(1.0, 0.0, 1.0),
+        Vector3::new(0.0, 1.0, 1.0),
+    ];
+
+    #[test]
+    fn ray_hits_triangle() {
+        let t = ray_triangle(
+            Vector3::new(0.2, 0.2, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            TRIANGLE,
+        );
+        assert_eq!(Some(1.0), t);
+    }
+
+    #[test]
+    fn ray_misses_triangle() {
+        let origin = Vector3::new(0.8, 0.8, 0.0);
+        assert_eq!(
+            None,
+            ray_triangle(origin, Vector3::new(0.0, 0.0, 1.0), TRIANGLE)
+        );
+        let behind = Vector3::new(0.2, 0.2, 2.0);
+        assert_eq!(
+            None,
+            ray_triangle(behind, Vector3::new(0.0, 0.0, 1.0), TRIANGLE)
+        );
+    }
+
+    #[test]
+    fn ray_box_slabs() {
+        let (min, max) = (Vector3::new(1.0, 1.0, 1.0), Vector3::new(2.0, 2.0, 2.0));
+        assert!(ray_box(
+            Vector3::ZERO,
+            Vector3::new(1.0, 1.0, 1.0),
+            min,
+            max
+        ));
+        assert!(!ray_box(
+            Vector3::ZERO,
+            Vector3::new(-1.0, 1.0, 1.0),
+            min,
+            max
+        ));
+        assert!(!ray_box(
+            Vector3::ZERO,
+            Vector3::new(1.0, 0.0, 0.0),
+            min,
+            max
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+//! Fitting of bounding spheres to point sets.
+
+use maths::Vector3;
+
+/// Returns the center and radius of a sphere containing all the `points`,
+/// using Ritter's approximation of the minimal enclosing sphere.
+///
+/// The radius is usually within 5 % of the minimal one and never more than twice as large.
+///
+/// Panics when `points` is empty.
+pub fn ritter_sphere(points: &[Vector3]) -> (Vector3, f64) {
+    assert!(!points.is_empty(), "cannot fit a sphere to no points");
+
+    let farthest_from = |from: Vector3| {
+        points
+            .iter()
+            .copied()
+            .max_by(|a, b| (*a - from).len2().total_cmp(&(*b - from).len2()))
+            .expect("points are not empty")
+    };
+    let y = farthest_from(points[0]);
+    let z = farthest_from(y);
+
+    let mut center = (y + z) / 2.0;
+    let mut radius = (z - y).len() / 2.0;
+    for p in points {
+        let distance = (*p - center).len();
+        if distance > radius {
+            let grown = (radius + distance) / 2.0;
+            center = center + (*p - center) * ((grown - radius) / distance);
+            radius = grown;
+        }
+    }
+
+    (center, radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_point() {
+        let (center, radius) = ritter_sphere(&[Vector3::new(1.0, 2.0, 3.0)]);
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), center);
+        assert_eq!(0.0, radius);
+    }
+
+    #[test]
+    fn encloses_all_points() {
+        let points = (0..50)
+            .map(|i| {
+                let t = i as f64 * 0.7;
+                Vector3::new(t.sin() * 3.0, (t * 1.3).cos(), t.cos() * 2.0 + 1.0)
+            })
+            .collect::<Vec<_>>();
+
+        let (center, radius) = ritter_sphere(&points);
+        for p in &points {
+            assert!((*p - center).len() <= radius + 1e-9);
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::collides_group_impl;
+use crate::collides_reverse_impl;
 use crate::common::{
-    Bounded, Collides, Projectable, Rotation, Transformation, Translation, Treeable,
+    Bounded, Collides, Contact, Projectable, Rotation, Transformation, Translation, Treeable,
 };
 use crate::primitive::{OrientedBoxCollider, PointCollider, SphereCollider, TriangleCollider};
 use itertools::Itertools;
-use maths::{Quaternion, Vector3};
+use maths::{bounds_of, Quaternion, Vector3};
 
 /// # Axis Aligned Box Collider
 /// Collision primitive for box that is aligned with the x,y,z axes.
@@ -47,8 +48,68 @@ impl AlignedBoxCollider {
         self.max - self.min
     }
 
+    /// Returns the box scaled about the origin by the `factors` along each axis.
+    ///
+    /// Negative factors mirror the box.
+    pub fn scale_nonuniform(&self, factors: Vector3) -> Self {
+        let a = self.min.component_mul(&factors);
+        let b = self.max.component_mul(&factors);
+        Self::from_min_max(a.minimized(&b), a.maximized(&b))
+    }
+
+    /// Returns the smallest box containing both boxes.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_min_max(
+            self.min.minimized(&other.min),
+            self.max.maximized(&other.max),
+        )
+    }
+
+    /// Returns the overlapping region of both boxes or `None` if they are disjoint.
+    ///
+    /// Boxes touching only by a face, an edge or a corner result in a flat box.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.maximized(&other.min);
+        let max = self.max.minimized(&other.max);
+
+        if min.x() <= max.x() && min.y() <= max.y() && min.z() <= max.z() {
+            Some(Self::from_min_max(min, max))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest distance between both boxes, `0.0` if they touch or overlap.
+    #[inline]
+    pub fn gap(&self, other: &Self) -> f64 {
+        let gap = (self.min - other.max).maximized(&(other.min - self.max));
+        gap.maximized(&Vector3::ZERO).len()
+    }
+
+    /// Classifies the contact of both boxes based on their gap or the distance
+    /// needed to push them apart along a single axis.
+    pub fn classify(&self, other: &Self) -> Contact {
+        match self.intersection(other) {
+            None => Contact::Clear {
+                distance: self.gap(other),
+            },
+            Some(_) => {
+                let escape = (self.max - other.min).minimized(&(other.max - self.min));
+                let depth = escape.x().min(escape.y()).min(escape.z());
+                if depth > 0.0 {
+                    Contact::Penetrating { depth }
+                } else {
+                    Contact::Touching
+                }
+            }
+        }
+    }
+
+    /// Returns the eight corners of the box.
     #[inline]
-    fn corners(&self) -> [Vector3; 8] {
+    pub fn corners(&self) -> [Vector3; 8] {
         [
             Vector3::new(self.min.x(), self.min.y(), self.min.z()),
             Vector3::new(self.max.x(), self.min.y(), self.min.z()),
@@ -64,23 +125,12 @@ impl AlignedBoxCollider {
 
 impl Treeable for AlignedBoxCollider {
     fn bound_children(&self, other: &Self) -> Self {
-        let min = self.min.minimized(&other.min);
-        let max = self.max.maximized(&other.max);
-        Self::from_min_max(min, max)
+        self.union(other)
     }
 
     fn bound_triangle(triangle: &TriangleCollider) -> Self {
         let (a, b, c) = triangle.points();
-        let min = Vector3::new(
-            a.x().min(b.x()).min(c.x()),
-            a.y().min(b.y()).min(c.y()),
-            a.z().min(b.z()).min(c.z()),
-        );
-        let max = Vector3::new(
-            a.x().max(b.x()).max(c.x()),
-            a.y().max(b.y()).max(c.y()),
-            a.z().max(b.z()).max(c.z()),
-        );
+        let (min, max) = bounds_of(&[*a, *b, *c]).unwrap();
         Self::from_min_max(min, max)
     }
 }
@@ -180,11 +230,9 @@ impl Collides<SphereCollider> for AlignedBoxCollider {
     }
 }
 
-impl Collides<OrientedBoxCollider> for AlignedBoxCollider {
-    fn collides_with(&self, other: &OrientedBoxCollider) -> bool {
-        other.collides_with(self)
-    }
-}
+collides_reverse_impl!(
+    OrientedBoxCollider => AlignedBoxCollider,
+);
 
 collides_group_impl!(
     AlignedBoxCollider, PointCollider
@@ -268,6 +316,95 @@ mod tests {
         assert_quaternion(rotation, transformed.rotation());
     }
 
+    #[test]
+    fn union_offset_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(1.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+
+        let union = aabb.union(&other_aabb);
+
+        assert_vectors(Vector3::new(-0.5, -0.5, -0.5), union.min());
+        assert_vectors(Vector3::new(1.5, 0.5, 0.5), union.max());
+        assert_eq!(union, other_aabb.union(&aabb));
+    }
+
+    #[test]
+    fn intersection_overlapping_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(1.0, 1.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+
+        let intersection = aabb.intersection(&other_aabb).unwrap();
+
+        assert_vectors(Vector3::new(0.0, 0.0, -1.0), intersection.min());
+        assert_vectors(Vector3::new(1.0, 1.0, 1.0), intersection.max());
+        assert_eq!(Some(intersection), other_aabb.intersection(&aabb));
+    }
+
+    #[test]
+    fn intersection_disjoint_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(2.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+
+        assert_eq!(None, aabb.intersection(&other_aabb));
+    }
+
+    #[test]
+    fn classify_separated_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(4.0, 5.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+
+        assert_eq!(Contact::Clear { distance: 5.0 }, aabb.classify(&other_aabb));
+        assert_eq!(Contact::Clear { distance: 5.0 }, other_aabb.classify(&aabb));
+    }
+
+    #[test]
+    fn classify_touching_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(1.0, 0.5, 0.0), Vector3::new(1.0, 1.0, 1.0));
+
+        assert_eq!(Contact::Touching, aabb.classify(&other_aabb));
+    }
+
+    #[test]
+    fn classify_overlapping_boxes() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(1.5, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+
+        assert_eq!(
+            Contact::Penetrating { depth: 0.5 },
+            aabb.classify(&other_aabb)
+        );
+    }
+
+    #[test]
+    fn classify_contained_box() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(5.0, 5.0, 5.0), Vector3::new(10.0, 10.0, 10.0));
+        let other_aabb =
+            AlignedBoxCollider::new(Vector3::new(5.0, 5.0, 5.0), Vector3::new(2.0, 2.0, 2.0));
+
+        assert_eq!(
+            Contact::Penetrating { depth: 6.0 },
+            aabb.classify(&other_aabb)
+        );
+        assert_eq!(
+            Contact::Penetrating { depth: 6.0 },
+            other_aabb.classify(&aabb)
+        );
+    }
+
     #[test]
     fn aabb_corner_collide() {
         let aabb =
@@ -451,4 +588,29 @@ mod tests {
         assert!(aabb.collides_with(&sphere));
         assert!(sphere.collides_with(&aabb));
     }
+
+    #[test]
+    fn scale_nonuniform_stretches_x() {
+        let aabb =
+            AlignedBoxCollider::new(Vector3::new(1.0, 1.0, 1.0), Vector3::new(2.0, 2.0, 2.0));
+
+        let scaled = aabb.scale_nonuniform(Vector3::new(2.0, 1.0, 1.0));
+
+        assert_eq!(Vector3::new(4.0, 2.0, 2.0), scaled.size());
+        assert_eq!(Vector3::new(0.0, 0.0, 0.0), scaled.min());
+        assert_eq!(Vector3::new(4.0, 2.0, 2.0), scaled.max());
+    }
+
+    #[test]
+    fn scale_nonuniform_mirrors() {
+        let aabb = AlignedBoxCollider::from_min_max(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 1.0, 1.0),
+        );
+
+        let scaled = aabb.scale_nonuniform(Vector3::new(-1.0, 1.0, 1.0));
+
+        assert_eq!(Vector3::new(-2.0, 0.0, 0.0), scaled.min());
+        assert_eq!(Vector3::new(-1.0, 1.0, 1.0), scaled.max());
+    }
 }
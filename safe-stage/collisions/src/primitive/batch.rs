@@ -0,0 +1,125 @@
+use crate::common::{Bounded, Collides};
+use crate::primitive::AlignedBoxCollider;
+use maths::Axis;
+
+/// Number of boxes tested together, four `f64` fill a 256-bit register.
+const LANES: usize = 4;
+
+/// Tests every box of `boxes` against the `query`, same as [Collides::collides_with].
+///
+/// Boxes are tested in groups of [LANES] laid out per axis, so the comparisons of a group
+/// compile to vector instructions on stable Rust without `std::simd`.
+///
+/// ## Example
+/// ```
+/// use collisions::primitive::{collides_batch, AlignedBoxCollider};
+/// use maths::Vector3;
+///
+/// let boxes = [
+///     AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+///     AlignedBoxCollider::new(Vector3::new(5.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+/// ];
+/// let query = AlignedBoxCollider::new(Vector3::new(1.0, 1.0, 1.0), Vector3::new(1.0, 1.0, 1.0));
+///
+/// assert_eq!(vec![true, false], collides_batch(&boxes, &query));
+/// ```
+pub fn collides_batch(boxes: &[AlignedBoxCollider], query: &AlignedBoxCollider) -> Vec<bool> {
+    let query_min = query.min();
+    let query_max = query.max();
+    let mut result = Vec::with_capacity(boxes.len());
+
+    let chunks = boxes.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        let min = |axis| -> [f64; LANES] { std::array::from_fn(|i| chunk[i].min().get(axis)) };
+        let max = |axis| -> [f64; LANES] { std::array::from_fn(|i| chunk[i].max().get(axis)) };
+        let (min_x, min_y, min_z) = (min(Axis::X), min(Axis::Y), min(Axis::Z));
+        let (max_x, max_y, max_z) = (max(Axis::X), max(Axis::Y), max(Axis::Z));
+
+        let mut hits = [true; LANES];
+        for lane in 0..LANES {
+            hits[lane] = (min_x[lane] <= query_max.x())
+                & (max_x[lane] >= query_min.x())
+                & (min_y[lane] <= query_max.y())
+                & (max_y[lane] >= query_min.y())
+                & (min_z[lane] <= query_max.z())
+                & (max_z[lane] >= query_min.z());
+        }
+        result.extend_from_slice(&hits);
+    }
+
+    result.extend(remainder.iter().map(|b| b.collides_with(query)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maths::Vector3;
+
+    #[test]
+    fn batch_matches_scalar() {
+        let query =
+            AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+        let steps = [-3.0, -2.0, -1.5, 0.0, 1.5, 2.0, 3.0];
+        let boxes = steps
+            .iter()
+            .flat_map(|&x| steps.iter().map(move |&y| (x, y)))
+            .map(|(x, y)| {
+                AlignedBoxCollider::new(Vector3::new(x, y, 0.5 * x), Vector3::new(2.0, 2.0, 2.0))
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(0, boxes.len() % LANES);
+
+        let batch = collides_batch(&boxes, &query);
+
+        assert_eq!(boxes.len(), batch.len());
+        for (b, hit) in boxes.iter().zip(&batch) {
+            assert_eq!(b.collides_with(&query), *hit, "{b:?}");
+        }
+        assert!(batch.iter().any(|hit| *hit));
+        assert!(batch.iter().any(|hit| !*hit));
+    }
+
+    #[test]
+    fn touching_boxes_collide() {
+        let query = AlignedBoxCollider::from_min_max(Vector3::ZERO, Vector3::new(1.0, 1.0, 1.0));
+        let touching = [
+            AlignedBoxCollider::from_min_max(
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(2.0, 1.0, 1.0),
+            ),
+            AlignedBoxCollider::from_min_max(
+                Vector3::new(0.0, -1.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+            ),
+            AlignedBoxCollider::from_min_max(
+                Vector3::new(1.0, 1.0, 1.0),
+                Vector3::new(2.0, 2.0, 2.0),
+            ),
+            AlignedBoxCollider::from_min_max(
+                Vector3::new(1.0 + 1e-12, 0.0, 0.0),
+                Vector3::new(2.0, 1.0, 1.0),
+            ),
+            AlignedBoxCollider::from_min_max(
+                Vector3::new(0.0, 0.0, -2.0),
+                Vector3::new(1.0, 1.0, -1e-12),
+            ),
+        ];
+
+        let batch = collides_batch(&touching, &query);
+
+        let scalar = touching
+            .iter()
+            .map(|b| b.collides_with(&query))
+            .collect::<Vec<_>>();
+        assert_eq!(scalar, batch);
+        assert_eq!(vec![true, true, true, false, false], batch);
+    }
+
+    #[test]
+    fn empty_batch() {
+        let query = AlignedBoxCollider::from_min_max(Vector3::ZERO, Vector3::ZERO);
+        assert!(collides_batch(&[], &query).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::collides_group_impl;
+use crate::collides_reverse_impl;
+use crate::common::{Bounded, Collides, Rotation, Transformation, Translation};
+use crate::primitive::algo::gjk::gjk_intersects;
+use crate::primitive::TriangleCollider;
+use maths::{bounds_of, Quaternion, Vector3};
+
+/// # Convex Hull Collider
+/// Collision primitive for the convex hull of a set of points.
+///
+/// Collisions are checked with the GJK algorithm, so the hull faces are never constructed.
+///
+/// ## Example
+/// ```
+/// use collisions::primitive::ConvexHullCollider;
+/// use collisions::common::Collides;
+/// use maths::Vector3;
+///
+/// let hull1 = ConvexHullCollider::new(vec![
+///     Vector3::new(0.0, 0.0, 0.0),
+///     Vector3::new(1.0, 0.0, 0.0),
+///     Vector3::new(0.0, 1.0, 0.0),
+///     Vector3::new(0.0, 0.0, 1.0),
+/// ]);
+/// let hull2 = ConvexHullCollider::new(vec![
+///     Vector3::new(0.2, 0.2, 0.2),
+///     Vector3::new(2.0, 0.0, 0.0),
+///     Vector3::new(0.0, 2.0, 0.0),
+/// ]);
+///
+/// assert!(hull1.collides_with(&hull2));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHullCollider {
+    points: Vec<Vector3>,
+}
+
+impl ConvexHullCollider {
+    /// Creates a new `ConvexHullCollider` enclosing all the `points`.
+    ///
+    /// **Expects a non-empty list of points.**
+    pub fn new(points: Vec<Vector3>) -> Self {
+        assert!(!points.is_empty());
+        Self { points }
+    }
+
+    /// Creates a new `ConvexHullCollider` enclosing all the vertices of the `triangles`.
+    ///
+    /// **Expects a non-empty list of triangles.**
+    pub fn from_triangles(triangles: &[TriangleCollider]) -> Self {
+        let mut points = Vec::with_capacity(triangles.len() * 3);
+        for (a, b, c) in triangles.iter().map(TriangleCollider::points) {
+            for p in [a, b, c] {
+                if !points.contains(p) {
+                    points.push(*p);
+                }
+            }
+        }
+        Self::new(points)
+    }
+
+    /// Returns the points spanning the hull.
+    #[inline]
+    pub fn points(&self) -> &[Vector3] {
+        &self.points
+    }
+
+    /// Returns the furthest point of the hull in the given direction.
+    pub fn support(&self, direction: &Vector3) -> Vector3 {
+        *self
+            .points
+            .iter()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .expect("hull is not empty")
+    }
+
+    /// Returns true if the point lies inside or on the boundary of the hull.
+    pub fn contains(&self, point: &Vector3) -> bool {
+        gjk_intersects(|d| self.support(d), |_| *point)
+    }
+}
+
+impl Bounded for ConvexHullCollider {
+    fn min(&self) -> Vector3 {
+        bounds_of(&self.points).unwrap().0
+    }
+
+    fn max(&self) -> Vector3 {
+        bounds_of(&self.points).unwrap().1
+    }
+}
+
+impl Rotation for ConvexHullCollider {
+    fn rotate(&self, rotation: &Quaternion) -> Self {
+        self.rotate_around(rotation, &self.center())
+    }
+
+    fn rotate_around(&self, rotation: &Quaternion, pivot: &Vector3) -> Self {
+        Self::new(
+            self.points
+                .iter()
+                .map(|p| p.rotate_around(rotation, pivot))
+                .collect(),
+        )
+    }
+}
+
+impl Translation for ConvexHullCollider {
+    fn translate(&self, translation: &Vector3) -> Self {
+        Self::new(self.points.iter().map(|p| *p + translation).collect())
+    }
+}
+
+impl Transformation for ConvexHullCollider {
+    fn transform(&self, rotation: &Quaternion, pivot: &Vector3, translation: &Vector3) -> Self {
+        Self::new(
+            self.points
+                .iter()
+                .map(|p| p.rotate_around(rotation, pivot) + translation)
+                .collect(),
+        )
+    }
+}
+
+impl Collides<Self> for ConvexHullCollider {
+    fn collides_with(&self, other: &Self) -> bool {
+        gjk_intersects(|d| self.support(d), |d| other.support(d))
+    }
+}
+
+impl Collides<TriangleCollider> for ConvexHullCollider {
+    fn collides_with(&self, other: &TriangleCollider) -> bool {
+        let (a, b, c) = other.points();
+        let triangle = [*a, *b, *c];
+        gjk_intersects(
+            |d| self.support(d),
+            |d| {
+                *triangle
+                    .iter()
+                    .max_by(|p, q| p.dot(d).total_cmp(&q.dot(d)))
+                    .unwrap()
+            },
+        )
+    }
+}
+
+collides_reverse_impl!(
+    ConvexHullCollider => TriangleCollider,
+);
+
+collides_group_impl!(
+    ConvexHullCollider, ConvexHullCollider
+    ConvexHullCollider, TriangleCollider
+    TriangleCollider, ConvexHullCollider
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::OrientedBoxCollider;
+
+    fn box_hull(obb: &OrientedBoxCollider) -> ConvexHullCollider {
+        let half = obb.size() / 2.0;
+        let mut points = Vec::new();
+        for x in [-1.0, 1.0] {
+            for y in [-1.0, 1.0] {
+                for z in [-1.0, 1.0] {
+                    let corner =
+                        obb.center() + Vector3::new(half.x() * x, half.y() * y, half.z() * z);
+                    points.push(corner.rotate_around(&obb.rotation(), &obb.center()));
+                }
+            }
+        }
+        ConvexHullCollider::new(points)
+    }
+
+    #[test]
+    fn hulls_match_oriented_boxes() {
+        let base = OrientedBoxCollider::new(
+            Vector3::ZERO,
+            Vector3::new(2.0, 1.0, 1.0),
+            Quaternion::from_euler(&Vector3::new(0.3, 0.2, 0.1)),
+        );
+        let base_hull = box_hull(&base);
+        let mut hits = 0;
+        for i in 0..50 {
+            let t = i as f64;
+            let other = OrientedBoxCollider::new(
+                Vector3::new(t.sin() * 2.0, (t * 0.7).cos() * 1.5, (t * 1.3).sin()),
+                Vector3::new(1.0, 0.5, 1.5),
+                Quaternion::from_euler(&Vector3::new(t * 0.4, t * 0.9, t * 0.2)),
+            );
+
+            let expected = base.collides_with(&other);
+            assert_eq!(expected, base_hull.collides_with(&box_hull(&other)), "{i}");
+            hits += expected as usize;
+        }
+        assert!(hits > 0 && hits < 50);
+    }
+
+    #[test]
+    fn contains_points() {
+        let hull = ConvexHullCollider::new(vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ]);
+
+        assert!(hull.contains(&Vector3::new(0.1, 0.1, 0.1)));
+        assert!(hull.contains(&Vector3::new(0.5, 0.0, 0.0)));
+        assert!(!hull.contains(&Vector3::new(0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn triangle_against_hull() {
+        let hull = box_hull(&OrientedBoxCollider::new(
+            Vector3::ZERO,
+            Vector3::new(1.0, 1.0, 1.0),
+            Quaternion::IDENTITY,
+        ));
+        let crossing = TriangleCollider::new(
+            Vector3::new(-2.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.1, 0.0),
+            Vector3::new(0.0, 0.0, 2.0),
+        );
+        let outside = crossing.translate(&Vector3::new(0.0, 3.0, 0.0));
+
+        assert!(hull.collides_with(&crossing));
+        assert!(!outside.collides_with(&hull));
+    }
+}
@@ -1,8 +1,8 @@
 use crate::collides_group_impl;
 use crate::common::{Bounded, Collides, Projectable, Rotation, Transformation, Translation};
-use crate::primitive::{AlignedBoxCollider, PointCollider, SphereCollider};
+use crate::primitive::{AlignedBoxCollider, PointCollider, SphereCollider, TriangleCollider};
 use itertools::Itertools;
-use maths::{Quaternion, Vector3};
+use maths::{bounds_of, Local, Quaternion, Vector3, World};
 
 /// # Oriented Bounding Box Collider
 /// Collision primitive for box which has a rotation.
@@ -54,7 +54,8 @@ impl OrientedBoxCollider {
         self.rotation
     }
 
-    fn corners(&self) -> [Vector3; 8] {
+    /// Returns the eight corners of the box.
+    pub fn corners(&self) -> [Vector3; 8] {
         let half_size = self.size() / 2.0;
         let negative_pos = self.center - half_size;
         let positive_pos = self.center + half_size;
@@ -89,17 +90,11 @@ impl OrientedBoxCollider {
 
 impl Bounded for OrientedBoxCollider {
     fn min(&self) -> Vector3 {
-        self.corners()
-            .into_iter()
-            .reduce(|a, b| a.minimized(&b))
-            .unwrap()
+        bounds_of(&self.corners()).unwrap().0
     }
 
     fn max(&self) -> Vector3 {
-        self.corners()
-            .into_iter()
-            .reduce(|a, b| a.maximized(&b))
-            .unwrap()
+        bounds_of(&self.corners()).unwrap().1
     }
 }
 
@@ -181,26 +176,21 @@ impl Collides<Self> for OrientedBoxCollider {
 impl Collides<PointCollider> for OrientedBoxCollider {
     fn collides_with(&self, other: &PointCollider) -> bool {
         let halfs = self.size / 2.0;
-        let min = self.center - halfs;
-        let max = self.center + halfs;
-
-        let inverse_point = other.position().rotate(&self.rotation.conjugate());
+        let local = World::new(other.position()).to_local(&self.center, &self.rotation);
+        let local = local.vector().abs();
 
-        inverse_point >= min && inverse_point <= max
+        local.x() <= halfs.x() && local.y() <= halfs.y() && local.z() <= halfs.z()
     }
 }
 
 impl Collides<SphereCollider> for OrientedBoxCollider {
     fn collides_with(&self, other: &SphereCollider) -> bool {
         let halfs = self.size / 2.0;
-        let min = self.center - halfs;
-        let max = self.center + halfs;
-
-        let inverse_center = other.center().rotate(&self.rotation.conjugate());
-        let clamped = inverse_center.clamp(&min, &max);
+        let local = World::new(other.center()).to_local(&self.center, &self.rotation);
+        let clamped = Local::new(local.vector().clamp(&-halfs, &halfs));
 
-        let closest = clamped.rotate(&self.rotation);
-        let distance = (closest - other.center()).len();
+        let closest = clamped.to_world(&self.center, &self.rotation);
+        let distance = (closest.vector() - other.center()).len();
 
         distance <= other.radius()
     }
@@ -238,11 +228,43 @@ impl Collides<AlignedBoxCollider> for OrientedBoxCollider {
     }
 }
 
+impl Collides<TriangleCollider> for OrientedBoxCollider {
+    fn collides_with(&self, other: &TriangleCollider) -> bool {
+        let (ax, ay, az) = self.separating_axes();
+
+        let (a, b, c) = other.points();
+        let ab = *b - a;
+        let bc = *c - b;
+        let ca = *a - c;
+
+        let axes = [
+            ax,
+            ay,
+            az,
+            ab.cross(&bc),
+            ax.cross(&ab),
+            ax.cross(&bc),
+            ax.cross(&ca),
+            ay.cross(&ab),
+            ay.cross(&bc),
+            ay.cross(&ca),
+            az.cross(&ab),
+            az.cross(&bc),
+            az.cross(&ca),
+        ];
+
+        axes.iter()
+            .map(Vector3::normalize)
+            .all(|axis| self.intersects(other, &axis))
+    }
+}
+
 collides_group_impl!(
     OrientedBoxCollider, PointCollider
     OrientedBoxCollider, SphereCollider
     OrientedBoxCollider, AlignedBoxCollider
     OrientedBoxCollider, OrientedBoxCollider
+    OrientedBoxCollider, TriangleCollider
 );
 
 #[cfg(test)]
@@ -501,6 +523,31 @@ mod tests {
         assert!(!point.collides_with(&obb));
     }
 
+    #[test]
+    fn point_off_center_collide() {
+        let obb = OrientedBoxCollider::new(
+            Vector3::new(5.0, 0.0, 0.0),
+            Vector3::new(4.0, 2.0, 2.0),
+            Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90.0_f64.to_radians())),
+        );
+
+        assert!(obb.collides_with(&PointCollider::new(Vector3::new(5.0, 1.9, 0.0))));
+        assert!(!obb.collides_with(&PointCollider::new(Vector3::new(6.5, 0.0, 0.0))));
+        assert!(!obb.collides_with(&PointCollider::new(Vector3::new(0.0, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn sphere_off_center_collide() {
+        let obb = OrientedBoxCollider::new(
+            Vector3::new(5.0, 0.0, 0.0),
+            Vector3::new(4.0, 2.0, 2.0),
+            Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90.0_f64.to_radians())),
+        );
+
+        assert!(obb.collides_with(&SphereCollider::new(Vector3::new(5.0, 2.5, 0.0), 0.6)));
+        assert!(!obb.collides_with(&SphereCollider::new(Vector3::new(6.5, 0.0, 0.0), 0.4)));
+    }
+
     #[test]
     fn sphere_corner_collide() {
         let obb = OrientedBoxCollider::new(
@@ -1,8 +1,12 @@
 use crate::collides_group_impl;
+use crate::collides_reverse_impl;
 use crate::common::{Bounded, Collides, Projectable, Rotation, Transformation, Translation};
 use crate::primitive::{AlignedBoxCollider, OrientedBoxCollider, SphereCollider};
 use maths::{Quaternion, Vector3};
 
+/// Maximum distance between two points considered to be the same point.
+const POINT_EPSILON: f64 = 1e-9;
+
 /// # Point Collider
 /// Collision primitive for representing single point.
 ///
@@ -86,27 +90,21 @@ impl Transformation for PointCollider {
 
 impl Collides<Self> for PointCollider {
     fn collides_with(&self, other: &Self) -> bool {
-        self.position() == other.position()
+        (self.position() - other.position()).len2() <= POINT_EPSILON * POINT_EPSILON
     }
 }
 
 impl Collides<SphereCollider> for PointCollider {
     fn collides_with(&self, other: &SphereCollider) -> bool {
-        other.collides_with(self)
-    }
-}
-
-impl Collides<AlignedBoxCollider> for PointCollider {
-    fn collides_with(&self, other: &AlignedBoxCollider) -> bool {
-        other.collides_with(self)
+        let radius = other.radius();
+        (self.position() - other.center()).len2() <= radius * radius
     }
 }
 
-impl Collides<OrientedBoxCollider> for PointCollider {
-    fn collides_with(&self, other: &OrientedBoxCollider) -> bool {
-        other.collides_with(self)
-    }
-}
+collides_reverse_impl!(
+    AlignedBoxCollider => PointCollider,
+    OrientedBoxCollider => PointCollider,
+);
 
 collides_group_impl!(
     PointCollider, PointCollider
@@ -180,4 +178,36 @@ mod tests {
 
         assert!(!point.collides_with(&other));
     }
+
+    #[test]
+    fn points_within_epsilon_collide() {
+        let point = PointCollider::new(Vector3::new(1.0, 2.0, 3.0));
+        let other = PointCollider::new(Vector3::new(1.0 + 1e-12, 2.0, 3.0));
+
+        assert!(point.collides_with(&other));
+    }
+
+    #[test]
+    fn near_points_dont_collide() {
+        let point = PointCollider::new(Vector3::new(1.0, 2.0, 3.0));
+        let other = PointCollider::new(Vector3::new(1.0 + 1e-6, 2.0, 3.0));
+
+        assert!(!point.collides_with(&other));
+    }
+
+    #[test]
+    fn point_inside_sphere_collides() {
+        let point = PointCollider::new(Vector3::new(0.5, 0.5, 0.5));
+        let sphere = SphereCollider::new(Vector3::new(0.0, 0.0, 0.0), 1.0);
+
+        assert!(point.collides_with(&sphere));
+    }
+
+    #[test]
+    fn point_outside_sphere_doesnt_collide() {
+        let point = PointCollider::new(Vector3::new(1.0, 1.0, 0.0));
+        let sphere = SphereCollider::new(Vector3::new(0.0, 0.0, 0.0), 1.0);
+
+        assert!(!point.collides_with(&sphere));
+    }
 }
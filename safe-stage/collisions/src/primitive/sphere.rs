@@ -1,4 +1,5 @@
 use crate::collides_group_impl;
+use crate::collides_reverse_impl;
 use crate::common::{
     Bounded, Collides, Projectable, Rotation, Transformation, Translation, Treeable,
 };
@@ -43,6 +44,15 @@ impl SphereCollider {
     pub const fn radius(&self) -> f64 {
         self.radius
     }
+
+    /// Sphere scaled by different factors is an ellipsoid, which cannot be represented by this collider.
+    ///
+    /// Returns the smallest box containing the ellipsoid after scaling about the origin.
+    pub fn scale_nonuniform(&self, factors: Vector3) -> OrientedBoxCollider {
+        let center = self.center.component_mul(&factors);
+        let size = factors.abs() * (self.radius * 2.0);
+        OrientedBoxCollider::new(center, size, Quaternion::IDENTITY)
+    }
 }
 
 impl Treeable for SphereCollider {
@@ -143,17 +153,10 @@ impl Collides<PointCollider> for SphereCollider {
     }
 }
 
-impl Collides<AlignedBoxCollider> for SphereCollider {
-    fn collides_with(&self, other: &AlignedBoxCollider) -> bool {
-        other.collides_with(self)
-    }
-}
-
-impl Collides<OrientedBoxCollider> for SphereCollider {
-    fn collides_with(&self, other: &OrientedBoxCollider) -> bool {
-        other.collides_with(self)
-    }
-}
+collides_reverse_impl!(
+    AlignedBoxCollider => SphereCollider,
+    OrientedBoxCollider => SphereCollider,
+);
 
 collides_group_impl!(
     SphereCollider, PointCollider
@@ -312,4 +315,14 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn scale_nonuniform_bounds_ellipsoid() {
+        let sphere = SphereCollider::new(Vector3::new(1.0, 0.0, 0.0), 1.0);
+
+        let scaled = sphere.scale_nonuniform(Vector3::new(2.0, 1.0, 0.5));
+
+        assert_vectors(Vector3::new(2.0, 0.0, 0.0), scaled.center());
+        assert_vectors(Vector3::new(4.0, 2.0, 1.0), scaled.size());
+    }
 }
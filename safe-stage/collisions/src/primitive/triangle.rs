@@ -1,8 +1,14 @@
 use crate::collides_group_impl;
-use crate::common::{Collides, Rotation, Transformation, Translation};
-use crate::primitive::algo::guigue_2003;
+use crate::collides_reverse_impl;
+use crate::common::{Collides, Distance, Projectable, Rotation, Transformation, Translation};
+use crate::primitive::algo::epa::epa_penetration;
+use crate::primitive::algo::{distance, guigue_2003};
+use crate::primitive::OrientedBoxCollider;
 use maths::{Quaternion, Vector3};
 
+/// Tolerance for treating a vertex as lying in a plane when computing intersection segments.
+const PLANE_EPSILON: f64 = 1e-12;
+
 /// # Triangle Collider
 /// Collision primitive for representing a triangle.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -36,6 +42,131 @@ impl TriangleCollider {
     pub fn points(&self) -> (&Vector3, &Vector3, &Vector3) {
         (&self.a, &self.b, &self.c)
     }
+
+    /// Returns the triangle with vertices scaled about the origin by the `factors` along each axis.
+    ///
+    /// **Expects all factors to be non-zero.**
+    pub fn scale_nonuniform(&self, factors: Vector3) -> Self {
+        Self::new(
+            self.a.component_mul(&factors),
+            self.b.component_mul(&factors),
+            self.c.component_mul(&factors),
+        )
+    }
+
+    /// Returns the triangle reflected across the plane given by `plane_normal` and `plane_point`.
+    ///
+    /// The winding is flipped so the normal still points outward of the mirrored part.
+    pub fn mirror(&self, plane_normal: Vector3, plane_point: Vector3) -> Self {
+        let normal = plane_normal.normalize();
+        let reflect = |p: &Vector3| *p - normal * (2.0 * normal.dot(&(*p - plane_point)));
+        Self::new(reflect(&self.a), reflect(&self.c), reflect(&self.b))
+    }
+
+    /// Returns the unit face normal, oriented by the counter-clockwise winding of the vertices.
+    pub fn normal(&self) -> Vector3 {
+        (self.b - self.a).cross(&(self.c - self.a)).normalize()
+    }
+
+    /// Returns the furthest vertex of the triangle in the given direction.
+    pub fn support(&self, direction: &Vector3) -> Vector3 {
+        *[&self.a, &self.b, &self.c]
+            .into_iter()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .expect("triangle has three vertices")
+    }
+
+    /// Returns the shortest translation of the `other` triangle separating it from this one,
+    /// as a unit direction and a depth, or `None` if they do not intersect.
+    ///
+    /// See [epa_penetration] for the coplanar and degenerate triangles.
+    pub fn penetration(&self, other: &TriangleCollider) -> Option<(Vector3, f64)> {
+        epa_penetration(|d| other.support(d), |d| self.support(d))
+    }
+
+    /// Returns the line segment along which the triangle overlaps the `other` triangle.
+    ///
+    /// Returns `None` when the triangles do not intersect or are coplanar.
+    /// Touching in a single point yields a degenerate segment.
+    pub fn intersection_segment(&self, other: &TriangleCollider) -> Option<(Vector3, Vector3)> {
+        let n1 = self.normal();
+        let n2 = other.normal();
+        let direction = n1.cross(&n2);
+        if direction.len2() <= PLANE_EPSILON {
+            return None;
+        }
+
+        let (s1, e1) = self.plane_crossing(&n2, &other.a, &direction)?;
+        let (s2, e2) = other.plane_crossing(&n1, &self.a, &direction)?;
+
+        let start = if direction.dot(&s1) >= direction.dot(&s2) {
+            s1
+        } else {
+            s2
+        };
+        let end = if direction.dot(&e1) <= direction.dot(&e2) {
+            e1
+        } else {
+            e2
+        };
+
+        if direction.dot(&start) > direction.dot(&end) + PLANE_EPSILON {
+            return None;
+        }
+
+        Some((start, end))
+    }
+
+    /// Returns the part of the triangle lying in the plane given by `normal` and `point`,
+    /// with the endpoints ordered along `direction`.
+    fn plane_crossing(
+        &self,
+        normal: &Vector3,
+        point: &Vector3,
+        direction: &Vector3,
+    ) -> Option<(Vector3, Vector3)> {
+        let vertices = [self.a, self.b, self.c];
+        let distances = vertices.map(|v| {
+            let d = normal.dot(&(v - *point));
+            if d.abs() <= PLANE_EPSILON {
+                0.0
+            } else {
+                d
+            }
+        });
+
+        let mut crossing: Option<(Vector3, Vector3)> = None;
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let p = if distances[i] == 0.0 {
+                vertices[i]
+            } else if distances[i] * distances[j] < 0.0 {
+                let t = distances[i] / (distances[i] - distances[j]);
+                vertices[i].lerp(&vertices[j], t)
+            } else {
+                continue;
+            };
+
+            crossing = Some(match crossing {
+                None => (p, p),
+                Some((start, end)) if direction.dot(&p) < direction.dot(&start) => (p, end),
+                Some((start, end)) if direction.dot(&p) > direction.dot(&end) => (start, p),
+                Some(segment) => segment,
+            });
+        }
+
+        crossing
+    }
+
+    /// Same as [Collides::collides_with], but with the coplanarity tolerance
+    /// scaled by the size of the triangles and the offsets between them.
+    ///
+    /// Use for large triangles.
+    pub fn collides_with_relative_eps(&self, other: &Self) -> bool {
+        guigue_2003::tri_tri_overlap_test_3d_relative(
+            self.a, self.b, self.c, other.a, other.b, other.c,
+        )
+    }
 }
 
 impl Collides<Self> for TriangleCollider {
@@ -47,7 +178,33 @@ impl Collides<Self> for TriangleCollider {
     }
 }
 
-collides_group_impl!(TriangleCollider, TriangleCollider);
+collides_reverse_impl!(
+    OrientedBoxCollider => TriangleCollider,
+);
+
+collides_group_impl!(
+    TriangleCollider, TriangleCollider
+    TriangleCollider, OrientedBoxCollider
+);
+
+impl Projectable for TriangleCollider {
+    fn project(&self, axis: &Vector3) -> (f64, f64) {
+        let a = axis.dot(&self.a);
+        let b = axis.dot(&self.b);
+        let c = axis.dot(&self.c);
+        (a.min(b).min(c), a.max(b).max(c))
+    }
+}
+
+impl Distance<Self> for TriangleCollider {
+    fn distance_to(&self, other: &Self) -> f64 {
+        if self.collides_with(other) {
+            return 0.0;
+        }
+
+        distance::tri_tri_distance([self.a, self.b, self.c], [other.a, other.b, other.c])
+    }
+}
 
 impl Rotation for TriangleCollider {
     fn rotate(&self, rotation: &Quaternion) -> Self {
@@ -413,4 +570,126 @@ mod tests {
         let collides = t1.collides_with(&t2);
         assert!(!collides);
     }
+
+    #[test]
+    fn distant_nearly_coplanar_triangles_collide_with_relative_eps() {
+        let t1 = TriangleCollider::new(
+            Vector3::new(1e3, 1e3, 1.0),
+            Vector3::new(2e3, 1e3, 1.0),
+            Vector3::new(1e3, 2e3, 1.0),
+        );
+        let t2 = t1.translate(&Vector3::new(100.0, 100.0, 1e-12));
+
+        assert!(!t1.collides_with(&t2));
+        assert!(t1.collides_with_relative_eps(&t2));
+        assert!(t2.collides_with_relative_eps(&t1));
+    }
+
+    #[test]
+    fn relative_eps_keeps_separated_triangles_apart() {
+        let t1 = TriangleCollider::new(
+            Vector3::new(1e6, 1e6, 1e6),
+            Vector3::new(1e6 + 1e3, 1e6, 1e6),
+            Vector3::new(1e6, 1e6 + 1e3, 1e6),
+        );
+        let t2 = t1.translate(&Vector3::new(100.0, 100.0, 1.0));
+
+        assert!(!t1.collides_with_relative_eps(&t2));
+    }
+
+    #[test]
+    fn relative_eps_keeps_distant_parallel_triangles_apart() {
+        let t1 = TriangleCollider::new(
+            Vector3::new(1e6, 1e6, 1e6),
+            Vector3::new(1e6 + 1.0, 1e6, 1e6),
+            Vector3::new(1e6, 1e6 + 1.0, 1e6),
+        );
+        let t2 = t1.translate(&Vector3::new(0.0, 0.0, 500.0));
+
+        assert!(!t1.collides_with_relative_eps(&t2));
+        assert!(!t2.collides_with_relative_eps(&t1));
+    }
+
+    #[test]
+    fn scale_nonuniform_vertices() {
+        let triangle = TriangleCollider::new(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(-1.0, 0.5, 2.0),
+            Vector3::new(0.0, -4.0, 1.0),
+        );
+
+        let scaled = triangle.scale_nonuniform(Vector3::new(2.0, 0.5, -1.0));
+
+        let (a, b, c) = scaled.points();
+        assert_eq!(Vector3::new(2.0, 1.0, -3.0), *a);
+        assert_eq!(Vector3::new(-2.0, 0.25, -2.0), *b);
+        assert_eq!(Vector3::new(0.0, -2.0, -1.0), *c);
+    }
+
+    #[test]
+    fn intersection_segment_crossing_triangles() {
+        let t1 = TriangleCollider::new(
+            Vector3::new(-1.0, -1.0, 0.0),
+            Vector3::new(1.0, -1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let t2 = TriangleCollider::new(
+            Vector3::new(0.0, -2.0, -1.0),
+            Vector3::new(0.0, 2.0, -1.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        );
+
+        let (start, end) = t1.intersection_segment(&t2).expect("triangles cross");
+        for p in [start, end] {
+            assert!(t1.normal().dot(&(p - t1.a)).abs() < 1e-9);
+            assert!(t2.normal().dot(&(p - t2.a)).abs() < 1e-9);
+        }
+        assert!((start - end).len() > 0.1);
+
+        let (rev_start, rev_end) = t2.intersection_segment(&t1).unwrap();
+        let matches = |a: Vector3, b: Vector3| (a - b).len() < 1e-9;
+        assert!(
+            (matches(start, rev_start) && matches(end, rev_end))
+                || (matches(start, rev_end) && matches(end, rev_start))
+        );
+    }
+
+    #[test]
+    fn intersection_segment_separated_or_coplanar() {
+        let t1 = TriangleCollider::new(
+            Vector3::new(-1.0, -1.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let t2 = TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(-1.0, 2.0, 1.0),
+            Vector3::new(-1.0, 1.0, -1.0),
+        );
+        let coplanar = t1.translate(&Vector3::new(0.5, 0.0, 0.0));
+
+        assert_eq!(None, t1.intersection_segment(&t2));
+        assert_eq!(None, t1.intersection_segment(&coplanar));
+    }
+
+    #[test]
+    fn mirror_across_yz_plane() {
+        let t = TriangleCollider::new(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 1.0),
+            Vector3::new(1.0, 1.0, 2.0),
+        );
+
+        let mirrored = t.mirror(Vector3::new(1.0, 0.0, 0.0), Vector3::ZERO);
+
+        let (a, b, c) = t.points();
+        let (ma, mb, mc) = mirrored.points();
+        assert_eq!(&Vector3::new(-a.x(), a.y(), a.z()), ma);
+        assert_eq!(&Vector3::new(-c.x(), c.y(), c.z()), mb);
+        assert_eq!(&Vector3::new(-b.x(), b.y(), b.z()), mc);
+
+        let normal = t.normal();
+        let expected = Vector3::new(-normal.x(), normal.y(), normal.z());
+        assert!((mirrored.normal() - expected).len() < 1e-12);
+    }
 }
@@ -0,0 +1,124 @@
+//! # Test scenes
+//! Shared fixtures for tests and benchmarks.
+//!
+//! Available in tests of this crate or with the `scene` feature.
+
+use crate::primitive::{AlignedBoxCollider, TriangleCollider};
+use maths::Vector3;
+
+/// Returns `n * n * n` unit boxes in a grid, one unit apart from each other.
+pub fn boxes_grid(n: usize) -> Vec<AlignedBoxCollider> {
+    let mut boxes = Vec::with_capacity(n * n * n);
+    for x in 0..n {
+        for y in 0..n {
+            for z in 0..n {
+                let center = Vector3::new(x as f64, y as f64, z as f64) * 2.0;
+                boxes.push(AlignedBoxCollider::new(center, Vector3::new(1.0, 1.0, 1.0)));
+            }
+        }
+    }
+
+    boxes
+}
+
+/// Returns the 12 surface triangles of the box between `min` and `max`.
+pub fn box_triangles(min: Vector3, max: Vector3) -> Vec<TriangleCollider> {
+    let corner = |x: bool, y: bool, z: bool| {
+        Vector3::new(
+            if x { max.x() } else { min.x() },
+            if y { max.y() } else { min.y() },
+            if z { max.z() } else { min.z() },
+        )
+    };
+    let faces = [
+        [(0, 0, 0), (1, 0, 0), (1, 1, 0), (0, 1, 0)],
+        [(0, 0, 1), (1, 0, 1), (1, 1, 1), (0, 1, 1)],
+        [(0, 0, 0), (1, 0, 0), (1, 0, 1), (0, 0, 1)],
+        [(0, 1, 0), (1, 1, 0), (1, 1, 1), (0, 1, 1)],
+        [(0, 0, 0), (0, 1, 0), (0, 1, 1), (0, 0, 1)],
+        [(1, 0, 0), (1, 1, 0), (1, 1, 1), (1, 0, 1)],
+    ];
+
+    faces
+        .iter()
+        .flat_map(|face| {
+            let [a, b, c, d] = face.map(|(x, y, z)| corner(x == 1, y == 1, z == 1));
+            [
+                TriangleCollider::new(a, b, c),
+                TriangleCollider::new(a, c, d),
+            ]
+        })
+        .collect()
+}
+
+/// Returns two unit boxes overlapping by half of their size along the x axis.
+pub fn two_overlapping_boxes() -> (AlignedBoxCollider, AlignedBoxCollider) {
+    (
+        AlignedBoxCollider::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0)),
+        AlignedBoxCollider::new(Vector3::new(0.5, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0)),
+    )
+}
+
+/// Returns `count` triangles with vertices in the `[-10, 10]` cube,
+/// the same `seed` always results in the same triangles.
+pub fn random_triangles(seed: u64, count: usize) -> Vec<TriangleCollider> {
+    let mut state = seed;
+    let mut next = || {
+        //  SplitMix64, good enough for fixtures without extra dependencies
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+    };
+
+    let mut triangles = Vec::with_capacity(count);
+    while triangles.len() < count {
+        let a = Vector3::new(next(), next(), next());
+        let b = Vector3::new(next(), next(), next());
+        let c = Vector3::new(next(), next(), next());
+        if a != b && b != c && c != a {
+            triangles.push(TriangleCollider::new(a, b, c));
+        }
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Collides;
+
+    #[test]
+    fn grid_of_non_overlapping_unit_boxes() {
+        let boxes = boxes_grid(2);
+
+        assert_eq!(8, boxes.len());
+        for b in &boxes {
+            assert_eq!(Vector3::new(1.0, 1.0, 1.0), b.size());
+        }
+        for (i, a) in boxes.iter().enumerate() {
+            for b in &boxes[i + 1..] {
+                assert!(!a.collides_with(b));
+            }
+        }
+    }
+
+    #[test]
+    fn random_triangles_are_deterministic() {
+        let first = random_triangles(7, 16);
+        let second = random_triangles(7, 16);
+
+        assert_eq!(16, first.len());
+        assert_eq!(first, second);
+        assert_ne!(first, random_triangles(8, 16));
+    }
+
+    #[test]
+    fn overlapping_boxes_collide() {
+        let (a, b) = two_overlapping_boxes();
+        assert!(a.collides_with(&b));
+    }
+}
@@ -0,0 +1,201 @@
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+/// Version of the serialized collider format, bump on every breaking change.
+pub const COLLIDER_FORMAT_VERSION: u32 = 1;
+
+/// # Versioned Collider Error
+/// Error of loading a versioned collider.
+#[derive(Error, Debug, PartialEq)]
+pub enum VersionedColliderError {
+    #[error("Version mismatch, expected {expected} but found {found}")]
+    VersionMismatch { expected: u32, found: u32 },
+}
+
+/// # Versioned Collider
+/// Envelope for serialized colliders tagged with the format version.
+///
+/// Use [VersionedCollider::load] to get the collider, it rejects payloads of other versions.
+///
+/// Deserialization fails with [VersionedColliderError::VersionMismatch] for other versions.
+/// The version is checked before the collider is read when the `version` precedes
+/// the `inner` collider, as it does when serialized, otherwise right after reading the collider.
+///
+/// ## Example
+/// ```
+/// use collisions::primitive::PointCollider;
+/// use collisions::versioned::VersionedCollider;
+/// use maths::Vector3;
+///
+/// let envelope = VersionedCollider::new(PointCollider::new(Vector3::ZERO));
+/// let point = envelope.load().unwrap();
+/// ```
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct VersionedCollider<T> {
+    version: u32,
+    inner: T,
+}
+
+impl<T> VersionedCollider<T> {
+    /// Wraps the collider with the current format version.
+    pub fn new(inner: T) -> Self {
+        Self {
+            version: COLLIDER_FORMAT_VERSION,
+            inner,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Unwraps the collider if it was serialized with the current format version.
+    pub fn load(self) -> Result<T, VersionedColliderError> {
+        if self.version != COLLIDER_FORMAT_VERSION {
+            return Err(VersionedColliderError::VersionMismatch {
+                expected: COLLIDER_FORMAT_VERSION,
+                found: self.version,
+            });
+        }
+
+        Ok(self.inner)
+    }
+}
+
+fn check_version<E: de::Error>(found: u32) -> Result<(), E> {
+    if found != COLLIDER_FORMAT_VERSION {
+        return Err(E::custom(VersionedColliderError::VersionMismatch {
+            expected: COLLIDER_FORMAT_VERSION,
+            found,
+        }));
+    }
+
+    Ok(())
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VersionedCollider<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["version", "inner"];
+        deserializer.deserialize_struct(
+            "VersionedCollider",
+            FIELDS,
+            VersionedColliderVisitor(PhantomData),
+        )
+    }
+}
+
+struct VersionedColliderVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for VersionedColliderVisitor<T> {
+    type Value = VersionedCollider<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct VersionedCollider")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let version = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        check_version(version)?;
+        let inner = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(VersionedCollider { version, inner })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut version = None;
+        let mut inner = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => {
+                    if version.is_some() {
+                        return Err(de::Error::duplicate_field("version"));
+                    }
+                    let found = map.next_value()?;
+                    check_version(found)?;
+                    version = Some(found);
+                }
+                "inner" => {
+                    if inner.is_some() {
+                        return Err(de::Error::duplicate_field("inner"));
+                    }
+                    inner = Some(map.next_value()?);
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| de::Error::missing_field("version"))?;
+        let inner = inner.ok_or_else(|| de::Error::missing_field("inner"))?;
+        Ok(VersionedCollider { version, inner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::SphereCollider;
+    use maths::Vector3;
+
+    #[test]
+    fn load_current_version() {
+        let sphere = SphereCollider::new(Vector3::new(1.0, 2.0, 3.0), 4.0);
+        let json = serde_json::to_string(&VersionedCollider::new(sphere.clone())).unwrap();
+
+        let envelope: VersionedCollider<SphereCollider> = serde_json::from_str(&json).unwrap();
+        let actual = envelope.load().unwrap();
+        assert_eq!(sphere, actual);
+    }
+
+    #[test]
+    fn load_bumped_version() {
+        let sphere = SphereCollider::new(Vector3::new(1.0, 2.0, 3.0), 4.0);
+        let mut json = serde_json::to_value(VersionedCollider::new(sphere)).unwrap();
+        json["version"] = serde_json::Value::from(COLLIDER_FORMAT_VERSION + 1);
+
+        let actual = serde_json::from_value::<VersionedCollider<SphereCollider>>(json);
+        let expected = VersionedColliderError::VersionMismatch {
+            expected: COLLIDER_FORMAT_VERSION,
+            found: COLLIDER_FORMAT_VERSION + 1,
+        };
+        assert_eq!(expected.to_string(), actual.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn bumped_version_rejected_before_inner() {
+        let json = format!(
+            r#"{{"version":{},"inner":{{"incompatible":true}}}}"#,
+            COLLIDER_FORMAT_VERSION + 1
+        );
+
+        let actual = serde_json::from_str::<VersionedCollider<SphereCollider>>(&json);
+        let expected = VersionedColliderError::VersionMismatch {
+            expected: COLLIDER_FORMAT_VERSION,
+            found: COLLIDER_FORMAT_VERSION + 1,
+        };
+        assert!(actual
+            .unwrap_err()
+            .to_string()
+            .starts_with(&expected.to_string()));
+    }
+
+    #[test]
+    fn inner_before_version_loads() {
+        let sphere = SphereCollider::new(Vector3::new(1.0, 2.0, 3.0), 4.0);
+        let json = format!(
+            r#"{{"inner":{},"version":{}}}"#,
+            serde_json::to_string(&sphere).unwrap(),
+            COLLIDER_FORMAT_VERSION
+        );
+
+        let envelope: VersionedCollider<SphereCollider> = serde_json::from_str(&json).unwrap();
+        assert_eq!(sphere, envelope.load().unwrap());
+    }
+}
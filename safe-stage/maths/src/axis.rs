@@ -1,3 +1,5 @@
+use crate::Vector3;
+
 /// # Axis
 /// Axis enum, can be used to index `Vector3`.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -6,3 +8,36 @@ pub enum Axis {
     Y,
     Z,
 }
+
+/// Returns the axis with the largest component of `diff`.
+///
+/// Ties are broken in the order `X`, `Y`, `Z`,
+/// so equal extents always resolve to the earliest axis.
+pub fn dominant_axis(diff: Vector3) -> Axis {
+    if diff.x() >= diff.y() && diff.x() >= diff.z() {
+        Axis::X
+    } else if diff.y() >= diff.z() {
+        Axis::Y
+    } else {
+        Axis::Z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_largest_component() {
+        assert_eq!(Axis::X, dominant_axis(Vector3::new(3.0, 1.0, 2.0)));
+        assert_eq!(Axis::Y, dominant_axis(Vector3::new(1.0, 3.0, 2.0)));
+        assert_eq!(Axis::Z, dominant_axis(Vector3::new(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn ties_prefer_earlier_axis() {
+        assert_eq!(Axis::X, dominant_axis(Vector3::new(1.0, 1.0, 1.0)));
+        assert_eq!(Axis::X, dominant_axis(Vector3::new(2.0, 1.0, 2.0)));
+        assert_eq!(Axis::Y, dominant_axis(Vector3::new(1.0, 2.0, 2.0)));
+    }
+}
@@ -6,11 +6,13 @@ pub mod asserts;
 mod axis;
 mod nan;
 mod quaternion;
+mod space;
 mod vector2;
 mod vector3;
 
-pub use axis::Axis;
+pub use axis::{dominant_axis, Axis};
 pub use nan::NaNExtension;
 pub use quaternion::Quaternion;
+pub use space::{Local, World};
 pub use vector2::Vector2;
-pub use vector3::Vector3;
+pub use vector3::{bounds_of, Vector3};
@@ -127,6 +127,71 @@ impl Quaternion {
         (vector, angle)
     }
 
+    /// Creates a quaternion rotating the unit axes onto the given orthonormal right-handed basis.
+    pub fn from_basis(x: &Vector3, y: &Vector3, z: &Vector3) -> Self {
+        let trace = x.x() + y.y() + z.z();
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::normalized(
+                s / 4.0,
+                (y.z() - z.y()) / s,
+                (z.x() - x.z()) / s,
+                (x.y() - y.x()) / s,
+            )
+        } else if x.x() > y.y() && x.x() > z.z() {
+            let s = (1.0 + x.x() - y.y() - z.z()).sqrt() * 2.0;
+            Self::normalized(
+                (y.z() - z.y()) / s,
+                s / 4.0,
+                (y.x() + x.y()) / s,
+                (z.x() + x.z()) / s,
+            )
+        } else if y.y() > z.z() {
+            let s = (1.0 + y.y() - x.x() - z.z()).sqrt() * 2.0;
+            Self::normalized(
+                (z.x() - x.z()) / s,
+                (y.x() + x.y()) / s,
+                s / 4.0,
+                (z.y() + y.z()) / s,
+            )
+        } else {
+            let s = (1.0 + z.z() - x.x() - y.y()).sqrt() * 2.0;
+            Self::normalized(
+                (x.y() - y.x()) / s,
+                (z.x() + x.z()) / s,
+                (z.y() + y.z()) / s,
+                s / 4.0,
+            )
+        }
+    }
+
+    /// Creates a rotation mapping `+Z` onto `forward` and `+Y` as close to `up` as possible.
+    ///
+    /// When `up` is parallel to `forward`, another perpendicular axis is used instead.
+    /// Returns [Quaternion::IDENTITY] for a zero `forward`.
+    pub fn look_rotation(forward: &Vector3, up: &Vector3) -> Self {
+        const PARALLEL_EPSILON: f64 = 1e-12;
+
+        if forward.len() == 0.0 {
+            return Quaternion::IDENTITY;
+        }
+
+        let z = forward.normalize();
+        let mut x = up.cross(&z);
+        if x.len() < PARALLEL_EPSILON {
+            let fallback = if z.x().abs() < 0.9 {
+                Vector3::new(1.0, 0.0, 0.0)
+            } else {
+                Vector3::new(0.0, 1.0, 0.0)
+            };
+            x = fallback.cross(&z);
+        }
+        let x = x.normalize();
+        let y = z.cross(&x);
+
+        Self::from_basis(&x, &y, &z)
+    }
+
     /// Returns the conjugate of the quaternion.
     #[inline]
     pub const fn conjugate(&self) -> Self {
@@ -149,6 +214,73 @@ impl Quaternion {
             Self::raw(self.w / len, self.x / len, self.y / len, self.z / len)
         }
     }
+
+    /// Returns the dot product of two quaternions.
+    #[inline]
+    pub const fn dot(&self, other: &Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Spherical linear interpolation along the shorter arc between two unit quaternions.
+    ///
+    /// Falls back to normalized linear interpolation for nearly identical rotations.
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let mut dot = self.dot(other);
+        let other = if dot < 0.0 {
+            dot = -dot;
+            Self::raw(-other.w, -other.x, -other.y, -other.z)
+        } else {
+            *other
+        };
+
+        let (a, b) = if dot > 1.0 - 1e-9 {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.min(1.0).acos();
+            let sine = theta.sin();
+            (((1.0 - t) * theta).sin() / sine, (t * theta).sin() / sine)
+        };
+
+        Self::normalized(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        )
+    }
+
+    /// Returns `true` if all components of the quaternion are finite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.w.is_finite() && self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Returns the quaternion with each non-finite component replaced by the `fallback` component.
+    #[inline]
+    pub const fn scrub(&self, fallback: &Quaternion) -> Quaternion {
+        Self::raw(
+            if self.w.is_finite() {
+                self.w
+            } else {
+                fallback.w
+            },
+            if self.x.is_finite() {
+                self.x
+            } else {
+                fallback.x
+            },
+            if self.y.is_finite() {
+                self.y
+            } else {
+                fallback.y
+            },
+            if self.z.is_finite() {
+                self.z
+            } else {
+                fallback.z
+            },
+        )
+    }
 }
 
 macro_rules! from_vector3_impl {
@@ -187,8 +319,60 @@ mul_impl! { Quaternion, Quaternion &Quaternion, Quaternion Quaternion, &Quaterni
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::asserts::assert_vectors;
     use assert_float_eq::*;
 
+    #[test]
+    fn look_rotation_aims_forward() {
+        let forward = Vector3::new(1.0, 2.0, -0.5);
+        let up = Vector3::new(0.0, 0.0, 1.0);
+
+        let rotation = Quaternion::look_rotation(&forward, &up);
+
+        let z = Vector3::new(0.0, 0.0, 1.0).rotate(&rotation);
+        let y = Vector3::new(0.0, 1.0, 0.0).rotate(&rotation);
+        assert_vectors(forward.normalize(), z);
+        assert!(y.dot(&z).abs() < 1e-9);
+        assert!(y.dot(&up) > 0.9);
+    }
+
+    #[test]
+    fn look_rotation_parallel_up() {
+        for forward in [Vector3::new(0.0, 0.0, 2.0), Vector3::new(-3.0, 0.0, 0.0)] {
+            let rotation = Quaternion::look_rotation(&forward, &forward);
+
+            assert!(rotation.is_finite());
+            let z = Vector3::new(0.0, 0.0, 1.0).rotate(&rotation);
+            assert_vectors(forward.normalize(), z);
+        }
+
+        assert_eq!(
+            Quaternion::IDENTITY,
+            Quaternion::look_rotation(&Vector3::ZERO, &Vector3::new(0.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn from_basis_recovers_rotation() {
+        let angles = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.3, -1.2, 2.5),
+            Vector3::new(3.1, 0.1, -3.0),
+            Vector3::new(-1.5, 1.5, 0.2),
+        ];
+        for euler in angles {
+            let rotation = Quaternion::from_euler(&euler);
+            let x = Vector3::new(1.0, 0.0, 0.0).rotate(&rotation);
+            let y = Vector3::new(0.0, 1.0, 0.0).rotate(&rotation);
+            let z = Vector3::new(0.0, 0.0, 1.0).rotate(&rotation);
+
+            let actual = Quaternion::from_basis(&x, &y, &z);
+
+            let probe = Vector3::new(0.4, -2.0, 1.3);
+            assert_vectors(probe.rotate(&rotation), probe.rotate(&actual));
+        }
+    }
+
     #[test]
     fn raw() {
         let quaternion = Quaternion::raw(1.0, 2.0, 3.0, 4.0);
@@ -352,4 +536,46 @@ mod tests {
         };
         let _undefined = q.conjugate();
     }
+
+    #[test]
+    fn slerp_endpoints_and_half() {
+        let a = Quaternion::from_axis_angle(&Vector3::new(0.0, 0.0, 1.0), 0.2);
+        let b = Quaternion::from_axis_angle(&Vector3::new(0.0, 0.0, 1.0), 1.4);
+
+        assert_float_absolute_eq!(1.0, a.slerp(&b, 0.0).dot(&a), 1e-12);
+        assert_float_absolute_eq!(1.0, a.slerp(&b, 1.0).dot(&b), 1e-12);
+
+        let (_, angle) = a.slerp(&b, 0.5).to_axis_angle();
+        assert_float_absolute_eq!(0.8, angle, 1e-9);
+    }
+
+    #[test]
+    fn slerp_takes_shorter_arc() {
+        let a = Quaternion::from_axis_angle(&Vector3::new(1.0, 0.0, 0.0), 0.1);
+        let b = Quaternion::from_axis_angle(&Vector3::new(1.0, 0.0, 0.0), -0.3);
+        let negated = Quaternion::raw(-b.w(), -b.x(), -b.y(), -b.z());
+
+        let half = a.slerp(&negated, 0.5);
+        assert_float_absolute_eq!(-0.1, half.to_euler().x(), 1e-9);
+    }
+
+    #[test]
+    fn slerp_identical() {
+        let a = Quaternion::from_euler(&Vector3::new(0.3, -0.2, 1.1));
+        assert_float_absolute_eq!(1.0, a.slerp(&a, 0.7).dot(&a), 1e-12);
+    }
+
+    #[test]
+    fn finite() {
+        assert!(Quaternion::IDENTITY.is_finite());
+        assert!(!Quaternion::raw(1.0, 0.0, f64::INFINITY, 0.0).is_finite());
+    }
+
+    #[test]
+    fn scrub() {
+        let quaternion = Quaternion::raw(f64::INFINITY, 0.5, 0.5, 0.5);
+        let scrubbed = quaternion.scrub(&Quaternion::IDENTITY);
+
+        assert_eq!(Quaternion::raw(1.0, 0.5, 0.5, 0.5), scrubbed);
+    }
 }
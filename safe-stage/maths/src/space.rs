@@ -0,0 +1,94 @@
+use crate::{Quaternion, Vector3};
+
+/// # World Vector
+/// Vector expressed in world coordinates.
+///
+/// Can only be turned into a [Local] vector with an explicit frame,
+/// so a rotation cannot be applied twice or not at all by accident.
+///
+/// ## Example
+/// ```
+/// use maths::{Quaternion, Vector3, World};
+///
+/// let origin = Vector3::new(1.0, 0.0, 0.0);
+/// let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90.0_f64.to_radians()));
+///
+/// let local = World::new(Vector3::new(1.0, 1.0, 0.0)).to_local(&origin, &rotation);
+/// let world = local.to_world(&origin, &rotation);
+///
+/// assert!((world.vector() - Vector3::new(1.0, 1.0, 0.0)).len() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct World(Vector3);
+
+/// # Local Vector
+/// Vector expressed in the coordinates of a frame placed at `origin` with `rotation` in the world.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Local(Vector3);
+
+impl World {
+    #[inline]
+    pub const fn new(vector: Vector3) -> Self {
+        Self(vector)
+    }
+
+    /// Returns the raw world coordinates.
+    #[inline]
+    pub const fn vector(&self) -> Vector3 {
+        self.0
+    }
+
+    /// Expresses the vector in the frame placed at `origin` with `rotation`.
+    #[inline]
+    pub fn to_local(&self, origin: &Vector3, rotation: &Quaternion) -> Local {
+        Local((self.0 - origin).rotate(&rotation.conjugate()))
+    }
+}
+
+impl Local {
+    #[inline]
+    pub const fn new(vector: Vector3) -> Self {
+        Self(vector)
+    }
+
+    /// Returns the raw local coordinates.
+    #[inline]
+    pub const fn vector(&self) -> Vector3 {
+        self.0
+    }
+
+    /// Expresses the vector in the world, given the frame it is local to.
+    #[inline]
+    pub fn to_world(&self, origin: &Vector3, rotation: &Quaternion) -> World {
+        World(self.0.rotate(rotation) + origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts::assert_vectors;
+
+    #[test]
+    fn world_to_local_and_back() {
+        let origin = Vector3::new(1.0, -2.0, 3.0);
+        let rotation = Quaternion::from_euler(&Vector3::new(0.3, -1.2, 2.5));
+        let point = World::new(Vector3::new(-4.0, 5.0, 0.5));
+
+        let back = point
+            .to_local(&origin, &rotation)
+            .to_world(&origin, &rotation);
+
+        assert_vectors(point.vector(), back.vector());
+    }
+
+    #[test]
+    fn local_is_relative_to_origin() {
+        let origin = Vector3::new(1.0, 1.0, 0.0);
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90.0_f64.to_radians()));
+
+        let local = World::new(Vector3::new(1.0, 2.0, 0.0)).to_local(&origin, &rotation);
+
+        assert_vectors(Vector3::new(1.0, 0.0, 0.0), local.vector());
+    }
+}
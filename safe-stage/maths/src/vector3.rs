@@ -19,6 +19,10 @@ use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 /// assert_eq!(v1_v2.x(), 5.0);
 /// assert_eq!(v2_v3.x(), 2.0);
 /// ```
+///
+/// `PartialOrd` compares lexicographically by `x`, then `y`, then `z`,
+/// it is meant for sorting and deduplication, not for geometric bounds,
+/// use [bounds_of] for componentwise minimum and maximum.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Vector3 {
@@ -82,19 +86,33 @@ impl Vector3 {
     }
 
     /// Returns the dot product of the vector and the `other` vector.
+    ///
+    /// With the `strict-fp` feature, every operation is rounded separately in a fixed order.
+    /// Rust does not fuse the operations by default, so the results are currently the same without it.
     #[inline]
     pub const fn dot(&self, other: &Vector3) -> f64 {
-        self.x * other.x + self.y * other.y + self.z * other.z
+        #[cfg(feature = "strict-fp")]
+        return strict_dot(self, other);
+
+        #[cfg(not(feature = "strict-fp"))]
+        return self.x * other.x + self.y * other.y + self.z * other.z;
     }
 
     /// Returns the cross product of the vector and the `other` vector.
+    ///
+    /// With the `strict-fp` feature, every operation is rounded separately in a fixed order.
+    /// Rust does not fuse the operations by default, so the results are currently the same without it.
     #[inline]
     pub const fn cross(&self, other: &Vector3) -> Vector3 {
-        Vector3::new(
+        #[cfg(feature = "strict-fp")]
+        return strict_cross(self, other);
+
+        #[cfg(not(feature = "strict-fp"))]
+        return Vector3::new(
             self.y * other.z - self.z * other.y,
             self.z * other.x - self.x * other.z,
             self.x * other.y - self.y * other.x,
-        )
+        );
     }
 
     /// Returns the length of the vector **squared**
@@ -156,11 +174,73 @@ impl Vector3 {
         Vector3::new(self.x.abs(), self.y.abs(), self.z.abs())
     }
 
+    /// Returns the vector with each component multiplied by the corresponding `other` component.
+    #[inline]
+    pub const fn component_mul(&self, other: &Vector3) -> Vector3 {
+        Vector3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+
     /// Returns a linear interpolation between the vector and the `other` vector.
     #[inline]
     pub fn lerp(&self, other: &Vector3, t: f64) -> Vector3 {
         *self + (*other - *self) * t
     }
+
+    /// Returns `true` if all components of the vector are finite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Returns the vector with each non-finite component replaced by the `fallback` component.
+    #[inline]
+    pub const fn scrub(&self, fallback: &Vector3) -> Vector3 {
+        Vector3::new(
+            if self.x.is_finite() {
+                self.x
+            } else {
+                fallback.x
+            },
+            if self.y.is_finite() {
+                self.y
+            } else {
+                fallback.y
+            },
+            if self.z.is_finite() {
+                self.z
+            } else {
+                fallback.z
+            },
+        )
+    }
+}
+
+/// Dot product rounding every operation separately in a fixed order.
+///
+/// Rust does not contract floating point operations into fused multiply-add on its own,
+/// so this is the same as the default [Vector3::dot] and the `strict-fp` feature is a no-op.
+/// It only pins the evaluation, in case the default products switch to `mul_add` for speed.
+#[inline]
+#[cfg(any(test, feature = "strict-fp"))]
+const fn strict_dot(a: &Vector3, b: &Vector3) -> f64 {
+    let x = a.x * b.x;
+    let y = a.y * b.y;
+    let z = a.z * b.z;
+    let xy = x + y;
+    xy + z
+}
+
+/// Cross product rounding every operation separately in a fixed order, see [strict_dot].
+#[inline]
+#[cfg(any(test, feature = "strict-fp"))]
+const fn strict_cross(a: &Vector3, b: &Vector3) -> Vector3 {
+    let yz = a.y * b.z;
+    let zy = a.z * b.y;
+    let zx = a.z * b.x;
+    let xz = a.x * b.z;
+    let xy = a.x * b.y;
+    let yx = a.y * b.x;
+    Vector3::new(yz - zy, zx - xz, xy - yx)
 }
 
 macro_rules! neg_impl {
@@ -264,6 +344,14 @@ macro_rules! from_quaternion_impl {
 
 from_quaternion_impl! { &Quaternion Quaternion }
 
+/// Componentwise minimum and maximum of the points, `None` for an empty slice.
+pub fn bounds_of(points: &[Vector3]) -> Option<(Vector3, Vector3)> {
+    let (first, rest) = points.split_first()?;
+    Some(rest.iter().fold((*first, *first), |(min, max), p| {
+        (min.minimized(p), max.maximized(p))
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use std::f64;
@@ -516,6 +604,30 @@ mod tests {
         assert_eq!(3.0, min.z());
     }
 
+    #[test]
+    fn bounds_of_points() {
+        let points = [
+            Vector3::new(1.0, -2.0, 3.0),
+            Vector3::new(-4.0, 5.0, 0.5),
+            Vector3::new(2.0, 0.0, -6.0),
+        ];
+        let (min, max) = bounds_of(&points).unwrap();
+
+        assert_eq!(Vector3::new(-4.0, -2.0, -6.0), min);
+        assert_eq!(Vector3::new(2.0, 5.0, 3.0), max);
+    }
+
+    #[test]
+    fn bounds_of_single_point() {
+        let point = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(Some((point, point)), bounds_of(&[point]));
+    }
+
+    #[test]
+    fn bounds_of_empty() {
+        assert_eq!(None, bounds_of(&[]));
+    }
+
     #[test]
     fn maximized() {
         let v1 = Vector3::new(1.0, 2.0, 3.0);
@@ -548,6 +660,43 @@ mod tests {
         assert_eq!(4.5, lerp.z());
     }
 
+    #[test]
+    fn finite() {
+        assert!(Vector3::new(1.0, -2.0, 3.0).is_finite());
+        assert!(!Vector3::new(1.0, f64::INFINITY, 3.0).is_finite());
+        assert!(!Vector3::new(f64::NEG_INFINITY, 2.0, 3.0).is_finite());
+    }
+
+    #[test]
+    fn scrub() {
+        let vector = Vector3::new(1.0, f64::INFINITY, 3.0);
+        let scrubbed = vector.scrub(&Vector3::new(4.0, 5.0, 6.0));
+
+        assert_eq!(Vector3::new(1.0, 5.0, 3.0), scrubbed);
+        assert!(scrubbed.is_finite());
+    }
+
+    #[test]
+    fn component_mul_vectors() {
+        let a = Vector3::new(1.0, -2.0, 3.0);
+        let b = Vector3::new(2.0, 3.0, -0.5);
+        assert_eq!(Vector3::new(2.0, -6.0, -1.5), a.component_mul(&b));
+    }
+
+    #[test]
+    fn strict_matches_default_exactly() {
+        //  Values chosen so that a fused multiply-add would round differently
+        let x = 1.0 + 2f64.powi(-30);
+        let a = Vector3::new(x, -1.0, 1.0 / 3.0);
+        let b = Vector3::new(x, 1.0, 0.0);
+
+        assert_eq!(a.dot(&b).to_bits(), strict_dot(&a, &b).to_bits());
+        assert_eq!(a.cross(&b), strict_cross(&a, &b));
+
+        let fused = a.z.mul_add(b.z, a.x.mul_add(b.x, a.y * b.y));
+        assert_ne!(fused.to_bits(), strict_dot(&a, &b).to_bits());
+    }
+
     #[test]
     #[should_panic]
     fn panic_on_undefined_math() {
@@ -1,9 +1,10 @@
 use crate::immovable::Immovable;
 use crate::loader::load_stl_from_bytes;
-use crate::parts::holder::Holder;
+use crate::parts::holder::{Holder, SampleId, DEFAULT_SAMPLE_ID};
 use collisions::common::{Rotation, Translation};
 use collisions::{collider_group, PrimaryCollider};
 use maths::{Quaternion, Vector3};
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 const CIRCLE: &[u8] = include_bytes!("./models/Holder Circle.stl");
@@ -21,15 +22,20 @@ macro_rules! thesis_holder_impl {
         #[derive(Clone)]
         pub struct $name {
             body: PrimaryCollider,
-            sample: Option<PrimaryCollider>,
+            samples: BTreeMap<SampleId, PrimaryCollider>,
+            sample_rotation: Quaternion,
         }
 
         impl Default for $name {
             fn default() -> Self {
                 let body = PrimaryCollider::build(&load_stl_from_bytes($source).unwrap())
                     .rotate_around(&$rotation, &Vector3::ZERO);
-                let sample = None;
-                Self { body, sample }
+                let samples = BTreeMap::new();
+                Self {
+                    body,
+                    samples,
+                    sample_rotation: Quaternion::IDENTITY,
+                }
             }
         }
 
@@ -39,15 +45,36 @@ macro_rules! thesis_holder_impl {
             }
 
             fn collider(&self) -> Immovable {
-                if let Some(sample) = &self.sample {
-                    collider_group!(self.body.clone(), sample.clone())
+                let mut collider = collider_group!(self.body.clone());
+                if self.sample_rotation == Quaternion::IDENTITY {
+                    collider.0.extend(self.samples.values().cloned());
                 } else {
-                    collider_group!(self.body.clone())
+                    collider.0.extend(
+                        self.samples
+                            .values()
+                            .map(|s| s.rotate_around(&self.sample_rotation, &$offset)),
+                    );
                 }
+                collider
             }
 
             fn swap_sample(&mut self, sample: Option<PrimaryCollider>) {
-                self.sample = sample.map(|s| s.translate(&$offset));
+                self.samples.clear();
+                if let Some(sample) = sample {
+                    self.add_sample(DEFAULT_SAMPLE_ID, sample);
+                }
+            }
+
+            fn add_sample(&mut self, id: SampleId, sample: PrimaryCollider) {
+                self.samples.insert(id, sample.translate(&$offset));
+            }
+
+            fn remove_sample(&mut self, id: SampleId) {
+                self.samples.remove(&id);
+            }
+
+            fn set_sample_rotation(&mut self, rotation: Quaternion) {
+                self.sample_rotation = rotation;
             }
         }
     };
@@ -65,3 +92,72 @@ thesis_holder_impl!(
     SQUARE_MODEL_ROTATION,
     SQUARE_SAMPLE_OFFSET
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembly::thesis::ThesisStage;
+    use crate::movable::Movable;
+    use crate::parts::stage::Stage;
+    use crate::position::sixaxis::SixAxis;
+    use collisions::primitive::TriangleCollider;
+
+    fn sample(x: f64) -> PrimaryCollider {
+        PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(x, 0.0, 0.0),
+            Vector3::new(x + 1e-3, 0.0, 0.0),
+            Vector3::new(x, 1e-3, 0.0),
+        )])
+    }
+
+    #[test]
+    fn multiple_samples_in_stage_collider() {
+        let state = SixAxis::from_position(Vector3::ZERO);
+        let mut stage = ThesisStage::default();
+        stage.swap_holder(Some(Box::new(ThesisHolderCircle::default())));
+        let without_samples = stage.move_to(&state).0.len();
+
+        let holder = stage.active_holder_mut().unwrap();
+        holder.add_sample(1, sample(-10e-3));
+        holder.add_sample(2, sample(10e-3));
+        assert_eq!(without_samples + 2, stage.move_to(&state).0.len());
+
+        stage.active_holder_mut().unwrap().remove_sample(1);
+        assert_eq!(without_samples + 1, stage.move_to(&state).0.len());
+
+        let collider = stage.active_holder().unwrap().collider();
+        let expected = sample(10e-3)
+            .translate(&CIRCLE_SAMPLE_OFFSET)
+            .triangle_buffer();
+        let actual = collider.0.last().unwrap().triangle_buffer();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn rotated_sample_in_holder_collider() {
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90_f64.to_radians()));
+        let mut holder = ThesisHolderSquare::default();
+        holder.add_sample(1, sample(10e-3));
+        holder.set_sample_rotation(rotation);
+
+        let collider = holder.collider();
+        let expected = sample(10e-3)
+            .translate(&SQUARE_SAMPLE_OFFSET)
+            .rotate_around(&rotation, &SQUARE_SAMPLE_OFFSET)
+            .triangle_buffer();
+        let actual = collider.0.last().unwrap().triangle_buffer();
+        assert_eq!(expected, actual);
+        //  the sample lies along +x from the attachment point, after rotation along +y
+        let min_y = actual.iter().map(|v| v.y()).fold(f64::INFINITY, f64::min);
+        assert!((min_y - 10e-3).abs() < 1e-9);
+
+        holder.set_sample_rotation(Quaternion::IDENTITY);
+        let expected = sample(10e-3)
+            .translate(&SQUARE_SAMPLE_OFFSET)
+            .triangle_buffer();
+        assert_eq!(
+            expected,
+            holder.collider().0.last().unwrap().triangle_buffer()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::immovable::Immovable;
+use crate::immovable::{transformed_bounds, Immovable};
 use crate::loader::load_stl_from_bytes;
 use crate::movable::Movable;
 use crate::parts::holder::Holder;
@@ -6,6 +6,7 @@ use crate::parts::stage::Stage;
 use crate::position::sixaxis::SixAxis;
 use collisions::common::{Rotation, Transformation};
 use collisions::complex::group::ColliderGroup;
+use collisions::primitive::AlignedBoxCollider;
 use collisions::{collider_group, PrimaryCollider};
 use maths::{Quaternion, Vector3};
 use std::sync::{Arc, LazyLock};
@@ -51,6 +52,16 @@ impl Stage for ThesisStage {
     fn active_holder_mut(&mut self) -> Option<&mut (dyn Holder + 'static)> {
         self.holder.as_deref_mut()
     }
+
+    fn rotation_pivot(&self, coords: &SixAxis) -> Vector3 {
+        let (offset, _, _) = Self::pose(coords);
+        ROTATION_PIVOT + offset
+    }
+
+    fn orientation(&self, coords: &SixAxis) -> Quaternion {
+        let (_, tilt, rotation) = Self::pose(coords);
+        tilt * rotation
+    }
 }
 
 impl Default for ThesisStage {
@@ -67,11 +78,19 @@ impl Default for ThesisStage {
     }
 }
 
-impl Movable<SixAxis> for ThesisStage {
-    fn move_to(&self, coords: &SixAxis) -> Immovable {
+impl ThesisStage {
+    /// Returns the offset, tilt, and rotation applied to the parts at the given coordinates.
+    fn pose(coords: &SixAxis) -> (Vector3, Quaternion, Quaternion) {
         let offset = coords.pos + STAGE_POSITION;
         let tilt = Quaternion::from_euler(&Vector3::new(0.0, coords.rot.y(), 0.0));
         let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, coords.rot.z()));
+        (offset, tilt, rotation)
+    }
+}
+
+impl Movable<SixAxis> for ThesisStage {
+    fn move_to(&self, coords: &SixAxis) -> Immovable {
+        let (offset, tilt, rotation) = Self::pose(coords);
 
         let base = self
             .base
@@ -93,4 +112,24 @@ impl Movable<SixAxis> for ThesisStage {
             collider_group!(base, tilter)
         }
     }
+
+    fn bounds_at(&self, coords: &SixAxis) -> AlignedBoxCollider {
+        let (offset, tilt, rotation) = Self::pose(coords);
+
+        let base = transformed_bounds(&self.base, &Quaternion::IDENTITY, &Vector3::ZERO, &offset);
+        let tilter = transformed_bounds(&self.tilter, &tilt, &ROTATION_PIVOT, &offset);
+        let bounds = base.union(&tilter);
+
+        match self.holder.as_ref() {
+            Some(holder) => holder.collider().0.iter().fold(bounds, |bounds, i| {
+                bounds.union(&transformed_bounds(
+                    i,
+                    &(tilt * rotation),
+                    &ROTATION_PIVOT,
+                    &offset,
+                ))
+            }),
+            None => bounds,
+        }
+    }
 }
@@ -1,4 +1,59 @@
+pub mod proxy;
+
+use collisions::common::{Bounded, Transformation};
 use collisions::complex::group::ColliderGroup;
+use collisions::primitive::AlignedBoxCollider;
 use collisions::PrimaryCollider;
+use maths::{bounds_of, Quaternion, Vector3};
 
 pub type Immovable = ColliderGroup<PrimaryCollider>;
+
+/// Returns the axis aligned bounds of all the colliders in the group.
+///
+/// An empty group results in an inverted box with infinite bounds,
+/// which collides with nothing and leaves any box unchanged by [AlignedBoxCollider::union].
+pub fn group_bounds(group: &Immovable) -> AlignedBoxCollider {
+    let corners = group
+        .0
+        .iter()
+        .flat_map(|c| [c.min(), c.max()])
+        .collect::<Vec<_>>();
+    let (min, max) = bounds_of(&corners).unwrap_or((
+        Vector3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        Vector3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+    ));
+    AlignedBoxCollider::from_min_max(min, max)
+}
+
+/// Returns the axis aligned bounds of the transformed root bounds of the `collider`.
+///
+/// Cheaper than transforming the collider, but not tight for rotations.
+pub fn transformed_bounds(
+    collider: &PrimaryCollider,
+    rotation: &Quaternion,
+    pivot: &Vector3,
+    translation: &Vector3,
+) -> AlignedBoxCollider {
+    let oriented = AlignedBoxCollider::from_min_max(collider.min(), collider.max()).transform(
+        rotation,
+        pivot,
+        translation,
+    );
+    AlignedBoxCollider::from_min_max(oriented.min(), oriented.max())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::Collides;
+
+    #[test]
+    fn empty_group_bounds_collide_with_nothing() {
+        let bounds = group_bounds(&collider_group!());
+        let unit = AlignedBoxCollider::new(Vector3::ZERO, Vector3::new(1.0, 1.0, 1.0));
+
+        assert!(!bounds.collides_with(&unit));
+        assert_eq!(unit, bounds.union(&unit));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::immovable::Immovable;
+use collisions::common::Collides;
+use collisions::primitive::{AlignedBoxCollider, OrientedBoxCollider};
+
+/// Checks if the box proxy collides with any part of the immovable,
+/// without building a tree for the proxy.
+pub fn box_collides_immovable(proxy: &AlignedBoxCollider, immovable: &Immovable) -> bool {
+    obb_collides_immovable(&OrientedBoxCollider::from(proxy), immovable)
+}
+
+/// Checks if the oriented box proxy collides with any part of the immovable,
+/// without building a tree for the proxy.
+pub fn obb_collides_immovable(proxy: &OrientedBoxCollider, immovable: &Immovable) -> bool {
+    immovable.0.iter().any(|tree| tree.collides_with(proxy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::Rotation;
+    use collisions::scene::{box_triangles, random_triangles};
+    use collisions::PrimaryCollider;
+    use maths::{Quaternion, Vector3};
+
+    #[test]
+    fn proxy_agrees_with_tree() {
+        let immovable = collider_group!(
+            PrimaryCollider::build(&random_triangles(7, 8)),
+            PrimaryCollider::build(&random_triangles(11, 8))
+        );
+
+        for x in -6..=6 {
+            for y in -6..=6 {
+                let center = Vector3::new(x as f64 * 2.0, y as f64 * 2.0, 1.0);
+                let half = Vector3::new(0.5, 0.75, 1.0);
+                let proxy = AlignedBoxCollider::new(center, half * 2.0);
+                let tree = collider_group!(PrimaryCollider::build(&box_triangles(
+                    center - half,
+                    center + half
+                )));
+
+                assert_eq!(
+                    immovable.collides_with(&tree),
+                    box_collides_immovable(&proxy, &immovable),
+                    "{center:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rotated_proxy_agrees_with_tree() {
+        let immovable = collider_group!(PrimaryCollider::build(&random_triangles(7, 16)));
+        let rotation = Quaternion::from_euler(&Vector3::new(0.3, 0.2, 0.1));
+
+        for x in -6..=6 {
+            let center = Vector3::new(x as f64 * 2.0, 0.0, 0.0);
+            let half = Vector3::new(1.0, 1.0, 1.0);
+            let proxy = OrientedBoxCollider::new(center, half * 2.0, rotation);
+            let tree = collider_group!(PrimaryCollider::build(&box_triangles(
+                center - half,
+                center + half
+            ))
+            .rotate_around(&rotation, &center));
+
+            assert_eq!(
+                immovable.collides_with(&tree),
+                obb_collides_immovable(&proxy, &immovable),
+                "{center:?}"
+            );
+        }
+    }
+}
@@ -1,11 +1,60 @@
+pub mod obj;
+pub mod tagged;
+pub mod transform;
+
 use collisions::primitive::TriangleCollider;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use tinystl::StlData;
 
 use maths::Vector3;
+use thiserror::Error;
 pub use tinystl::Error as StlError;
 
+/// Limits on the size of loaded meshes, for loading files from untrusted sources.
+///
+/// STL faces are always triangles, facets with more vertices are rejected as invalid,
+/// so only the number of triangles applies to STL.
+/// OBJ polygons are triangulated and limited by all of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadLimits {
+    pub max_triangles: usize,
+    pub max_vertices: usize,
+    pub max_face_vertices: usize,
+}
+
+impl Default for LoadLimits {
+    fn default() -> Self {
+        Self {
+            max_triangles: 1_000_000,
+            max_vertices: 3_000_000,
+            max_face_vertices: 1_000,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("Invalid STL: {0}")]
+    Stl(StlError),
+    #[error("Mesh has {triangles} triangles, limit is {limit}")]
+    TooLarge { triangles: usize, limit: usize },
+    #[error("Mesh has {vertices} vertices, limit is {limit}")]
+    TooManyVertices { vertices: usize, limit: usize },
+    #[error("Face at line {line} has more than {limit} vertices")]
+    FaceTooLarge { line: usize, limit: usize },
+    #[error("Invalid OBJ at line {line}")]
+    Obj { line: usize },
+    #[error("Failed to read the mesh: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<StlError> for LoadError {
+    fn from(value: StlError) -> Self {
+        LoadError::Stl(value)
+    }
+}
+
 pub fn load_stl_from_file(path: impl AsRef<Path>) -> Result<Vec<TriangleCollider>, StlError> {
     let file = std::fs::File::open(path).map_err(StlError::Io)?;
     let reader = BufReader::new(file);
@@ -33,6 +82,260 @@ pub fn load_stl(reader: impl BufRead) -> Result<Vec<TriangleCollider>, StlError>
     Ok(triangles)
 }
 
+/// Same as [load_stl_from_bytes], rejecting meshes exceeding the `limits`.
+pub fn load_stl_from_bytes_limited(
+    data: &[u8],
+    limits: &LoadLimits,
+) -> Result<Vec<TriangleCollider>, LoadError> {
+    load_stl_limited(BufReader::new(data), limits)
+}
+
+/// Same as [load_stl], rejecting meshes exceeding the `limits`.
+///
+/// Binary files declaring too many triangles are rejected before any triangle is read,
+/// ASCII files are rejected as soon as the first facet over the limit starts.
+pub fn load_stl_limited(
+    mut reader: impl BufRead,
+    limits: &LoadLimits,
+) -> Result<Vec<TriangleCollider>, LoadError> {
+    if let Some(declared) = declared_binary_count(reader.fill_buf().map_err(StlError::Io)?) {
+        check_triangle_count(declared, limits)?;
+    }
+
+    let counted = FacetLimit::new(reader, limits.max_triangles);
+    let triangles = load_stl(counted).map_err(|e| match e {
+        StlError::Io(io) if io.get_ref().is_some_and(|e| e.is::<FacetLimitExceeded>()) => {
+            LoadError::TooLarge {
+                triangles: limits.max_triangles + 1,
+                limit: limits.max_triangles,
+            }
+        }
+        e => LoadError::Stl(e),
+    })?;
+    check_triangle_count(triangles.len(), limits)?;
+    Ok(triangles)
+}
+
+#[derive(Error, Debug)]
+#[error("Too many facets")]
+struct FacetLimitExceeded;
+
+/// Reader failing once more than `limit` lines starting with `facet` were read,
+/// counting the facets of ASCII STL while it is parsed.
+struct FacetLimit<R> {
+    inner: R,
+    limit: usize,
+    facets: usize,
+    line: Vec<u8>,
+}
+
+impl<R> FacetLimit<R> {
+    /// Leading bytes of a line needed to recognize a facet.
+    const PREFIX: usize = b"facet".len();
+
+    fn new(inner: R, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            facets: 0,
+            line: Vec::with_capacity(Self::PREFIX),
+        }
+    }
+
+    fn check(&self) -> std::io::Result<()> {
+        if self.facets > self.limit {
+            Err(std::io::Error::other(FacetLimitExceeded))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn count(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if byte == b'\n' {
+                self.line.clear();
+            } else if self.line.len() < Self::PREFIX
+                && !(self.line.is_empty() && byte.is_ascii_whitespace())
+            {
+                self.line.push(byte);
+                if self.line == b"facet" {
+                    self.facets += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Read for FacetLimit<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.check()?;
+        let read = self.inner.read(buf)?;
+        self.count(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for FacetLimit<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.check()?;
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if let Ok(buffer) = self.inner.fill_buf() {
+            let consumed = buffer[..amount.min(buffer.len())].to_vec();
+            self.count(&consumed);
+        }
+        self.inner.consume(amount);
+    }
+}
+
+/// Binary STL starts with an 80 byte header followed by the triangle count,
+/// ASCII STL starts with `solid`.
+fn declared_binary_count(start: &[u8]) -> Option<usize> {
+    if start.starts_with(b"solid") || start.len() < 84 {
+        return None;
+    }
+
+    let count = u32::from_le_bytes(start[80..84].try_into().unwrap());
+    Some(count as usize)
+}
+
+fn check_triangle_count(triangles: usize, limits: &LoadLimits) -> Result<(), LoadError> {
+    if triangles > limits.max_triangles {
+        Err(LoadError::TooLarge {
+            triangles,
+            limit: limits.max_triangles,
+        })
+    } else {
+        Ok(())
+    }
+}
+
 fn array_to_vector(v: &[f32; 3]) -> Vector3 {
     Vector3::new(v[0] as f64, v[1] as f64, v[2] as f64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary_stl(triangles: u32) -> Vec<u8> {
+        let mut data = vec![0u8; 80];
+        data.extend(triangles.to_le_bytes());
+        for i in 0..triangles {
+            let offset = i as f32;
+            let values = [
+                0.0,
+                0.0,
+                1.0,
+                offset,
+                0.0,
+                0.0,
+                offset + 1.0,
+                0.0,
+                0.0,
+                offset,
+                1.0,
+                0.0,
+            ];
+            for v in values {
+                data.extend(f32::to_le_bytes(v));
+            }
+            data.extend([0u8; 2]);
+        }
+        data
+    }
+
+    #[test]
+    fn limited_loads_within_limit() {
+        let limits = LoadLimits {
+            max_triangles: 3,
+            ..Default::default()
+        };
+        let triangles = load_stl_from_bytes_limited(&binary_stl(3), &limits).unwrap();
+        assert_eq!(3, triangles.len());
+    }
+
+    #[test]
+    fn limited_rejects_too_many_triangles() {
+        let limits = LoadLimits {
+            max_triangles: 3,
+            ..Default::default()
+        };
+        let result = load_stl_from_bytes_limited(&binary_stl(4), &limits);
+        assert!(matches!(
+            result,
+            Err(LoadError::TooLarge {
+                triangles: 4,
+                limit: 3
+            })
+        ));
+    }
+
+    fn ascii_stl(triangles: usize, vertices: usize) -> String {
+        let mut data = String::from("solid test\n");
+        for i in 0..triangles {
+            data.push_str("  facet normal 0 0 1\n    outer loop\n");
+            for v in 0..vertices {
+                data.push_str(&format!("      vertex {i} {v} {}\n", v % 2));
+            }
+            data.push_str("    endloop\n  endfacet\n");
+        }
+        data.push_str("endsolid test\n");
+        data
+    }
+
+    #[test]
+    fn limited_loads_ascii_within_limit() {
+        let limits = LoadLimits {
+            max_triangles: 3,
+            ..Default::default()
+        };
+        let triangles = load_stl_from_bytes_limited(ascii_stl(3, 3).as_bytes(), &limits).unwrap();
+        assert_eq!(3, triangles.len());
+    }
+
+    #[test]
+    fn limited_rejects_too_many_ascii_triangles_while_reading() {
+        struct Unreadable;
+        impl Read for Unreadable {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("read past the facet over the limit"))
+            }
+        }
+
+        let limits = LoadLimits {
+            max_triangles: 3,
+            ..Default::default()
+        };
+        let data = ascii_stl(4, 3);
+        let reader = BufReader::new(data.as_bytes().chain(Unreadable));
+
+        let result = load_stl_limited(reader, &limits);
+        assert!(matches!(
+            result,
+            Err(LoadError::TooLarge {
+                triangles: 4,
+                limit: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn limited_rejects_ascii_face_over_three_vertices() {
+        let limits = LoadLimits::default();
+        let result = load_stl_from_bytes_limited(ascii_stl(1, 4).as_bytes(), &limits);
+        assert!(matches!(result, Err(LoadError::Stl(_))));
+    }
+
+    #[test]
+    fn limited_rejects_declared_count_before_reading() {
+        let mut data = binary_stl(1);
+        data[80..84].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let limits = LoadLimits::default();
+        let result = load_stl_from_bytes_limited(&data, &limits);
+        assert!(matches!(result, Err(LoadError::TooLarge { .. })));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::loader::tagged::TaggedMesh;
+use crate::loader::{LoadError, LoadLimits};
+use collisions::primitive::TriangleCollider;
+use maths::Vector3;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+/// Loads the triangles of an OBJ file, tagged by the object or group they belong to.
+///
+/// Group ids are assigned in the order the `o` and `g` names first appear,
+/// faces before any name share one more group.
+/// Polygons are triangulated as fans, everything except vertices, faces and names is ignored.
+///
+/// Rejects meshes exceeding the default [LoadLimits].
+pub fn load_obj_tagged(reader: impl BufRead) -> Result<TaggedMesh, LoadError> {
+    load_obj_tagged_limited(reader, &LoadLimits::default())
+}
+
+/// Same as [load_obj_tagged], rejecting meshes exceeding the `limits` while they are read.
+pub fn load_obj_tagged_limited(
+    reader: impl BufRead,
+    limits: &LoadLimits,
+) -> Result<TaggedMesh, LoadError> {
+    let mut vertices = Vec::new();
+    let mut mesh = TaggedMesh::new();
+    let mut groups = HashMap::new();
+    let mut current = None;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let invalid = || LoadError::Obj { line: index + 1 };
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                if vertices.len() >= limits.max_vertices {
+                    return Err(LoadError::TooManyVertices {
+                        vertices: vertices.len() + 1,
+                        limit: limits.max_vertices,
+                    });
+                }
+                let mut coordinate = || -> Result<f64, LoadError> {
+                    let value = tokens.next().ok_or_else(invalid)?;
+                    let value = value.parse::<f64>().map_err(|_| invalid())?;
+                    value.is_finite().then_some(value).ok_or_else(invalid)
+                };
+                vertices.push(Vector3::new(coordinate()?, coordinate()?, coordinate()?));
+            }
+            Some("o") | Some("g") => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+                let next = groups.len() as u32;
+                current = Some(*groups.entry(name).or_insert(next));
+            }
+            Some("f") => {
+                let corners = tokens
+                    .take(limits.max_face_vertices.saturating_add(1))
+                    .map(|t| resolve_index(t, vertices.len()).ok_or_else(invalid))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if corners.len() > limits.max_face_vertices {
+                    return Err(LoadError::FaceTooLarge {
+                        line: index + 1,
+                        limit: limits.max_face_vertices,
+                    });
+                }
+                if corners.len() < 3 {
+                    return Err(invalid());
+                }
+                let triangles = mesh.len() + corners.len() - 2;
+                if triangles > limits.max_triangles {
+                    return Err(LoadError::TooLarge {
+                        triangles,
+                        limit: limits.max_triangles,
+                    });
+                }
+
+                let next = groups.len() as u32;
+                let group =
+                    *current.get_or_insert_with(|| *groups.entry(String::new()).or_insert(next));
+                for pair in corners[1..].windows(2) {
+                    mesh.push(
+                        TriangleCollider::new(
+                            vertices[corners[0]],
+                            vertices[pair[0]],
+                            vertices[pair[1]],
+                        ),
+                        group,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(mesh)
+}
+
+/// Face corners are `v`, `v/vt`, `v//vn` or `v/vt/vn`, with 1-based or negative relative indices.
+fn resolve_index(corner: &str, count: usize) -> Option<usize> {
+    let index = corner.split('/').next()?.parse::<i64>().ok()?;
+    let resolved = match index {
+        0 => return None,
+        i if i > 0 => i - 1,
+        i => count as i64 + i,
+    };
+    (0..count as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::common::Translation;
+    use collisions::PrimaryCollider;
+
+    const TWO_GROUPS: &str = "\
+# two separated quads
+o floor
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+f 1 2 3 4
+g wall
+v 0 0 5
+v 1 0 5
+v 1 0 6
+f -3/1/1 -2/2/2 -1/3/3
+";
+
+    #[test]
+    fn groups_survive_into_collisions() {
+        let mesh = load_obj_tagged(TWO_GROUPS.as_bytes()).unwrap();
+        assert_eq!(3, mesh.len());
+        assert_eq!(&[0, 0, 1], mesh.group_ids());
+
+        let tagged = mesh.build();
+        let probe = PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.5, 0.5, -1.0),
+            Vector3::new(0.5, 0.5, 1.0),
+            Vector3::new(0.6, 0.6, 1.0),
+        )]);
+
+        assert_eq!(Some(0), tagged.first_collision(&probe));
+        let near_wall = probe.translate(&Vector3::new(0.0, -0.5, 5.5));
+        assert_eq!(Some(1), tagged.first_collision(&near_wall));
+        let away = probe.translate(&Vector3::new(10.0, 0.0, 0.0));
+        assert_eq!(None, tagged.first_collision(&away));
+    }
+
+    fn limits(max_triangles: usize, max_vertices: usize, max_face_vertices: usize) -> LoadLimits {
+        LoadLimits {
+            max_triangles,
+            max_vertices,
+            max_face_vertices,
+        }
+    }
+
+    #[test]
+    fn limited_loads_within_limits() {
+        let mesh = load_obj_tagged_limited(TWO_GROUPS.as_bytes(), &limits(3, 7, 4)).unwrap();
+        assert_eq!(3, mesh.len());
+    }
+
+    #[test]
+    fn limited_rejects_too_many_vertices() {
+        let result = load_obj_tagged_limited(TWO_GROUPS.as_bytes(), &limits(3, 6, 4));
+        assert!(matches!(
+            result,
+            Err(LoadError::TooManyVertices {
+                vertices: 7,
+                limit: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn limited_rejects_too_many_triangles() {
+        let result = load_obj_tagged_limited(TWO_GROUPS.as_bytes(), &limits(2, 7, 4));
+        assert!(matches!(
+            result,
+            Err(LoadError::TooLarge {
+                triangles: 3,
+                limit: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn limited_rejects_face_over_limit() {
+        let result = load_obj_tagged_limited(TWO_GROUPS.as_bytes(), &limits(3, 7, 3));
+        assert!(matches!(
+            result,
+            Err(LoadError::FaceTooLarge { line: 7, limit: 3 })
+        ));
+    }
+
+    #[test]
+    fn invalid_face_reports_line() {
+        let result = load_obj_tagged("v 0 0 0\nv 1 0 0\nf 1 2 3\n".as_bytes());
+        assert!(matches!(result, Err(LoadError::Obj { line: 3 })));
+    }
+}
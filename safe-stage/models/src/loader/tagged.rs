@@ -0,0 +1,86 @@
+use collisions::common::Collides;
+use collisions::primitive::TriangleCollider;
+use collisions::PrimaryCollider;
+
+/// # Tagged Mesh
+/// Triangle mesh where every triangle keeps the id of the group it was loaded from,
+/// e.g. an object or a group of an OBJ file.
+#[derive(Debug, Clone, Default)]
+pub struct TaggedMesh {
+    triangles: Vec<TriangleCollider>,
+    group_ids: Vec<u32>,
+}
+
+impl TaggedMesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `triangle` into the group with `group_id`.
+    pub fn push(&mut self, triangle: TriangleCollider, group_id: u32) {
+        self.triangles.push(triangle);
+        self.group_ids.push(group_id);
+    }
+
+    pub fn triangles(&self) -> &[TriangleCollider] {
+        &self.triangles
+    }
+
+    /// Returns the group id of every triangle, in the same order as [TaggedMesh::triangles].
+    pub fn group_ids(&self) -> &[u32] {
+        &self.group_ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Builds a BVH for each group, ordered by the group id.
+    pub fn build(&self) -> TaggedCollider {
+        let mut ids = self.group_ids.clone();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let groups = ids
+            .into_iter()
+            .map(|id| {
+                let triangles = self
+                    .triangles
+                    .iter()
+                    .zip(&self.group_ids)
+                    .filter(|(_, group)| **group == id)
+                    .map(|(t, _)| t.clone())
+                    .collect::<Vec<_>>();
+                (id, PrimaryCollider::build(&triangles))
+            })
+            .collect();
+        TaggedCollider(groups)
+    }
+}
+
+/// # Tagged Collider
+/// Collider of a [TaggedMesh] able to report which group collided.
+#[derive(Debug, Clone)]
+pub struct TaggedCollider(Vec<(u32, PrimaryCollider)>);
+
+impl TaggedCollider {
+    /// Returns the lowest group id colliding with `other`.
+    pub fn first_collision<T>(&self, other: &T) -> Option<u32>
+    where
+        PrimaryCollider: Collides<T>,
+    {
+        self.0
+            .iter()
+            .find(|(_, collider)| collider.collides_with(other))
+            .map(|(id, _)| *id)
+    }
+
+    /// Returns the collider of each group with its id.
+    pub fn groups(&self) -> &[(u32, PrimaryCollider)] {
+        &self.0
+    }
+}
@@ -0,0 +1,89 @@
+use collisions::common::{Transformation, Translation};
+use collisions::primitive::TriangleCollider;
+use maths::{bounds_of, Quaternion, Vector3};
+
+/// Translates the mesh so the center of its bounding box lands on `target_origin`.
+pub fn recenter(triangles: &mut [TriangleCollider], target_origin: Vector3) {
+    let Some(center) = mesh_center(triangles) else {
+        return;
+    };
+
+    let offset = target_origin - center;
+    for triangle in triangles.iter_mut() {
+        *triangle = triangle.translate(&offset);
+    }
+}
+
+/// Rotates the mesh around the origin and then translates it.
+pub fn apply_transform(triangles: &mut [TriangleCollider], rot: &Quaternion, translate: &Vector3) {
+    for triangle in triangles.iter_mut() {
+        *triangle = triangle.transform(rot, &Vector3::ZERO, translate);
+    }
+}
+
+fn mesh_center(triangles: &[TriangleCollider]) -> Option<Vector3> {
+    let points = triangles
+        .iter()
+        .flat_map(|t| {
+            let (a, b, c) = t.points();
+            [*a, *b, *c]
+        })
+        .collect::<Vec<_>>();
+    let (min, max) = bounds_of(&points)?;
+    Some(min + (max - min) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maths::asserts::assert_vectors;
+
+    fn mesh() -> Vec<TriangleCollider> {
+        vec![
+            TriangleCollider::new(
+                Vector3::new(10.0, 20.0, 30.0),
+                Vector3::new(14.0, 20.0, 30.0),
+                Vector3::new(10.0, 26.0, 30.0),
+            ),
+            TriangleCollider::new(
+                Vector3::new(10.0, 20.0, 38.0),
+                Vector3::new(13.0, 21.0, 33.0),
+                Vector3::new(11.0, 25.0, 35.0),
+            ),
+        ]
+    }
+
+    #[test]
+    fn recentered_bounding_box_center() {
+        let mut triangles = mesh();
+        let target = Vector3::new(-1.0, 0.5, 2.0);
+
+        recenter(&mut triangles, target);
+
+        assert_vectors(target, mesh_center(&triangles).unwrap());
+    }
+
+    #[test]
+    fn recenter_empty_mesh() {
+        let mut triangles = vec![];
+        recenter(&mut triangles, Vector3::new(1.0, 2.0, 3.0));
+        assert!(triangles.is_empty());
+    }
+
+    #[test]
+    fn transform_rotates_then_translates() {
+        let mut triangles = vec![TriangleCollider::new(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        )];
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90_f64.to_radians()));
+
+        apply_transform(&mut triangles, &rotation, &Vector3::new(0.0, 0.0, 5.0));
+
+        let (a, b, c) = triangles[0].points();
+        assert_vectors(Vector3::new(0.0, 1.0, 5.0), *a);
+        assert_vectors(Vector3::new(-1.0, 0.0, 5.0), *b);
+        assert_vectors(Vector3::new(0.0, 0.0, 6.0), *c);
+    }
+}
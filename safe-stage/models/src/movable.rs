@@ -1,7 +1,18 @@
-use crate::immovable::Immovable;
+pub mod sweepable;
+
+use crate::immovable::{group_bounds, Immovable};
+use collisions::primitive::AlignedBoxCollider;
 
 /// Entity can be moved to position `P` where it is represented as `C`.
 pub trait Movable<P>: Send + Sync {
     /// Get the representation of the entity at the given position.
     fn move_to(&self, position: &P) -> Immovable;
+
+    /// Get the axis aligned bounds of the entity at the given position.
+    ///
+    /// Defaults to the bounds of the full representation,
+    /// implementors can provide a cheaper conservative estimate.
+    fn bounds_at(&self, position: &P) -> AlignedBoxCollider {
+        group_bounds(&self.move_to(position))
+    }
 }
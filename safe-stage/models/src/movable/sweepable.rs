@@ -0,0 +1,318 @@
+use crate::immovable::Immovable;
+use crate::movable::Movable;
+use crate::position::linear::LinearState;
+use crate::position::sixaxis::SixAxis;
+use collisions::common::{Bounded, Collides, Transformation};
+use collisions::primitive::{AlignedBoxCollider, ConvexHullCollider, OrientedBoxCollider};
+use collisions::PrimaryCollider;
+use maths::{Quaternion, Vector3};
+
+/// Largest rotation in radians covered by a single segment of a sweep.
+pub const SWEEP_MAX_ANGLE: f64 = 0.0174532925;
+
+/// Entity can report the volume it sweeps through when moving between two positions.
+pub trait Sweepable<P>: Movable<P> {
+    /// Get a conservative collider of the volume the entity sweeps
+    /// along the movement from `from` to `to`, see [SweptVolume].
+    ///
+    /// Anything the entity collides with at any state of the movement collides with the volume.
+    fn swept_collider(&self, from: &P, to: &P) -> SweptVolume;
+}
+
+impl<M> Sweepable<SixAxis> for M
+where
+    M: Movable<SixAxis> + ?Sized,
+{
+    fn swept_collider(&self, from: &SixAxis, to: &SixAxis) -> SweptVolume {
+        //  Same difference as interpolated by `lerp_t`, not the shortest rotation
+        let rotation = (to.rot - from.rot).abs();
+        let angle = rotation.x().max(rotation.y()).max(rotation.z());
+        let segments = ((angle / SWEEP_MAX_ANGLE).ceil() as usize).max(1);
+
+        let states = (0..=2 * segments)
+            .map(|i| from.lerp_t(to, i as f64 / (2 * segments) as f64))
+            .collect::<Vec<_>>();
+        sweep_states(self, &states)
+    }
+}
+
+impl<M> Sweepable<LinearState> for M
+where
+    M: Movable<LinearState> + ?Sized,
+{
+    fn swept_collider(&self, from: &LinearState, to: &LinearState) -> SweptVolume {
+        sweep_states(self, &[*from, from.lerp(to, 0.5), *to])
+    }
+}
+
+/// # Swept Volume
+/// Solid volume swept by a moving entity, a union of convex pieces.
+///
+/// Each piece is the convex hull of a triangle of the entity at both ends of a segment
+/// of the movement, so obstacles fully inside the volume collide with it too.
+/// Segments that do not move the triangles along straight lines, e.g. rotations,
+/// are inflated by twice the largest deviation of the vertices from their chords
+/// in the middle of the segment.
+#[derive(Debug, Clone)]
+pub struct SweptVolume {
+    pieces: Vec<ConvexHullCollider>,
+    bounds: AlignedBoxCollider,
+}
+
+impl SweptVolume {
+    /// Returns the convex pieces of the volume.
+    #[inline]
+    pub fn pieces(&self) -> &[ConvexHullCollider] {
+        &self.pieces
+    }
+}
+
+impl Bounded for SweptVolume {
+    /// Degenerate point at the origin for an entity without triangles.
+    fn min(&self) -> Vector3 {
+        self.bounds.min()
+    }
+
+    fn max(&self) -> Vector3 {
+        self.bounds.max()
+    }
+}
+
+impl Collides<PrimaryCollider> for SweptVolume {
+    fn collides_with(&self, other: &PrimaryCollider) -> bool {
+        let other_bounds = AlignedBoxCollider::from_min_max(other.min(), other.max());
+        !self.pieces.is_empty()
+            && self.bounds.collides_with(&other_bounds)
+            && self.pieces.iter().any(|piece| other.collides_with(piece))
+    }
+}
+
+impl Collides<SweptVolume> for PrimaryCollider {
+    #[inline]
+    fn collides_with(&self, other: &SweptVolume) -> bool {
+        other.collides_with(self)
+    }
+}
+
+/// Box proxy of a part that can report the hull of its sweep between two poses.
+///
+/// Poses rotate the box about the origin and then translate it.
+pub trait SweptHull {
+    /// Get the convex hull of the box corners at both `from` and `to`.
+    ///
+    /// Covers translations exactly and is tighter than the bounds of both boxes,
+    /// large rotations are not covered, see [Sweepable::swept_collider].
+    fn swept_hull(&self, from: &SixAxis, to: &SixAxis) -> ConvexHullCollider;
+}
+
+impl SweptHull for OrientedBoxCollider {
+    fn swept_hull(&self, from: &SixAxis, to: &SixAxis) -> ConvexHullCollider {
+        let corners = [from, to]
+            .into_iter()
+            .flat_map(|pose| {
+                let rotation = Quaternion::from_euler(&pose.rot);
+                self.transform(&rotation, &Vector3::ZERO, &pose.pos)
+                    .corners()
+            })
+            .collect();
+        ConvexHullCollider::new(corners)
+    }
+}
+
+impl SweptHull for AlignedBoxCollider {
+    fn swept_hull(&self, from: &SixAxis, to: &SixAxis) -> ConvexHullCollider {
+        OrientedBoxCollider::from(self).swept_hull(from, to)
+    }
+}
+
+/// Expects `states` with the ends of the segments at even indices and their middles in between.
+fn sweep_states<P, M>(movable: &M, states: &[P]) -> SweptVolume
+where
+    M: Movable<P> + ?Sized,
+{
+    let triangles = states
+        .iter()
+        .map(|s| triangles_of(&movable.move_to(s)))
+        .collect::<Vec<_>>();
+
+    let mut pieces = Vec::new();
+    for i in (0..triangles.len() - 2).step_by(2) {
+        let (start, middle, end) = (&triangles[i], &triangles[i + 1], &triangles[i + 2]);
+        let margin = 2.0 * chord_deviation(start, middle, end);
+        for (s, e) in start.chunks_exact(3).zip(end.chunks_exact(3)) {
+            pieces.push(inflated_hull(s.iter().chain(e), margin));
+        }
+    }
+
+    let bounds = pieces
+        .iter()
+        .map(|p| AlignedBoxCollider::from_min_max(p.min(), p.max()))
+        .reduce(|a, b| a.union(&b))
+        .unwrap_or_else(|| AlignedBoxCollider::from_min_max(Vector3::ZERO, Vector3::ZERO));
+    SweptVolume { pieces, bounds }
+}
+
+fn triangles_of(group: &Immovable) -> Vec<Vector3> {
+    group
+        .0
+        .iter()
+        .flat_map(|item| item.triangle_buffer())
+        .collect()
+}
+
+/// Largest distance of a vertex in the `middle` from the middle of its chord.
+fn chord_deviation(start: &[Vector3], middle: &[Vector3], end: &[Vector3]) -> f64 {
+    start
+        .iter()
+        .zip(middle)
+        .zip(end)
+        .map(|((s, m), e)| (*m - (*s + *e) / 2.0).len())
+        .fold(0.0, f64::max)
+}
+
+/// Hull of the `points`, each replaced by a cube of half size `margin`,
+/// which encloses the sphere of radius `margin`.
+fn inflated_hull<'a>(points: impl Iterator<Item = &'a Vector3>, margin: f64) -> ConvexHullCollider {
+    let mut corners = Vec::new();
+    for point in points {
+        if margin <= 0.0 {
+            corners.push(*point);
+            continue;
+        }
+
+        for x in [-margin, margin] {
+            for y in [-margin, margin] {
+                for z in [-margin, margin] {
+                    corners.push(*point + Vector3::new(x, y, z));
+                }
+            }
+        }
+    }
+    ConvexHullCollider::new(corners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::{Rotation, Translation};
+    use collisions::primitive::{PointCollider, TriangleCollider};
+    use collisions::scene::box_triangles;
+
+    struct TestPart(PrimaryCollider);
+
+    impl Movable<SixAxis> for TestPart {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            collider_group!(self.0.translate(&position.pos))
+        }
+    }
+
+    fn wall(x: f64) -> Immovable {
+        collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(x, -1.0, -1.0),
+            Vector3::new(x, 2.0, -1.0),
+            Vector3::new(x, -1.0, 2.0),
+        )]))
+    }
+
+    #[test]
+    fn swept_translation_encloses_path() {
+        let part = TestPart(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.5, 0.0, 0.0),
+            Vector3::new(0.0, 0.5, 0.5),
+        )]));
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 0.0));
+
+        let swept = collider_group!(part.swept_collider(&from, &to));
+
+        assert!(swept.collides_with(&part.move_to(&from)));
+        assert!(swept.collides_with(&part.move_to(&to)));
+        for x in [0.25, 2.0, 5.0, 9.0, 10.25] {
+            assert!(swept.collides_with(&wall(x)), "missed wall at {x}");
+        }
+        assert!(!swept.collides_with(&wall(-1.0)));
+        assert!(!swept.collides_with(&wall(11.0)));
+    }
+
+    #[test]
+    fn swept_translation_contains_enclosed_obstacle() {
+        let part = TestPart(PrimaryCollider::build(&box_triangles(
+            Vector3::new(0.0, -0.5, -0.5),
+            Vector3::new(1.0, 0.5, 0.5),
+        )));
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 0.0));
+        let obstacle = collider_group!(PrimaryCollider::build(&box_triangles(
+            Vector3::new(5.45, 0.25, -0.05),
+            Vector3::new(5.55, 0.35, 0.05),
+        )));
+
+        let swept = collider_group!(part.swept_collider(&from, &to));
+
+        assert!(swept.collides_with(&obstacle));
+        assert!(!swept.collides_with(&wall(12.0)));
+    }
+
+    /// Rotates about the z axis through the origin.
+    struct TestArm(PrimaryCollider);
+
+    impl Movable<SixAxis> for TestArm {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, position.rot.z()));
+            collider_group!(self.0.rotate_around(&rotation, &Vector3::ZERO))
+        }
+    }
+
+    #[test]
+    fn swept_rotation_covers_arcs() {
+        let arm = TestArm(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, -0.1),
+            Vector3::new(10.0, 0.0, -0.1),
+            Vector3::new(10.0, 0.0, 0.1),
+        )]));
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis {
+            pos: Vector3::ZERO,
+            rot: Vector3::new(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        };
+
+        let swept = collider_group!(arm.swept_collider(&from, &to));
+
+        //  the tip of the arm at a quarter of the way, outside of any chord of the arc
+        let angle = std::f64::consts::FRAC_PI_8 + SWEEP_MAX_ANGLE / 2.0;
+        let tip = Vector3::new(angle.cos(), angle.sin(), 0.0) * 9.9999;
+        let obstacle = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            tip,
+            tip + Vector3::new(1e-6, 0.0, 0.0),
+            tip + Vector3::new(0.0, 1e-6, 0.0),
+        )]));
+        assert!(swept.collides_with(&obstacle));
+        assert!(!swept.collides_with(&wall(11.0)));
+    }
+
+    #[test]
+    fn swept_hull_of_translation() {
+        let proxy = AlignedBoxCollider::new(Vector3::ZERO, Vector3::new(1.0, 1.0, 1.0));
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(3.0, 3.0, 0.0));
+
+        let hull = proxy.swept_hull(&from, &to);
+
+        let mut expected = proxy.translate(&from.pos).corners().to_vec();
+        expected.extend(proxy.translate(&to.pos).corners());
+        assert_eq!(16, hull.points().len());
+        for corner in &expected {
+            assert!(hull.points().contains(corner), "missing corner {corner:?}");
+        }
+
+        let union = proxy.translate(&from.pos).union(&proxy.translate(&to.pos));
+        assert_eq!((union.min(), union.max()), (hull.min(), hull.max()));
+
+        let off_path = Vector3::new(3.0, 0.0, 0.0);
+        assert!(union.collides_with(&PointCollider::new(off_path)));
+        assert!(!hull.contains(&off_path));
+        assert!(hull.contains(&Vector3::new(1.5, 1.5, 0.0)));
+    }
+}
@@ -1,5 +1,12 @@
 use crate::immovable::Immovable;
 use collisions::PrimaryCollider;
+use maths::Quaternion;
+
+/// Identifier of a sample placed on a holder.
+pub type SampleId = u64;
+
+/// Identifier of the sample placed by [Holder::swap_sample].
+pub const DEFAULT_SAMPLE_ID: SampleId = 0;
 
 /// # Stage Holder
 pub trait Holder: Send + Sync {
@@ -7,6 +14,16 @@ pub trait Holder: Send + Sync {
     fn cloned(&self) -> Box<dyn Holder>;
     /// Get the full representation of the holder.
     fn collider(&self) -> Immovable;
-    /// Swap the attached sample with the given one.
+    /// Swap all the attached samples with the given one.
+    ///
+    /// The given sample is attached under [DEFAULT_SAMPLE_ID].
     fn swap_sample(&mut self, sample: Option<PrimaryCollider>);
+    /// Attach another sample, replacing the sample with the same id.
+    fn add_sample(&mut self, id: SampleId, sample: PrimaryCollider);
+    /// Detach the sample with the given id.
+    fn remove_sample(&mut self, id: SampleId);
+    /// Rotate all the samples relative to the holder around the point they are attached at.
+    ///
+    /// Replaces the previous rotation, [Quaternion::IDENTITY] restores the original placement.
+    fn set_sample_rotation(&mut self, rotation: Quaternion);
 }
@@ -1,9 +1,11 @@
 use crate::movable::Movable;
 use crate::parts::holder::Holder;
 use crate::position::sixaxis::SixAxis;
+use maths::{Quaternion, Vector3};
 use std::sync::Arc;
 
 /// # Stage
+/// Moved stage ends with the active holder, its [Holder::collider] appended after the stage itself.
 pub trait Stage: Movable<SixAxis> {
     /// Get the Stage as [Arc] reference.
     fn as_movable(&self) -> Arc<dyn Movable<SixAxis>>;
@@ -13,4 +15,8 @@ pub trait Stage: Movable<SixAxis> {
     fn active_holder(&self) -> Option<&dyn Holder>;
     /// Get the mutable reference to the active holder.
     fn active_holder_mut(&mut self) -> Option<&mut (dyn Holder + 'static)>;
+    /// Get the point the stage rotates about when moved to `coords`.
+    fn rotation_pivot(&self, coords: &SixAxis) -> Vector3;
+    /// Get the rotation of the holder about [Stage::rotation_pivot] when moved to `coords`.
+    fn orientation(&self, coords: &SixAxis) -> Quaternion;
 }
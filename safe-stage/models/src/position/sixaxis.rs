@@ -1,4 +1,5 @@
-use maths::Vector3;
+use crate::parts::stage::Stage;
+use maths::{Axis, Quaternion, Vector3};
 use std::f64::consts;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
@@ -15,6 +16,21 @@ pub struct SixAxis {
     pub rot: Vector3,
 }
 
+/// Single degree of freedom of [SixAxis].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dof {
+    Px,
+    Py,
+    Pz,
+    Rx,
+    Ry,
+    Rz,
+}
+
+impl Dof {
+    pub const ALL: [Dof; 6] = [Dof::Px, Dof::Py, Dof::Pz, Dof::Rx, Dof::Ry, Dof::Rz];
+}
+
 impl Debug for SixAxis {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Coordinates")
@@ -174,26 +190,47 @@ impl SixAxis {
         self.dot(self).sqrt()
     }
 
+    /// Returns the time needed to move to `other`, given by the slowest axis.
+    ///
+    /// Axes without any distance to travel take no time, regardless of their speed.
+    /// Returns [f64::INFINITY] when `other` is unreachable,
+    /// i.e. an axis with zero speed has a non-zero distance to travel.
     #[inline]
     pub fn time_to(&self, other: &SixAxis, speed: &SixAxis) -> f64 {
+        self.axis_times(other, speed)
+            .into_iter()
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the degree of freedom taking the longest time when moving to `other`,
+    /// see [SixAxis::time_to].
+    ///
+    /// On a tie the first in the order of [Dof] is returned.
+    pub fn limiting_dof(&self, other: &SixAxis, speed: &SixAxis) -> Dof {
+        let times = self.axis_times(other, speed);
+        let mut limiting = 0;
+        for (i, time) in times.iter().enumerate() {
+            if *time > times[limiting] {
+                limiting = i;
+            }
+        }
+
+        Dof::ALL[limiting]
+    }
+
+    /// Time needed by each degree of freedom, in the order of [Dof].
+    #[inline]
+    fn axis_times(&self, other: &SixAxis, speed: &SixAxis) -> [f64; 6] {
         let pos = (self.pos - other.pos).abs();
         let rot = self.shortest_rotation(other);
         [
-            pos.x() / speed.pos.x(),
-            pos.y() / speed.pos.y(),
-            pos.z() / speed.pos.z(),
-            rot.x() / speed.rot.x(),
-            rot.y() / speed.rot.y(),
-            rot.z() / speed.rot.z(),
+            axis_time(pos.x(), speed.pos.x()),
+            axis_time(pos.y(), speed.pos.y()),
+            axis_time(pos.z(), speed.pos.z()),
+            axis_time(rot.x(), speed.rot.x()),
+            axis_time(rot.y(), speed.rot.y()),
+            axis_time(rot.z(), speed.rot.z()),
         ]
-        .iter()
-        .filter(|a| !a.is_nan())
-        .map(|a| a.abs())
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .map_or_else(
-            || panic!("Cannot determine time_to: {self:?}, {other:?}, speed: {speed:?}"),
-            |x| x,
-        )
     }
 
     pub fn time_to_path(&self, path: &[SixAxis], speed: &SixAxis) -> f64 {
@@ -220,6 +257,27 @@ impl SixAxis {
         self.time_to(&closest, speed)
     }
 
+    /// Returns the geodesic angle between the orientations of `self` and `other`.
+    ///
+    /// Unlike [SixAxis::shortest_rotation], this is the single rotation angle
+    /// needed to get from one orientation to the other, in range `[0, PI]`.
+    pub fn angular_distance(&self, other: &SixAxis) -> f64 {
+        let a = Quaternion::from_euler(&self.rot);
+        let b = Quaternion::from_euler(&other.rot);
+        let dot = a.w() * b.w() + a.x() * b.x() + a.y() * b.y() + a.z() * b.z();
+        2.0 * dot.abs().min(1.0).acos()
+    }
+
+    /// Returns the time needed to move to `other` with a linear and an angular speed limit.
+    ///
+    /// Translation and rotation happen simultaneously, so the slower of the two limits the move.
+    /// Returns [f64::INFINITY] when a non-zero distance has to be covered at zero speed.
+    pub fn move_duration(&self, other: &SixAxis, lin_speed: f64, ang_speed: f64) -> f64 {
+        let translation = axis_time((other.pos - self.pos).len(), lin_speed);
+        let rotation = axis_time(self.angular_distance(other), ang_speed);
+        translation.max(rotation)
+    }
+
     #[inline]
     pub const fn shortest_rotation(&self, other: &SixAxis) -> Vector3 {
         Vector3::new(
@@ -290,12 +348,59 @@ impl SixAxis {
 
         max_steps.ceil() as usize
     }
+
+    /// Tilts by `angle_deg` degrees around the `axis` while keeping the `eucentric` point
+    /// on the holder of the `stage` fixed.
+    ///
+    /// The holder is rotated by [Stage::orientation] about [Stage::rotation_pivot],
+    /// expects the pivot to move along with the position.
+    pub fn tilt_about_eucentric(
+        &self,
+        angle_deg: f64,
+        axis: Axis,
+        eucentric: Vector3,
+        stage: &dyn Stage,
+    ) -> SixAxis {
+        let angle = angle_deg.to_radians();
+        let tilt = match axis {
+            Axis::X => Vector3::new(angle, 0.0, 0.0),
+            Axis::Y => Vector3::new(0.0, angle, 0.0),
+            Axis::Z => Vector3::new(0.0, 0.0, angle),
+        };
+        let tilted = SixAxis {
+            pos: self.pos,
+            rot: self.rot + tilt,
+        };
+
+        let local =
+            (eucentric - stage.rotation_pivot(self)).rotate(&stage.orientation(self).conjugate());
+        let moved = local.rotate(&stage.orientation(&tilted)) + stage.rotation_pivot(&tilted);
+
+        SixAxis {
+            pos: self.pos + (eucentric - moved),
+            rot: tilted.rot,
+        }
+    }
+}
+
+#[inline]
+fn axis_time(distance: f64, speed: f64) -> f64 {
+    if distance == 0.0 {
+        0.0
+    } else if speed == 0.0 {
+        f64::INFINITY
+    } else {
+        (distance / speed).abs()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assembly::thesis::{ThesisHolderCircle, ThesisStage};
+    use crate::movable::Movable;
     use assert_float_eq::*;
+    use maths::asserts::assert_vectors_close;
 
     fn r(rad: f64) -> f64 {
         rad.to_radians()
@@ -322,6 +427,84 @@ mod tests {
         assert_float_absolute_eq!(r(0.0), d, 1e-10);
     }
 
+    #[test]
+    fn time_to_unreachable_on_zero_speed() {
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis::from_position(Vector3::new(1.0, 2.0, 0.0));
+        let speed = SixAxis {
+            pos: Vector3::new(1.0, 0.0, 1.0),
+            rot: Vector3::new(1.0, 1.0, 1.0),
+        };
+
+        assert_eq!(f64::INFINITY, from.time_to(&to, &speed));
+    }
+
+    #[test]
+    fn angular_distance_is_geodesic() {
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis {
+            pos: Vector3::ZERO,
+            rot: Vector3::new(0.0, 0.0, 3.0 * consts::FRAC_PI_2),
+        };
+
+        assert_float_absolute_eq!(consts::FRAC_PI_2, from.angular_distance(&to), 1e-10);
+        assert_float_absolute_eq!(0.0, to.angular_distance(&to), 1e-7);
+    }
+
+    #[test]
+    fn move_duration_rotation_dominates() {
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis {
+            pos: Vector3::new(0.1, 0.0, 0.0),
+            rot: Vector3::new(consts::FRAC_PI_2, 0.0, 0.0),
+        };
+
+        let duration = from.move_duration(&to, 1.0, 0.5);
+        assert_float_absolute_eq!(consts::PI, duration, 1e-10);
+    }
+
+    #[test]
+    fn move_duration_translation_dominates() {
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis {
+            pos: Vector3::new(3.0, 4.0, 0.0),
+            rot: Vector3::new(0.0, 0.1, 0.0),
+        };
+
+        assert_float_absolute_eq!(2.5, from.move_duration(&to, 2.0, 1.0), 1e-10);
+        assert_eq!(f64::INFINITY, from.move_duration(&to, 0.0, 1.0));
+        assert_eq!(0.0, from.move_duration(&from, 0.0, 0.0));
+    }
+
+    #[test]
+    fn limiting_dof_rotation_dominates() {
+        let from = SixAxis::from_position(Vector3::ZERO);
+        let to = SixAxis {
+            pos: Vector3::new(0.1, -0.2, 0.05),
+            rot: Vector3::new(0.1, 1.2, -0.1),
+        };
+        let speed = SixAxis {
+            pos: Vector3::new(1.0, 1.0, 1.0),
+            rot: Vector3::new(0.5, 0.5, 0.5),
+        };
+
+        assert_eq!(Dof::Ry, from.limiting_dof(&to, &speed));
+        assert_eq!(Dof::Px, from.limiting_dof(&from, &speed));
+    }
+
+    #[test]
+    fn time_to_zero_distance() {
+        let from = SixAxis::from_position(Vector3::new(1.0, 2.0, 3.0));
+        let no_speed = SixAxis::from_position(Vector3::ZERO);
+        let speed = SixAxis {
+            pos: Vector3::new(1.0, 0.0, 1.0),
+            rot: Vector3::new(1.0, 1.0, 1.0),
+        };
+
+        assert_eq!(0.0, from.time_to(&from, &no_speed));
+        assert_eq!(0.0, from.time_to(&from, &speed));
+    }
+
     #[test]
     fn time_to_segment_middle() {
         let start = SixAxis {
@@ -397,4 +580,24 @@ mod tests {
         let actual = start.stepping(&end, &step);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn tilt_about_eucentric_keeps_point_fixed() {
+        let mut stage = ThesisStage::default();
+        stage.swap_holder(Some(Box::new(ThesisHolderCircle::default())));
+        let holder_vertex =
+            |state: &SixAxis, i: usize| stage.move_to(state).0.last().unwrap().triangle_buffer()[i];
+        let state = SixAxis {
+            pos: Vector3::new(1e-3, -2e-3, 3e-3),
+            rot: Vector3::new(0.0, r(10.0), r(30.0)),
+        };
+        let eucentric = holder_vertex(&state, 0);
+
+        for axis in [Axis::Y, Axis::Z] {
+            let tilted = state.tilt_about_eucentric(40.0, axis, eucentric, &stage);
+
+            assert_vectors_close(eucentric, holder_vertex(&tilted, 0), 1e-9);
+            assert!((holder_vertex(&state, 1) - holder_vertex(&tilted, 1)).len() > 1e-6);
+        }
+    }
 }
@@ -1 +1,2 @@
+pub mod grayscale;
 pub mod height_map;
@@ -0,0 +1,38 @@
+/// Converts raw 8-bit grayscale pixels into a height map,
+/// mapping `0..=255` linearly to `0.0..=z_scale`.
+///
+/// Pixels are expected in row-major order,
+/// matching the layout of [height_map_to_sample_model](super::height_map::height_map_to_sample_model).
+pub fn height_map_from_grayscale(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    z_scale: f64,
+) -> Vec<f64> {
+    assert_eq!(pixels.len(), width * height);
+    assert!(z_scale.is_finite());
+
+    pixels
+        .iter()
+        .map(|&p| p as f64 / u8::MAX as f64 * z_scale)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::*;
+
+    #[test]
+    fn gradient_scales_linearly() {
+        let pixels = [0, 51, 102, 153, 204, 255];
+
+        let heights = height_map_from_grayscale(&pixels, 3, 2, 10.0);
+
+        let expected = [0.0, 2.0, 4.0, 6.0, 8.0, 10.0];
+        assert_eq!(expected.len(), heights.len());
+        for (expected, actual) in expected.iter().zip(&heights) {
+            assert_float_absolute_eq!(expected, actual);
+        }
+    }
+}
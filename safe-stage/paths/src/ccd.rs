@@ -0,0 +1,196 @@
+use collisions::common::{Collides, Distance};
+use models::immovable::Immovable;
+use models::movable::Movable;
+use models::position::sixaxis::SixAxis;
+
+/// Outcome of a continuous collision check along a linear movement.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Advancement {
+    /// The whole movement is free of collisions.
+    Clear,
+    /// The first reached state within the tolerance of an immovable.
+    Contact(SixAxis),
+}
+
+/// Continuous collision check of the linear movement from `from` to `to`
+/// by conservative advancement.
+///
+/// Each step advances by the current clearance divided by the motion bound,
+/// so no part of the movable can pass through an immovable between steps,
+/// no matter how thin it is.
+///
+/// The motion bound is derived from the movement and `radius`,
+/// the largest distance of any point of the movable from the rotation pivots.
+/// Contact is reported once the clearance drops to `tolerance`,
+/// the reported state is then collision-free unless `from` already collides.
+pub fn conservative_advancement(
+    from: &SixAxis,
+    to: &SixAxis,
+    movable: &dyn Movable<SixAxis>,
+    immovable: &Immovable,
+    radius: f64,
+    tolerance: f64,
+) -> Advancement {
+    assert!(tolerance > 0.0);
+    assert!(radius >= 0.0);
+
+    let bound = motion_bound(from, to, radius);
+
+    let mut t = 0.0;
+    loop {
+        let state = from.lerp_t(to, t);
+        let moved = movable.move_to(&state);
+        if immovable.collides_with(&moved) {
+            return Advancement::Contact(state);
+        }
+
+        let clearance = immovable.distance_to(&moved);
+        if clearance <= tolerance {
+            return Advancement::Contact(state);
+        }
+        if t >= 1.0 || bound <= 0.0 {
+            return Advancement::Clear;
+        }
+
+        //  Keep half the tolerance in reserve, so a contact found by advancing is never touching
+        t = (t + (clearance - tolerance / 2.0) / bound).min(1.0);
+    }
+}
+
+/// Largest distance any point within `radius` of the rotation pivots moves
+/// along the linear movement from `from` to `to`.
+pub fn motion_bound(from: &SixAxis, to: &SixAxis, radius: f64) -> f64 {
+    let translation = (to.pos - from.pos).len();
+    let rotation = (to.rot - from.rot).abs();
+    translation + (rotation.x() + rotation.y() + rotation.z()) * radius
+}
+
+/// Continuous collision check of two parts moving at the same time
+/// by conservative advancement, `first` and `second` return the parts at time `t` in `[0, 1]`.
+///
+/// The `bound` is the sum of the largest distances any point of either part moves
+/// over the whole time, see [motion_bound].
+/// Returns the first time at which the parts are within the `tolerance`,
+/// or `None` when they never are.
+pub fn conservative_advancement_pair(
+    first: impl Fn(f64) -> Immovable,
+    second: impl Fn(f64) -> Immovable,
+    bound: f64,
+    tolerance: f64,
+) -> Option<f64> {
+    assert!(tolerance > 0.0);
+    assert!(bound >= 0.0);
+
+    let mut t = 0.0;
+    loop {
+        let (a, b) = (first(t), second(t));
+        if a.collides_with(&b) {
+            return Some(t);
+        }
+
+        let clearance = a.distance_to(&b);
+        if clearance <= tolerance {
+            return Some(t);
+        }
+        if t >= 1.0 || bound <= 0.0 {
+            return None;
+        }
+
+        t = (t + (clearance - tolerance / 2.0) / bound).min(1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::Translation;
+    use collisions::primitive::TriangleCollider;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+
+    struct TestPart(PrimaryCollider);
+
+    impl Movable<SixAxis> for TestPart {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            collider_group!(self.0.translate(&position.pos))
+        }
+    }
+
+    fn part() -> TestPart {
+        TestPart(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.5, 0.0, 0.0),
+            Vector3::new(0.0, 0.5, 0.5),
+        )]))
+    }
+
+    fn thin_wall(x: f64) -> Immovable {
+        collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(x, -1.0, -1.0),
+            Vector3::new(x, 2.0, -1.0),
+            Vector3::new(x, -1.0, 2.0),
+        )]))
+    }
+
+    #[test]
+    fn detects_thin_wall_missed_by_sampling() {
+        let part = part();
+        let wall = thin_wall(5.0);
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 0.0));
+
+        let coarse_hit = (0..=3)
+            .map(|i| from.lerp_t(&to, i as f64 / 3.0))
+            .any(|s| wall.collides_with(&part.move_to(&s)));
+        assert!(!coarse_hit);
+
+        let result = conservative_advancement(&from, &to, &part, &wall, 0.0, 1e-6);
+        let Advancement::Contact(contact) = result else {
+            panic!("expected contact with the wall");
+        };
+        assert!(contact.pos.x() > 4.4 && contact.pos.x() <= 4.5);
+    }
+
+    #[test]
+    fn clear_without_obstacles_on_path() {
+        let part = part();
+        let wall = thin_wall(20.0);
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 0.0));
+
+        let result = conservative_advancement(&from, &to, &part, &wall, 0.0, 1e-6);
+        assert!(matches!(result, Advancement::Clear));
+    }
+
+    #[test]
+    fn detects_crossing_of_two_moving_parts() {
+        let part = part();
+        //  both cross x = 5 at the same time, one along x and the other along y
+        let along_x =
+            |t: f64| part.move_to(&SixAxis::from_position(Vector3::new(10.0 * t, 0.0, 0.0)));
+        let along_y = |t: f64| {
+            part.move_to(&SixAxis::from_position(Vector3::new(
+                5.0,
+                10.0 * t - 5.0,
+                0.0,
+            )))
+        };
+        let late_y = |t: f64| {
+            part.move_to(&SixAxis::from_position(Vector3::new(
+                5.0,
+                10.0 * t - 9.0,
+                0.0,
+            )))
+        };
+
+        let contact = conservative_advancement_pair(along_x, along_y, 20.0, 1e-6);
+        assert!(contact.is_some_and(|t| t > 0.4 && t < 0.5));
+
+        //  same paths crossing, but never at the same place at the same time
+        assert_eq!(
+            None,
+            conservative_advancement_pair(along_x, late_y, 20.0, 1e-6)
+        );
+    }
+}
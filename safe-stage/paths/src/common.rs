@@ -1,4 +1,8 @@
+pub mod collision_cache;
 pub mod heapstate;
+pub mod heuristic;
 pub mod reconstruct;
 pub mod sight;
+pub mod step;
 pub mod timing;
+pub mod whitelist;
@@ -0,0 +1,305 @@
+use collisions::common::Collides;
+use maths::Vector3;
+use models::immovable::Immovable;
+use models::movable::Movable;
+use models::position::sixaxis::SixAxis;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// # Collision Cache
+/// Remembers collision results of poses,
+/// evicting the least recently used ones once the capacity is reached.
+///
+/// Only valid for a single movable and immovable pair, must be cleared when either changes.
+///
+/// Conservative, a colliding pose is never reported as free.
+/// Free results are only reused for the exact same pose,
+/// colliding results are reused for every pose in the grid cell of the colliding one,
+/// so a free pose may be reported as colliding when it is up to half a cell from a colliding one.
+/// Use a grid finer than the clearance kept around the obstacles.
+pub struct CollisionCache {
+    grid: SixAxis,
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+/// Cached result, the result is given by the kind of the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CacheKey {
+    /// The exact pose is free.
+    Free(SixAxis),
+    /// A pose snapped to the grid cell collides.
+    Colliding(SixAxis),
+}
+
+#[derive(Default)]
+struct CacheInner {
+    results: HashMap<CacheKey, u64>,
+    //  Lazily cleaned usage order, entries with an older tick than in `results` are stale
+    order: VecDeque<(CacheKey, u64)>,
+    tick: u64,
+}
+
+impl CacheInner {
+    /// Drops stale entries from the usage order.
+    fn compact(&mut self) {
+        let results = &self.results;
+        self.order
+            .retain(|(key, tick)| results.get(key) == Some(tick));
+    }
+}
+
+impl CollisionCache {
+    pub fn new(grid: SixAxis, capacity: usize) -> Self {
+        Self {
+            grid,
+            capacity: capacity.max(1),
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// Checks the collision of the movable at the state, using the cached result if present.
+    ///
+    /// A cached collision may come from another pose in the grid cell, see [CollisionCache].
+    pub fn collides(
+        &self,
+        state: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+    ) -> bool {
+        self.collides_by(state, || immovable.collides_with(&movable.move_to(state)))
+    }
+
+    /// Same as [CollisionCache::collides], computing missing results with `check`.
+    pub fn collides_by(&self, state: &SixAxis, check: impl FnOnce() -> bool) -> bool {
+        let free = CacheKey::Free(exact(state));
+        let colliding = CacheKey::Colliding(self.quantize(state));
+        if self.lookup(&free) {
+            return false;
+        }
+        if self.lookup(&colliding) {
+            return true;
+        }
+
+        let result = check();
+        self.insert(if result { colliding } else { free });
+        result
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        *self.inner.lock().unwrap() = CacheInner::default();
+    }
+
+    fn quantize(&self, state: &SixAxis) -> SixAxis {
+        //  Adding zero turns negative zero into positive one, so equal poses hash equally
+        let snap = |value: f64, step: f64| {
+            if step > 0.0 {
+                (value / step).round() * step + 0.0
+            } else {
+                value + 0.0
+            }
+        };
+        SixAxis {
+            pos: Vector3::new(
+                snap(state.pos.x(), self.grid.pos.x()),
+                snap(state.pos.y(), self.grid.pos.y()),
+                snap(state.pos.z(), self.grid.pos.z()),
+            ),
+            rot: Vector3::new(
+                snap(state.rot.x(), self.grid.rot.x()),
+                snap(state.rot.y(), self.grid.rot.y()),
+                snap(state.rot.z(), self.grid.rot.z()),
+            ),
+        }
+    }
+
+    /// Returns whether the `key` is cached, marking it as used.
+    fn lookup(&self, key: &CacheKey) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+        let Some(used) = inner.results.get_mut(key) else {
+            return false;
+        };
+        *used = tick;
+        inner.order.push_back((*key, tick));
+        if inner.order.len() > self.capacity * 4 {
+            inner.compact();
+        }
+        true
+    }
+
+    fn insert(&self, key: CacheKey) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+        inner.results.insert(key, tick);
+        inner.order.push_back((key, tick));
+
+        while inner.results.len() > self.capacity {
+            let Some((oldest, used)) = inner.order.pop_front() else {
+                break;
+            };
+            if inner.results.get(&oldest) == Some(&used) {
+                inner.results.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Returns the `state` with negative zeros turned into positive ones, so equal poses hash equally.
+fn exact(state: &SixAxis) -> SixAxis {
+    SixAxis {
+        pos: state.pos + Vector3::ZERO,
+        rot: state.rot + Vector3::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::Translation;
+    use collisions::primitive::TriangleCollider;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingPart(PrimaryCollider, AtomicUsize);
+
+    impl Movable<SixAxis> for CountingPart {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            collider_group!(self.0.translate(&position.pos))
+        }
+    }
+
+    fn part() -> CountingPart {
+        CountingPart(
+            PrimaryCollider::build(&[TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+            )]),
+            AtomicUsize::new(0),
+        )
+    }
+
+    fn cache(capacity: usize) -> CollisionCache {
+        CollisionCache::new(
+            SixAxis {
+                pos: Vector3::new(0.1, 0.1, 0.1),
+                rot: Vector3::new(0.1, 0.1, 0.1),
+            },
+            capacity,
+        )
+    }
+
+    fn wall() -> Immovable {
+        collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.5, -1.0, -1.0),
+            Vector3::new(0.5, 2.0, -1.0),
+            Vector3::new(0.5, -1.0, 2.0),
+        )]))
+    }
+
+    #[test]
+    fn revisited_pose_hits() {
+        let part = part();
+        let immovable = collider_group!();
+        let cache = cache(8);
+        let pose = SixAxis::from_position(Vector3::new(1.0, 2.0, 3.0));
+
+        assert!(!cache.collides(&pose, &part, &immovable));
+        assert!(!cache.collides(&pose, &part, &immovable));
+
+        assert_eq!(1, part.1.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn free_result_is_not_shared_with_the_cell() {
+        let part = part();
+        let cache = cache(8);
+        let free = SixAxis::from_position(Vector3::new(0.54, 0.0, 0.0));
+        let colliding = SixAxis::from_position(Vector3::new(0.46, 0.0, 0.0));
+
+        assert!(!cache.collides(&free, &part, &wall()));
+        assert!(cache.collides(&colliding, &part, &wall()));
+
+        assert_eq!(2, part.1.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn colliding_result_is_shared_with_the_cell() {
+        let part = part();
+        let immovable = wall();
+        let cache = cache(8);
+        let pose = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let near = SixAxis::from_position(Vector3::new(0.01, 0.0, -0.01));
+
+        assert!(cache.collides(&pose, &part, &immovable));
+        assert!(cache.collides(&near, &part, &immovable));
+
+        assert_eq!(1, part.1.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn distinct_poses_miss() {
+        let part = part();
+        let immovable = collider_group!();
+        let cache = cache(8);
+
+        for x in 0..4 {
+            let pose = SixAxis::from_position(Vector3::new(x as f64, 0.0, 0.0));
+            cache.collides(&pose, &part, &immovable);
+        }
+
+        assert_eq!(4, part.1.load(Ordering::Relaxed));
+        assert_eq!(4, cache.len());
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        let part = part();
+        let immovable = collider_group!();
+        let cache = cache(2);
+        let a = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let b = SixAxis::from_position(Vector3::new(1.0, 0.0, 0.0));
+        let c = SixAxis::from_position(Vector3::new(2.0, 0.0, 0.0));
+
+        cache.collides(&a, &part, &immovable);
+        cache.collides(&b, &part, &immovable);
+        cache.collides(&a, &part, &immovable);
+        cache.collides(&c, &part, &immovable);
+        assert_eq!(3, part.1.load(Ordering::Relaxed));
+
+        //  `b` was the least recently used
+        cache.collides(&a, &part, &immovable);
+        assert_eq!(3, part.1.load(Ordering::Relaxed));
+        cache.collides(&b, &part, &immovable);
+        assert_eq!(4, part.1.load(Ordering::Relaxed));
+        assert_eq!(2, cache.len());
+    }
+
+    #[test]
+    fn cached_result_matches_collision() {
+        let part = part();
+        let immovable = wall();
+        let cache = cache(8);
+        let hit = SixAxis::from_position(Vector3::ZERO);
+        let miss = SixAxis::from_position(Vector3::new(5.0, 0.0, 0.0));
+
+        assert!(cache.collides(&hit, &part, &immovable));
+        assert!(cache.collides(&hit, &part, &immovable));
+        assert!(!cache.collides(&miss, &part, &immovable));
+    }
+}
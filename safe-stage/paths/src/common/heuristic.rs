@@ -0,0 +1,65 @@
+use models::position::sixaxis::SixAxis;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Memoized heuristic values towards a single goal, keyed by a quantized state.
+pub struct HeuristicCache<K>(HashMap<K, f64>);
+
+impl<K> HeuristicCache<K>
+where
+    K: Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Returns the cached value for the key or computes and caches it.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> f64
+    where
+        F: FnOnce() -> f64,
+    {
+        *self.0.entry(key).or_insert_with(compute)
+    }
+}
+
+/// Quantizes the state to the grid given by the position and rotation steps.
+pub fn quantize_sixaxis(state: &SixAxis, pos_step: f64, rot_step: f64) -> [i64; 6] {
+    [
+        (state.pos.x() / pos_step).round() as i64,
+        (state.pos.y() / pos_step).round() as i64,
+        (state.pos.z() / pos_step).round() as i64,
+        (state.rot.x() / rot_step).round() as i64,
+        (state.rot.y() / rot_step).round() as i64,
+        (state.rot.z() / rot_step).round() as i64,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maths::Vector3;
+    use std::cell::Cell;
+
+    #[test]
+    fn revisited_states_are_not_recomputed() {
+        let calls = Cell::new(0);
+        let heuristic = |s: &SixAxis| {
+            calls.set(calls.get() + 1);
+            s.pos.len()
+        };
+        let mut cache = HeuristicCache::new();
+
+        let a = SixAxis::from_position(Vector3::new(1.0, 0.0, 0.0));
+        let b = SixAxis::from_position(Vector3::new(0.0, 2.0, 0.0));
+        let a_again = SixAxis::from_position(Vector3::new(1.0 + 1e-9, 0.0, 0.0));
+
+        let states = [a, b, a, a_again, b];
+        let values = states
+            .iter()
+            .map(|s| cache.get_or_compute(quantize_sixaxis(s, 0.1, 0.1), || heuristic(s)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(2, calls.get());
+        assert_eq!(vec![1.0, 2.0, 1.0, 1.0, 2.0], values);
+    }
+}
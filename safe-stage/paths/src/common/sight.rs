@@ -1,3 +1,4 @@
+use crate::common::whitelist::CollisionWhitelist;
 use collisions::common::Collides;
 use maths::NaNExtension;
 use models::immovable::Immovable;
@@ -46,11 +47,32 @@ pub fn line_of_sight_step_par(
     movable: &dyn Movable<SixAxis>,
     immovable: &Immovable,
     step: &SixAxis,
+) -> bool {
+    line_of_sight_whitelisted_par(
+        from,
+        to,
+        movable,
+        immovable,
+        step,
+        &CollisionWhitelist::new(),
+    )
+}
+
+/// Same as [line_of_sight_step_par], ignoring collisions of the pairs allowed by the `whitelist`.
+///
+/// **Runs in parallel using Rayon.**
+pub fn line_of_sight_whitelisted_par(
+    from: &SixAxis,
+    to: &SixAxis,
+    movable: &dyn Movable<SixAxis>,
+    immovable: &Immovable,
+    step: &SixAxis,
+    whitelist: &CollisionWhitelist,
 ) -> bool {
     let max_steps = from.stepping(to, step);
     (0..=max_steps).into_par_iter().all(|i| {
         let t = (i as f64 / max_steps as f64).map_nan(0.0);
         let state = from.lerp_t(to, t);
-        !immovable.collides_with(&movable.move_to(&state))
+        !whitelist.collides(immovable, &movable.move_to(&state))
     })
 }
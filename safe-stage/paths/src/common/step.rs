@@ -0,0 +1,159 @@
+use maths::Vector3;
+use models::position::linear::LinearState;
+use models::position::sixaxis::SixAxis;
+
+/// Returns the largest rotation step for which a point at `radius` from the pivot
+/// sweeps an arc no longer than `pos_step`.
+pub fn max_rotation_step(radius: f64, pos_step: f64) -> f64 {
+    if radius <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    pos_step / radius
+}
+
+/// Checks that the rotation steps are fine enough for a part of `radius`
+/// not to tunnel through obstacles thinner than the positional step.
+///
+/// Logs a warning and returns `false` if any rotation step is too coarse.
+pub fn validate_rotation_step(step: &SixAxis, radius: f64) -> bool {
+    let pos_step = step.pos.x().min(step.pos.y()).min(step.pos.z());
+    let max = max_rotation_step(radius, pos_step);
+    let coarsest = step.rot.x().max(step.rot.y()).max(step.rot.z());
+    if coarsest > max {
+        log::warn!(
+            "Rotation step {coarsest} is too coarse for part of radius {radius}, use at most {max}"
+        );
+        return false;
+    }
+
+    true
+}
+
+/// Rounds `value` to the nearest multiple of `step` away from `origin`.
+///
+/// A `step` that is not positive and finite means the axis has no lattice,
+/// so `value` is returned unchanged.
+pub fn snap_to_step(value: f64, step: f64, origin: f64) -> f64 {
+    if !(step.is_finite() && step > 0.0) {
+        return value;
+    }
+
+    origin + ((value - origin) / step).round() * step
+}
+
+/// Rounds every axis of `state` to the lattice of `step` anchored at `origin`.
+pub fn snap_six_axis(state: &SixAxis, step: &SixAxis, origin: &SixAxis) -> SixAxis {
+    let snap = |value: &Vector3, step: &Vector3, origin: &Vector3| {
+        Vector3::new(
+            snap_to_step(value.x(), step.x(), origin.x()),
+            snap_to_step(value.y(), step.y(), origin.y()),
+            snap_to_step(value.z(), step.z(), origin.z()),
+        )
+    };
+
+    SixAxis {
+        pos: snap(&state.pos, &step.pos, &origin.pos),
+        rot: snap(&state.rot, &step.rot, &origin.rot),
+    }
+}
+
+/// Rounds `state` to the lattice of `step` anchored at `origin`, staying within the full range.
+pub fn snap_linear(state: &LinearState, step: &LinearState, origin: &LinearState) -> LinearState {
+    let snapped = snap_to_step(
+        state.as_relative(),
+        step.as_relative(),
+        origin.as_relative(),
+    );
+    LinearState::relative(snapped.clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc_stays_within_positional_step() {
+        for (radius, pos_step) in [(1.0, 0.1), (0.25, 0.001), (120.0, 0.5), (1e-3, 1e-4)] {
+            let angle = max_rotation_step(radius, pos_step);
+            assert!(radius * angle <= pos_step);
+            assert!(radius * angle * 1.01 > pos_step);
+        }
+    }
+
+    #[test]
+    fn point_part_has_no_limit() {
+        assert_eq!(f64::INFINITY, max_rotation_step(0.0, 0.1));
+    }
+
+    #[test]
+    fn coarse_rotation_is_rejected() {
+        let step = SixAxis {
+            pos: Vector3::new(0.1, 0.1, 0.1),
+            rot: Vector3::new(0.01, 0.01, 0.2),
+        };
+        assert!(!validate_rotation_step(&step, 1.0));
+        assert!(validate_rotation_step(&step, 0.5));
+    }
+
+    #[test]
+    fn off_grid_state_snaps_to_nearest_point() {
+        let step = SixAxis {
+            pos: Vector3::new(0.1, 0.1, 0.0),
+            rot: Vector3::new(0.05, 0.05, 0.05),
+        };
+        let origin = SixAxis {
+            pos: Vector3::new(0.03, 0.0, 0.5),
+            rot: Vector3::new(0.0, 0.01, 0.0),
+        };
+        let state = SixAxis {
+            pos: Vector3::new(0.24, -0.31, 0.7),
+            rot: Vector3::new(0.02, 0.07, -0.08),
+        };
+
+        let snapped = snap_six_axis(&state, &step, &origin);
+        let expected = SixAxis {
+            pos: Vector3::new(0.23, -0.3, 0.7),
+            rot: Vector3::new(0.0, 0.06, -0.1),
+        };
+        let epsilon = SixAxis {
+            pos: Vector3::new(1e-12, 1e-12, 1e-12),
+            rot: Vector3::new(1e-12, 1e-12, 1e-12),
+        };
+        assert!(snapped.close_to(&expected, &epsilon));
+    }
+
+    #[test]
+    fn on_grid_state_is_unchanged() {
+        let step = SixAxis {
+            pos: Vector3::new(0.25, 0.25, 0.25),
+            rot: Vector3::new(0.5, 0.5, 0.5),
+        };
+        let origin = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let state = SixAxis {
+            pos: Vector3::new(0.75, -0.5, 1.25),
+            rot: Vector3::new(1.0, -0.5, 0.0),
+        };
+
+        assert_eq!(state, snap_six_axis(&state, &step, &origin));
+    }
+
+    #[test]
+    fn linear_snap_stays_within_range() {
+        let step = LinearState::Partial(0.3);
+        let origin = LinearState::Partial(0.2);
+
+        assert_eq!(
+            LinearState::Partial(0.5),
+            snap_linear(&LinearState::Partial(0.45), &step, &origin)
+        );
+        assert_eq!(
+            LinearState::Full,
+            snap_linear(&LinearState::Full, &step, &origin)
+        );
+        assert_eq!(
+            LinearState::None,
+            snap_linear(&LinearState::None, &step, &origin)
+        );
+    }
+}
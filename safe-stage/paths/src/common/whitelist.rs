@@ -0,0 +1,81 @@
+use collisions::common::Collides;
+use models::immovable::Immovable;
+use std::collections::HashSet;
+
+/// # Collision Whitelist
+/// Pairs of groups allowed to touch, e.g. a sample resting on a holder.
+///
+/// A pair is given by the index of the group in the moved representation
+/// and the index of the group in the immovable.
+#[derive(Debug, Clone, Default)]
+pub struct CollisionWhitelist {
+    pairs: HashSet<(usize, usize)>,
+}
+
+impl CollisionWhitelist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows the `moved` group to touch the `immovable` group.
+    pub fn allow(mut self, moved: usize, immovable: usize) -> Self {
+        self.pairs.insert((moved, immovable));
+        self
+    }
+
+    pub fn is_allowed(&self, moved: usize, immovable: usize) -> bool {
+        self.pairs.contains(&(moved, immovable))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Checks if any group of the `moved` collides with any group of the `immovable`,
+    /// ignoring the allowed pairs.
+    pub fn collides(&self, immovable: &Immovable, moved: &Immovable) -> bool {
+        if self.is_empty() {
+            return immovable.collides_with(moved);
+        }
+
+        moved.0.iter().enumerate().any(|(m, moved)| {
+            immovable
+                .0
+                .iter()
+                .enumerate()
+                .any(|(i, immovable)| !self.is_allowed(m, i) && immovable.collides_with(moved))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::scene::box_triangles;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+
+    fn block(min: Vector3, max: Vector3) -> PrimaryCollider {
+        PrimaryCollider::build(&box_triangles(min, max))
+    }
+
+    #[test]
+    fn allowed_pair_is_ignored() {
+        let holder = block(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 0.0));
+        let wall = block(Vector3::new(5.0, -1.0, -1.0), Vector3::new(6.0, 1.0, 1.0));
+        let immovable = collider_group!(wall, holder);
+        let sample = collider_group!(block(
+            Vector3::new(-0.5, -0.5, 0.0),
+            Vector3::new(0.5, 0.5, 0.5)
+        ));
+
+        assert!(CollisionWhitelist::new().collides(&immovable, &sample));
+        assert!(CollisionWhitelist::new()
+            .allow(0, 0)
+            .collides(&immovable, &sample));
+        assert!(!CollisionWhitelist::new()
+            .allow(0, 1)
+            .collides(&immovable, &sample));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::common::heapstate::MinHeapState;
+use crate::common::heuristic::{quantize_sixaxis, HeuristicCache};
 use crate::common::reconstruct::reconstruct_path;
 use crate::neighbors::NeighborStrategy;
 use crate::path::PathResult;
@@ -68,6 +69,7 @@ where
         g_score.insert(*from, 0.0);
         let mut f_score = HashMap::new();
         f_score.insert(*from, self.heuristic(from, to));
+        let mut heuristic = HeuristicCache::new();
 
         while let Some(current) = open_set.pop() {
             let current = current.state;
@@ -100,7 +102,9 @@ where
                     }
                 }
 
-                let f = tentative_g_score + self.heuristic(&neighbor, to);
+                let key = quantize_sixaxis(&neighbor, self.move_step, self.rotate_step);
+                let f = tentative_g_score
+                    + heuristic.get_or_compute(key, || self.heuristic(&neighbor, to));
 
                 came_from.insert(neighbor, current);
                 g_score.insert(neighbor, tentative_g_score);
@@ -1,6 +1,6 @@
+use crate::common::whitelist::CollisionWhitelist;
 use crate::path::PathResult;
 use crate::strategy::PathStrategy;
-use collisions::common::Collides;
 use maths::NaNExtension;
 use models::immovable::Immovable;
 use models::movable::Movable;
@@ -16,11 +16,21 @@ use rayon::prelude::*;
 /// Single-threaded version available with [LinearStrategy].
 pub struct LinearParallelStrategy<P> {
     step_size: P,
+    whitelist: CollisionWhitelist,
 }
 
 impl<P> LinearParallelStrategy<P> {
     pub fn new(step_size: P) -> Self {
-        Self { step_size }
+        Self {
+            step_size,
+            whitelist: CollisionWhitelist::new(),
+        }
+    }
+
+    /// Ignore collisions of the pairs allowed by the `whitelist`.
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.whitelist = whitelist;
+        self
     }
 }
 
@@ -32,7 +42,7 @@ impl PathStrategy<SixAxis> for LinearParallelStrategy<SixAxis> {
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
     ) -> PathResult<SixAxis> {
-        if immovable.collides_with(&movable.move_to(from)) {
+        if self.whitelist.collides(immovable, &movable.move_to(from)) {
             return PathResult::InvalidStart(*from);
         }
 
@@ -40,7 +50,7 @@ impl PathStrategy<SixAxis> for LinearParallelStrategy<SixAxis> {
         let first = (1..=max_steps).into_par_iter().find_first(|i| {
             let t = (*i as f64 / max_steps as f64).map_nan(0.0);
             let state = from.lerp_t(to, t);
-            immovable.collides_with(&movable.move_to(&state))
+            self.whitelist.collides(immovable, &movable.move_to(&state))
         });
 
         if let Some(i) = first {
@@ -65,7 +75,7 @@ impl PathStrategy<LinearState> for LinearParallelStrategy<LinearState> {
         movable: &dyn Movable<LinearState>,
         immovable: &Immovable,
     ) -> PathResult<LinearState> {
-        if immovable.collides_with(&movable.move_to(from)) {
+        if self.whitelist.collides(immovable, &movable.move_to(from)) {
             return PathResult::InvalidStart(*from);
         }
 
@@ -75,7 +85,7 @@ impl PathStrategy<LinearState> for LinearParallelStrategy<LinearState> {
         let first = (1..=steps).into_par_iter().find_first(|i| {
             let t = (*i as f64 / steps as f64).map_nan(0.0);
             let state = from.lerp(to, t);
-            immovable.collides_with(&movable.move_to(&state))
+            self.whitelist.collides(immovable, &movable.move_to(&state))
         });
 
         if let Some(i) = first {
@@ -1,6 +1,6 @@
+use crate::common::whitelist::CollisionWhitelist;
 use crate::path::PathResult;
 use crate::strategy::PathStrategy;
-use collisions::common::Collides;
 use maths::{NaNExtension, Vector3};
 use models::immovable::Immovable;
 use models::movable::Movable;
@@ -18,6 +18,7 @@ pub struct SafeRotationPointParallelStrategy {
     tend_point: Vector3,
     move_step: Vector3,
     rotation_step: Vector3,
+    whitelist: CollisionWhitelist,
 }
 
 impl SafeRotationPointParallelStrategy {
@@ -26,8 +27,15 @@ impl SafeRotationPointParallelStrategy {
             tend_point,
             move_step,
             rotation_step,
+            whitelist: CollisionWhitelist::new(),
         }
     }
+
+    /// Ignore collisions of the pairs allowed by the `whitelist`.
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.whitelist = whitelist;
+        self
+    }
 }
 
 impl PathStrategy<SixAxis> for SafeRotationPointParallelStrategy {
@@ -38,7 +46,7 @@ impl PathStrategy<SixAxis> for SafeRotationPointParallelStrategy {
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
     ) -> PathResult<SixAxis> {
-        if immovable.collides_with(&movable.move_to(from)) {
+        if self.whitelist.collides(immovable, &movable.move_to(from)) {
             return PathResult::InvalidStart(*from);
         }
 
@@ -55,7 +63,7 @@ impl PathStrategy<SixAxis> for SafeRotationPointParallelStrategy {
                     let rot = from.rot.lerp(&to.rot, t);
                     let state = SixAxis { pos, rot };
 
-                    if immovable.collides_with(&movable.move_to(&state)) {
+                    if self.whitelist.collides(immovable, &movable.move_to(&state)) {
                         if j == 0 {
                             return (i, Some(false));
                         }
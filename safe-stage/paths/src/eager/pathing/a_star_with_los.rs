@@ -1,10 +1,12 @@
+use crate::cancel::CancelToken;
 use crate::common::heapstate::MinHeapState;
+use crate::common::heuristic::HeuristicCache;
 use crate::common::reconstruct::reconstruct_path;
-use crate::common::sight::line_of_sight_step_par;
+use crate::common::sight::line_of_sight_whitelisted_par;
+use crate::common::whitelist::CollisionWhitelist;
 use crate::eager::space::space_3d::Grid3DSpace;
 use crate::path::PathResult;
 use crate::strategy::PathStrategy;
-use collisions::common::Collides;
 use maths::Vector3;
 use models::immovable::Immovable;
 use models::movable::Movable;
@@ -25,6 +27,7 @@ pub struct AStar3DSpaceWithLoSStrategy<'a> {
     speed: Vector3,
     epsilon: Vector3,
     los_step: SixAxis,
+    whitelist: CollisionWhitelist,
 }
 
 impl<'a> AStar3DSpaceWithLoSStrategy<'a> {
@@ -39,9 +42,16 @@ impl<'a> AStar3DSpaceWithLoSStrategy<'a> {
             speed,
             epsilon,
             los_step: SixAxis::from_position(los_step),
+            whitelist: CollisionWhitelist::new(),
         }
     }
 
+    /// Ignore collisions of the pairs allowed by the `whitelist`.
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.whitelist = whitelist;
+        self
+    }
+
     #[inline]
     const fn with_speed(&self, v: &Vector3) -> Vector3 {
         Vector3::new(
@@ -92,7 +102,14 @@ impl<'a> AStar3DSpaceWithLoSStrategy<'a> {
             rot: *rot,
         };
 
-        line_of_sight_step_par(&sight_from, &sight_to, movable, immovable, &self.los_step)
+        line_of_sight_whitelisted_par(
+            &sight_from,
+            &sight_to,
+            movable,
+            immovable,
+            &self.los_step,
+            &self.whitelist,
+        )
     }
 }
 
@@ -104,7 +121,41 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
     ) -> PathResult<SixAxis> {
-        if immovable.collides_with(&movable.move_to(from)) {
+        self.search(from, to, movable, immovable, &CancelToken::new())
+    }
+
+    /// The `cancel` token is checked before taking every node from the open set.
+    fn find_path_cancellable(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<SixAxis> {
+        let result = self.search(from, to, movable, immovable, cancel);
+        if matches!(result, PathResult::Path(_)) {
+            progress(1.0);
+        }
+        result
+    }
+}
+
+impl AStar3DSpaceWithLoSStrategy<'_> {
+    fn search(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+        cancel: &CancelToken,
+    ) -> PathResult<SixAxis> {
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
+        if self.whitelist.collides(immovable, &movable.move_to(from)) {
             return PathResult::InvalidStart(*from);
         }
 
@@ -127,6 +178,7 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
         let mut came_from = HashMap::new();
         let mut g_score = HashMap::new();
         let mut f_score = HashMap::new();
+        let mut heuristic = HeuristicCache::new();
 
         open_set.push(MinHeapState {
             weight: 0.0,
@@ -136,6 +188,10 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
         f_score.insert(grid_start, self.heuristic(&from.pos, &to.pos));
 
         while let Some(MinHeapState { state: current, .. }) = open_set.pop() {
+            if cancel.is_cancelled() {
+                return PathResult::Cancelled;
+            }
+
             let current_global = self.space.grid_to_global(&current);
             let diff = (to.pos - current_global).abs();
 
@@ -143,7 +199,10 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
                 && diff.y() < self.epsilon.y()
                 && diff.z() < self.epsilon.z()
             {
-                if immovable.collides_with(&movable.move_to(&into_sixaxis(&to.pos, from))) {
+                if self
+                    .whitelist
+                    .collides(immovable, &movable.move_to(&into_sixaxis(&to.pos, from)))
+                {
                     return PathResult::UnreachableEnd(Some(self.grid_path_to_global_with_start(
                         &reconstruct_path(&came_from, &current),
                         from,
@@ -171,8 +230,8 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
                     continue;
                 }
 
-                if immovable.collides_with(&movable.move_to(&into_sixaxis(&neighbor_global, from)))
-                {
+                let at_neighbor = movable.move_to(&into_sixaxis(&neighbor_global, from));
+                if self.whitelist.collides(immovable, &at_neighbor) {
                     continue;
                 }
 
@@ -185,8 +244,9 @@ impl PathStrategy<SixAxis> for AStar3DSpaceWithLoSStrategy<'_> {
                     }
                 }
 
-                let tentative_f_score =
-                    tentative_g_score + self.heuristic(&neighbor_global, &to.pos);
+                let tentative_f_score = tentative_g_score
+                    + heuristic
+                        .get_or_compute(neighbor, || self.heuristic(&neighbor_global, &to.pos));
 
                 came_from.insert(neighbor, current);
                 g_score.insert(neighbor, tentative_g_score);
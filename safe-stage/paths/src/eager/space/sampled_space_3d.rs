@@ -1,3 +1,4 @@
+use crate::common::whitelist::CollisionWhitelist;
 use crate::eager::space::space_3d::Grid3DSpace;
 use bitvec::vec::BitVec;
 use collisions::common::Collides;
@@ -59,6 +60,29 @@ pub fn sample_grid_space_3d_par(
     immovable: &Immovable,
     step: &Vector3,
     rotation: &Vector3,
+) -> Grid3DSpace {
+    sample_grid_space_3d_whitelisted_par(
+        min,
+        max,
+        movable,
+        immovable,
+        step,
+        rotation,
+        &CollisionWhitelist::new(),
+    )
+}
+
+/// Same as [sample_grid_space_3d_par], ignoring collisions of the pairs allowed by the `whitelist`.
+///
+/// **Runs in parallel using Rayon.**
+pub fn sample_grid_space_3d_whitelisted_par(
+    min: &Vector3,
+    max: &Vector3,
+    movable: &dyn Movable<SixAxis>,
+    immovable: &Immovable,
+    step: &Vector3,
+    rotation: &Vector3,
+    whitelist: &CollisionWhitelist,
 ) -> Grid3DSpace {
     let diff = max - min;
     let (dx, dy, dz) = (
@@ -84,7 +108,7 @@ pub fn sample_grid_space_3d_par(
             };
 
             let at_sample = movable.move_to(&sample);
-            immovable.collides_with(&at_sample)
+            whitelist.collides(immovable, &at_sample)
         })
         .collect::<Vec<_>>();
 
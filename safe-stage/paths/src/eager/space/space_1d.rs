@@ -80,7 +80,7 @@ impl Grid1DSpace {
     }
 
     #[inline]
-    pub const fn neighbors_iter(&self, x: usize) -> NeighborsIter {
+    pub const fn neighbors_iter(&self, x: usize) -> NeighborsIter<'_> {
         NeighborsIter {
             state: NeighborsIterState::Init,
             x,
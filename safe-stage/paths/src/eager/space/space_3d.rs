@@ -144,7 +144,7 @@ impl Grid3DSpace {
     }
 
     #[inline]
-    pub const fn neighbors_iter(&self, x: usize, y: usize, z: usize) -> NeighborsIter {
+    pub const fn neighbors_iter(&self, x: usize, y: usize, z: usize) -> NeighborsIter<'_> {
         NeighborsIter {
             state: NeighborsIterState::Init,
             x,
@@ -1,6 +1,8 @@
 //! # Paths
 //! Focused on pathfinding algorithms optimized for an electron microscope.
 
+pub mod cancel;
+pub mod ccd;
 mod common;
 pub mod deferred;
 pub mod eager;
@@ -9,3 +11,5 @@ pub mod path;
 pub mod postprocess;
 pub mod resolver;
 pub mod strategy;
+
+pub use common::{collision_cache, sight, step, whitelist};
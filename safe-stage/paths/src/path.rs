@@ -1,3 +1,5 @@
+use collisions::common::Collides;
+use collisions::primitive::{OrientedBoxCollider, PointCollider};
 use models::position::sixaxis::SixAxis;
 
 /// # Path Result
@@ -11,12 +13,16 @@ pub enum PathResult<P> {
     Path(Vec<P>),
     /// Partial path from the start, end cannot be reached.
     UnreachableEnd(Option<Vec<P>>),
+    /// The search was cancelled before it finished.
+    Cancelled,
 }
 
 impl<P> PathResult<P> {
     pub fn nodes(&self) -> usize {
         match self {
-            PathResult::InvalidStart(_) | PathResult::UnreachableEnd(None) => 0,
+            PathResult::InvalidStart(_)
+            | PathResult::UnreachableEnd(None)
+            | PathResult::Cancelled => 0,
             PathResult::Path(path) | PathResult::UnreachableEnd(Some(path)) => path.len(),
         }
     }
@@ -29,6 +35,7 @@ impl<P> PathResult<P> {
         match self {
             PathResult::InvalidStart(p) => PathResult::InvalidStart(p.clone()),
             PathResult::UnreachableEnd(None) => PathResult::UnreachableEnd(None),
+            PathResult::Cancelled => PathResult::Cancelled,
             PathResult::UnreachableEnd(Some(path)) => {
                 PathResult::UnreachableEnd(Some(func(path.as_slice())))
             }
@@ -40,7 +47,9 @@ impl<P> PathResult<P> {
 impl PathResult<SixAxis> {
     pub fn time_length(&self, speed: &SixAxis) -> f64 {
         match self {
-            PathResult::InvalidStart(_) | PathResult::UnreachableEnd(None) => 0.0,
+            PathResult::InvalidStart(_)
+            | PathResult::UnreachableEnd(None)
+            | PathResult::Cancelled => 0.0,
             PathResult::Path(path) | PathResult::UnreachableEnd(Some(path)) => {
                 path.windows(2).fold(0.0, |acc, pair| match pair {
                     [from, to] => {
@@ -53,3 +62,81 @@ impl PathResult<SixAxis> {
         }
     }
 }
+
+/// Pairs every node of `path` with the time at which it is reached, starting at `0.0`.
+///
+/// Segment durations are given by [SixAxis::time_to],
+/// so the last timestamp equals [PathResult::time_length] of the same path.
+pub fn path_with_timestamps(path: &[SixAxis], speed: &SixAxis) -> Vec<(f64, SixAxis)> {
+    let mut time = 0.0;
+    let mut previous = path.first();
+    path.iter()
+        .map(|node| {
+            if let Some(previous) = previous {
+                time += previous.time_to(node, speed);
+            }
+            previous = Some(node);
+            (time, *node)
+        })
+        .collect()
+}
+
+/// Checks whether the position of every node of `path` lies inside `region`, boundary included.
+///
+/// Only the nodes are tested, segments between them are not.
+pub fn path_within(path: &[SixAxis], region: &OrientedBoxCollider) -> bool {
+    path.iter()
+        .all(|node| region.collides_with(&PointCollider::new(node.pos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maths::{Quaternion, Vector3};
+
+    #[test]
+    fn timestamps_accumulate_segment_times() {
+        let speed = SixAxis {
+            pos: Vector3::new(1.0, 2.0, 1.0),
+            rot: Vector3::new(1.0, 1.0, 1.0),
+        };
+        let path = vec![
+            SixAxis::from_position(Vector3::ZERO),
+            SixAxis::from_position(Vector3::new(2.0, 0.0, 0.0)),
+            SixAxis::from_position(Vector3::new(2.0, 3.0, 0.0)),
+        ];
+
+        let timed = path_with_timestamps(&path, &speed);
+
+        assert_eq!(3, timed.len());
+        assert_eq!(0.0, timed[0].0);
+        assert!(timed.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(3.5, timed[2].0);
+        assert_eq!(
+            PathResult::Path(path.clone()).time_length(&speed),
+            timed[2].0
+        );
+        assert!(timed.iter().zip(&path).all(|((_, a), b)| a == b));
+        assert!(path_with_timestamps(&[], &speed).is_empty());
+    }
+
+    #[test]
+    fn path_within_rotated_region() {
+        let region = OrientedBoxCollider::new(
+            Vector3::ZERO,
+            Vector3::new(4.0, 1.0, 1.0),
+            Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90_f64.to_radians())),
+        );
+        let inside = vec![
+            SixAxis::from_position(Vector3::new(0.0, -1.9, 0.0)),
+            SixAxis::from_position(Vector3::new(0.4, 0.0, 0.4)),
+            SixAxis::from_position(Vector3::new(0.0, 1.9, -0.4)),
+        ];
+        let mut exits = inside.clone();
+        exits.insert(2, SixAxis::from_position(Vector3::new(1.9, 0.0, 0.0)));
+
+        assert!(path_within(&inside, &region));
+        assert!(!path_within(&exits, &region));
+        assert!(path_within(&[], &region));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::common::sight::line_of_sight_step_par;
+use crate::common::sight::line_of_sight_whitelisted_par;
+use crate::common::whitelist::CollisionWhitelist;
 use crate::path::PathResult;
 use models::immovable::Immovable;
 use models::movable::Movable;
@@ -15,7 +16,20 @@ pub fn smooth_path_par(
     immovable: &Immovable,
     step: &SixAxis,
 ) -> PathResult<SixAxis> {
-    path.map(|p| smooth_path_nodes_par(p, movable, immovable, step))
+    smooth_path_whitelisted_par(path, movable, immovable, step, &CollisionWhitelist::new())
+}
+
+/// Same as [smooth_path_par], ignoring collisions of the pairs allowed by the `whitelist`.
+///
+/// **Runs in parallel using Rayon.**
+pub fn smooth_path_whitelisted_par(
+    path: PathResult<SixAxis>,
+    movable: &dyn Movable<SixAxis>,
+    immovable: &Immovable,
+    step: &SixAxis,
+    whitelist: &CollisionWhitelist,
+) -> PathResult<SixAxis> {
+    path.map(|p| smooth_path_nodes_par(p, movable, immovable, step, whitelist))
 }
 
 fn smooth_path_nodes_par(
@@ -23,11 +37,19 @@ fn smooth_path_nodes_par(
     movable: &dyn Movable<SixAxis>,
     immovable: &Immovable,
     step: &SixAxis,
+    whitelist: &CollisionWhitelist,
 ) -> Vec<SixAxis> {
     let mut smooth = vec![path[0]];
     let mut k = 0;
     for i in 1..path.len() - 1 {
-        if !line_of_sight_step_par(&path[k], &path[i + 1], movable, immovable, step) {
+        if !line_of_sight_whitelisted_par(
+            &path[k],
+            &path[i + 1],
+            movable,
+            immovable,
+            step,
+            whitelist,
+        ) {
             smooth.push(path[i]);
             k = i;
         }
@@ -1,6 +1,7 @@
 pub mod retract;
 pub mod stage;
 
+use crate::cancel::CancelToken;
 use crate::path::PathResult;
 use models::immovable::Immovable;
 use models::movable::Movable;
@@ -24,11 +25,27 @@ pub trait PathResolver<P> {
         immovable: &Immovable,
     ) -> Result<(), StateUpdateError>;
 
+    /// Resolve a path, returning [PathResult::Cancelled] once the `cancel` token is set.
+    ///
+    /// The estimated completion is reported to `progress` as a non-decreasing fraction
+    /// in `[0, 1]`, ending with `1` when a path is found.
     fn resolve_path(
         &self,
         from: &P,
         to: &P,
         movable: &dyn Movable<P>,
         immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
     ) -> PathResult<P>;
+
+    /// Round `state` to the nearest point of the search lattice anchored at `origin`.
+    ///
+    /// Resolvers without a lattice return `state` unchanged.
+    fn snap_to_grid(&self, state: &P, _origin: &P) -> P
+    where
+        P: Clone,
+    {
+        state.clone()
+    }
 }
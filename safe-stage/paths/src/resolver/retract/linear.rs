@@ -1,27 +1,34 @@
+use crate::cancel::CancelToken;
+use crate::common::step::snap_linear;
 use crate::common::timing::timed;
 use crate::deferred::pathing::linear_par::LinearParallelStrategy;
 use crate::path::PathResult;
 use crate::resolver::retract::RetractPathResolver;
 use crate::resolver::{PathResolver, StateUpdateError};
 use crate::strategy::PathStrategy;
+use collisions::collider_group;
 use collisions::common::Collides;
 use models::immovable::Immovable;
+use models::movable::sweepable::Sweepable;
 use models::movable::Movable;
 use models::position::linear::LinearState;
 
 /// # Retract Linear Resolver
 /// Path resolver intended for a retractable device.
-/// Resolves the path using linear interpolation with fixed step.
+/// Resolves the path using linear interpolation with fixed step,
+/// accepting the path early when the swept volume of the movement is free.
 ///
 /// **Runs in parallel using Rayon.**
 pub struct RetractLinearResolver {
     strategy: LinearParallelStrategy<LinearState>,
+    step_size: LinearState,
 }
 
 impl RetractLinearResolver {
     pub fn new(step_size: LinearState) -> Self {
         Self {
             strategy: LinearParallelStrategy::new(step_size),
+            step_size,
         }
     }
 }
@@ -48,10 +55,92 @@ impl PathResolver<LinearState> for RetractLinearResolver {
         to: &LinearState,
         movable: &dyn Movable<LinearState>,
         immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
     ) -> PathResult<LinearState> {
-        let (path, time_to_path) =
-            timed!({ self.strategy.find_path(from, to, movable, immovable) });
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
+        let swept = collider_group!(movable.swept_collider(from, to));
+        if !immovable.collides_with(&swept) {
+            log::info!("Linear path accepted by swept check");
+            progress(1.0);
+            return PathResult::Path(vec![*from, *to]);
+        }
+
+        let (path, time_to_path) = timed!({
+            self.strategy
+                .find_path_cancellable(from, to, movable, immovable, cancel, progress)
+        });
         log::info!("Linear path in {} ms", time_to_path.as_millis());
         path
     }
+
+    fn snap_to_grid(&self, state: &LinearState, origin: &LinearState) -> LinearState {
+        snap_linear(state, &self.step_size, origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::scene::box_triangles;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+
+    /// Unit box sliding along x by `10 * t`.
+    struct Slider;
+
+    impl Movable<LinearState> for Slider {
+        fn move_to(&self, position: &LinearState) -> Immovable {
+            let min = Vector3::new(position.as_relative() * 10.0, -0.5, -0.5);
+            let max = min + Vector3::new(1.0, 1.0, 1.0);
+            collider_group!(PrimaryCollider::build(&box_triangles(min, max)))
+        }
+    }
+
+    #[test]
+    fn obstacle_inside_swept_volume_blocks_path() {
+        let resolver = RetractLinearResolver::new(LinearState::relative(0.01));
+        let immovable = collider_group!(PrimaryCollider::build(&box_triangles(
+            Vector3::new(5.45, 0.25, -0.05),
+            Vector3::new(5.55, 0.35, 0.05),
+        )));
+        let from = LinearState::relative(0.0);
+        let to = LinearState::relative(1.0);
+
+        assert!(immovable.collides_with(&collider_group!(Slider.swept_collider(&from, &to))));
+
+        let actual = resolver.resolve_path(
+            &from,
+            &to,
+            &Slider,
+            &immovable,
+            &CancelToken::new(),
+            &mut |_| {},
+        );
+        assert!(!matches!(actual, PathResult::Path(_)));
+    }
+
+    #[test]
+    fn free_swept_volume_accepts_path() {
+        let resolver = RetractLinearResolver::new(LinearState::relative(0.01));
+        let immovable = collider_group!(PrimaryCollider::build(&box_triangles(
+            Vector3::new(5.0, 2.0, -0.5),
+            Vector3::new(6.0, 3.0, 0.5),
+        )));
+        let from = LinearState::relative(0.0);
+        let to = LinearState::relative(1.0);
+
+        let actual = resolver.resolve_path(
+            &from,
+            &to,
+            &Slider,
+            &immovable,
+            &CancelToken::new(),
+            &mut |_| {},
+        );
+        assert!(matches!(actual, PathResult::Path(path) if path == vec![from, to]));
+    }
 }
@@ -1,7 +1,14 @@
+use crate::common::whitelist::CollisionWhitelist;
 use crate::resolver::PathResolver;
 use models::position::sixaxis::SixAxis;
 
+pub mod bidirectional;
 pub mod down_rotate_find;
 pub mod linear;
+pub mod planar;
 
-pub trait StagePathResolver: PathResolver<SixAxis> {}
+pub trait StagePathResolver: PathResolver<SixAxis> {
+    /// Allows the given pairs of groups to touch, both when validating a new state
+    /// and when searching for a path.
+    fn set_whitelist(&mut self, whitelist: CollisionWhitelist);
+}
@@ -0,0 +1,230 @@
+use crate::cancel::CancelToken;
+use crate::common::step::snap_six_axis;
+use crate::common::timing::timed;
+use crate::common::whitelist::CollisionWhitelist;
+use crate::neighbors::no_rotation_grid::NoRotationGrid;
+use crate::path::PathResult;
+use crate::resolver::stage::planar::PlanarConstraint;
+use crate::resolver::stage::StagePathResolver;
+use crate::resolver::{PathResolver, StateUpdateError};
+use crate::strategy::bidirectional::BidirectionalStrategy;
+use crate::strategy::PathStrategy;
+use models::immovable::Immovable;
+use models::movable::Movable;
+use models::position::sixaxis::SixAxis;
+
+/// # Stage Bidirectional Resolver
+/// Path resolver intended for a stage.
+/// Resolves the path using [BidirectionalStrategy] on a grid with constant rotation.
+pub struct StageBidirectionalResolver {
+    strategy: BidirectionalStrategy<NoRotationGrid<3>>,
+    whitelist: CollisionWhitelist,
+    planar: Option<PlanarConstraint>,
+    step_size: SixAxis,
+    connect_distance: f64,
+    grid_step: SixAxis,
+}
+
+impl StageBidirectionalResolver {
+    pub fn new(step_size: SixAxis, connect_distance: f64) -> Self {
+        let grid_step = SixAxis::from_position(step_size.pos);
+        Self {
+            strategy: Self::strategy(&step_size, connect_distance, &grid_step),
+            whitelist: CollisionWhitelist::new(),
+            planar: None,
+            step_size,
+            connect_distance,
+            grid_step,
+        }
+    }
+
+    /// Same as [StageBidirectionalResolver::new], but the search only explores the X/Y plane
+    /// and targets outside of the plane of the start are unreachable.
+    pub fn new_planar(step_size: SixAxis, connect_distance: f64) -> Self {
+        let constraint = PlanarConstraint;
+        let grid_step = SixAxis::from_position(constraint.restrict_step(&step_size).pos);
+        Self {
+            strategy: Self::strategy(&step_size, connect_distance, &grid_step),
+            whitelist: CollisionWhitelist::new(),
+            planar: Some(constraint),
+            step_size,
+            connect_distance,
+            grid_step,
+        }
+    }
+
+    /// See [StagePathResolver::set_whitelist].
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.set_whitelist(whitelist);
+        self
+    }
+
+    fn strategy(
+        step_size: &SixAxis,
+        connect_distance: f64,
+        grid_step: &SixAxis,
+    ) -> BidirectionalStrategy<NoRotationGrid<3>> {
+        BidirectionalStrategy::new(
+            1.0,
+            connect_distance,
+            *step_size,
+            NoRotationGrid::<3>::new(grid_step.pos),
+        )
+    }
+}
+
+impl StagePathResolver for StageBidirectionalResolver {
+    fn set_whitelist(&mut self, whitelist: CollisionWhitelist) {
+        self.strategy = Self::strategy(&self.step_size, self.connect_distance, &self.grid_step)
+            .with_whitelist(whitelist.clone());
+        self.whitelist = whitelist;
+    }
+}
+
+impl PathResolver<SixAxis> for StageBidirectionalResolver {
+    fn update_state(
+        &mut self,
+        new: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+    ) -> Result<(), StateUpdateError> {
+        if self.whitelist.collides(immovable, &movable.move_to(new)) {
+            return Err(StateUpdateError::InvalidState);
+        }
+
+        Ok(())
+    }
+
+    fn resolve_path(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<SixAxis> {
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
+        if self.planar.is_some_and(|p| !p.holds(from, to)) {
+            return PathResult::UnreachableEnd(None);
+        }
+
+        let (path, time_to_path) = timed!({
+            self.strategy
+                .find_path_cancellable(from, to, movable, immovable, cancel, progress)
+        });
+        log::info!("Bidirectional path in {} ms", time_to_path.as_millis());
+        path
+    }
+
+    /// The grid keeps the rotation of `origin`, so it is snapped to it as well.
+    fn snap_to_grid(&self, state: &SixAxis, origin: &SixAxis) -> SixAxis {
+        snap_six_axis(state, &self.grid_step, origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::common::Translation;
+    use collisions::scene::box_triangles;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+
+    struct Cube(PrimaryCollider);
+
+    impl Movable<SixAxis> for Cube {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            collider_group!(self.0.translate(&position.pos))
+        }
+    }
+
+    fn cube() -> Cube {
+        let half = Vector3::new(0.5, 0.5, 0.5);
+        Cube(PrimaryCollider::build(&box_triangles(-half, half)))
+    }
+
+    /// Wall across the direct line, which can be passed over in Z or around in Y.
+    fn wall() -> Immovable {
+        collider_group!(PrimaryCollider::build(&box_triangles(
+            Vector3::new(4.0, -4.0, -2.0),
+            Vector3::new(6.0, 4.0, 2.0),
+        )))
+    }
+
+    const STEP: SixAxis = SixAxis {
+        pos: Vector3::new(0.5, 0.5, 0.5),
+        rot: Vector3::new(0.1, 0.1, 0.1),
+    };
+
+    #[test]
+    fn snap_keeps_rotation_without_lattice() {
+        let resolver = StageBidirectionalResolver::new(STEP, 4.0);
+        let origin = SixAxis {
+            pos: Vector3::ZERO,
+            rot: Vector3::new(0.3, 0.0, 0.0),
+        };
+        let state = SixAxis {
+            pos: Vector3::new(0.7, -0.2, 1.1),
+            rot: Vector3::new(0.0, 0.25, -0.4),
+        };
+
+        let snapped = resolver.snap_to_grid(&state, &origin);
+        assert_eq!(state.rot, snapped.rot);
+        assert_eq!(Vector3::new(0.5, 0.0, 1.0), snapped.pos);
+    }
+
+    #[test]
+    fn planar_path_keeps_height_and_rotation() {
+        let resolver = StageBidirectionalResolver::new_planar(STEP, 4.0);
+        let from = SixAxis {
+            pos: Vector3::new(0.0, 0.0, 1.0),
+            rot: Vector3::new(0.0, 0.0, 0.0),
+        };
+        let to = SixAxis {
+            pos: Vector3::new(10.0, 0.0, 1.0),
+            ..from
+        };
+
+        let result = resolver.resolve_path(
+            &from,
+            &to,
+            &cube(),
+            &wall(),
+            &CancelToken::new(),
+            &mut |_| {},
+        );
+
+        let PathResult::Path(path) = result else {
+            panic!("expected a path around the wall");
+        };
+        assert_eq!(from, path[0]);
+        assert_eq!(to, *path.last().unwrap());
+        for pose in &path {
+            assert_eq!(from.pos.z(), pose.pos.z());
+            assert_eq!(from.rot, pose.rot);
+        }
+    }
+
+    #[test]
+    fn planar_target_outside_plane_is_unreachable() {
+        let resolver = StageBidirectionalResolver::new_planar(STEP, 4.0);
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 1.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 3.0));
+
+        let result = resolver.resolve_path(
+            &from,
+            &to,
+            &cube(),
+            &wall(),
+            &CancelToken::new(),
+            &mut |_| {},
+        );
+
+        assert!(matches!(result, PathResult::UnreachableEnd(None)));
+    }
+}
@@ -1,28 +1,36 @@
+use crate::cancel::CancelToken;
 use crate::common::timing::timed;
+use crate::common::whitelist::CollisionWhitelist;
 use crate::deferred::pathing::rotation_point_par::SafeRotationPointParallelStrategy;
 use crate::eager::pathing::a_star_with_los::AStar3DSpaceWithLoSStrategy;
-use crate::eager::space::sampled_space_3d::sample_grid_space_3d_par;
+use crate::eager::space::sampled_space_3d::sample_grid_space_3d_whitelisted_par;
 use crate::eager::space::space_3d::Grid3DSpace;
 use crate::path::PathResult;
-use crate::postprocess::smooth_par::smooth_path_par;
+use crate::postprocess::smooth_par::smooth_path_whitelisted_par;
 use crate::resolver::stage::StagePathResolver;
 use crate::resolver::{PathResolver, StateUpdateError};
 use crate::strategy::PathStrategy;
-use collisions::common::Collides;
 use maths::Vector3;
 use models::immovable::Immovable;
 use models::movable::Movable;
 use models::position::sixaxis::SixAxis;
 use std::thread;
 
+/// Progress reported once the stage reached the safe rotation.
+const PROGRESS_ROTATED: f32 = 0.25;
+/// Progress reported once the rough path is found, the rest is smoothing.
+const PROGRESS_ROUGH: f32 = 0.9;
+
 /// # Down Rotate Find Resolver
 /// Path resolver intended for a stage.
 /// Resolver the path by combining [SafeRotationPointParallelStrategy],
-/// [AStar3DSpaceWithLoSStrategy] and [smooth_path_par].
+/// [AStar3DSpaceWithLoSStrategy] and [smooth_path_whitelisted_par].
 ///
 /// **Runs in parallel using Rayon.**
 pub struct DownRotateFindResolver {
     safe_rotation: SafeRotationPointParallelStrategy,
+    down_point: Vector3,
+    down_step: SixAxis,
     move_speed: Vector3,
     sample_min: Vector3,
     sample_max: Vector3,
@@ -31,6 +39,7 @@ pub struct DownRotateFindResolver {
     sample_epsilon: Vector3,
     los_step: Vector3,
     smoothing_step: SixAxis,
+    whitelist: CollisionWhitelist,
 }
 
 impl DownRotateFindResolver {
@@ -51,6 +60,8 @@ impl DownRotateFindResolver {
         let sample_space = None;
         Self {
             safe_rotation,
+            down_point,
+            down_step,
             move_speed,
             sample_min,
             sample_max,
@@ -59,11 +70,28 @@ impl DownRotateFindResolver {
             sample_epsilon,
             los_step,
             smoothing_step,
+            whitelist: CollisionWhitelist::new(),
         }
     }
+
+    /// See [StagePathResolver::set_whitelist].
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.set_whitelist(whitelist);
+        self
+    }
 }
 
-impl StagePathResolver for DownRotateFindResolver {}
+impl StagePathResolver for DownRotateFindResolver {
+    fn set_whitelist(&mut self, whitelist: CollisionWhitelist) {
+        self.safe_rotation = SafeRotationPointParallelStrategy::new(
+            self.down_point,
+            self.down_step.pos,
+            self.down_step.rot,
+        )
+        .with_whitelist(whitelist.clone());
+        self.whitelist = whitelist;
+    }
+}
 
 impl PathResolver<SixAxis> for DownRotateFindResolver {
     fn update_state(
@@ -72,18 +100,19 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
     ) -> Result<(), StateUpdateError> {
-        if immovable.collides_with(&movable.move_to(new)) {
+        if self.whitelist.collides(immovable, &movable.move_to(new)) {
             return Err(StateUpdateError::InvalidState);
         }
 
         let (sampled, time_to_resample) = timed!({
-            Some(sample_grid_space_3d_par(
+            Some(sample_grid_space_3d_whitelisted_par(
                 &self.sample_min,
                 &self.sample_max,
                 movable,
                 immovable,
                 &self.sample_step,
                 &new.rot,
+                &self.whitelist,
             ))
         });
 
@@ -99,7 +128,13 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
         to: &SixAxis,
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
     ) -> PathResult<SixAxis> {
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
         let mut start = *from;
         let mut prepath = Vec::new();
         let mut resampled = None;
@@ -110,21 +145,30 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
             let (resample, down) = thread::scope(|s| {
                 let resample = s.spawn(|| {
                     let (result, time_to_resampled) = timed!({
-                        sample_grid_space_3d_par(
+                        sample_grid_space_3d_whitelisted_par(
                             &self.sample_min,
                             &self.sample_max,
                             movable,
                             immovable,
                             &self.sample_step,
                             &start.rot,
+                            &self.whitelist,
                         )
                     });
                     log::info!("Resampled space in {} ms", time_to_resampled.as_millis());
                     result
                 });
                 let down = s.spawn(|| {
-                    let (result, time_to_position) =
-                        timed!({ self.safe_rotation.find_path(&start, to, movable, immovable) });
+                    let (result, time_to_position) = timed!({
+                        self.safe_rotation.find_path_cancellable(
+                            &start,
+                            to,
+                            movable,
+                            immovable,
+                            cancel,
+                            &mut |_| {},
+                        )
+                    });
                     log::info!(
                         "Found path to safe rotation in {} ms",
                         time_to_position.as_millis()
@@ -135,6 +179,10 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
                 (resample.join().unwrap(), down.join().unwrap())
             });
 
+            if cancel.is_cancelled() {
+                return PathResult::Cancelled;
+            }
+
             resampled = Some(resample);
             prepath = match down {
                 PathResult::Path(path) => path,
@@ -142,6 +190,7 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
             };
             start = *prepath.last().unwrap();
             log::debug!("New start point: {start:?}");
+            progress(PROGRESS_ROTATED);
         }
 
         let strategy = match (&self.sample_space, &resampled) {
@@ -150,16 +199,32 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
                 self.move_speed,
                 self.sample_epsilon,
                 self.los_step,
-            ),
+            )
+            .with_whitelist(self.whitelist.clone()),
             (None, None) => {
                 unreachable!("Resolver was not properly initialized by updating its state!");
             }
         };
 
-        let (path, time_to_path) = timed!({ strategy.find_path(&start, to, movable, immovable) });
+        let (path, time_to_path) = timed!({
+            let mut rough_progress =
+                |f: f32| progress(PROGRESS_ROTATED + f * (PROGRESS_ROUGH - PROGRESS_ROTATED));
+            strategy.find_path_cancellable(
+                &start,
+                to,
+                movable,
+                immovable,
+                cancel,
+                &mut rough_progress,
+            )
+        });
         log::info!("Found rough path in {} ms", time_to_path.as_millis());
         log::debug!("Rough path has {} nodes", path.nodes());
 
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
         let path = match path {
             PathResult::Path(mut path) => {
                 prepath.append(&mut path);
@@ -172,11 +237,21 @@ impl PathResolver<SixAxis> for DownRotateFindResolver {
             other => return other,
         };
 
-        let (smoothed, time_to_smooth) =
-            timed!({ smooth_path_par(path, movable, immovable, &self.smoothing_step) });
+        let (smoothed, time_to_smooth) = timed!({
+            smooth_path_whitelisted_par(
+                path,
+                movable,
+                immovable,
+                &self.smoothing_step,
+                &self.whitelist,
+            )
+        });
         log::info!("Smoothed path in {} ms", time_to_smooth.as_millis());
         log::debug!("Smoothed path has {} nodes", smoothed.nodes());
 
+        if matches!(smoothed, PathResult::Path(_)) {
+            progress(1.0);
+        }
         smoothed
     }
 }
@@ -1,10 +1,12 @@
+use crate::cancel::CancelToken;
+use crate::common::step::snap_six_axis;
 use crate::common::timing::timed;
+use crate::common::whitelist::CollisionWhitelist;
 use crate::deferred::pathing::linear_par::LinearParallelStrategy;
 use crate::path::PathResult;
 use crate::resolver::stage::StagePathResolver;
 use crate::resolver::{PathResolver, StateUpdateError};
 use crate::strategy::PathStrategy;
-use collisions::common::Collides;
 use models::immovable::Immovable;
 use models::movable::Movable;
 use models::position::sixaxis::SixAxis;
@@ -16,17 +18,33 @@ use models::position::sixaxis::SixAxis;
 /// **Runs in parallel using Rayon.**
 pub struct StageLinearResolver {
     strategy: LinearParallelStrategy<SixAxis>,
+    whitelist: CollisionWhitelist,
+    step_size: SixAxis,
 }
 
 impl StageLinearResolver {
     pub fn new(step_size: SixAxis) -> Self {
         Self {
             strategy: LinearParallelStrategy::new(step_size),
+            whitelist: CollisionWhitelist::new(),
+            step_size,
         }
     }
+
+    /// See [StagePathResolver::set_whitelist].
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.set_whitelist(whitelist);
+        self
+    }
 }
 
-impl StagePathResolver for StageLinearResolver {}
+impl StagePathResolver for StageLinearResolver {
+    fn set_whitelist(&mut self, whitelist: CollisionWhitelist) {
+        self.strategy =
+            LinearParallelStrategy::new(self.step_size).with_whitelist(whitelist.clone());
+        self.whitelist = whitelist;
+    }
+}
 
 impl PathResolver<SixAxis> for StageLinearResolver {
     fn update_state(
@@ -35,7 +53,7 @@ impl PathResolver<SixAxis> for StageLinearResolver {
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
     ) -> Result<(), StateUpdateError> {
-        if immovable.collides_with(&movable.move_to(new)) {
+        if self.whitelist.collides(immovable, &movable.move_to(new)) {
             return Err(StateUpdateError::InvalidState);
         }
 
@@ -48,10 +66,109 @@ impl PathResolver<SixAxis> for StageLinearResolver {
         to: &SixAxis,
         movable: &dyn Movable<SixAxis>,
         immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
     ) -> PathResult<SixAxis> {
-        let (path, time_to_path) =
-            timed!({ self.strategy.find_path(from, to, movable, immovable) });
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
+        let (path, time_to_path) = timed!({
+            self.strategy
+                .find_path_cancellable(from, to, movable, immovable, cancel, progress)
+        });
         log::info!("Linear path in {} ms", time_to_path.as_millis());
         path
     }
+
+    fn snap_to_grid(&self, state: &SixAxis, origin: &SixAxis) -> SixAxis {
+        snap_six_axis(state, &self.step_size, origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collisions::collider_group;
+    use collisions::scene::box_triangles;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+    use models::assembly::ball::BallStage;
+
+    const STEP: SixAxis = SixAxis {
+        pos: Vector3::new(0.1, 0.1, 0.1),
+        rot: Vector3::new(0.1, 0.1, 0.1),
+    };
+    const START: SixAxis = SixAxis::from_position(Vector3::ZERO);
+    const END: SixAxis = SixAxis::from_position(Vector3::new(1.0, 0.0, 0.0));
+
+    #[test]
+    fn cancelled_before_start() {
+        let resolver = StageLinearResolver::new(STEP);
+        let stage = BallStage::default();
+        let immovable = collider_group!();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        let actual = resolver.resolve_path(&START, &END, &stage, &immovable, &cancel, &mut |_| {});
+        assert!(matches!(actual, PathResult::Cancelled));
+    }
+
+    #[test]
+    fn never_cancelled() {
+        let resolver = StageLinearResolver::new(STEP);
+        let stage = BallStage::default();
+        let immovable = collider_group!();
+        let cancel = CancelToken::new();
+
+        let actual = resolver.resolve_path(&START, &END, &stage, &immovable, &cancel, &mut |_| {});
+        assert!(matches!(actual, PathResult::Path(_)));
+    }
+
+    struct Sample;
+    impl Movable<SixAxis> for Sample {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            let min = position.pos + Vector3::new(-0.5, -0.5, 0.0);
+            let max = position.pos + Vector3::new(0.5, 0.5, 0.5);
+            collider_group!(PrimaryCollider::build(&box_triangles(min, max)))
+        }
+    }
+
+    #[test]
+    fn whitelisted_sample_rests_on_holder() {
+        let holder = PrimaryCollider::build(&box_triangles(
+            Vector3::new(-1.0, -1.0, -1.0),
+            Vector3::new(1.0, 1.0, 0.0),
+        ));
+        let immovable = collider_group!(holder);
+
+        let mut strict = StageLinearResolver::new(STEP);
+        let mut whitelisted =
+            StageLinearResolver::new(STEP).with_whitelist(CollisionWhitelist::new().allow(0, 0));
+
+        assert!(strict.update_state(&START, &Sample, &immovable).is_err());
+        assert!(whitelisted
+            .update_state(&START, &Sample, &immovable)
+            .is_ok());
+    }
+
+    #[test]
+    fn whitelisted_sample_slides_on_holder() {
+        let holder = PrimaryCollider::build(&box_triangles(
+            Vector3::new(-1.0, -1.0, -1.0),
+            Vector3::new(1.0, 1.0, 0.0),
+        ));
+        let immovable = collider_group!(holder);
+        let cancel = CancelToken::new();
+
+        let strict = StageLinearResolver::new(STEP);
+        let whitelisted =
+            StageLinearResolver::new(STEP).with_whitelist(CollisionWhitelist::new().allow(0, 0));
+
+        let actual = strict.resolve_path(&START, &END, &Sample, &immovable, &cancel, &mut |_| {});
+        assert!(matches!(actual, PathResult::InvalidStart(_)));
+        let actual =
+            whitelisted.resolve_path(&START, &END, &Sample, &immovable, &cancel, &mut |_| {});
+        assert!(matches!(actual, PathResult::Path(path) if path == vec![START, END]));
+    }
 }
@@ -0,0 +1,65 @@
+use maths::Vector3;
+use models::position::sixaxis::SixAxis;
+
+/// # Planar Constraint
+/// Restricts the stage movement to the X/Y plane,
+/// keeping the Z and rotation of the start pose, e.g. at a fixed focus height and tilt.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlanarConstraint;
+
+impl PlanarConstraint {
+    /// Returns the step with the Z and rotation steps zeroed,
+    /// so a grid search only explores the plane.
+    pub fn restrict_step(&self, step: &SixAxis) -> SixAxis {
+        SixAxis {
+            pos: Vector3::new(step.pos.x(), step.pos.y(), 0.0),
+            rot: Vector3::ZERO,
+        }
+    }
+
+    /// Checks if the `to` pose lies in the plane of the `from` pose.
+    pub fn holds(&self, from: &SixAxis, to: &SixAxis) -> bool {
+        from.pos.z() == to.pos.z() && from.rot == to.rot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restricted_step_stays_in_plane() {
+        let step = SixAxis {
+            pos: Vector3::new(0.1, 0.2, 0.3),
+            rot: Vector3::new(0.4, 0.5, 0.6),
+        };
+        let restricted = PlanarConstraint.restrict_step(&step);
+
+        assert_eq!(Vector3::new(0.1, 0.2, 0.0), restricted.pos);
+        assert_eq!(Vector3::ZERO, restricted.rot);
+    }
+
+    #[test]
+    fn holds_only_at_same_height_and_rotation() {
+        let from = SixAxis {
+            pos: Vector3::new(0.0, 0.0, 1.0),
+            rot: Vector3::new(0.0, 0.2, 0.0),
+        };
+        let moved = SixAxis {
+            pos: Vector3::new(3.0, -2.0, 1.0),
+            ..from
+        };
+        let raised = SixAxis {
+            pos: Vector3::new(3.0, -2.0, 1.5),
+            ..from
+        };
+        let tilted = SixAxis {
+            rot: Vector3::new(0.0, 0.3, 0.0),
+            ..moved
+        };
+
+        assert!(PlanarConstraint.holds(&from, &moved));
+        assert!(!PlanarConstraint.holds(&from, &raised));
+        assert!(!PlanarConstraint.holds(&from, &tilted));
+    }
+}
@@ -1,6 +1,12 @@
+pub mod bidirectional;
+
+use crate::cancel::CancelToken;
+use crate::common::sight::line_of_sight_step_par;
 use crate::path::PathResult;
 use models::immovable::Immovable;
 use models::movable::Movable;
+use models::position::sixaxis::SixAxis;
+use std::ops::RangeInclusive;
 
 /// # Path strategy
 /// A path strategy that can find a path from one point to another.
@@ -13,4 +19,106 @@ pub trait PathStrategy<P> {
         movable: &dyn Movable<P>,
         immovable: &Immovable,
     ) -> PathResult<P>;
+
+    /// Same as [PathStrategy::find_path], also reporting the estimated completion
+    /// as a non-decreasing fraction in `[0, 1]`, `1` is reported once a path is found.
+    ///
+    /// By default only the completion is reported.
+    fn find_path_with_progress(
+        &self,
+        from: &P,
+        to: &P,
+        movable: &dyn Movable<P>,
+        immovable: &Immovable,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<P> {
+        let result = self.find_path(from, to, movable, immovable);
+        if matches!(result, PathResult::Path(_)) {
+            progress(1.0);
+        }
+        result
+    }
+
+    /// Same as [PathStrategy::find_path_with_progress],
+    /// returning [PathResult::Cancelled] once the `cancel` token is set.
+    ///
+    /// By default the token is only checked before the search,
+    /// searches exploring many states check it while searching.
+    fn find_path_cancellable(
+        &self,
+        from: &P,
+        to: &P,
+        movable: &dyn Movable<P>,
+        immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<P> {
+        if cancel.is_cancelled() {
+            return PathResult::Cancelled;
+        }
+
+        self.find_path_with_progress(from, to, movable, immovable, progress)
+    }
+}
+
+/// Binary searches the coarsest step within `steps` for which the strategy built by `strategy`
+/// still finds a collision-free path from `from` to `to`, up to the `tolerance`.
+///
+/// Coarser steps search faster, so the result is the fastest reliable setting for the scene.
+/// Every segment of a found path is checked with the `validation_step`,
+/// paths cutting through obstacles between their nodes do not count.
+/// Returns `None` when even the finest step finds no path.
+///
+/// Assumes that finer steps do not fail where coarser ones succeed,
+/// for steps not aligned with the obstacles the result is only a good candidate.
+/// A `tolerance` that is not positive bisects down to the floating point precision.
+#[allow(clippy::too_many_arguments)]
+pub fn coarsest_step<S>(
+    from: &SixAxis,
+    to: &SixAxis,
+    movable: &dyn Movable<SixAxis>,
+    immovable: &Immovable,
+    steps: RangeInclusive<f64>,
+    tolerance: f64,
+    validation_step: &SixAxis,
+    strategy: impl Fn(f64) -> S,
+) -> Option<f64>
+where
+    S: PathStrategy<SixAxis>,
+{
+    let finds_path = |step: f64| match strategy(step).find_path(from, to, movable, immovable) {
+        PathResult::Path(path) => path.windows(2).all(|segment| {
+            line_of_sight_step_par(
+                &segment[0],
+                &segment[1],
+                movable,
+                immovable,
+                validation_step,
+            )
+        }),
+        _ => false,
+    };
+
+    let (mut found, mut failed) = steps.into_inner();
+    if finds_path(failed) {
+        return Some(failed);
+    }
+    if !finds_path(found) {
+        return None;
+    }
+
+    while failed - found > tolerance {
+        let middle = (found + failed) / 2.0;
+        if middle == found || middle == failed {
+            break;
+        }
+
+        if finds_path(middle) {
+            found = middle;
+        } else {
+            failed = middle;
+        }
+    }
+
+    Some(found)
 }
@@ -0,0 +1,567 @@
+use crate::cancel::CancelToken;
+use crate::common::collision_cache::CollisionCache;
+use crate::common::heapstate::MinHeapState;
+use crate::common::reconstruct::reconstruct_path;
+use crate::common::sight::line_of_sight_whitelisted_par;
+use crate::common::whitelist::CollisionWhitelist;
+use crate::neighbors::NeighborStrategy;
+use crate::path::PathResult;
+use crate::strategy::PathStrategy;
+use models::immovable::Immovable;
+use models::movable::Movable;
+use models::position::sixaxis::SixAxis;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Highest progress reported before the frontiers are connected.
+const PROGRESS_BEFORE_CONNECT: f32 = 0.99;
+
+/// # Bidirectional Pathfinding Strategy
+/// Grows one A* frontier from the start and one from the end, alternating between them.
+///
+/// The frontiers are connected once the latest nodes of both are closer than `connect_distance`
+/// and have a collision-free segment between them.
+pub struct BidirectionalStrategy<N> {
+    move_cost: f64,
+    connect_distance: f64,
+    los_step: SixAxis,
+    neighbor_strategy: N,
+    cache: Option<CollisionCache>,
+    whitelist: CollisionWhitelist,
+}
+
+struct Frontier {
+    open_set: BinaryHeap<MinHeapState<f64, SixAxis>>,
+    came_from: HashMap<SixAxis, SixAxis>,
+    g_score: HashMap<SixAxis, f64>,
+    target: SixAxis,
+    /// Latest node taken from the open set.
+    tip: SixAxis,
+    closest: (SixAxis, f64),
+}
+
+impl Frontier {
+    fn new(start: SixAxis, target: SixAxis, heuristic: f64) -> Self {
+        let mut open_set = BinaryHeap::new();
+        open_set.push(MinHeapState {
+            weight: heuristic,
+            state: start,
+        });
+        let mut g_score = HashMap::new();
+        g_score.insert(start, 0.0);
+        Self {
+            open_set,
+            came_from: HashMap::new(),
+            g_score,
+            target,
+            tip: start,
+            closest: (start, heuristic),
+        }
+    }
+}
+
+impl<N> BidirectionalStrategy<N>
+where
+    N: NeighborStrategy<SixAxis>,
+{
+    pub fn new(
+        move_cost: f64,
+        connect_distance: f64,
+        los_step: SixAxis,
+        neighbor_strategy: N,
+    ) -> Self {
+        Self {
+            move_cost,
+            connect_distance,
+            los_step,
+            neighbor_strategy,
+            cache: None,
+            whitelist: CollisionWhitelist::new(),
+        }
+    }
+
+    /// Ignore collisions of the pairs allowed by the `whitelist`.
+    pub fn with_whitelist(mut self, whitelist: CollisionWhitelist) -> Self {
+        self.whitelist = whitelist;
+        self
+    }
+
+    /// Consult the cache before every collision test of a node.
+    ///
+    /// The cache is cleared at the start of every search.
+    /// Nodes closer to colliding ones than half of the cache grid
+    /// may be rejected as colliding, see [CollisionCache].
+    pub fn with_collision_cache(mut self, cache: CollisionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn collides(
+        &self,
+        state: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+    ) -> bool {
+        let check = || self.whitelist.collides(immovable, &movable.move_to(state));
+        match &self.cache {
+            Some(cache) => cache.collides_by(state, check),
+            None => check(),
+        }
+    }
+
+    fn heuristic(&self, from: &SixAxis, to: &SixAxis) -> f64 {
+        from.euclidean_to(to) * self.move_cost
+    }
+
+    fn expand(
+        &self,
+        frontier: &mut Frontier,
+        current: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+    ) {
+        for neighbor in self.neighbor_strategy.neighbors(current) {
+            let tentative_g_score =
+                frontier.g_score[current] + current.euclidean_to(&neighbor) * self.move_cost;
+            if let Some(g) = frontier.g_score.get(&neighbor) {
+                if tentative_g_score >= *g {
+                    continue;
+                }
+            }
+
+            if self.collides(&neighbor, movable, immovable) {
+                continue;
+            }
+
+            let h = self.heuristic(&neighbor, &frontier.target);
+            if h < frontier.closest.1 {
+                frontier.closest = (neighbor, h);
+            }
+
+            frontier.came_from.insert(neighbor, *current);
+            frontier.g_score.insert(neighbor, tentative_g_score);
+            frontier.open_set.push(MinHeapState {
+                weight: tentative_g_score + h,
+                state: neighbor,
+            });
+        }
+    }
+}
+
+impl<N> PathStrategy<SixAxis> for BidirectionalStrategy<N>
+where
+    N: NeighborStrategy<SixAxis>,
+{
+    fn find_path(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+    ) -> PathResult<SixAxis> {
+        self.find_path_with_progress(from, to, movable, immovable, &mut |_| {})
+    }
+
+    /// Progress is estimated by how close the frontiers got to their targets,
+    /// relative to the distance between the start and the end.
+    fn find_path_with_progress(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<SixAxis> {
+        self.find_path_cancellable(from, to, movable, immovable, &CancelToken::new(), progress)
+    }
+
+    /// The `cancel` token is checked before taking every node from the frontiers.
+    fn find_path_cancellable(
+        &self,
+        from: &SixAxis,
+        to: &SixAxis,
+        movable: &dyn Movable<SixAxis>,
+        immovable: &Immovable,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
+    ) -> PathResult<SixAxis> {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+
+        if self.whitelist.collides(immovable, &movable.move_to(from)) {
+            return PathResult::InvalidStart(*from);
+        }
+
+        if self.whitelist.collides(immovable, &movable.move_to(to)) {
+            return PathResult::UnreachableEnd(None);
+        }
+
+        let heuristic = self.heuristic(from, to);
+        let mut forward = Frontier::new(*from, *to, heuristic);
+        let mut backward = Frontier::new(*to, *from, heuristic);
+        let mut is_forward = true;
+        let mut reported = 0.0;
+        progress(reported);
+
+        loop {
+            if cancel.is_cancelled() {
+                return PathResult::Cancelled;
+            }
+
+            let (active, other) = if is_forward {
+                (&mut forward, &backward)
+            } else {
+                (&mut backward, &forward)
+            };
+            let Some(current) = active.open_set.pop() else {
+                break;
+            };
+            let current = current.state;
+            active.tip = current;
+
+            if current.euclidean_to(&other.tip) <= self.connect_distance
+                && line_of_sight_whitelisted_par(
+                    &current,
+                    &other.tip,
+                    movable,
+                    immovable,
+                    &self.los_step,
+                    &self.whitelist,
+                )
+            {
+                let mut path = reconstruct_path(&forward.came_from, &forward.tip);
+                let mut rest = reconstruct_path(&backward.came_from, &backward.tip);
+                rest.reverse();
+                if rest.first() == path.last() {
+                    rest.remove(0);
+                }
+                path.extend(rest);
+                progress(1.0);
+                return PathResult::Path(path);
+            }
+
+            self.expand(active, &current, movable, immovable);
+            is_forward = !is_forward;
+
+            let remaining = forward.closest.1.min(backward.closest.1);
+            let estimate = if heuristic > 0.0 {
+                (1.0 - remaining / heuristic) as f32
+            } else {
+                0.0
+            };
+            //  Leave the completion for the connection of the frontiers
+            let estimate = estimate.clamp(0.0, PROGRESS_BEFORE_CONNECT);
+            if estimate > reported {
+                reported = estimate;
+                progress(reported);
+            }
+        }
+
+        PathResult::UnreachableEnd(Some(reconstruct_path(
+            &forward.came_from,
+            &forward.closest.0,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::sight::line_of_sight_step_par;
+    use crate::deferred::pathing::a_star::AStarStrategy;
+    use crate::neighbors::no_rotation_grid::NoRotationGrid;
+    use crate::strategy::coarsest_step;
+    use collisions::common::Translation;
+    use collisions::complex::group::ColliderGroup;
+    use collisions::scene::box_triangles;
+    use collisions::PrimaryCollider;
+    use maths::Vector3;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const STEP: f64 = 0.5;
+    const CONNECT: f64 = 8.0;
+
+    /// Unit cube counting every collision query.
+    struct CountingPart(PrimaryCollider, AtomicUsize);
+
+    impl CountingPart {
+        fn new() -> Self {
+            let half = Vector3::new(0.5, 0.5, 0.5);
+            Self(
+                PrimaryCollider::build(&box_triangles(-half, half)),
+                AtomicUsize::new(0),
+            )
+        }
+
+        fn queries(&self) -> usize {
+            self.1.load(Ordering::Relaxed)
+        }
+    }
+
+    impl Movable<SixAxis> for CountingPart {
+        fn move_to(&self, position: &SixAxis) -> Immovable {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            ColliderGroup(vec![self.0.translate(&position.pos)])
+        }
+    }
+
+    fn slab(min: Vector3, max: Vector3) -> PrimaryCollider {
+        PrimaryCollider::build(&box_triangles(min, max))
+    }
+
+    /// Closed corridor along the `x` axis with a baffle in the middle blocking the direct line.
+    fn corridor() -> Immovable {
+        ColliderGroup(vec![
+            slab(
+                Vector3::new(-5.0, -10.0, -2.0),
+                Vector3::new(25.0, 10.0, -0.75),
+            ),
+            slab(
+                Vector3::new(-5.0, -10.0, 0.75),
+                Vector3::new(25.0, 10.0, 2.0),
+            ),
+            slab(
+                Vector3::new(-5.0, -10.0, -2.0),
+                Vector3::new(25.0, -8.0, 2.0),
+            ),
+            slab(Vector3::new(-5.0, 8.0, -2.0), Vector3::new(25.0, 10.0, 2.0)),
+            slab(
+                Vector3::new(-5.0, -10.0, -2.0),
+                Vector3::new(-3.0, 10.0, 2.0),
+            ),
+            slab(
+                Vector3::new(23.0, -10.0, -2.0),
+                Vector3::new(25.0, 10.0, 2.0),
+            ),
+            slab(Vector3::new(9.0, -5.0, -2.0), Vector3::new(11.0, 5.0, 2.0)),
+        ])
+    }
+
+    fn assert_valid_path(path: &[SixAxis], part: &CountingPart, immovable: &Immovable) {
+        let step = SixAxis::from_position(Vector3::new(0.05, 0.05, 0.05));
+        for pair in path.windows(2) {
+            assert!(line_of_sight_step_par(
+                &pair[0], &pair[1], part, immovable, &step
+            ));
+        }
+    }
+
+    #[test]
+    fn corridor_expands_less_than_one_direction() {
+        let immovable = corridor();
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(20.0, 0.0, 0.0));
+        let grid = || NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP));
+
+        let single_part = CountingPart::new();
+        let single = AStarStrategy::new(STEP, 1.0, 0.1, grid());
+        let single_result = single.find_path(&from, &to, &single_part, &immovable);
+
+        let both_part = CountingPart::new();
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let both = BidirectionalStrategy::new(1.0, CONNECT, los_step, grid());
+        let both_result = both.find_path(&from, &to, &both_part, &immovable);
+
+        let PathResult::Path(single_path) = single_result else {
+            panic!("expected a path from one direction");
+        };
+        let PathResult::Path(both_path) = both_result else {
+            panic!("expected a path from both directions");
+        };
+        assert_eq!(from, both_path[0]);
+        assert_eq!(to, *both_path.last().unwrap());
+        assert_valid_path(&single_path, &single_part, &immovable);
+        assert_valid_path(&both_path, &both_part, &immovable);
+        assert!(both_part.queries() < single_part.queries());
+    }
+
+    #[test]
+    fn progress_grows_to_completion() {
+        let immovable = corridor();
+        let part = CountingPart::new();
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(20.0, 0.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let grid = NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP));
+        let strategy = BidirectionalStrategy::new(1.0, CONNECT, los_step, grid);
+
+        let mut reported = Vec::new();
+        let result = strategy
+            .find_path_with_progress(&from, &to, &part, &immovable, &mut |f| reported.push(f));
+
+        assert!(matches!(result, PathResult::Path(_)));
+        assert!(reported.len() > 2);
+        assert!(reported.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(0.0, reported[0]);
+        assert_eq!(1.0, *reported.last().unwrap());
+    }
+
+    #[test]
+    fn cache_does_not_change_path() {
+        let immovable = corridor();
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(20.0, 0.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let grid = || NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP));
+
+        let plain_part = CountingPart::new();
+        let plain = BidirectionalStrategy::new(1.0, CONNECT, los_step, grid());
+        let plain_result = plain.find_path(&from, &to, &plain_part, &immovable);
+
+        let cached_part = CountingPart::new();
+        let cache =
+            CollisionCache::new(SixAxis::from_position(Vector3::new(STEP, STEP, STEP)), 4096);
+        let cached =
+            BidirectionalStrategy::new(1.0, CONNECT, los_step, grid()).with_collision_cache(cache);
+        let cached_result = cached.find_path(&from, &to, &cached_part, &immovable);
+
+        let (PathResult::Path(plain_path), PathResult::Path(cached_path)) =
+            (plain_result, cached_result)
+        else {
+            panic!("expected paths");
+        };
+        assert_eq!(plain_path, cached_path);
+        assert!(cached_part.queries() <= plain_part.queries());
+    }
+
+    #[test]
+    fn direct_line_connects_immediately() {
+        let immovable = corridor();
+        let part = CountingPart::new();
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(0.0, 6.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let strategy = BidirectionalStrategy::new(
+            1.0,
+            CONNECT,
+            los_step,
+            NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP)),
+        );
+
+        let result = strategy.find_path(&from, &to, &part, &immovable);
+
+        let PathResult::Path(path) = result else {
+            panic!("expected a path");
+        };
+        assert_eq!(vec![from, to], path);
+    }
+
+    #[test]
+    fn cancelled_from_another_thread_mid_search() {
+        //  wall too large to get around in any reasonable time
+        let immovable = ColliderGroup(vec![slab(
+            Vector3::new(4.0, -1e4, -1e4),
+            Vector3::new(5.0, 1e4, 1e4),
+        )]);
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(10.0, 0.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let grid = NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP));
+        let strategy = BidirectionalStrategy::new(1.0, CONNECT, los_step, grid);
+        let part = CountingPart::new();
+        let cancel = CancelToken::new();
+
+        let result = std::thread::scope(|s| {
+            s.spawn(|| {
+                while part.queries() < 1000 {
+                    std::thread::yield_now();
+                }
+                cancel.cancel();
+            });
+            strategy.find_path_cancellable(&from, &to, &part, &immovable, &cancel, &mut |_| {})
+        });
+
+        assert!(matches!(result, PathResult::Cancelled));
+    }
+
+    #[test]
+    fn invalid_start() {
+        let immovable = corridor();
+        let part = CountingPart::new();
+        let from = SixAxis::from_position(Vector3::new(9.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(20.0, 0.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let strategy = BidirectionalStrategy::new(
+            1.0,
+            CONNECT,
+            los_step,
+            NoRotationGrid::<3>::new(Vector3::new(STEP, STEP, STEP)),
+        );
+
+        let result = strategy.find_path(&from, &to, &part, &immovable);
+
+        assert!(matches!(result, PathResult::InvalidStart(_)));
+    }
+
+    #[test]
+    fn coarsest_step_finds_the_last_working_step() {
+        let immovable = corridor();
+        let part = CountingPart::new();
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0));
+        let to = SixAxis::from_position(Vector3::new(20.0, 0.0, 0.0));
+        let los_step = SixAxis::from_position(Vector3::new(0.25, 0.25, 0.25));
+        let strategy = |step: f64| {
+            BidirectionalStrategy::new(
+                1.0,
+                step,
+                los_step,
+                NoRotationGrid::<3>::new(Vector3::new(step, step, step)),
+            )
+        };
+        let finds_path = |step: f64| {
+            coarsest_step(
+                &from,
+                &to,
+                &part,
+                &immovable,
+                step..=step,
+                0.0,
+                &los_step,
+                strategy,
+            )
+            .is_some()
+        };
+
+        let coarsest = coarsest_step(
+            &from,
+            &to,
+            &part,
+            &immovable,
+            0.5..=1.06,
+            1e-3,
+            &los_step,
+            strategy,
+        )
+        .unwrap();
+
+        assert!(finds_path(coarsest));
+        assert!(finds_path(coarsest - 0.05));
+        assert!(!finds_path(coarsest + 2e-3));
+
+        let exact = coarsest_step(
+            &from,
+            &to,
+            &part,
+            &immovable,
+            0.5..=1.06,
+            0.0,
+            &los_step,
+            strategy,
+        )
+        .unwrap();
+        assert!((exact - coarsest).abs() <= 1e-3);
+        assert_eq!(
+            None,
+            coarsest_step(
+                &from,
+                &to,
+                &part,
+                &immovable,
+                2.4..=3.0,
+                1e-3,
+                &los_step,
+                strategy
+            )
+        );
+    }
+}
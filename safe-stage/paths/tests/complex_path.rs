@@ -7,6 +7,7 @@ use models::parts::equipment::Equipment;
 use models::parts::holder::Holder;
 use models::parts::stage::Stage;
 use models::position::sixaxis::SixAxis;
+use paths::cancel::CancelToken;
 use paths::path::PathResult;
 use paths::resolver::stage::down_rotate_find::DownRotateFindResolver;
 use paths::resolver::stage::StagePathResolver;
@@ -43,7 +44,14 @@ fn complex_path_resolution() {
     println!("State updated in {:?} ms", update_time.as_millis());
 
     let (path, path_time) = timed!({
-        stage_resolver.resolve_path(&START_POSITION, &END_POSITION, stage.as_ref(), &immovable)
+        stage_resolver.resolve_path(
+            &START_POSITION,
+            &END_POSITION,
+            stage.as_ref(),
+            &immovable,
+            &CancelToken::new(),
+            &mut |_| {},
+        )
     });
     println!("Path found in {:?} ms", path_time.as_millis());
 
@@ -70,7 +70,9 @@ mod tests {
     use super::*;
     use collisions::complex::group::ColliderGroup;
     use collisions::PrimaryCollider;
+    use maths::{Quaternion, Vector3};
     use models::movable::Movable;
+    use models::parts::holder::SampleId;
     use models::position::linear::LinearState;
     use models::position::sixaxis::SixAxis;
     use std::sync::Arc;
@@ -116,6 +118,15 @@ mod tests {
         fn swap_sample(&mut self, _sample: Option<PrimaryCollider>) {
             unreachable!()
         }
+        fn add_sample(&mut self, _id: SampleId, _sample: PrimaryCollider) {
+            unreachable!()
+        }
+        fn remove_sample(&mut self, _id: SampleId) {
+            unreachable!()
+        }
+        fn set_sample_rotation(&mut self, _rotation: Quaternion) {
+            unreachable!()
+        }
     }
 
     #[test]
@@ -159,6 +170,12 @@ mod tests {
         fn active_holder_mut(&mut self) -> Option<&mut (dyn Holder + 'static)> {
             unreachable!()
         }
+        fn rotation_pivot(&self, _coords: &SixAxis) -> Vector3 {
+            unreachable!()
+        }
+        fn orientation(&self, _coords: &SixAxis) -> Quaternion {
+            unreachable!()
+        }
     }
 
     #[test]
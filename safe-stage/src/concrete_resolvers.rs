@@ -71,8 +71,10 @@ mod tests {
     use models::movable::Movable;
     use models::position::linear::LinearState;
     use models::position::sixaxis::SixAxis;
+    use paths::cancel::CancelToken;
     use paths::path::PathResult;
     use paths::resolver::{PathResolver, StateUpdateError};
+    use paths::whitelist::CollisionWhitelist;
 
     struct TestRetractResolver;
     impl RetractPathResolver for TestRetractResolver {}
@@ -92,6 +94,8 @@ mod tests {
             _to: &LinearState,
             _movable: &dyn Movable<LinearState>,
             _immovable: &Immovable,
+            _cancel: &CancelToken,
+            _progress: &mut dyn FnMut(f32),
         ) -> PathResult<LinearState> {
             unreachable!()
         }
@@ -103,7 +107,11 @@ mod tests {
     }
 
     struct TestStageResolver;
-    impl StagePathResolver for TestStageResolver {}
+    impl StagePathResolver for TestStageResolver {
+        fn set_whitelist(&mut self, _whitelist: CollisionWhitelist) {
+            unreachable!()
+        }
+    }
     impl PathResolver<SixAxis> for TestStageResolver {
         fn update_state(
             &mut self,
@@ -120,6 +128,8 @@ mod tests {
             _to: &SixAxis,
             _movable: &dyn Movable<SixAxis>,
             _immovable: &Immovable,
+            _cancel: &CancelToken,
+            _progress: &mut dyn FnMut(f32),
         ) -> PathResult<SixAxis> {
             unreachable!()
         }
@@ -1,11 +1,14 @@
 use crate::configuration::chamber::ChamberConfig;
-use crate::configuration::equipment::EquipmentConfig;
+use crate::configuration::contact::{ObstaclePart, StagePart};
+use crate::configuration::equipment::{EquipmentConfig, ObstructionTier};
 use crate::configuration::resolver_retract::ResolverRetractConfig;
 use crate::configuration::resolver_stage::ResolverStageConfig;
 use crate::configuration::retract::RetractConfig;
 use crate::configuration::stage::StageConfig;
 use crate::ffi::opaque_ffi_for_type;
 use crate::id::Id;
+use crate::types::{CLinearState, CSixAxis};
+use std::collections::HashSet;
 use thiserror::Error;
 
 #[cfg(not(feature = "ffi"))]
@@ -14,18 +17,38 @@ pub mod builder;
 pub mod builder_ffi; //  Keep module public for bindings generation
 
 pub mod chamber;
+pub mod contact;
 pub mod equipment;
+pub mod hash;
 pub mod holder;
+pub mod lint;
+#[cfg(feature = "serde")]
+pub mod path_envelope;
 pub mod resolver_retract;
 pub mod resolver_stage;
 pub mod retract;
 pub mod stage;
 
-opaque_ffi_for_type!(CBoxSliceEqupment, Box<[EquipmentConfig]>);
+opaque_ffi_for_type!(CBoxSliceEqupment, Box<[(EquipmentConfig, ObstructionTier)]>);
 opaque_ffi_for_type!(
     CBoxSliceRetracts,
     Box<[(Id, (RetractConfig, ResolverRetractConfig))]>
 );
+opaque_ffi_for_type!(CBoxSliceRetractHomes, Box<[(Id, CLinearState)]>);
+opaque_ffi_for_type!(CBoxSliceStageContacts, Box<[(StagePart, ObstaclePart)]>);
+
+/// Home pose used for the stage unless configured otherwise.
+pub(crate) const DEFAULT_STAGE_HOME: CSixAxis = CSixAxis {
+    x: 0.0,
+    y: 0.0,
+    z: 0.0,
+    rx: 0.0,
+    ry: 0.0,
+    rz: 0.0,
+};
+
+/// Home state used for retracts unless configured otherwise.
+pub(crate) const DEFAULT_RETRACT_HOME: CLinearState = CLinearState { t: 0.0 };
 
 #[cfg_attr(feature = "ffi", repr(C))]
 pub struct Configuration {
@@ -34,6 +57,9 @@ pub struct Configuration {
     stage_resolver: ResolverStageConfig,
     equipment: CBoxSliceEqupment,
     retracts: CBoxSliceRetracts,
+    stage_home: CSixAxis,
+    retract_homes: CBoxSliceRetractHomes,
+    stage_contacts: CBoxSliceStageContacts,
 }
 
 impl Configuration {
@@ -44,7 +70,10 @@ impl Configuration {
         equipment: Vec<EquipmentConfig>,
         retracts: Vec<(Id, (RetractConfig, ResolverRetractConfig))>,
     ) -> Self {
-        let equipment = equipment.into_boxed_slice();
+        let equipment = equipment
+            .into_iter()
+            .map(|e| (e, ObstructionTier::default()))
+            .collect();
         let retracts = retracts.into_boxed_slice();
         Self {
             chamber,
@@ -52,9 +81,70 @@ impl Configuration {
             stage_resolver,
             equipment: CBoxSliceEqupment::from_inner(equipment),
             retracts: CBoxSliceRetracts::from_inner(retracts),
+            stage_home: DEFAULT_STAGE_HOME,
+            retract_homes: CBoxSliceRetractHomes::from_inner(Box::new([])),
+            stage_contacts: CBoxSliceStageContacts::from_inner(Box::new([])),
         }
     }
 
+    /// Set the home poses, retracts without a home are homed at [DEFAULT_RETRACT_HOME].
+    pub(crate) fn with_homes(
+        mut self,
+        stage_home: CSixAxis,
+        retract_homes: Vec<(Id, CLinearState)>,
+    ) -> Self {
+        self.stage_home = stage_home;
+        self.retract_homes = CBoxSliceRetractHomes::from_inner(retract_homes.into_boxed_slice());
+        self
+    }
+
+    /// Replace the equipment with the `equipment` presented up to the paired tier.
+    pub(crate) fn with_visible_equipment(
+        mut self,
+        equipment: Vec<(EquipmentConfig, ObstructionTier)>,
+    ) -> Self {
+        self.equipment = CBoxSliceEqupment::from_inner(equipment.into_boxed_slice());
+        self
+    }
+
+    /// Set the pairs of parts allowed to touch while the stage moves.
+    pub(crate) fn with_stage_contacts(mut self, contacts: Vec<(StagePart, ObstaclePart)>) -> Self {
+        self.stage_contacts = CBoxSliceStageContacts::from_inner(contacts.into_boxed_slice());
+        self
+    }
+
+    /// Mount the `equipment` after the existing equipment, with the default presentation tier.
+    pub(crate) fn push_equipment(&mut self, equipment: EquipmentConfig) {
+        let mut mounted = std::mem::take(self.equipment.inner_mut()).into_vec();
+        mounted.push((equipment, ObstructionTier::default()));
+        *self.equipment.inner_mut() = mounted.into_boxed_slice();
+    }
+
+    /// Unmount the equipment at `index`, the following equipment moves one index down.
+    ///
+    /// Stage contacts of the equipment are dropped and contacts of the following equipment
+    /// are moved with it.
+    ///
+    /// **Expects the `index` to be in range.**
+    pub(crate) fn remove_equipment(&mut self, index: usize) {
+        let mut mounted = std::mem::take(self.equipment.inner_mut()).into_vec();
+        mounted.remove(index);
+        *self.equipment.inner_mut() = mounted.into_boxed_slice();
+
+        let contacts = std::mem::take(self.stage_contacts.inner_mut())
+            .into_vec()
+            .into_iter()
+            .filter_map(|(part, obstacle)| match obstacle {
+                ObstaclePart::Equipment(i) if i == index => None,
+                ObstaclePart::Equipment(i) if i > index => {
+                    Some((part, ObstaclePart::Equipment(i - 1)))
+                }
+                _ => Some((part, obstacle)),
+            })
+            .collect::<Vec<_>>();
+        *self.stage_contacts.inner_mut() = contacts.into_boxed_slice();
+    }
+
     pub fn chamber(&self) -> &ChamberConfig {
         &self.chamber
     }
@@ -67,7 +157,8 @@ impl Configuration {
         &self.stage_resolver
     }
 
-    pub fn equipment(&self) -> &[EquipmentConfig] {
+    /// Mounted equipment, each with the least obstructive tier it is presented in.
+    pub fn equipment(&self) -> &[(EquipmentConfig, ObstructionTier)] {
         self.equipment.inner()
     }
 
@@ -75,6 +166,28 @@ impl Configuration {
         self.retracts.inner()
     }
 
+    pub fn stage_home(&self) -> &CSixAxis {
+        &self.stage_home
+    }
+
+    /// Pairs of parts allowed to touch while the stage moves, e.g. a sample resting on equipment.
+    ///
+    /// Collisions of the pairs are ignored by the stage resolver, both for the states and the paths,
+    /// and by the other stage queries of the microscope.
+    pub fn stage_contacts(&self) -> &[(StagePart, ObstaclePart)] {
+        self.stage_contacts.inner()
+    }
+
+    /// Returns the configured home of the retract, [DEFAULT_RETRACT_HOME] if it has none.
+    pub fn retract_home(&self, id: Id) -> CLinearState {
+        self.retract_homes
+            .inner()
+            .iter()
+            .find(|(i, _)| *i == id)
+            .map(|(_, home)| *home)
+            .unwrap_or(DEFAULT_RETRACT_HOME)
+    }
+
     /// # Safety
     /// Takes ownership of the configuration and drops it.
     #[cfg(feature = "ffi")]
@@ -84,6 +197,21 @@ impl Configuration {
     }
 }
 
+impl Clone for Configuration {
+    fn clone(&self) -> Self {
+        Self {
+            chamber: self.chamber.clone(),
+            stage: self.stage.clone(),
+            stage_resolver: self.stage_resolver.clone(),
+            equipment: CBoxSliceEqupment::from_inner(self.equipment.inner().clone()),
+            retracts: CBoxSliceRetracts::from_inner(self.retracts.inner().clone()),
+            stage_home: self.stage_home,
+            retract_homes: CBoxSliceRetractHomes::from_inner(self.retract_homes.inner().clone()),
+            stage_contacts: CBoxSliceStageContacts::from_inner(self.stage_contacts.inner().clone()),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "ffi", repr(u8))]
 pub enum ConfigBuilderResult {
@@ -94,6 +222,29 @@ pub enum ConfigBuilderResult {
     MissingChamber = 1,
     #[error("Missing configuration for stage")]
     MissingStage = 2,
+    #[error("Duplicate id of a retract")]
+    DuplicateRetractId = 3,
+    #[error("Home of a retract that is not configured")]
+    UnknownRetractId = 4,
+}
+
+fn has_duplicate_ids<T>(items: &[(Id, T)]) -> bool {
+    let mut seen = HashSet::with_capacity(items.len());
+    !items.iter().all(|(id, _)| seen.insert(*id))
+}
+
+fn has_unknown_ids<T, U>(items: &[(Id, T)], known: &[(Id, U)]) -> bool {
+    items
+        .iter()
+        .any(|(id, _)| !known.iter().any(|(known, _)| known == id))
+}
+
+/// Sets the `home` of the retract with the `id`, replacing its earlier home.
+fn set_retract_home(homes: &mut Vec<(Id, CLinearState)>, id: Id, home: CLinearState) {
+    match homes.iter_mut().find(|(i, _)| *i == id) {
+        Some((_, existing)) => *existing = home,
+        None => homes.push((id, home)),
+    }
 }
 
 #[cfg(test)]
@@ -141,8 +292,14 @@ pub mod tests {
         assert_eq!(
             config.equipment(),
             &[
-                EquipmentConfig::ThesisDetectorAlpha,
-                EquipmentConfig::ThesisDetectorBeta
+                (
+                    EquipmentConfig::ThesisDetectorAlpha,
+                    ObstructionTier::default()
+                ),
+                (
+                    EquipmentConfig::ThesisDetectorBeta,
+                    ObstructionTier::default()
+                )
             ]
         );
         assert_eq!(
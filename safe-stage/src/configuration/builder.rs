@@ -1,18 +1,26 @@
 use crate::configuration::chamber::ChamberConfig;
-use crate::configuration::equipment::EquipmentConfig;
+use crate::configuration::contact::{ObstaclePart, StagePart};
+use crate::configuration::equipment::{EquipmentConfig, ObstructionTier};
 use crate::configuration::resolver_retract::ResolverRetractConfig;
 use crate::configuration::resolver_stage::ResolverStageConfig;
 use crate::configuration::retract::RetractConfig;
 use crate::configuration::stage::StageConfig;
-use crate::configuration::{ConfigBuilderResult, Configuration};
+use crate::configuration::{
+    has_duplicate_ids, has_unknown_ids, set_retract_home, ConfigBuilderResult, Configuration,
+    DEFAULT_STAGE_HOME,
+};
 use crate::id::Id;
+use crate::types::{CLinearState, CSixAxis};
 
 pub struct ConfigurationBuilder {
     chamber: Option<ChamberConfig>,
     stage: Option<StageConfig>,
     stage_resolver: Option<ResolverStageConfig>,
-    equipment: Vec<EquipmentConfig>,
+    equipment: Vec<(EquipmentConfig, ObstructionTier)>,
     retracts: Vec<(Id, (RetractConfig, ResolverRetractConfig))>,
+    stage_home: CSixAxis,
+    retract_homes: Vec<(Id, CLinearState)>,
+    stage_contacts: Vec<(StagePart, ObstaclePart)>,
 }
 
 impl Default for ConfigurationBuilder {
@@ -30,6 +38,23 @@ impl ConfigurationBuilder {
             stage_resolver: None,
             equipment: Vec::new(),
             retracts: Vec::new(),
+            stage_home: DEFAULT_STAGE_HOME,
+            retract_homes: Vec::new(),
+            stage_contacts: Vec::new(),
+        }
+    }
+
+    /// Create a configuration builder holding everything from the `config`.
+    pub fn from_config(config: &Configuration) -> Self {
+        Self {
+            chamber: Some(config.chamber().clone()),
+            stage: Some(config.stage().clone()),
+            stage_resolver: Some(config.stage_resolver().clone()),
+            equipment: config.equipment().to_vec(),
+            retracts: config.retracts().to_vec(),
+            stage_home: *config.stage_home(),
+            retract_homes: config.retract_homes.inner().to_vec(),
+            stage_contacts: config.stage_contacts().to_vec(),
         }
     }
 
@@ -48,7 +73,17 @@ impl ConfigurationBuilder {
 
     /// Add an equipment configuration.
     pub fn with_equipment(mut self, equipment: EquipmentConfig) -> Self {
-        self.equipment.push(equipment);
+        self.equipment.push((equipment, ObstructionTier::default()));
+        self
+    }
+
+    /// Add an equipment configuration presented only up to the given tier.
+    pub fn with_equipment_visibility(
+        mut self,
+        equipment: EquipmentConfig,
+        visibility: ObstructionTier,
+    ) -> Self {
+        self.equipment.push((equipment, visibility));
         self
     }
 
@@ -63,6 +98,26 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the home pose of the stage.
+    pub fn with_stage_home(mut self, home: CSixAxis) -> Self {
+        self.stage_home = home;
+        self
+    }
+
+    /// Set the home state of the retract with the given id, replacing its earlier home.
+    ///
+    /// The retract must be added to the builder before building.
+    pub fn with_retract_home(mut self, id: Id, home: CLinearState) -> Self {
+        set_retract_home(&mut self.retract_homes, id, home);
+        self
+    }
+
+    /// Allow the part of the stage to touch the obstacle, see [Configuration::stage_contacts].
+    pub fn with_stage_contact(mut self, part: StagePart, obstacle: ObstaclePart) -> Self {
+        self.stage_contacts.push((part, obstacle));
+        self
+    }
+
     /// Build the configuration.
     pub fn build(self) -> Result<Configuration, ConfigBuilderResult> {
         let chamber = if let Some(chamber) = self.chamber {
@@ -78,21 +133,33 @@ impl ConfigurationBuilder {
                 return Err(ConfigBuilderResult::MissingStage);
             };
 
-        Ok(Configuration::new(
-            chamber,
-            stage,
-            resolver,
-            self.equipment,
-            self.retracts,
-        ))
+        if has_duplicate_ids(&self.retracts) {
+            return Err(ConfigBuilderResult::DuplicateRetractId);
+        }
+        if has_unknown_ids(&self.retract_homes, &self.retracts) {
+            return Err(ConfigBuilderResult::UnknownRetractId);
+        }
+
+        Ok(
+            Configuration::new(chamber, stage, resolver, Vec::new(), self.retracts)
+                .with_homes(self.stage_home, self.retract_homes)
+                .with_visible_equipment(self.equipment)
+                .with_stage_contacts(self.stage_contacts),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ChamberConfig, ConfigBuilderResult, ConfigurationBuilder, StageConfig};
+    use super::{
+        ChamberConfig, ConfigBuilderResult, ConfigurationBuilder, EquipmentConfig, ObstructionTier,
+        StageConfig,
+    };
+    use crate::configuration::resolver_retract::ResolverRetractConfig;
     use crate::configuration::resolver_stage::ResolverStageConfig;
-    use crate::types::CSixAxis;
+    use crate::configuration::retract::RetractConfig;
+    use crate::id::make_id;
+    use crate::types::{CLinearState, CSixAxis};
 
     const STEP: CSixAxis = CSixAxis {
         x: 0.1,
@@ -133,4 +200,99 @@ mod tests {
             .build();
         assert!(matches!(config, Err(ConfigBuilderResult::MissingStage)));
     }
+
+    #[test]
+    fn build_duplicate_retract_id() {
+        let resolver = ResolverRetractConfig::RetractLinearResolver {
+            step_size: CLinearState { t: 0.1 },
+        };
+        let config = ConfigurationBuilder::default()
+            .with_chamber(ChamberConfig::ThesisChamber)
+            .with_stage(
+                StageConfig::ThesisStage,
+                ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            )
+            .with_retract(make_id!(10), RetractConfig::ThesisRetract, resolver.clone())
+            .with_retract(make_id!(10), RetractConfig::ThesisRetract, resolver)
+            .build();
+        assert!(matches!(
+            config,
+            Err(ConfigBuilderResult::DuplicateRetractId)
+        ));
+    }
+
+    #[test]
+    fn build_with_homes() {
+        let home = CSixAxis {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            rx: 0.0,
+            ry: 0.1,
+            rz: 0.2,
+        };
+        let resolver = ResolverRetractConfig::RetractLinearResolver {
+            step_size: CLinearState { t: 0.1 },
+        };
+        let config = ConfigurationBuilder::default()
+            .with_chamber(ChamberConfig::ThesisChamber)
+            .with_stage(
+                StageConfig::ThesisStage,
+                ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            )
+            .with_retract(make_id!(10), RetractConfig::ThesisRetract, resolver.clone())
+            .with_retract(make_id!(11), RetractConfig::ThesisRetract, resolver)
+            .with_stage_home(home)
+            .with_retract_home(make_id!(10), CLinearState { t: 0.2 })
+            .with_retract_home(make_id!(10), CLinearState { t: 0.5 })
+            .build()
+            .unwrap();
+
+        assert_eq!(&home, config.stage_home());
+        assert_eq!(CLinearState { t: 0.5 }, config.retract_home(make_id!(10)));
+        assert_eq!(CLinearState { t: 0.0 }, config.retract_home(make_id!(11)));
+    }
+
+    #[test]
+    fn build_home_of_unknown_retract() {
+        let config = ConfigurationBuilder::default()
+            .with_chamber(ChamberConfig::ThesisChamber)
+            .with_stage(
+                StageConfig::ThesisStage,
+                ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            )
+            .with_retract_home(make_id!(10), CLinearState { t: 0.5 })
+            .build();
+        assert!(matches!(config, Err(ConfigBuilderResult::UnknownRetractId)));
+    }
+
+    #[test]
+    fn from_config_keeps_everything() {
+        let resolver = ResolverRetractConfig::RetractLinearResolver {
+            step_size: CLinearState { t: 0.1 },
+        };
+        let config = ConfigurationBuilder::default()
+            .with_chamber(ChamberConfig::ThesisChamber)
+            .with_stage(
+                StageConfig::ThesisStage,
+                ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            )
+            .with_equipment_visibility(EquipmentConfig::ThesisDetectorAlpha, ObstructionTier::Full)
+            .with_retract(make_id!(10), RetractConfig::ThesisRetract, resolver)
+            .with_stage_home(STEP)
+            .with_retract_home(make_id!(10), CLinearState { t: 0.5 })
+            .build()
+            .unwrap();
+
+        let rebuilt = ConfigurationBuilder::from_config(&config).build().unwrap();
+
+        assert_eq!(config.chamber(), rebuilt.chamber());
+        assert_eq!(config.stage(), rebuilt.stage());
+        assert_eq!(config.stage_resolver(), rebuilt.stage_resolver());
+        assert_eq!(config.equipment(), rebuilt.equipment());
+        assert_eq!(ObstructionTier::Full, rebuilt.equipment()[0].1);
+        assert_eq!(config.retracts(), rebuilt.retracts());
+        assert_eq!(config.stage_home(), rebuilt.stage_home());
+        assert_eq!(CLinearState { t: 0.5 }, rebuilt.retract_home(make_id!(10)));
+    }
 }
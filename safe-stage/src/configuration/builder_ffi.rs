@@ -1,18 +1,25 @@
 use crate::configuration::chamber::ChamberConfig;
-use crate::configuration::equipment::EquipmentConfig;
+use crate::configuration::contact::{ObstaclePart, StagePart};
+use crate::configuration::equipment::{EquipmentConfig, ObstructionTier};
 use crate::configuration::resolver_retract::ResolverRetractConfig;
 use crate::configuration::resolver_stage::ResolverStageConfig;
 use crate::configuration::retract::RetractConfig;
 use crate::configuration::stage::StageConfig;
-use crate::configuration::{ConfigBuilderResult, Configuration};
+use crate::configuration::{
+    has_duplicate_ids, has_unknown_ids, set_retract_home, ConfigBuilderResult, Configuration,
+    DEFAULT_STAGE_HOME,
+};
 use crate::id::Id;
+use crate::types::{CLinearState, CSixAxis};
 use std::ffi::c_void;
 
 type ChamberType = ChamberConfig;
 type StageType = StageConfig;
 type StageResolverType = ResolverStageConfig;
-type EquipmentsType = Vec<EquipmentConfig>;
+type EquipmentsType = Vec<(EquipmentConfig, ObstructionTier)>;
 type RetractsType = Vec<(Id, (RetractConfig, ResolverRetractConfig))>;
+type RetractHomesType = Vec<(Id, CLinearState)>;
+type StageContactsType = Vec<(StagePart, ObstaclePart)>;
 
 #[repr(C)]
 pub struct ConfigurationBuilder {
@@ -21,6 +28,9 @@ pub struct ConfigurationBuilder {
     stage_resolver: *const StageResolverType,
     equipment: *mut c_void,
     retracts: *mut c_void,
+    stage_home: CSixAxis,
+    retract_homes: *mut c_void,
+    stage_contacts: *mut c_void,
 }
 
 impl Default for ConfigurationBuilder {
@@ -64,6 +74,16 @@ impl ConfigurationBuilder {
         &mut *ptr
     }
 
+    unsafe fn retract_homes(&mut self) -> &mut RetractHomesType {
+        let ptr = self.retract_homes as *mut RetractHomesType;
+        &mut *ptr
+    }
+
+    unsafe fn stage_contacts(&mut self) -> &mut StageContactsType {
+        let ptr = self.stage_contacts as *mut StageContactsType;
+        &mut *ptr
+    }
+
     /// Create a new instance of configuration builder.
     ///
     /// # Safety
@@ -72,12 +92,17 @@ impl ConfigurationBuilder {
     pub extern "C" fn builder_new() -> Self {
         let equipment: Box<EquipmentsType> = Box::default();
         let retracts: Box<RetractsType> = Box::default();
+        let retract_homes: Box<RetractHomesType> = Box::default();
+        let stage_contacts: Box<StageContactsType> = Box::default();
         Self {
             chamber: std::ptr::null(),
             stage: std::ptr::null(),
             stage_resolver: std::ptr::null(),
             equipment: Box::into_raw(equipment) as *mut c_void,
             retracts: Box::into_raw(retracts) as *mut c_void,
+            stage_home: DEFAULT_STAGE_HOME,
+            retract_homes: Box::into_raw(retract_homes) as *mut c_void,
+            stage_contacts: Box::into_raw(stage_contacts) as *mut c_void,
         }
     }
 
@@ -124,7 +149,23 @@ impl ConfigurationBuilder {
     /// The returned builder must be dropped after use.
     #[no_mangle]
     pub unsafe extern "C" fn builder_with_equipment(mut self, equipment: EquipmentConfig) -> Self {
-        self.equipment().push(equipment);
+        self.equipment()
+            .push((equipment, ObstructionTier::default()));
+        self
+    }
+
+    /// Add an equipment configuration presented only up to the given tier.
+    ///
+    /// # Safety
+    /// Takes ownership of the equipment configuration.
+    /// The returned builder must be dropped after use.
+    #[no_mangle]
+    pub unsafe extern "C" fn builder_with_equipment_visibility(
+        mut self,
+        equipment: EquipmentConfig,
+        visibility: ObstructionTier,
+    ) -> Self {
+        self.equipment().push((equipment, visibility));
         self
     }
 
@@ -144,6 +185,46 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the home pose of the stage.
+    ///
+    /// # Safety
+    /// The returned builder must be dropped after use.
+    #[no_mangle]
+    pub extern "C" fn builder_with_stage_home(mut self, home: CSixAxis) -> Self {
+        self.stage_home = home;
+        self
+    }
+
+    /// Set the home state of the retract with the given id, replacing its earlier home.
+    ///
+    /// The retract must be added to the builder before building.
+    ///
+    /// # Safety
+    /// The returned builder must be dropped after use.
+    #[no_mangle]
+    pub unsafe extern "C" fn builder_with_retract_home(
+        mut self,
+        id: Id,
+        home: CLinearState,
+    ) -> Self {
+        set_retract_home(self.retract_homes(), id, home);
+        self
+    }
+
+    /// Allow the part of the stage to touch the obstacle, see [Configuration::stage_contacts].
+    ///
+    /// # Safety
+    /// The returned builder must be dropped after use.
+    #[no_mangle]
+    pub unsafe extern "C" fn builder_with_stage_contact(
+        mut self,
+        part: StagePart,
+        obstacle: ObstaclePart,
+    ) -> Self {
+        self.stage_contacts().push((part, obstacle));
+        self
+    }
+
     /// Build the configuration.
     ///
     /// # Safety
@@ -168,13 +249,26 @@ impl ConfigurationBuilder {
                 return ConfigBuilderResult::MissingStage;
             };
 
-        config.write(Configuration::new(
-            chamber,
-            stage,
-            resolver,
-            self.equipment().drain(..).collect(),
-            self.retracts().drain(..).collect(),
-        ));
+        if has_duplicate_ids(self.retracts()) {
+            return ConfigBuilderResult::DuplicateRetractId;
+        }
+        let homes = self.retract_homes().clone();
+        if has_unknown_ids(&homes, self.retracts()) {
+            return ConfigBuilderResult::UnknownRetractId;
+        }
+
+        config.write(
+            Configuration::new(
+                chamber,
+                stage,
+                resolver,
+                Vec::new(),
+                self.retracts().drain(..).collect(),
+            )
+            .with_homes(self.stage_home, homes)
+            .with_visible_equipment(self.equipment().drain(..).collect())
+            .with_stage_contacts(self.stage_contacts().drain(..).collect()),
+        );
         ConfigBuilderResult::Success
     }
 
@@ -200,6 +294,8 @@ impl Drop for ConfigurationBuilder {
             }
             let _e = Box::from_raw(self.equipment as *mut EquipmentsType);
             let _r = Box::from_raw(self.retracts as *mut RetractsType);
+            let _h = Box::from_raw(self.retract_homes as *mut RetractHomesType);
+            let _c = Box::from_raw(self.stage_contacts as *mut StageContactsType);
         };
     }
 }
@@ -269,6 +365,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn build_duplicate_retract_id() {
+        unsafe {
+            let resolver = ResolverRetractConfig::RetractLinearResolver {
+                step_size: CLinearState { t: 0.1 },
+            };
+            let mut config = MaybeUninit::<Configuration>::uninit();
+            let result = ConfigurationBuilder::default()
+                .builder_with_chamber(ChamberConfig::ThesisChamber)
+                .builder_with_stage(
+                    StageConfig::ThesisStage,
+                    ResolverStageConfig::StageLinearResolver { step_size: STEP },
+                )
+                .builder_with_retract(
+                    Id::id_new(10),
+                    RetractConfig::ThesisRetract,
+                    resolver.clone(),
+                )
+                .builder_with_retract(Id::id_new(10), RetractConfig::ThesisRetract, resolver)
+                .builder_build(&mut *config.as_mut_ptr());
+
+            assert!(matches!(result, ConfigBuilderResult::DuplicateRetractId));
+        }
+    }
+
+    #[test]
+    fn build_with_homes() {
+        unsafe {
+            let home = CSixAxis {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+                rx: 0.0,
+                ry: 0.1,
+                rz: 0.2,
+            };
+            let mut config = MaybeUninit::<Configuration>::uninit();
+            let result = ConfigurationBuilder::default()
+                .builder_with_chamber(ChamberConfig::ThesisChamber)
+                .builder_with_stage(
+                    StageConfig::ThesisStage,
+                    ResolverStageConfig::StageLinearResolver { step_size: STEP },
+                )
+                .builder_with_retract(
+                    Id::id_new(10),
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                )
+                .builder_with_stage_home(home)
+                .builder_with_retract_home(Id::id_new(10), CLinearState { t: 0.2 })
+                .builder_with_retract_home(Id::id_new(10), CLinearState { t: 0.5 })
+                .builder_build(&mut *config.as_mut_ptr());
+
+            assert!(matches!(result, ConfigBuilderResult::Success));
+            let config = config.assume_init();
+            assert_eq!(&home, config.stage_home());
+            assert_eq!(CLinearState { t: 0.5 }, config.retract_home(Id::id_new(10)));
+        }
+    }
+
+    #[test]
+    fn build_home_of_unknown_retract() {
+        unsafe {
+            let mut config = MaybeUninit::<Configuration>::uninit();
+            let result = ConfigurationBuilder::default()
+                .builder_with_chamber(ChamberConfig::ThesisChamber)
+                .builder_with_stage(
+                    StageConfig::ThesisStage,
+                    ResolverStageConfig::StageLinearResolver { step_size: STEP },
+                )
+                .builder_with_retract_home(Id::id_new(10), CLinearState { t: 0.5 })
+                .builder_build(&mut *config.as_mut_ptr());
+
+            assert!(matches!(result, ConfigBuilderResult::UnknownRetractId));
+        }
+    }
+
     #[test]
     fn dropped_before_build_without_leak() {
         unsafe {
@@ -340,7 +515,7 @@ mod tests {
                 EquipmentConfig::ThesisDetectorBeta,
             ];
             let actual = builder.equipment();
-            assert!(actual.iter().eq(expected.iter()));
+            assert!(actual.iter().map(|(e, _)| e).eq(expected.iter()));
 
             let expected = [(
                 Id::id_new(10),
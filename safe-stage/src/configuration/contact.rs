@@ -0,0 +1,47 @@
+use crate::id::Id;
+
+/// Part of the stage assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ffi", repr(u8))]
+pub enum StagePart {
+    /// Stage without the holder.
+    Stage,
+    /// Body of the holder.
+    Holder,
+    /// Samples attached to the holder.
+    Sample,
+}
+
+/// Part the stage moves among, identified independently of the order of the colliders.
+///
+/// Equipment is identified by its order among the mounted equipment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ffi", repr(C, u8))]
+pub enum ObstaclePart {
+    Chamber,
+    Equipment(usize),
+    Retract(Id),
+}
+
+impl ObstaclePart {
+    /// Create a new **Chamber** obstacle.
+    #[cfg(feature = "ffi")]
+    #[no_mangle]
+    pub extern "C" fn obstacle_chamber() -> Self {
+        ObstaclePart::Chamber
+    }
+
+    /// Create a new **Equipment** obstacle.
+    #[cfg(feature = "ffi")]
+    #[no_mangle]
+    pub extern "C" fn obstacle_equipment(index: usize) -> Self {
+        ObstaclePart::Equipment(index)
+    }
+
+    /// Create a new **Retract** obstacle.
+    #[cfg(feature = "ffi")]
+    #[no_mangle]
+    pub extern "C" fn obstacle_retract(id: Id) -> Self {
+        ObstaclePart::Retract(id)
+    }
+}
@@ -1,6 +1,23 @@
 use crate::concrete_parts::ConcreteEquipment;
 use models::assembly::thesis::{ThesisDetectorAlpha, ThesisDetectorBeta};
 
+/// Least obstructive presentation of the static parts in which the equipment is still shown.
+///
+/// Tiers are ordered from the full view to the non-obstructive view,
+/// equipment is presented in every view up to its tier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ffi", repr(u8))]
+pub enum ObstructionTier {
+    /// Shown only in the full view.
+    Full,
+    /// Shown in the full and less obstructive views.
+    LessObstructive,
+    /// Shown in all the views.
+    #[default]
+    NonObstructive,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "ffi", repr(C, u8))]
 pub enum EquipmentConfig {
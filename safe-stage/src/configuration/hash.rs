@@ -0,0 +1,337 @@
+use crate::configuration::chamber::ChamberConfig;
+use crate::configuration::contact::{ObstaclePart, StagePart};
+use crate::configuration::equipment::EquipmentConfig;
+use crate::configuration::resolver_retract::ResolverRetractConfig;
+use crate::configuration::resolver_stage::ResolverStageConfig;
+use crate::configuration::retract::RetractConfig;
+use crate::configuration::stage::StageConfig;
+use crate::configuration::Configuration;
+use crate::id::Id;
+use crate::types::{CSixAxis, CVector3};
+use collisions::common::Bounded;
+use maths::Vector3;
+use models::immovable::Immovable;
+use models::position::linear::LinearState;
+use models::position::sixaxis::SixAxis;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a hasher, unlike the standard hasher its output does not change between releases.
+///
+/// Configurations are written field by field,
+/// every variant starts with its own tag so that variants with equal fields differ.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    fn write_tag(&mut self, tag: u8) {
+        self.write(&[tag]);
+    }
+
+    fn write_id(&mut self, id: Id) {
+        self.write_u64(id.value());
+    }
+
+    fn write_vector(&mut self, value: &Vector3) {
+        for v in [value.x(), value.y(), value.z()] {
+            self.write_f64(v);
+        }
+    }
+
+    fn write_c_vector(&mut self, value: &CVector3) {
+        for v in [value.x, value.y, value.z] {
+            self.write_f64(v);
+        }
+    }
+
+    fn write_c_six_axis(&mut self, value: &CSixAxis) {
+        for v in [value.x, value.y, value.z, value.rx, value.ry, value.rz] {
+            self.write_f64(v);
+        }
+    }
+
+    fn write_group(&mut self, group: &Immovable) {
+        self.write_u64(group.0.len() as u64);
+        for collider in &group.0 {
+            self.write_vector(&collider.min());
+            self.write_vector(&collider.max());
+        }
+    }
+
+    fn write_chamber(&mut self, chamber: &ChamberConfig) {
+        match chamber {
+            ChamberConfig::ThesisChamber => self.write_tag(0),
+            ChamberConfig::ExampleChamberWithConfig { offset_x, size } => {
+                self.write_tag(1);
+                self.write_f64(*offset_x);
+                self.write_u64(*size as u64);
+            }
+        }
+    }
+
+    fn write_stage(&mut self, stage: &StageConfig) {
+        match stage {
+            StageConfig::ThesisStage => self.write_tag(0),
+            StageConfig::ExampleStageWithConfig {
+                calibration_x,
+                tilt_correction,
+            } => {
+                self.write_tag(1);
+                self.write_f64(*calibration_x);
+                self.write_f64(*tilt_correction);
+            }
+        }
+    }
+
+    fn write_stage_resolver(&mut self, resolver: &ResolverStageConfig) {
+        match resolver {
+            ResolverStageConfig::StageLinearResolver { step_size } => {
+                self.write_tag(0);
+                self.write_c_six_axis(step_size);
+            }
+            ResolverStageConfig::DownRotateFindResolver {
+                down_point,
+                down_step,
+                move_speed,
+                sample_min,
+                sample_max,
+                sample_step,
+                sample_epsilon,
+                los_step,
+                smoothing_step,
+            } => {
+                self.write_tag(1);
+                self.write_c_vector(down_point);
+                self.write_c_six_axis(down_step);
+                self.write_c_vector(move_speed);
+                self.write_c_vector(sample_min);
+                self.write_c_vector(sample_max);
+                self.write_c_vector(sample_step);
+                self.write_c_vector(sample_epsilon);
+                self.write_c_vector(los_step);
+                self.write_c_six_axis(smoothing_step);
+            }
+            ResolverStageConfig::StageBidirectionalResolver {
+                step_size,
+                connect_distance,
+            } => {
+                self.write_tag(2);
+                self.write_c_six_axis(step_size);
+                self.write_f64(*connect_distance);
+            }
+            ResolverStageConfig::StagePlanarResolver {
+                step_size,
+                connect_distance,
+            } => {
+                self.write_tag(3);
+                self.write_c_six_axis(step_size);
+                self.write_f64(*connect_distance);
+            }
+            ResolverStageConfig::UnitVariant(value) => {
+                self.write_tag(4);
+                self.write_c_six_axis(value);
+            }
+            ResolverStageConfig::EmptyVariant => self.write_tag(5),
+        }
+    }
+
+    fn write_equipment(&mut self, equipment: &EquipmentConfig) {
+        match equipment {
+            EquipmentConfig::ThesisDetectorAlpha => self.write_tag(0),
+            EquipmentConfig::ThesisDetectorBeta => self.write_tag(1),
+            EquipmentConfig::ExampleEquipmentWithConfig { position, size } => {
+                self.write_tag(2);
+                self.write_f64(*position);
+                self.write_u64(*size as u64);
+            }
+        }
+    }
+
+    fn write_retract(&mut self, retract: &RetractConfig) {
+        match retract {
+            RetractConfig::ThesisRetract => self.write_tag(0),
+            RetractConfig::ExampleRetractionWithConfig { arm_length, speed } => {
+                self.write_tag(1);
+                self.write_f64(*arm_length);
+                self.write_f64(*speed);
+            }
+        }
+    }
+
+    fn write_retract_resolver(&mut self, resolver: &ResolverRetractConfig) {
+        match resolver {
+            ResolverRetractConfig::RetractLinearResolver { step_size } => {
+                self.write_tag(0);
+                self.write_f64(step_size.t);
+            }
+        }
+    }
+
+    fn write_contact(&mut self, (part, obstacle): &(StagePart, ObstaclePart)) {
+        self.write_tag(match part {
+            StagePart::Stage => 0,
+            StagePart::Holder => 1,
+            StagePart::Sample => 2,
+        });
+        match obstacle {
+            ObstaclePart::Chamber => self.write_tag(0),
+            ObstaclePart::Equipment(index) => {
+                self.write_tag(1);
+                self.write_u64(*index as u64);
+            }
+            ObstaclePart::Retract(id) => {
+                self.write_tag(2);
+                self.write_id(*id);
+            }
+        }
+    }
+}
+
+impl Configuration {
+    /// Stable hash of the parts, their resolvers, the allowed stage contacts
+    /// and the bounds of the built geometry, homes are not included.
+    ///
+    /// The stage and retracts are built at their neutral poses.
+    pub fn config_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+
+        hasher.write_chamber(self.chamber());
+        hasher.write_group(&self.chamber().build().get_ref().full());
+
+        hasher.write_stage(self.stage());
+        hasher.write_stage_resolver(self.stage_resolver());
+        let neutral = SixAxis::from_position(Vector3::ZERO);
+        hasher.write_group(&self.stage().build().get_ref().move_to(&neutral));
+
+        hasher.write_u64(self.equipment().len() as u64);
+        for (equipment, _) in self.equipment() {
+            hasher.write_equipment(equipment);
+            hasher.write_group(&equipment.build().get_ref().collider());
+        }
+
+        hasher.write_u64(self.retracts().len() as u64);
+        for (id, (retract, resolver)) in self.retracts() {
+            hasher.write_id(*id);
+            hasher.write_retract(retract);
+            hasher.write_retract_resolver(resolver);
+            hasher.write_group(&retract.build().get_ref().move_to(&LinearState::None));
+        }
+
+        hasher.write_u64(self.stage_contacts().len() as u64);
+        for contact in self.stage_contacts() {
+            hasher.write_contact(contact);
+        }
+
+        hasher.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::make_id;
+    use crate::types::CLinearState;
+
+    const STEP: CSixAxis = CSixAxis {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+        rx: 0.4,
+        ry: 0.5,
+        rz: 0.6,
+    };
+
+    fn config(equipment: Vec<EquipmentConfig>, step: CSixAxis) -> Configuration {
+        Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: step },
+            equipment,
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        )
+    }
+
+    #[test]
+    fn identical_configurations_share_hash() {
+        let first = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP);
+        let second = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP);
+
+        assert_eq!(first.config_hash(), second.config_hash());
+    }
+
+    #[test]
+    fn stage_resolver_changes_hash() {
+        let first = config(vec![], STEP);
+        let second = config(vec![], CSixAxis { x: 1.0, ..STEP });
+
+        assert_ne!(first.config_hash(), second.config_hash());
+    }
+
+    #[test]
+    fn retract_resolver_changes_hash() {
+        let first = config(vec![], STEP);
+        let mut retracts = first.retracts().to_vec();
+        retracts[0].1 .1 = ResolverRetractConfig::RetractLinearResolver {
+            step_size: CLinearState { t: 0.2 },
+        };
+        let second = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            retracts,
+        );
+
+        assert_ne!(first.config_hash(), second.config_hash());
+    }
+
+    #[test]
+    fn stage_contacts_change_hash() {
+        let first = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP);
+        let chamber = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP)
+            .with_stage_contacts(vec![(StagePart::Holder, ObstaclePart::Chamber)]);
+        let equipment = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP)
+            .with_stage_contacts(vec![(StagePart::Holder, ObstaclePart::Equipment(0))]);
+
+        assert_ne!(first.config_hash(), chamber.config_hash());
+        assert_ne!(first.config_hash(), equipment.config_hash());
+        assert_ne!(chamber.config_hash(), equipment.config_hash());
+    }
+
+    #[test]
+    fn equipment_changes_hash() {
+        let first = config(vec![EquipmentConfig::ThesisDetectorAlpha], STEP);
+        let second = config(vec![EquipmentConfig::ThesisDetectorBeta], STEP);
+        let third = config(vec![], STEP);
+
+        assert_ne!(first.config_hash(), second.config_hash());
+        assert_ne!(first.config_hash(), third.config_hash());
+    }
+}
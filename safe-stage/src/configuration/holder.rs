@@ -1,6 +1,7 @@
 use models::assembly::thesis::{ThesisHolderCircle, ThesisHolderSquare};
 use models::parts::holder::Holder;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "ffi", repr(C, u8))]
 pub enum HolderConfig {
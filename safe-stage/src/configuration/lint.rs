@@ -0,0 +1,237 @@
+use crate::configuration::Configuration;
+use crate::id::Id;
+use collisions::common::Bounded;
+use maths::Vector3;
+use models::immovable::Immovable;
+use models::position::linear::LinearState;
+use models::position::sixaxis::SixAxis;
+
+/// Relative tolerance of the triangle area and box size below which they are considered zero.
+const DEGENERATE_EPSILON: f64 = 1e-12;
+
+/// Part of the configuration the geometry belongs to,
+/// equipment is identified by its order in the configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometryPart {
+    Chamber,
+    Stage,
+    Equipment(usize),
+    Retract(Id),
+}
+
+/// Suspicious geometry found in a built part.
+///
+/// `item` is the index of the collider within the part,
+/// `triangle` is the index within the triangle buffer of the collider.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometryWarning {
+    /// Triangle with a zero area.
+    DegenerateTriangle {
+        part: GeometryPart,
+        item: usize,
+        triangle: usize,
+    },
+    /// Triangle with an infinite coordinate.
+    NonFiniteVertex {
+        part: GeometryPart,
+        item: usize,
+        triangle: usize,
+    },
+    /// Collider bounded by a box flat in at least two axes, so it cannot enclose any surface.
+    ZeroSizeBox { part: GeometryPart, item: usize },
+}
+
+impl Configuration {
+    /// Build all the parts and scan them for degenerate triangles,
+    /// non-finite vertices, and zero-size boxes.
+    ///
+    /// The stage and retracts are checked at their home poses.
+    pub fn lint_geometry(&self) -> Vec<GeometryWarning> {
+        let mut warnings = lint_group(
+            GeometryPart::Chamber,
+            &self.chamber().build().get_ref().full(),
+        );
+
+        let stage = self.stage().build();
+        let stage_home = SixAxis::from(self.stage_home());
+        warnings.extend(lint_group(
+            GeometryPart::Stage,
+            &stage.get_ref().move_to(&stage_home),
+        ));
+
+        for (index, (equipment, _)) in self.equipment().iter().enumerate() {
+            let collider = equipment.build().get_ref().collider();
+            warnings.extend(lint_group(GeometryPart::Equipment(index), &collider));
+        }
+
+        for (id, (retract, _)) in self.retracts() {
+            let home = LinearState::from(&self.retract_home(*id));
+            let collider = retract.build().get_ref().move_to(&home);
+            warnings.extend(lint_group(GeometryPart::Retract(*id), &collider));
+        }
+
+        warnings
+    }
+}
+
+/// Scan every collider of the group, see [Configuration::lint_geometry].
+pub(crate) fn lint_group(part: GeometryPart, group: &Immovable) -> Vec<GeometryWarning> {
+    let mut warnings = Vec::new();
+    for (item, collider) in group.0.iter().enumerate() {
+        let size = collider.max() - collider.min();
+        let scale = size.x().abs().max(size.y().abs()).max(size.z().abs());
+        let flat_axes = [size.x(), size.y(), size.z()]
+            .into_iter()
+            .filter(|s| *s <= DEGENERATE_EPSILON * scale.max(1.0))
+            .count();
+        if size.is_finite() && flat_axes >= 2 {
+            warnings.push(GeometryWarning::ZeroSizeBox { part, item });
+        }
+
+        for (triangle, vertices) in collider.triangle_buffer().chunks_exact(3).enumerate() {
+            if let Some(warning) = lint_triangle(part, item, triangle, vertices) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    warnings
+}
+
+fn lint_triangle(
+    part: GeometryPart,
+    item: usize,
+    triangle: usize,
+    vertices: &[Vector3],
+) -> Option<GeometryWarning> {
+    let (a, b, c) = (vertices[0], vertices[1], vertices[2]);
+    if !(a.is_finite() && b.is_finite() && c.is_finite()) {
+        return Some(GeometryWarning::NonFiniteVertex {
+            part,
+            item,
+            triangle,
+        });
+    }
+
+    let longest = (b - a).len2().max((c - b).len2()).max((a - c).len2());
+    let doubled_area = (b - a).cross(&(c - a)).len();
+    if doubled_area <= DEGENERATE_EPSILON * longest {
+        return Some(GeometryWarning::DegenerateTriangle {
+            part,
+            item,
+            triangle,
+        });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::chamber::ChamberConfig;
+    use crate::configuration::equipment::EquipmentConfig;
+    use crate::configuration::resolver_retract::ResolverRetractConfig;
+    use crate::configuration::resolver_stage::ResolverStageConfig;
+    use crate::configuration::retract::RetractConfig;
+    use crate::configuration::stage::StageConfig;
+    use crate::id::make_id;
+    use crate::types::{CLinearState, CSixAxis};
+    use collisions::collider_group;
+    use collisions::primitive::TriangleCollider;
+    use collisions::PrimaryCollider;
+
+    #[test]
+    fn thesis_configuration_is_clean() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver {
+                step_size: CSixAxis {
+                    x: 0.1,
+                    y: 0.1,
+                    z: 0.1,
+                    rx: 0.1,
+                    ry: 0.1,
+                    rz: 0.1,
+                },
+            },
+            vec![EquipmentConfig::ThesisDetectorAlpha],
+            vec![(
+                make_id!(1),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        );
+
+        assert_eq!(Vec::<GeometryWarning>::new(), config.lint_geometry());
+    }
+
+    #[test]
+    fn degenerate_triangle_is_reported() {
+        let part = GeometryPart::Equipment(2);
+        let group = collider_group!(
+            PrimaryCollider::build(&[TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+            )]),
+            PrimaryCollider::build(&[
+                TriangleCollider::new(
+                    Vector3::new(0.0, 0.0, 0.0),
+                    Vector3::new(1.0, 0.0, 1.0),
+                    Vector3::new(0.0, 1.0, 1.0),
+                ),
+                TriangleCollider::new(
+                    Vector3::new(0.0, 0.0, 0.0),
+                    Vector3::new(1.0, 1.0, 1.0),
+                    Vector3::new(2.0, 2.0, 2.0),
+                ),
+            ])
+        );
+
+        let warnings = lint_group(part, &group);
+        let degenerate = group.0[1]
+            .triangle_buffer()
+            .chunks_exact(3)
+            .position(|t| t[2] == Vector3::new(2.0, 2.0, 2.0))
+            .unwrap();
+
+        assert_eq!(
+            vec![GeometryWarning::DegenerateTriangle {
+                part,
+                item: 1,
+                triangle: degenerate,
+            }],
+            warnings
+        );
+    }
+
+    #[test]
+    fn non_finite_and_zero_size_are_reported() {
+        let part = GeometryPart::Stage;
+        let point = PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(1.0, 1.0 + 1e-15, 1.0),
+            Vector3::new(1.0, 1.0, 1.0 + 1e-15),
+        )]);
+        let non_finite = PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(f64::INFINITY, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )]);
+
+        let warnings = lint_group(part, &collider_group!(point, non_finite));
+
+        assert!(warnings.contains(&GeometryWarning::ZeroSizeBox { part, item: 0 }));
+        assert!(warnings.contains(&GeometryWarning::NonFiniteVertex {
+            part,
+            item: 1,
+            triangle: 0,
+        }));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::configuration::Configuration;
+use thiserror::Error;
+
+/// # Path Envelope Error
+/// Error of loading a saved path for replay.
+#[derive(Error, Debug, PartialEq)]
+pub enum PathEnvelopeError {
+    #[error("Configuration mismatch, expected hash {expected:#x} but found {found:#x}")]
+    ConfigMismatch { expected: u64, found: u64 },
+}
+
+/// # Path Envelope
+/// Envelope for serialized paths tagged with the [Configuration::config_hash]
+/// of the configuration they were planned in.
+///
+/// Use [PathEnvelope::load] to get the path, it rejects paths planned with other geometry.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct PathEnvelope<P> {
+    config_hash: u64,
+    path: Vec<P>,
+}
+
+impl<P> PathEnvelope<P> {
+    /// Wraps the path with the hash of the configuration it was planned in.
+    pub fn new(config: &Configuration, path: Vec<P>) -> Self {
+        Self {
+            config_hash: config.config_hash(),
+            path,
+        }
+    }
+
+    pub fn config_hash(&self) -> u64 {
+        self.config_hash
+    }
+
+    /// Unwraps the path if the current configuration has the same hash.
+    pub fn load(self, config: &Configuration) -> Result<Vec<P>, PathEnvelopeError> {
+        let current = config.config_hash();
+        if self.config_hash != current {
+            return Err(PathEnvelopeError::ConfigMismatch {
+                expected: current,
+                found: self.config_hash,
+            });
+        }
+
+        Ok(self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::chamber::ChamberConfig;
+    use crate::configuration::equipment::EquipmentConfig;
+    use crate::configuration::resolver_stage::ResolverStageConfig;
+    use crate::configuration::stage::StageConfig;
+    use crate::types::CSixAxis;
+
+    const STEP: CSixAxis = CSixAxis {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+        rx: 0.4,
+        ry: 0.5,
+        rz: 0.6,
+    };
+
+    fn config(equipment: Vec<EquipmentConfig>) -> Configuration {
+        Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            equipment,
+            vec![],
+        )
+    }
+
+    fn saved_path() -> String {
+        let path = vec![
+            CSixAxis {
+                x: 0.0,
+                y: 0.0,
+                z: 0.05,
+                rx: 0.0,
+                ry: 0.0,
+                rz: 0.0,
+            },
+            CSixAxis {
+                x: 0.03,
+                y: 0.0,
+                z: 0.05,
+                rx: 0.0,
+                ry: 0.0,
+                rz: 0.1,
+            },
+        ];
+        let config = config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        serde_json::to_string(&PathEnvelope::new(&config, path)).unwrap()
+    }
+
+    #[test]
+    fn replay_in_identical_configuration() {
+        let json = saved_path();
+        let config = config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+
+        let envelope: PathEnvelope<CSixAxis> = serde_json::from_str(&json).unwrap();
+        let path = envelope.load(&config).unwrap();
+        assert_eq!(2, path.len());
+        assert_eq!(0.1, path[1].rz);
+    }
+
+    #[test]
+    fn reject_replay_in_modified_configuration() {
+        let json = saved_path();
+        let config = config(vec![EquipmentConfig::ThesisDetectorBeta]);
+
+        let envelope: PathEnvelope<CSixAxis> = serde_json::from_str(&json).unwrap();
+        let saved = envelope.config_hash();
+        assert_eq!(
+            Err(PathEnvelopeError::ConfigMismatch {
+                expected: config.config_hash(),
+                found: saved,
+            }),
+            envelope.load(&config)
+        );
+    }
+}
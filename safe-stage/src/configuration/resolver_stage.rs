@@ -1,5 +1,6 @@
 use crate::concrete_resolvers::ConcreteStageResolver;
 use crate::types::{CSixAxis, CVector3};
+use paths::resolver::stage::bidirectional::StageBidirectionalResolver;
 use paths::resolver::stage::down_rotate_find::DownRotateFindResolver;
 use paths::resolver::stage::linear::StageLinearResolver;
 
@@ -21,6 +22,16 @@ pub enum ResolverStageConfig {
         los_step: CVector3,
         smoothing_step: CSixAxis,
     },
+    StageBidirectionalResolver {
+        step_size: CSixAxis,
+        connect_distance: f64,
+    },
+    /// Bidirectional resolver with a [PlanarConstraint](paths::resolver::stage::planar::PlanarConstraint)
+    /// keeping the Z and rotation of the start.
+    StagePlanarResolver {
+        step_size: CSixAxis,
+        connect_distance: f64,
+    },
     UnitVariant(CSixAxis),
     EmptyVariant,
 }
@@ -33,6 +44,29 @@ impl ResolverStageConfig {
         ResolverStageConfig::StageLinearResolver { step_size }
     }
 
+    /// Create a new **StageBidirectionalResolver** configuration.
+    #[cfg(feature = "ffi")]
+    #[no_mangle]
+    pub extern "C" fn stage_bidirectional_resolver(
+        step_size: CSixAxis,
+        connect_distance: f64,
+    ) -> Self {
+        ResolverStageConfig::StageBidirectionalResolver {
+            step_size,
+            connect_distance,
+        }
+    }
+
+    /// Create a new **StagePlanarResolver** configuration.
+    #[cfg(feature = "ffi")]
+    #[no_mangle]
+    pub extern "C" fn stage_planar_resolver(step_size: CSixAxis, connect_distance: f64) -> Self {
+        ResolverStageConfig::StagePlanarResolver {
+            step_size,
+            connect_distance,
+        }
+    }
+
     /// Create a new **DownRotateFindResolver** configuration.
     #[cfg(feature = "ffi")]
     #[no_mangle]
@@ -60,6 +94,17 @@ impl ResolverStageConfig {
         }
     }
 
+    /// Step of the resolver used to validate it against the size of the stage.
+    pub(crate) fn step_size(&self) -> Option<CSixAxis> {
+        match self {
+            ResolverStageConfig::StageLinearResolver { step_size }
+            | ResolverStageConfig::StageBidirectionalResolver { step_size, .. }
+            | ResolverStageConfig::StagePlanarResolver { step_size, .. } => Some(*step_size),
+            ResolverStageConfig::DownRotateFindResolver { down_step, .. } => Some(*down_step),
+            _ => None,
+        }
+    }
+
     pub(crate) fn build(&self) -> ConcreteStageResolver {
         match self {
             ResolverStageConfig::StageLinearResolver { step_size } => {
@@ -86,6 +131,20 @@ impl ResolverStageConfig {
                 los_step.into(),
                 smoothing_step.into(),
             )),
+            ResolverStageConfig::StageBidirectionalResolver {
+                step_size,
+                connect_distance,
+            } => ConcreteStageResolver::new(StageBidirectionalResolver::new(
+                step_size.into(),
+                *connect_distance,
+            )),
+            ResolverStageConfig::StagePlanarResolver {
+                step_size,
+                connect_distance,
+            } => ConcreteStageResolver::new(StageBidirectionalResolver::new_planar(
+                step_size.into(),
+                *connect_distance,
+            )),
             _ => unimplemented!(),
         }
     }
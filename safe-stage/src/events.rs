@@ -0,0 +1,71 @@
+use crate::configuration::equipment::EquipmentConfig;
+use crate::configuration::holder::HolderConfig;
+use crate::ffi::opaque_ffi_for_type;
+use crate::id::Id;
+use crate::types::{CLinearState, CSixAxis, CVector3};
+
+/// # Microscope Event
+/// Mutation of a [crate::microscope::Microscope] recorded with its parameters.
+///
+/// Parameters are recorded as passed in, before any sanitization,
+/// so replaying them reproduces the same state even for invalid input.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MicroscopeEvent {
+    ClearSample,
+    UpdateHolder(HolderConfig),
+    RemoveHolder,
+    SetSampleRotation(CVector3),
+    AddEquipment(EquipmentConfig),
+    RemoveEquipment(usize),
+    UpdateSampleHeightMap {
+        height_map: Vec<f64>,
+        size_x: usize,
+        size_y: usize,
+        real_x: f64,
+        real_y: f64,
+    },
+    UpdateStageState(CSixAxis),
+    UpdateRetractState(Id, CLinearState),
+    UpdateResolvers,
+    Home,
+}
+
+opaque_ffi_for_type!(CBoxSliceEvents, Box<[MicroscopeEvent]>);
+
+/// # Microscope Event Log
+/// Events recorded by a [crate::microscope::Microscope] in the order they happened.
+///
+/// Intended for reproducing bug reports by replaying the events onto a fresh microscope.
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct MicroscopeEventLog {
+    events: CBoxSliceEvents,
+}
+
+impl MicroscopeEventLog {
+    pub fn new(events: Vec<MicroscopeEvent>) -> Self {
+        Self {
+            events: CBoxSliceEvents::from_inner(events.into_boxed_slice()),
+        }
+    }
+
+    pub fn events(&self) -> &[MicroscopeEvent] {
+        self.events.inner()
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl MicroscopeEventLog {
+    /// Get the number of recorded events.
+    #[no_mangle]
+    pub extern "C" fn microscope_event_log_len(&self) -> usize {
+        self.events().len()
+    }
+
+    /// # Safety
+    /// Takes ownership of the event log and drops it.
+    #[no_mangle]
+    pub extern "C" fn microscope_event_log_drop(self) {
+        //  dropped after leaving scope
+    }
+}
@@ -121,8 +121,6 @@ pub(crate) use ffi_vec_for_type;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     opaque_ffi_for_type!(TestVecU8, Vec<u8>);
 
     #[test]
@@ -1,3 +1,4 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ffi", repr(C))]
 pub struct Id(u64);
@@ -13,6 +14,11 @@ impl Id {
     pub fn new(id: u64) -> Self {
         Id(id)
     }
+
+    /// Raw value of the id.
+    pub(crate) fn value(&self) -> u64 {
+        self.0
+    }
 }
 
 #[cfg(all(test, feature = "ffi"))]
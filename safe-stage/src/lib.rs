@@ -12,10 +12,12 @@
 pub mod concrete_parts;
 pub mod concrete_resolvers;
 pub mod configuration;
+pub mod events;
 mod ffi;
 pub mod id;
 pub mod microscope;
 pub mod presentation;
+pub mod snapshot;
 pub mod types;
 
 /// Initializes logging for the Rust library.
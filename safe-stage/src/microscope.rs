@@ -1,29 +1,66 @@
 use crate::concrete_parts::{ConcreteChamber, ConcreteEquipment, ConcreteRetract, ConcreteStage};
 use crate::concrete_resolvers::{ConcreteRetractResolver, ConcreteStageResolver};
+#[cfg(not(feature = "ffi"))]
+use crate::configuration::builder::ConfigurationBuilder;
+use crate::configuration::contact::{ObstaclePart, StagePart};
+use crate::configuration::equipment::{EquipmentConfig, ObstructionTier};
 use crate::configuration::holder::HolderConfig;
+#[cfg(not(feature = "ffi"))]
+use crate::configuration::ConfigBuilderResult;
 use crate::configuration::Configuration;
+use crate::events::{MicroscopeEvent, MicroscopeEventLog};
 use crate::ffi::opaque_ffi_for_type;
 use crate::id::Id;
-use crate::presentation::{collider_to_triangle_buffer_per_item, TriangleBufferVec};
-use crate::types::{CLinearState, CPathResultLinearState, CPathResultSixAxis, CSixAxis};
+#[cfg(feature = "ffi")]
+use crate::presentation::{bounding_boxes_to_buffer, BoundingBoxBuffer};
+use crate::presentation::{
+    collider_to_bounding_boxes, collider_to_interleaved_buffer_per_item,
+    collider_to_triangle_buffer_per_item, transform_buffer, triangle_chunk, triangle_chunk_count,
+    InterleavedBufferVec, TriangleBufferVec,
+};
+use crate::snapshot::MicroscopeState;
+use crate::types::{
+    CAxis, CCancelToken, CLinearState, CPathResultLinearState, CPathResultSixAxis, CSixAxis,
+    CVector3, PathResultState,
+};
+use collisions::common::{Bounded, Collides, Distance};
 use collisions::complex::group::ColliderGroup;
+use collisions::primitive::AlignedBoxCollider;
 use collisions::PrimaryCollider;
-use maths::Vector2;
-use models::immovable::Immovable;
+use maths::{bounds_of, NaNExtension, Quaternion, Vector2, Vector3};
+use models::immovable::{group_bounds, Immovable};
+use models::movable::sweepable::Sweepable;
 use models::movable::Movable;
 use models::position::linear::LinearState;
-use models::position::sixaxis::SixAxis;
+use models::position::sixaxis::{Dof, SixAxis};
 use models::sample::height_map::height_map_to_sample_model;
+use paths::cancel::CancelToken;
+use paths::ccd::{
+    conservative_advancement, conservative_advancement_pair, motion_bound, Advancement,
+};
+use paths::path::PathResult;
 use paths::resolver::StateUpdateError as ResolverUpdateError;
+use paths::sight::line_of_sight_whitelisted_par;
+use paths::step::{max_rotation_step, validate_rotation_step};
+use paths::whitelist::CollisionWhitelist;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::f64::consts;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
-opaque_ffi_for_type!(BoxSliceEquipment, Box<[ConcreteEquipment]>);
+opaque_ffi_for_type!(
+    BoxSliceEquipment,
+    Box<[(ConcreteEquipment, ObstructionTier)]>
+);
 opaque_ffi_for_type!(
     HashMapRetracts,
     HashMap<Id, (ConcreteRetract, ConcreteRetractResolver, CLinearState)>
 );
+opaque_ffi_for_type!(HashMapRetractHomes, HashMap<Id, CLinearState>);
+opaque_ffi_for_type!(CachedImmovable, Mutex<Option<Arc<Immovable>>>);
+opaque_ffi_for_type!(FrozenEquipment, Option<PrimaryCollider>);
+opaque_ffi_for_type!(EventRecording, Option<Vec<MicroscopeEvent>>);
+opaque_ffi_for_type!(BuiltConfiguration, Configuration);
 
 #[cfg_attr(feature = "ffi", repr(u8))]
 #[derive(Error, Debug)]
@@ -45,6 +82,46 @@ impl From<ResolverUpdateError> for StateUpdateError {
     }
 }
 
+/// Static part of the microscope, equipment is identified by its order in the configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ffi", repr(C, u8))]
+pub enum StaticPart {
+    Chamber,
+    Equipment(usize),
+    Stage,
+    Retract(Id),
+}
+
+/// Pair of static parts colliding with each other.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ffi", repr(C))]
+#[error("Static parts {first:?} and {second:?} collide")]
+pub struct StaticCollision {
+    pub first: StaticPart,
+    pub second: StaticPart,
+}
+
+/// Outcome of validating a stage path that was not produced by the resolver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathValidation {
+    /// No segment of the path collides.
+    Ok,
+    /// Index of the first colliding segment, segment `i` moves from node `i` to node `i + 1`.
+    Collision(usize),
+}
+
+/// Rough estimate of how demanding planning a stage path is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ffi", repr(u8))]
+pub enum Difficulty {
+    /// The straight line is free of collisions.
+    Trivial,
+    /// The straight line is blocked, but mostly free.
+    Moderate,
+    /// Either end is blocked, or most of the straight line is.
+    Hard,
+}
+
 #[cfg(feature = "ffi")]
 fn result_to_error_enum(result: Result<(), StateUpdateError>) -> StateUpdateError {
     match result {
@@ -59,8 +136,15 @@ pub struct Microscope {
     stage: ConcreteStage,
     stage_resolver: ConcreteStageResolver,
     stage_state: CSixAxis,
+    stage_home: CSixAxis,
     equipment: BoxSliceEquipment,
     retracts: HashMapRetracts,
+    retract_homes: HashMapRetractHomes,
+    stage_immovable: CachedImmovable,
+    static_immovable: CachedImmovable,
+    frozen_equipment: FrozenEquipment,
+    recording: EventRecording,
+    configuration: BuiltConfiguration,
 }
 
 #[cfg(feature = "ffi")]
@@ -71,6 +155,50 @@ impl Microscope {
         Self::build(config)
     }
 
+    /// Build microscope from configuration and apply the recorded events in order,
+    /// see [Microscope::microscope_start_recording].
+    #[no_mangle]
+    pub extern "C" fn microscope_replay(config: &Configuration, log: &MicroscopeEventLog) -> Self {
+        Self::safe_replay(config, log.events())
+    }
+
+    /// Build a new microscope from `config` carrying over the states of this one still valid in it.
+    ///
+    /// The holder with its sample is moved over, the stage state is kept when the stage is the same,
+    /// and retract states are kept for retracts with the same id and configuration.
+    /// Other states start at their home, the same as after building from the configuration.
+    ///
+    /// The resolvers are updated with the states against the new geometry,
+    /// the result is written into `update`, see [Microscope::microscope_update_resolvers].
+    #[no_mangle]
+    pub extern "C" fn microscope_with_config(
+        &self,
+        config: &Configuration,
+        update: &mut StateUpdateError,
+    ) -> Self {
+        let (microscope, result) = self.safe_with_config(config);
+        *update = result_to_error_enum(result);
+        microscope
+    }
+
+    /// Start recording every mutation of the microscope, discarding any previous recording.
+    ///
+    /// # Safety
+    /// Expects exclusive mutable reference to the microscope.
+    #[no_mangle]
+    pub extern "C" fn microscope_start_recording(&mut self) {
+        self.safe_start_recording()
+    }
+
+    /// Stop recording and take the recorded events, empty if not recording.
+    ///
+    /// # Safety
+    /// Expects exclusive mutable reference to the microscope.
+    #[no_mangle]
+    pub extern "C" fn microscope_stop_recording(&mut self) -> MicroscopeEventLog {
+        self.safe_stop_recording()
+    }
+
     /// Clear the sample from the active holder.
     ///
     /// # Safety
@@ -98,6 +226,36 @@ impl Microscope {
         self.safe_remove_holder()
     }
 
+    /// Rotate the samples relative to the active holder, given as euler angles in radians.
+    ///
+    /// The rotation is around the point the samples are attached at,
+    /// it is reset when the holder is replaced.
+    ///
+    /// # Safety
+    /// Expects exclusive mutable reference to the microscope.
+    #[no_mangle]
+    pub extern "C" fn microscope_set_sample_rotation(&mut self, rotation: &CVector3) {
+        self.safe_set_sample_rotation(rotation)
+    }
+
+    /// Mount new equipment, identified by the next index after the existing equipment.
+    ///
+    /// Resolvers are not updated, call [Microscope::microscope_update_resolvers] afterwards.
+    #[no_mangle]
+    pub extern "C" fn microscope_add_equipment(&mut self, equipment: &EquipmentConfig) {
+        self.safe_add_equipment(equipment)
+    }
+
+    /// Unmount the equipment at the `index`, shifting the indices of the following equipment.
+    ///
+    /// Stage contacts of the equipment are dropped, contacts of the following equipment follow it.
+    /// Returns `false` when there is no equipment at the `index`.
+    /// Resolvers are not updated, call [Microscope::microscope_update_resolvers] afterwards.
+    #[no_mangle]
+    pub extern "C" fn microscope_remove_equipment(&mut self, index: usize) -> bool {
+        self.safe_remove_equipment(index)
+    }
+
     /// Update the sample height map.
     ///
     /// # Safety
@@ -125,6 +283,61 @@ impl Microscope {
         self.safe_update_stage_state(state)
     }
 
+    /// Get the current stage state.
+    #[no_mangle]
+    pub extern "C" fn microscope_stage_state(&self) -> CSixAxis {
+        self.safe_stage_state()
+    }
+
+    /// Get the current state of the retract.
+    ///
+    /// Returns `false` and leaves `state` untouched if the id is not valid.
+    #[no_mangle]
+    pub extern "C" fn microscope_retract_state(&self, id: Id, state: &mut CLinearState) -> bool {
+        match self.safe_retract_state(id) {
+            Some(s) => {
+                *state = s;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take a snapshot of the current stage and retract states.
+    #[no_mangle]
+    pub extern "C" fn microscope_snapshot(&self) -> MicroscopeState {
+        self.safe_snapshot()
+    }
+
+    /// Round the stage state to the nearest point of the stage resolver grid,
+    /// which is anchored at the current stage state.
+    ///
+    /// Axes the resolver does not search on a grid are left unchanged.
+    #[no_mangle]
+    pub extern "C" fn microscope_snap_stage_to_grid(&self, state: &CSixAxis) -> CSixAxis {
+        self.safe_snap_stage_to_grid(state)
+    }
+
+    /// Round the retract state to the nearest point of the retract resolver grid,
+    /// which is anchored at the current retract state.
+    ///
+    /// Returns `false` and leaves `snapped` untouched if the id is not valid.
+    #[no_mangle]
+    pub extern "C" fn microscope_snap_retract_to_grid(
+        &self,
+        id: Id,
+        state: &CLinearState,
+        snapped: &mut CLinearState,
+    ) -> bool {
+        match self.safe_snap_retract_to_grid(id, state) {
+            Some(s) => {
+                *snapped = s;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Update the retract state.
     ///
     /// # Safety
@@ -147,228 +360,707 @@ impl Microscope {
         result_to_error_enum(self.safe_update_resolvers())
     }
 
+    /// Reset the stage and all retracts to their configured home states
+    /// and update the resolvers.
+    ///
+    /// # Safety
+    /// Expects exclusive mutable reference to the microscope.
+    #[no_mangle]
+    pub extern "C" fn microscope_home(&mut self) -> StateUpdateError {
+        result_to_error_enum(self.safe_home())
+    }
+
+    /// Check that the chamber, equipment, the stage at home pose
+    /// and the retracts at their home states do not collide.
+    ///
+    /// Equipment and retracts are mounted into the chamber walls,
+    /// so they are checked only against the non-obstructive parts of the chamber.
+    ///
+    /// Returns `false` and writes the first colliding pair into `collision` otherwise.
+    #[no_mangle]
+    pub extern "C" fn microscope_validate_static_configuration(
+        &self,
+        collision: &mut StaticCollision,
+    ) -> bool {
+        match self.safe_validate_static_configuration() {
+            Ok(()) => true,
+            Err(c) => {
+                *collision = c;
+                false
+            }
+        }
+    }
+
+    /// Assemble the static parts and validate the static configuration ahead of the first query.
+    ///
+    /// Optional but recommended, otherwise the assembly happens during the first query.
+    ///
+    /// Returns `false` and writes the first colliding pair into `collision`
+    /// when the static configuration is invalid.
+    #[no_mangle]
+    pub extern "C" fn microscope_prepare(&mut self, collision: &mut StaticCollision) -> bool {
+        match self.safe_prepare() {
+            Ok(()) => true,
+            Err(c) => {
+                *collision = c;
+                false
+            }
+        }
+    }
+
     /// Find a path for the stage from the latest state to the given one.
     #[no_mangle]
     pub extern "C" fn microscope_find_stage_path(&self, state: &CSixAxis) -> CPathResultSixAxis {
         self.safe_find_stage_path(state)
     }
 
-    /// Find a path for the retract from the latest state to the given one.
+    /// Find a path for the stage from the latest state to the given one.
+    ///
+    /// Returns a cancelled result once the token is cancelled.
     #[no_mangle]
-    pub extern "C" fn microscope_find_retract_path(
+    pub extern "C" fn microscope_find_stage_path_cancellable(
         &self,
-        id: Id,
-        state: &CLinearState,
-    ) -> CPathResultLinearState {
-        self.safe_find_retract_path(id, state)
+        state: &CSixAxis,
+        cancel: &CCancelToken,
+    ) -> CPathResultSixAxis {
+        self.safe_find_stage_path_cancellable(state, cancel.inner())
     }
 
-    /// Present the full view of static parts.
+    /// Find a path for the stage from the latest state to the given one.
+    ///
+    /// Reports the estimated completion to `progress` as a non-decreasing fraction
+    /// in `[0, 1]`, ending with `1` when a path is found.
+    /// Returns a cancelled result once the token is cancelled.
     #[no_mangle]
-    pub extern "C" fn microscope_present_static_full(&self) -> TriangleBufferVec {
-        self.safe_present_static_full()
+    pub extern "C" fn microscope_find_stage_path_with_progress(
+        &self,
+        state: &CSixAxis,
+        cancel: &CCancelToken,
+        progress: extern "C" fn(f32),
+    ) -> CPathResultSixAxis {
+        self.safe_find_stage_path_with_progress(state, cancel.inner(), &mut |f| progress(f))
     }
 
-    /// Present the less obstructive view of static parts.
+    /// Find a path for the stage from `current` to the `goal`,
+    /// reusing the still collision-free tail of the `previous` path.
+    /// The tail is checked with the step of the configured stage resolver.
+    ///
+    /// Returns an invalid start when `current` collides.
+    /// Returns a cancelled result once the token is cancelled.
+    ///
+    /// # Safety
+    /// The `previous` must be a pointer to an array of `CSixAxis` values with a length of `len`.
     #[no_mangle]
-    pub extern "C" fn microscope_present_static_less_obstructive(&self) -> TriangleBufferVec {
-        self.safe_present_static_less_obstructive()
+    pub unsafe extern "C" fn microscope_replan_from(
+        &self,
+        current: &CSixAxis,
+        goal: &CSixAxis,
+        previous: *const CSixAxis,
+        len: usize,
+        cancel: &CCancelToken,
+    ) -> CPathResultSixAxis {
+        let previous = &*std::ptr::slice_from_raw_parts(previous, len);
+        let previous = previous.iter().map(SixAxis::from).collect::<Vec<_>>();
+        self.safe_replan_from(current, goal, &previous, cancel.inner())
     }
 
-    /// Present the non-obstructive view of static parts.
+    /// Check every segment of a scripted stage path for collisions along the movement.
+    ///
+    /// Segments are checked at the suggested step size,
+    /// see [Microscope::microscope_suggest_step_size], so features of the microscope cannot be skipped.
+    ///
+    /// Returns `false` and writes the index of the first colliding segment into `segment` otherwise.
+    ///
+    /// # Safety
+    /// The `path` must be a pointer to an array of `CSixAxis` values with a length of `len`.
     #[no_mangle]
-    pub extern "C" fn microscope_present_static_non_obstructive(&self) -> TriangleBufferVec {
-        self.safe_present_static_non_obstructive()
+    pub unsafe extern "C" fn microscope_validate_stage_path(
+        &self,
+        path: *const CSixAxis,
+        len: usize,
+        segment: &mut usize,
+    ) -> bool {
+        let path = &*std::ptr::slice_from_raw_parts(path, len);
+        match self.safe_validate_stage_path(path) {
+            PathValidation::Ok => true,
+            PathValidation::Collision(s) => {
+                *segment = s;
+                false
+            }
+        }
     }
 
-    /// Present the stage at the current state.
+    /// Write the axis aligned bounds of the volume the stage sweeps along each path segment
+    /// into `min` and `max`, segment `i` moves from node `i` to node `i + 1`.
+    ///
+    /// Returns the number of written segments.
+    ///
+    /// # Safety
+    /// The `path` must be a pointer to an array of `CSixAxis` values with a length of `len`,
+    /// `min` and `max` must point to arrays of `CVector3` with a length of at least `len - 1`.
     #[no_mangle]
-    pub extern "C" fn microscope_present_stage(&self) -> TriangleBufferVec {
-        self.safe_present_stage()
+    pub unsafe extern "C" fn microscope_swept_volume_bounds(
+        &self,
+        path: *const CSixAxis,
+        len: usize,
+        min: *mut CVector3,
+        max: *mut CVector3,
+    ) -> usize {
+        let path = &*std::ptr::slice_from_raw_parts(path, len);
+        let bounds = self.safe_swept_volume_bounds(path);
+        for (i, bound) in bounds.iter().enumerate() {
+            *min.add(i) = CVector3::from(&bound.min());
+            *max.add(i) = CVector3::from(&bound.max());
+        }
+        bounds.len()
     }
 
-    /// Present the stage at the given state.
+    /// Check whether the stage and the retract collide while both move at the same time,
+    /// the stage from `stage_from` to `stage_to` and the retract from `retract_from` to `retract_to`.
+    ///
+    /// Both movements progress together, the parts are compared where they are at the same time,
+    /// conservatively by advancing by the clearance between them.
+    /// Returns `false` and leaves `collides` untouched if the retract id is not valid.
     #[no_mangle]
-    pub extern "C" fn microscope_present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
-        self.safe_present_stage_at(state)
+    pub extern "C" fn microscope_moving_pair_collides(
+        &self,
+        stage_from: &CSixAxis,
+        stage_to: &CSixAxis,
+        retract_id: Id,
+        retract_from: &CLinearState,
+        retract_to: &CLinearState,
+        collides: &mut bool,
+    ) -> bool {
+        match self.safe_moving_pair_collides(
+            (stage_from, stage_to),
+            retract_id,
+            (retract_from, retract_to),
+        ) {
+            Some(c) => {
+                *collides = c;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Present the retract at the current state.
+    /// Order in which to visit the stage `targets` from `start` to keep the total travel time low,
+    /// written as indices into `targets` into `order`.
+    ///
+    /// Travel time is the [SixAxis::time_to] with the `speed` of each axis.
+    /// Nearest neighbor heuristic ignoring obstacles, the order is not guaranteed to be optimal.
+    ///
+    /// # Safety
+    /// The `targets` must be a pointer to an array of `CSixAxis` values with a length of `len`,
+    /// `order` must point to an array of `usize` with a length of at least `len`.
     #[no_mangle]
-    pub extern "C" fn microscope_present_retract(&self, id: Id) -> TriangleBufferVec {
-        self.safe_present_retract(id)
+    pub unsafe extern "C" fn microscope_order_targets(
+        &self,
+        start: &CSixAxis,
+        targets: *const CSixAxis,
+        len: usize,
+        speed: &CSixAxis,
+        order: *mut usize,
+    ) {
+        let targets = &*std::ptr::slice_from_raw_parts(targets, len);
+        for (i, index) in self
+            .safe_order_targets(start, targets, speed)
+            .into_iter()
+            .enumerate()
+        {
+            *order.add(i) = index;
+        }
     }
 
-    /// Present the retract at the given state.
+    /// Find the first of the `candidates` directions in which the stage can move straight
+    /// into the `target`, starting `distance` away from it, and write it into `direction`.
+    ///
+    /// Candidates are directions of the movement, the start is `target - candidate * distance`
+    /// with the rotation of the target. Zero and non-finite candidates are skipped.
+    /// Approaches are checked at the suggested step size, see [Microscope::microscope_suggest_step_size].
+    ///
+    /// Returns `false` and leaves `direction` untouched when every approach collides.
+    ///
+    /// # Safety
+    /// The `candidates` must be a pointer to an array of `CVector3` values with a length of `len`.
     #[no_mangle]
-    pub extern "C" fn microscope_present_retract_at(
+    pub unsafe extern "C" fn microscope_approach_direction(
         &self,
-        id: Id,
-        state: &CLinearState,
-    ) -> TriangleBufferVec {
-        self.safe_present_retract_at(id, state)
+        target: &CSixAxis,
+        candidates: *const CVector3,
+        len: usize,
+        distance: f64,
+        direction: &mut CVector3,
+    ) -> bool {
+        let candidates = &*std::ptr::slice_from_raw_parts(candidates, len);
+        let candidates = candidates.iter().map(Vector3::from).collect::<Vec<_>>();
+        match self.safe_approach_direction(target, &candidates, distance) {
+            Some(d) => {
+                *direction = CVector3::from(&d);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// # Safety
-    /// Takes ownership of the microscope and drops it.
+    /// Farthest stage state on the straight line from `from` towards `goal` free of collisions.
+    ///
+    /// Results in `from` when it touches an obstacle.
+    /// Returns `false` when `from` already collides, otherwise writes the state into `reached`.
     #[no_mangle]
-    pub extern "C" fn microscope_drop(self) {
-        //  dropped after leaving scope
+    pub extern "C" fn microscope_advance_toward(
+        &self,
+        from: &CSixAxis,
+        goal: &CSixAxis,
+        reached: &mut CSixAxis,
+    ) -> bool {
+        match self.safe_advance_toward(from, goal) {
+            Some(state) => {
+                *reached = state;
+                true
+            }
+            None => false,
+        }
     }
-}
 
-#[cfg(not(feature = "ffi"))]
-impl Microscope {
-    /// Build microscope from configuration.
-    pub fn from_config(config: &Configuration) -> Self {
-        Self::build(config)
+    /// Find the collision-free stage state closest to the `desired` one,
+    /// differing in each axis by at most the matching component of `search_radius`.
+    ///
+    /// Distance is the [SixAxis::time_to] with the radius as the speed,
+    /// axes with zero radius are kept.
+    /// The search gives up after a bounded number of collision checks,
+    /// so with many searched axes only the poses closest to `desired` are tried.
+    ///
+    /// Returns `false` when no free state is within the radius, otherwise writes it into `nearest`.
+    #[no_mangle]
+    pub extern "C" fn microscope_nearest_valid_pose(
+        &self,
+        desired: &CSixAxis,
+        search_radius: &CSixAxis,
+        nearest: &mut CSixAxis,
+    ) -> bool {
+        match self.safe_nearest_valid_pose(desired, search_radius) {
+            Some(free) => {
+                *nearest = free;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Clear the sample from the active holder.
-    pub fn clear_sample(&mut self) {
-        self.safe_clear_sample();
+    /// Fraction of collision-free stage states on the grid spanning `min` to `max`, both included,
+    /// with the spacing of `grid` in each axis.
+    ///
+    /// Axes with zero or non-finite spacing, or an empty range, are sampled only at `min`,
+    /// so zero rotational spacing samples positions only.
+    /// Returns `false` when the grid has more than 100 thousand states,
+    /// otherwise writes the fraction into `fraction`.
+    #[no_mangle]
+    pub extern "C" fn microscope_free_fraction(
+        &self,
+        grid: &CSixAxis,
+        min: &CSixAxis,
+        max: &CSixAxis,
+        fraction: &mut f64,
+    ) -> bool {
+        match self.safe_free_fraction(grid, (min, max)) {
+            Some(free) => {
+                *fraction = free;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Update the active stage holder.
-    pub fn update_holder(&mut self, holder: &HolderConfig) {
-        self.safe_update_holder(holder);
+    /// Check which of the stage `candidates` are collision-free,
+    /// writing `true` into `valid` at the index of each free candidate and `false` otherwise.
+    ///
+    /// # Safety
+    /// The `candidates` must be a pointer to an array of `CSixAxis` values with a length of `len`,
+    /// `valid` must point to an array of `bool` with a length of at least `len`.
+    #[no_mangle]
+    pub unsafe extern "C" fn microscope_filter_valid_poses(
+        &self,
+        candidates: *const CSixAxis,
+        len: usize,
+        valid: *mut bool,
+    ) {
+        let candidates = &*std::ptr::slice_from_raw_parts(candidates, len);
+        for (i, free) in self
+            .safe_filter_valid_poses(candidates)
+            .into_iter()
+            .enumerate()
+        {
+            *valid.add(i) = free;
+        }
     }
 
-    /// Remove the active stage holder.
-    pub fn remove_holder(&mut self) {
-        self.safe_remove_holder();
+    /// Push a stage state slightly intruding into geometry out to a nearby free state,
+    /// moving it by at most `max_distance`.
+    ///
+    /// The state is moved by its [separation vector](Microscope::microscope_separation_vector)
+    /// when it is not longer than `max_distance`.
+    ///
+    /// Returns `false` when no free state is that close, otherwise writes it into `nudged`.
+    #[no_mangle]
+    pub extern "C" fn microscope_nudge_to_free(
+        &self,
+        state: &CSixAxis,
+        max_distance: f64,
+        nudged: &mut CSixAxis,
+    ) -> bool {
+        match self.safe_nudge_to_free(state, max_distance) {
+            Some(free) => {
+                *nudged = free;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Update the sample height map.
-    pub fn update_sample_height_map(
-        &mut self,
-        height_map: &[f64],
-        size_x: usize,
-        size_y: usize,
-        real_x: f64,
-        real_y: f64,
-    ) {
-        self.safe_update_sample_height_map(height_map, size_x, size_y, real_x, real_y);
+    /// Find the minimal translation of the stage at `state` that makes it collision-free,
+    /// zero if it already is.
+    ///
+    /// The penetration of the deepest colliding pair of triangles is computed by EPA
+    /// and the stage is moved out of it, repeatedly until nothing collides.
+    /// Each pair is left with a small clearance, so the translation is marginally longer.
+    ///
+    /// Returns `false` when the pairs cannot be separated in a few steps,
+    /// otherwise writes the translation into `separation`.
+    #[no_mangle]
+    pub extern "C" fn microscope_separation_vector(
+        &self,
+        state: &CSixAxis,
+        separation: &mut CVector3,
+    ) -> bool {
+        match self.safe_separation_vector(state) {
+            Some(vector) => {
+                *separation = CVector3::from(&vector);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Update the stage state.
-    pub fn update_stage_state(&mut self, state: &CSixAxis) {
-        self.safe_update_stage_state(state);
+    /// Rotate a colliding stage state about the `axis` to the nearest free orientation,
+    /// turning it by at most `max_angle` in either direction.
+    ///
+    /// Returns `false` when no free orientation is that close, otherwise writes it into `rotated`.
+    #[no_mangle]
+    pub extern "C" fn microscope_rotate_to_free(
+        &self,
+        state: &CSixAxis,
+        axis: CAxis,
+        max_angle: f64,
+        rotated: &mut CSixAxis,
+    ) -> bool {
+        match self.safe_rotate_to_free(state, axis, max_angle) {
+            Some(free) => {
+                *rotated = free;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Update the retract state.
-    pub fn update_retract_state(
-        &mut self,
-        id: Id,
-        state: &CLinearState,
-    ) -> Result<(), StateUpdateError> {
-        self.safe_update_retract_state(id, state)
+    /// Compute the collision-free travel limits of each degree of freedom of the stage,
+    /// moving one at a time from the current stage state
+    /// by the step of the configured stage resolver.
+    ///
+    /// Writes the componentwise lowest pose into `min` and the highest into `max`,
+    /// both are the current stage state if it collides.
+    #[no_mangle]
+    pub extern "C" fn microscope_reachable_extents(&self, min: &mut CSixAxis, max: &mut CSixAxis) {
+        (*min, *max) = self.safe_reachable_extents();
     }
 
-    /// Update the resolvers for stage and all retracts.
-    pub fn update_resolvers(&mut self) -> Result<(), StateUpdateError> {
-        self.safe_update_resolvers()
+    /// Estimate how demanding planning the stage path between the two states is,
+    /// without running the resolver.
+    /// The straight line is sampled with the step of the configured stage resolver.
+    #[no_mangle]
+    pub extern "C" fn microscope_estimate_difficulty(
+        &self,
+        from: &CSixAxis,
+        to: &CSixAxis,
+    ) -> Difficulty {
+        self.safe_estimate_difficulty(from, to)
     }
 
-    /// Find a path for the stage from the latest state to the given one.
-    pub fn find_stage_path(&self, state: &CSixAxis) -> CPathResultSixAxis {
-        self.safe_find_stage_path(state)
+    /// Smallest distance between the stage and the rest of the microscope along the whole path.
+    ///
+    /// Segments are sampled with the step of the configured stage resolver,
+    /// returns `0.0` when the path collides and infinity for an empty path.
+    ///
+    /// # Safety
+    /// The `path` must be a pointer to an array of `CSixAxis` values with a length of `len`.
+    #[no_mangle]
+    pub unsafe extern "C" fn microscope_path_min_clearance(
+        &self,
+        path: *const CSixAxis,
+        len: usize,
+    ) -> f64 {
+        let path = &*std::ptr::slice_from_raw_parts(path, len);
+        self.safe_path_min_clearance(path)
     }
 
-    /// Find a path for the retract from latest state to the given one.
-    pub fn find_retract_path(&self, id: Id, state: &CLinearState) -> CPathResultLinearState {
+    /// Find a path for the retract from the latest state to the given one.
+    ///
+    /// Results in [PathResultState::InvalidId] if the id is not valid.
+    #[no_mangle]
+    pub extern "C" fn microscope_find_retract_path(
+        &self,
+        id: Id,
+        state: &CLinearState,
+    ) -> CPathResultLinearState {
         self.safe_find_retract_path(id, state)
     }
 
-    /// Present the full view of static parts.
-    pub fn present_static_full(&self) -> TriangleBufferVec {
-        self.safe_present_static_full()
+    /// Suggest a step size for stage resolvers based on the scale of the geometry.
+    ///
+    /// Positional step is derived from the smallest static feature,
+    /// rotational step from the extent of the stage.
+    /// Both are kept within fixed bounds, the largest ones are used for an empty scene.
+    #[no_mangle]
+    pub extern "C" fn microscope_suggest_step_size(&self) -> CSixAxis {
+        self.safe_suggest_step_size()
+    }
+
+    /// Present the full view of static parts.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_full(&self) -> TriangleBufferVec {
+        self.safe_present_static_full()
+    }
+
+    /// Number of chunks of `chunk_size` triangles in the full view of static parts.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_full_chunk_count(
+        &self,
+        chunk_size: usize,
+    ) -> usize {
+        self.safe_present_static_full_chunk_count(chunk_size)
+    }
+
+    /// Write the vertices of one chunk of the full view of static parts into `out_ptr`,
+    /// returning the number of written triangles.
+    ///
+    /// Chunks concatenate to the items of [Microscope::microscope_present_static_full] in order.
+    ///
+    /// # Safety
+    /// The `out_ptr` must be a pointer to an array of `CVector3` values with a length of at least `3 * chunk_size`.
+    #[no_mangle]
+    pub unsafe extern "C" fn microscope_present_static_full_chunk(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+        out_ptr: *mut CVector3,
+    ) -> usize {
+        let chunk = self.safe_present_static_full_chunk(chunk_index, chunk_size);
+        std::ptr::copy_nonoverlapping(chunk.as_ptr(), out_ptr, chunk.len());
+        chunk.len() / 3
+    }
+
+    /// Present only the bounding boxes of static parts as consecutive `min`, `max` pairs.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_boxes(&self) -> BoundingBoxBuffer {
+        bounding_boxes_to_buffer(&self.safe_present_static_boxes())
+    }
+
+    /// Present the full view of static parts rigidly transformed around the pivot.
+    ///
+    /// Does not change the collision state of the microscope.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_full_rotated(
+        &self,
+        rotation: &CSixAxis,
+        pivot: &CVector3,
+    ) -> TriangleBufferVec {
+        self.safe_present_static_full_rotated(rotation, pivot)
+    }
+
+    /// Present the full view of static parts as interleaved positions and normals.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_full_with_normals(&self) -> InterleavedBufferVec {
+        self.safe_present_static_full_with_normals()
     }
 
     /// Present the less obstructive view of static parts.
-    pub fn present_static_less_obstructive(&self) -> TriangleBufferVec {
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_less_obstructive(&self) -> TriangleBufferVec {
         self.safe_present_static_less_obstructive()
     }
 
     /// Present the non-obstructive view of static parts.
-    pub fn present_static_non_obstructive(&self) -> TriangleBufferVec {
+    #[no_mangle]
+    pub extern "C" fn microscope_present_static_non_obstructive(&self) -> TriangleBufferVec {
         self.safe_present_static_non_obstructive()
     }
 
     /// Present the stage at the current state.
-    pub fn present_stage(&self) -> TriangleBufferVec {
+    #[no_mangle]
+    pub extern "C" fn microscope_present_stage(&self) -> TriangleBufferVec {
         self.safe_present_stage()
     }
 
     /// Present the stage at the given state.
-    pub fn present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
+    #[no_mangle]
+    pub extern "C" fn microscope_present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
         self.safe_present_stage_at(state)
     }
 
-    /// Present the retract at the current state.
-    pub fn present_retract(&self, id: Id) -> TriangleBufferVec {
+    /// Write the axis aligned bounds of the stage at the given state into `min` and `max`.
+    ///
+    /// Bounds transformed parts instead of every triangle, so may be larger than tight bounds.
+    #[no_mangle]
+    pub extern "C" fn microscope_stage_bounds_at(
+        &self,
+        state: &CSixAxis,
+        min: &mut CVector3,
+        max: &mut CVector3,
+    ) {
+        let (lower, upper) = self.safe_stage_bounds_at(state);
+        *min = CVector3::from(&lower);
+        *max = CVector3::from(&upper);
+    }
+
+    /// Write the axis aligned bounds of the stage and all retracts at their current states
+    /// into `min` and `max`.
+    ///
+    /// Bounds transformed parts of the stage, so may be larger than tight bounds.
+    #[no_mangle]
+    pub extern "C" fn microscope_dynamic_bounds(&self, min: &mut CVector3, max: &mut CVector3) {
+        let (lower, upper) = self.safe_dynamic_bounds();
+        *min = CVector3::from(&lower);
+        *max = CVector3::from(&upper);
+    }
+
+    /// Write the principal axes of the stage geometry at the current state
+    /// into `first`, `second` and `third`, ordered by decreasing extent of the stage along them.
+    ///
+    /// The axes are unit vectors forming a right-handed frame, their signs are arbitrary.
+    #[no_mangle]
+    pub extern "C" fn microscope_stage_principal_axes(
+        &self,
+        first: &mut CVector3,
+        second: &mut CVector3,
+        third: &mut CVector3,
+    ) {
+        let [x, y, z] = self.safe_stage_principal_axes();
+        *first = CVector3::from(&x);
+        *second = CVector3::from(&y);
+        *third = CVector3::from(&z);
+    }
+
+    /// Present the retract at the current state, empty if the id is not valid.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_retract(&self, id: Id) -> TriangleBufferVec {
         self.safe_present_retract(id)
     }
 
-    /// Present the retract at the given state.
-    pub fn present_retract_at(&self, id: Id, state: &CLinearState) -> TriangleBufferVec {
+    /// Present the retract at the given state, empty if the id is not valid.
+    #[no_mangle]
+    pub extern "C" fn microscope_present_retract_at(
+        &self,
+        id: Id,
+        state: &CLinearState,
+    ) -> TriangleBufferVec {
         self.safe_present_retract_at(id, state)
     }
+
+    /// # Safety
+    /// Takes ownership of the microscope and drops it.
+    #[no_mangle]
+    pub extern "C" fn microscope_drop(self) {
+        //  dropped after leaving scope
+    }
 }
 
+#[cfg(not(feature = "ffi"))]
 impl Microscope {
-    fn build(config: &Configuration) -> Self {
-        let chamber = config.chamber().build();
-        let stage = config.stage().build();
-        let stage_resolver = config.stage_resolver().build();
-        let stage_state = CSixAxis {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-            rx: 0.0,
-            ry: 0.0,
-            rz: 0.0,
-        };
-        let equipment = config
-            .equipment()
-            .iter()
-            .map(|e| e.build())
-            .collect::<Vec<ConcreteEquipment>>();
-        let retracts = config
-            .retracts()
-            .iter()
-            .map(|(id, (r, rr))| (*id, (r.build(), rr.build(), CLinearState { t: 0.0 })))
-            .collect::<HashMap<Id, (ConcreteRetract, ConcreteRetractResolver, CLinearState)>>();
-        Self {
-            chamber,
-            stage,
-            stage_resolver,
-            stage_state,
-            equipment: BoxSliceEquipment::from_inner(equipment.into_boxed_slice()),
-            retracts: HashMapRetracts::from_inner(retracts),
-        }
+    /// Build microscope from configuration.
+    pub fn from_config(config: &Configuration) -> Self {
+        Self::build(config)
     }
 
-    fn is_valid_retract(&self, id: Id) -> bool {
-        self.retracts.inner().get(&id).is_some()
+    /// Build microscope from configuration and apply the recorded events in order,
+    /// see [Microscope::start_recording].
+    pub fn replay(config: &Configuration, events: &[MicroscopeEvent]) -> Self {
+        Self::safe_replay(config, events)
     }
 
-    fn safe_clear_sample(&mut self) {
-        if let Some(h) = self.stage.get_mut().active_holder_mut() {
-            h.swap_sample(None)
-        }
+    /// Build a new microscope from `config` carrying over the states of this one still valid in it.
+    ///
+    /// The holder with its sample is moved over, the stage state is kept when the stage is the same,
+    /// and retract states are kept for retracts with the same id and configuration.
+    /// Other states start at their home, the same as after [Microscope::from_config].
+    ///
+    /// The resolvers are updated with the states against the new geometry,
+    /// returned along the microscope, see [Microscope::update_resolvers].
+    pub fn with_config(&self, config: &Configuration) -> (Self, Result<(), StateUpdateError>) {
+        self.safe_with_config(config)
     }
 
-    fn safe_update_holder(&mut self, holder: &HolderConfig) {
-        let holder = holder.build();
-        self.stage.get_mut().swap_holder(Some(holder));
+    /// Build a new microscope from the configuration of this one with the edits of `modify` applied,
+    /// carrying over the states still valid, see [Microscope::with_config].
+    pub fn with_modified_config<F: FnOnce(&mut ConfigurationBuilder)>(
+        &self,
+        modify: F,
+    ) -> Result<(Microscope, Result<(), StateUpdateError>), ConfigBuilderResult> {
+        let mut builder = ConfigurationBuilder::from_config(self.configuration.inner());
+        modify(&mut builder);
+        Ok(self.safe_with_config(&builder.build()?))
     }
 
-    fn safe_remove_holder(&mut self) {
-        self.stage.get_mut().swap_holder(None);
+    /// Start recording every mutation of the microscope, discarding any previous recording.
+    pub fn start_recording(&mut self) {
+        self.safe_start_recording();
     }
 
-    fn safe_update_sample_height_map(
+    /// Stop recording and take the recorded events, empty if not recording.
+    pub fn stop_recording(&mut self) -> MicroscopeEventLog {
+        self.safe_stop_recording()
+    }
+
+    /// Clear the sample from the active holder.
+    pub fn clear_sample(&mut self) {
+        self.safe_clear_sample();
+    }
+
+    /// Update the active stage holder.
+    pub fn update_holder(&mut self, holder: &HolderConfig) {
+        self.safe_update_holder(holder);
+    }
+
+    /// Remove the active stage holder.
+    pub fn remove_holder(&mut self) {
+        self.safe_remove_holder();
+    }
+
+    /// Rotate the samples relative to the active holder, given as euler angles in radians.
+    ///
+    /// The rotation is around the point the samples are attached at,
+    /// it is reset when the holder is replaced.
+    pub fn set_sample_rotation(&mut self, rotation: &CVector3) {
+        self.safe_set_sample_rotation(rotation);
+    }
+
+    /// Mount new equipment, identified by the next index after the existing equipment.
+    ///
+    /// Resolvers are not updated, call [Microscope::update_resolvers] afterwards.
+    pub fn add_equipment(&mut self, equipment: &EquipmentConfig) {
+        self.safe_add_equipment(equipment)
+    }
+
+    /// Unmount the equipment at the `index`, shifting the indices of the following equipment.
+    ///
+    /// Stage contacts of the equipment are dropped, contacts of the following equipment follow it.
+    /// Returns `false` when there is no equipment at the `index`.
+    /// Resolvers are not updated, call [Microscope::update_resolvers] afterwards.
+    pub fn remove_equipment(&mut self, index: usize) -> bool {
+        self.safe_remove_equipment(index)
+    }
+
+    /// Update the sample height map.
+    pub fn update_sample_height_map(
         &mut self,
         height_map: &[f64],
         size_x: usize,
@@ -376,219 +1068,3465 @@ impl Microscope {
         real_x: f64,
         real_y: f64,
     ) {
-        let real_size = Vector2::new(real_x, real_y);
-        let model = height_map_to_sample_model(height_map, size_x, size_y, &real_size, 0.0);
-        let sample = if model.is_empty() {
-            None
-        } else {
-            Some(PrimaryCollider::build(&model))
-        };
-        if let Some(h) = self.stage.get_mut().active_holder_mut() {
-            h.swap_sample(sample)
-        }
-    }
-
-    fn movable_stage(&self) -> Arc<dyn Movable<SixAxis>> {
-        self.stage.get_ref().as_movable()
+        self.safe_update_sample_height_map(height_map, size_x, size_y, real_x, real_y);
     }
 
-    fn movable_retract(&self, id: Id) -> Option<Arc<dyn Movable<LinearState>>> {
-        self.retracts
-            .inner()
-            .get(&id)
-            .map(|(r, _, _)| r.get_ref().as_movable())
+    /// Update the stage state.
+    pub fn update_stage_state(&mut self, state: &CSixAxis) {
+        self.safe_update_stage_state(state);
     }
 
-    fn add_equipment(
-        &self,
-        mut group: ColliderGroup<PrimaryCollider>,
-    ) -> ColliderGroup<PrimaryCollider> {
-        for equipment in self.equipment.inner() {
-            group.extend(equipment.get_ref().collider())
-        }
-        group
+    /// Get the current stage state.
+    pub fn stage_state(&self) -> CSixAxis {
+        self.safe_stage_state()
     }
 
-    fn always_immovable(&self) -> ColliderGroup<PrimaryCollider> {
-        let immovable = self.chamber.get_ref().full();
-        self.add_equipment(immovable)
+    /// Get the current state of the retract, `None` if the id is not valid.
+    pub fn retract_state(&self, id: Id) -> Option<CLinearState> {
+        self.safe_retract_state(id)
     }
 
-    fn immovable_without_stage(&self) -> Immovable {
-        let mut immovable = self.always_immovable();
-        for (r, _, s) in self.retracts.inner().values() {
-            immovable.extend(r.get_ref().move_to(&s.into()));
-        }
-        immovable
+    /// Take a snapshot of the current stage and retract states.
+    pub fn snapshot(&self) -> MicroscopeState {
+        self.safe_snapshot()
     }
 
-    /// Stage is considered the only relevant part for retracts
-    fn immovable_stage(&self) -> Immovable {
-        let immovable = self
-            .stage
-            .get_ref()
-            .move_to(&SixAxis::from(&self.stage_state));
-        immovable
+    /// Round the stage state to the nearest point of the stage resolver grid,
+    /// which is anchored at the current stage state.
+    ///
+    /// Axes the resolver does not search on a grid are left unchanged.
+    pub fn snap_stage_to_grid(&self, state: &CSixAxis) -> CSixAxis {
+        self.safe_snap_stage_to_grid(state)
     }
 
-    fn update_stage_resolver_state(&mut self, state: &CSixAxis) -> Result<(), StateUpdateError> {
-        let movable = self.movable_stage();
-        let immovable = self.immovable_without_stage();
-
-        self.stage_resolver.get_mut().update_state(
-            &SixAxis::from(state),
-            movable.as_ref(),
-            &immovable,
-        )?;
-        Ok(())
+    /// Round the retract state to the nearest point of the retract resolver grid,
+    /// which is anchored at the current retract state, `None` if the id is not valid.
+    pub fn snap_retract_to_grid(&self, id: Id, state: &CLinearState) -> Option<CLinearState> {
+        self.safe_snap_retract_to_grid(id, state)
     }
 
-    fn update_retract_resolver_state(
+    /// Update the retract state.
+    pub fn update_retract_state(
         &mut self,
         id: Id,
         state: &CLinearState,
     ) -> Result<(), StateUpdateError> {
-        let movable = self.movable_retract(id).unwrap();
-        let immovable = self.immovable_stage();
+        self.safe_update_retract_state(id, state)
+    }
 
-        self.retracts
-            .inner_mut()
-            .get_mut(&id)
-            .unwrap()
-            .1
-            .get_mut()
-            .update_state(&LinearState::from(state), movable.as_ref(), &immovable)?;
-        Ok(())
+    /// Update the resolvers for stage and all retracts.
+    pub fn update_resolvers(&mut self) -> Result<(), StateUpdateError> {
+        self.safe_update_resolvers()
     }
 
-    fn safe_update_stage_state(&mut self, state: &CSixAxis) {
-        self.stage_state = *state;
+    /// Reset the stage and all retracts to their configured home states
+    /// and update the resolvers.
+    pub fn home(&mut self) -> Result<(), StateUpdateError> {
+        self.safe_home()
     }
 
-    fn safe_update_retract_state(
-        &mut self,
-        id: Id,
-        state: &CLinearState,
-    ) -> Result<(), StateUpdateError> {
-        if !self.is_valid_retract(id) {
-            return Err(StateUpdateError::InvalidId);
-        }
+    /// Check that the chamber, equipment, the stage at home pose
+    /// and the retracts at their home states do not collide.
+    ///
+    /// Equipment and retracts are mounted into the chamber walls,
+    /// so they are checked only against the non-obstructive parts of the chamber.
+    ///
+    /// Returns the first colliding pair otherwise.
+    pub fn validate_static_configuration(&self) -> Result<(), StaticCollision> {
+        self.safe_validate_static_configuration()
+    }
 
-        self.retracts.inner_mut().get_mut(&id).unwrap().2 = *state;
-        Ok(())
+    /// Assemble the static parts and validate the static configuration ahead of the first query.
+    ///
+    /// Optional but recommended, otherwise the assembly happens during the first query.
+    pub fn prepare(&mut self) -> Result<(), StaticCollision> {
+        self.safe_prepare()
     }
 
-    fn safe_update_resolvers(&mut self) -> Result<(), StateUpdateError> {
-        self.update_stage_resolver_state(&self.stage_state.clone())?;
+    /// Find a path for the stage from the latest state to the given one.
+    pub fn find_stage_path(&self, state: &CSixAxis) -> CPathResultSixAxis {
+        self.safe_find_stage_path(state)
+    }
 
-        let retracts_id_state = self
-            .retracts
-            .inner()
-            .iter()
-            .map(|(id, (_, _, s))| (*id, *s))
-            .collect::<Vec<_>>();
-        for (id, state) in retracts_id_state {
-            self.update_retract_resolver_state(id, &state)?;
-        }
+    /// Find a path for the stage from the latest state to the given one.
+    ///
+    /// Returns a cancelled result once the token is cancelled.
+    pub fn find_stage_path_cancellable(
+        &self,
+        state: &CSixAxis,
+        cancel: &CCancelToken,
+    ) -> CPathResultSixAxis {
+        self.safe_find_stage_path_cancellable(state, cancel.inner())
+    }
 
-        Ok(())
+    /// Find a path for the stage from the latest state to the given one.
+    ///
+    /// Reports the estimated completion to `progress` as a non-decreasing fraction
+    /// in `[0, 1]`, ending with `1` when a path is found.
+    /// Returns a cancelled result once the token is cancelled.
+    pub fn find_stage_path_with_progress(
+        &self,
+        state: &CSixAxis,
+        cancel: &CCancelToken,
+        mut progress: impl FnMut(f32),
+    ) -> CPathResultSixAxis {
+        self.safe_find_stage_path_with_progress(state, cancel.inner(), &mut progress)
     }
 
-    fn safe_find_stage_path(&self, state: &CSixAxis) -> CPathResultSixAxis {
-        let movable = self.movable_stage();
-        let immovable = self.immovable_without_stage();
-        let from = self.stage_state;
-        let result = self.stage_resolver.get_ref().resolve_path(
-            &SixAxis::from(&from),
+    /// Find a path for the stage from `current` to the `goal`,
+    /// reusing the still collision-free tail of the `previous` path.
+    /// The tail is checked with the step of the configured stage resolver.
+    ///
+    /// Returns an invalid start when `current` collides.
+    /// Returns a cancelled result once the token is cancelled.
+    pub fn replan_from(
+        &self,
+        current: &CSixAxis,
+        goal: &CSixAxis,
+        previous: &[SixAxis],
+        cancel: &CCancelToken,
+    ) -> CPathResultSixAxis {
+        self.safe_replan_from(current, goal, previous, cancel.inner())
+    }
+
+    /// Farthest stage state on the straight line from `from` towards `goal` free of collisions.
+    ///
+    /// Results in `from` when it touches an obstacle.
+    /// Returns `None` when `from` already collides.
+    pub fn advance_toward(&self, from: &CSixAxis, goal: &CSixAxis) -> Option<CSixAxis> {
+        self.safe_advance_toward(from, goal)
+    }
+
+    /// Find the first of the `candidates` directions in which the stage can move straight
+    /// into the `target`, starting `distance` away from it.
+    ///
+    /// Candidates are directions of the movement, the start is `target - candidate * distance`
+    /// with the rotation of the target. Zero and non-finite candidates are skipped.
+    /// Approaches are checked at the suggested step size, see [Microscope::suggest_step_size].
+    pub fn approach_direction(
+        &self,
+        target: &CSixAxis,
+        candidates: &[Vector3],
+        distance: f64,
+    ) -> Option<Vector3> {
+        self.safe_approach_direction(target, candidates, distance)
+    }
+
+    /// Find the collision-free stage state closest to the `desired` one,
+    /// differing in each axis by at most the matching component of `search_radius`.
+    ///
+    /// Distance is the [SixAxis::time_to] with the radius as the speed,
+    /// axes with zero radius are kept.
+    /// The search gives up after a bounded number of collision checks,
+    /// so with many searched axes only the poses closest to `desired` are tried.
+    pub fn nearest_valid_pose(
+        &self,
+        desired: &CSixAxis,
+        search_radius: &CSixAxis,
+    ) -> Option<CSixAxis> {
+        self.safe_nearest_valid_pose(desired, search_radius)
+    }
+
+    /// Fraction of collision-free stage states on the grid spanning `bounds.0` to `bounds.1`, both included,
+    /// with the spacing of `grid` in each axis.
+    ///
+    /// Axes with zero or non-finite spacing, or an empty range, are sampled only at `bounds.0`,
+    /// so zero rotational spacing samples positions only.
+    /// Returns `None` when the grid has more than 100 thousand states.
+    pub fn free_fraction(&self, grid: &CSixAxis, bounds: (CSixAxis, CSixAxis)) -> Option<f64> {
+        self.safe_free_fraction(grid, (&bounds.0, &bounds.1))
+    }
+
+    /// Check which of the stage `candidates` are collision-free,
+    /// `true` at the index of each free candidate.
+    pub fn filter_valid_poses(&self, candidates: &[CSixAxis]) -> Vec<bool> {
+        self.safe_filter_valid_poses(candidates)
+    }
+
+    /// Push a stage state slightly intruding into geometry out to a nearby free state,
+    /// moving it by at most `max_distance`.
+    ///
+    /// The state is moved by its [separation vector](Microscope::separation_vector)
+    /// when it is not longer than `max_distance`.
+    pub fn nudge_to_free(&self, state: &CSixAxis, max_distance: f64) -> Option<CSixAxis> {
+        self.safe_nudge_to_free(state, max_distance)
+    }
+
+    /// Find the minimal translation of the stage at `state` that makes it collision-free,
+    /// zero if it already is.
+    ///
+    /// The penetration of the deepest colliding pair of triangles is computed by EPA
+    /// and the stage is moved out of it, repeatedly until nothing collides.
+    /// Each pair is left with a small clearance, so the translation is marginally longer.
+    pub fn separation_vector(&self, state: &CSixAxis) -> Option<Vector3> {
+        self.safe_separation_vector(state)
+    }
+
+    /// Rotate a colliding stage state about the `axis` to the nearest free orientation,
+    /// turning it by at most `max_angle` in either direction.
+    pub fn rotate_to_free(
+        &self,
+        state: &CSixAxis,
+        axis: CAxis,
+        max_angle: f64,
+    ) -> Option<CSixAxis> {
+        self.safe_rotate_to_free(state, axis, max_angle)
+    }
+
+    /// Compute the collision-free travel limits of each degree of freedom of the stage,
+    /// moving one at a time from the current stage state
+    /// by the step of the configured stage resolver.
+    ///
+    /// Returns the componentwise lowest and highest pose,
+    /// both are the current stage state if it collides.
+    pub fn reachable_extents(&self) -> (CSixAxis, CSixAxis) {
+        self.safe_reachable_extents()
+    }
+
+    /// Estimate how demanding planning the stage path between the two states is,
+    /// without running the resolver.
+    /// The straight line is sampled with the step of the configured stage resolver.
+    pub fn estimate_difficulty(&self, from: &CSixAxis, to: &CSixAxis) -> Difficulty {
+        self.safe_estimate_difficulty(from, to)
+    }
+
+    /// Smallest distance between the stage and the rest of the microscope along the whole path.
+    ///
+    /// Segments are sampled with the step of the configured stage resolver,
+    /// returns `0.0` when the path collides and infinity for an empty path.
+    pub fn path_min_clearance(&self, path: &[CSixAxis]) -> f64 {
+        self.safe_path_min_clearance(path)
+    }
+
+    /// Check every segment of a scripted stage path for collisions along the movement.
+    ///
+    /// Segments are checked at the suggested step size,
+    /// see [Microscope::suggest_step_size], so features of the microscope cannot be skipped.
+    pub fn validate_stage_path(&self, path: &[CSixAxis]) -> PathValidation {
+        self.safe_validate_stage_path(path)
+    }
+
+    /// Axis aligned bounds of the volume the stage sweeps along each path segment,
+    /// segment `i` moves from node `i` to node `i + 1`.
+    pub fn swept_volume_bounds(&self, path: &[CSixAxis]) -> Vec<AlignedBoxCollider> {
+        self.safe_swept_volume_bounds(path)
+    }
+
+    /// Check whether the stage and the retract collide while both move at the same time,
+    /// the stage from `stage_from_to.0` to `stage_from_to.1` and the retract from `retract_from_to.0` to `retract_from_to.1`.
+    ///
+    /// Both movements progress together, the parts are compared where they are at the same time,
+    /// conservatively by advancing by the clearance between them.
+    /// `None` if the retract id is not valid.
+    pub fn moving_pair_collides(
+        &self,
+        stage_from_to: (CSixAxis, CSixAxis),
+        retract_id: Id,
+        retract_from_to: (CLinearState, CLinearState),
+    ) -> Option<bool> {
+        self.safe_moving_pair_collides(
+            (&stage_from_to.0, &stage_from_to.1),
+            retract_id,
+            (&retract_from_to.0, &retract_from_to.1),
+        )
+    }
+
+    /// Order in which to visit the stage `targets` from `start` to keep the total travel time low,
+    /// as indices into `targets`.
+    ///
+    /// Travel time is the [SixAxis::time_to] with the `speed` of each axis.
+    /// Nearest neighbor heuristic ignoring obstacles, the order is not guaranteed to be optimal.
+    pub fn order_targets(
+        &self,
+        start: &CSixAxis,
+        targets: &[CSixAxis],
+        speed: &CSixAxis,
+    ) -> Vec<usize> {
+        self.safe_order_targets(start, targets, speed)
+    }
+
+    /// Find a path for the retract from latest state to the given one.
+    ///
+    /// Results in [PathResultState::InvalidId] if the id is not valid.
+    pub fn find_retract_path(&self, id: Id, state: &CLinearState) -> CPathResultLinearState {
+        self.safe_find_retract_path(id, state)
+    }
+
+    /// Suggest a step size for stage resolvers based on the scale of the geometry.
+    ///
+    /// Positional step is derived from the smallest static feature,
+    /// rotational step from the extent of the stage.
+    /// Both are kept within fixed bounds, the largest ones are used for an empty scene.
+    pub fn suggest_step_size(&self) -> CSixAxis {
+        self.safe_suggest_step_size()
+    }
+
+    /// Present the full view of static parts.
+    pub fn present_static_full(&self) -> TriangleBufferVec {
+        self.safe_present_static_full()
+    }
+
+    /// Number of chunks of `chunk_size` triangles in the full view of static parts.
+    pub fn present_static_full_chunk_count(&self, chunk_size: usize) -> usize {
+        self.safe_present_static_full_chunk_count(chunk_size)
+    }
+
+    /// Present the vertices of one chunk of the full view of static parts.
+    ///
+    /// Chunks concatenate to the items of [Microscope::present_static_full] in order.
+    pub fn present_static_full_chunk(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+    ) -> Vec<CVector3> {
+        self.safe_present_static_full_chunk(chunk_index, chunk_size)
+    }
+
+    /// Present only the bounding boxes of static parts.
+    pub fn present_static_boxes(&self) -> Vec<AlignedBoxCollider> {
+        self.safe_present_static_boxes()
+    }
+
+    /// Present the full view of static parts rigidly transformed around the pivot.
+    ///
+    /// Does not change the collision state of the microscope.
+    pub fn present_static_full_rotated(
+        &self,
+        rotation: &CSixAxis,
+        pivot: &CVector3,
+    ) -> TriangleBufferVec {
+        self.safe_present_static_full_rotated(rotation, pivot)
+    }
+
+    /// Present the full view of static parts as interleaved positions and normals.
+    pub fn present_static_full_with_normals(&self) -> InterleavedBufferVec {
+        self.safe_present_static_full_with_normals()
+    }
+
+    /// Present the less obstructive view of static parts.
+    pub fn present_static_less_obstructive(&self) -> TriangleBufferVec {
+        self.safe_present_static_less_obstructive()
+    }
+
+    /// Present the non-obstructive view of static parts.
+    pub fn present_static_non_obstructive(&self) -> TriangleBufferVec {
+        self.safe_present_static_non_obstructive()
+    }
+
+    /// Present the stage at the current state.
+    pub fn present_stage(&self) -> TriangleBufferVec {
+        self.safe_present_stage()
+    }
+
+    /// Present the stage at the given state.
+    pub fn present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
+        self.safe_present_stage_at(state)
+    }
+
+    /// Get the axis aligned bounds of the stage at the given state as `(min, max)`.
+    ///
+    /// Bounds transformed parts instead of every triangle, so may be larger than tight bounds.
+    pub fn stage_bounds_at(&self, state: &CSixAxis) -> (Vector3, Vector3) {
+        self.safe_stage_bounds_at(state)
+    }
+
+    /// Get the axis aligned bounds of the stage and all retracts at their current states
+    /// as `(min, max)`.
+    ///
+    /// Bounds transformed parts of the stage, so may be larger than tight bounds.
+    pub fn dynamic_bounds(&self) -> (Vector3, Vector3) {
+        self.safe_dynamic_bounds()
+    }
+
+    /// Get the principal axes of the stage geometry at the current state,
+    /// ordered by decreasing extent of the stage along them.
+    ///
+    /// The axes are unit vectors forming a right-handed frame, their signs are arbitrary.
+    pub fn stage_principal_axes(&self) -> [Vector3; 3] {
+        self.safe_stage_principal_axes()
+    }
+
+    /// Present the retract at the current state, empty if the id is not valid.
+    pub fn present_retract(&self, id: Id) -> TriangleBufferVec {
+        self.safe_present_retract(id)
+    }
+
+    /// Present the retract at the given state, empty if the id is not valid.
+    pub fn present_retract_at(&self, id: Id, state: &CLinearState) -> TriangleBufferVec {
+        self.safe_present_retract_at(id, state)
+    }
+}
+
+impl Microscope {
+    fn build(config: &Configuration) -> Self {
+        let chamber = config.chamber().build();
+        let stage = config.stage().build();
+        let stage_resolver = config.stage_resolver().build();
+        let stage_home = *config.stage_home();
+        if let Some(step) = config.stage_resolver().step_size() {
+            let home = SixAxis::from(&stage_home);
+            let radius = rotation_radius(
+                &stage.get_ref().as_movable().move_to(&home),
+                stage.get_ref().rotation_pivot(&home),
+            );
+            validate_rotation_step(&SixAxis::from(&step), radius);
+        }
+        let equipment = config
+            .equipment()
+            .iter()
+            .map(|(e, tier)| (e.build(), *tier))
+            .collect::<Box<[(ConcreteEquipment, ObstructionTier)]>>();
+        let retract_homes = config
+            .retracts()
+            .iter()
+            .map(|(id, _)| (*id, config.retract_home(*id)))
+            .collect::<HashMap<Id, CLinearState>>();
+        let retracts = config
+            .retracts()
+            .iter()
+            .map(|(id, (r, rr))| (*id, (r.build(), rr.build(), retract_homes[id])))
+            .collect::<HashMap<Id, (ConcreteRetract, ConcreteRetractResolver, CLinearState)>>();
+        let mut microscope = Self {
+            chamber,
+            stage,
+            stage_resolver,
+            stage_state: stage_home,
+            stage_home,
+            equipment: BoxSliceEquipment::from_inner(equipment),
+            retracts: HashMapRetracts::from_inner(retracts),
+            retract_homes: HashMapRetractHomes::from_inner(retract_homes),
+            stage_immovable: CachedImmovable::from_inner(Mutex::new(None)),
+            static_immovable: CachedImmovable::from_inner(Mutex::new(None)),
+            frozen_equipment: FrozenEquipment::from_inner(None),
+            recording: EventRecording::from_inner(None),
+            configuration: BuiltConfiguration::from_inner(config.clone()),
+        };
+        microscope.freeze_equipment();
+        microscope
+    }
+
+    /// Equipment never moves, merge it into a single tree once instead of on every query.
+    ///
+    /// Equipment the stage may touch is kept apart, see [Microscope::stage_whitelist].
+    fn freeze_equipment(&mut self) {
+        let touched = self
+            .configuration
+            .inner()
+            .stage_contacts()
+            .iter()
+            .any(|(_, obstacle)| matches!(obstacle, ObstaclePart::Equipment(_)));
+        let equipment = self.extend_with_equipment(ColliderGroup(Vec::new()));
+        let frozen = (!touched && !equipment.0.is_empty()).then(|| equipment.into_bvh());
+        *self.frozen_equipment.inner_mut() = frozen;
+    }
+
+    fn is_valid_retract(&self, id: Id) -> bool {
+        self.retracts.inner().get(&id).is_some()
+    }
+
+    fn safe_replay(config: &Configuration, events: &[MicroscopeEvent]) -> Self {
+        let mut microscope = Self::build(config);
+        for event in events {
+            //  failures are part of the recording, they repeat the same way
+            match event {
+                MicroscopeEvent::ClearSample => microscope.safe_clear_sample(),
+                MicroscopeEvent::UpdateHolder(holder) => microscope.safe_update_holder(holder),
+                MicroscopeEvent::RemoveHolder => microscope.safe_remove_holder(),
+                MicroscopeEvent::SetSampleRotation(rotation) => {
+                    microscope.safe_set_sample_rotation(rotation)
+                }
+                MicroscopeEvent::AddEquipment(equipment) => {
+                    microscope.safe_add_equipment(equipment)
+                }
+                MicroscopeEvent::RemoveEquipment(index) => {
+                    let _ = microscope.safe_remove_equipment(*index);
+                }
+                MicroscopeEvent::UpdateSampleHeightMap {
+                    height_map,
+                    size_x,
+                    size_y,
+                    real_x,
+                    real_y,
+                } => microscope
+                    .safe_update_sample_height_map(height_map, *size_x, *size_y, *real_x, *real_y),
+                MicroscopeEvent::UpdateStageState(state) => {
+                    microscope.safe_update_stage_state(state)
+                }
+                MicroscopeEvent::UpdateRetractState(id, state) => {
+                    let _ = microscope.safe_update_retract_state(*id, state);
+                }
+                MicroscopeEvent::UpdateResolvers => {
+                    let _ = microscope.safe_update_resolvers();
+                }
+                MicroscopeEvent::Home => {
+                    let _ = microscope.safe_home();
+                }
+            }
+        }
+
+        microscope
+    }
+
+    fn safe_with_config(&self, config: &Configuration) -> (Self, Result<(), StateUpdateError>) {
+        let mut microscope = Self::build(config);
+        let previous = self.configuration.inner();
+
+        let holder = self.stage.get_ref().active_holder().map(|h| h.cloned());
+        microscope.stage.get_mut().swap_holder(holder);
+        if config.stage() == previous.stage() {
+            microscope.stage_state = self.stage_state;
+        }
+
+        for (id, (retract, _)) in config.retracts() {
+            let unchanged = previous
+                .retracts()
+                .iter()
+                .any(|(i, (r, _))| i == id && r == retract);
+            if let (true, Some((_, _, state))) = (unchanged, self.retracts.inner().get(id)) {
+                microscope.retracts.inner_mut().get_mut(id).unwrap().2 = *state;
+            }
+        }
+
+        let update = microscope.update_resolver_states();
+        (microscope, update)
+    }
+
+    fn safe_start_recording(&mut self) {
+        *self.recording.inner_mut() = Some(Vec::new());
+    }
+
+    fn safe_stop_recording(&mut self) -> MicroscopeEventLog {
+        MicroscopeEventLog::new(self.recording.inner_mut().take().unwrap_or_default())
+    }
+
+    /// Records the event when recording, `event` is only called then.
+    fn record(&mut self, event: impl FnOnce() -> MicroscopeEvent) {
+        if let Some(events) = self.recording.inner_mut() {
+            events.push(event());
+        }
+    }
+
+    fn safe_clear_sample(&mut self) {
+        self.record(|| MicroscopeEvent::ClearSample);
+        self.invalidate_immovable_stage();
+        if let Some(h) = self.stage.get_mut().active_holder_mut() {
+            h.swap_sample(None)
+        }
+    }
+
+    fn safe_update_holder(&mut self, holder: &HolderConfig) {
+        self.record(|| MicroscopeEvent::UpdateHolder(holder.clone()));
+        self.invalidate_immovable_stage();
+        let holder = holder.build();
+        self.stage.get_mut().swap_holder(Some(holder));
+    }
+
+    fn safe_remove_holder(&mut self) {
+        self.record(|| MicroscopeEvent::RemoveHolder);
+        self.invalidate_immovable_stage();
+        self.stage.get_mut().swap_holder(None);
+    }
+
+    fn safe_set_sample_rotation(&mut self, rotation: &CVector3) {
+        self.record(|| MicroscopeEvent::SetSampleRotation(*rotation));
+        let rotation = Vector3::from(rotation).scrub(&Vector3::ZERO);
+        self.invalidate_immovable_stage();
+        if let Some(h) = self.stage.get_mut().active_holder_mut() {
+            h.set_sample_rotation(Quaternion::from_euler(&rotation))
+        }
+    }
+
+    fn safe_add_equipment(&mut self, equipment: &EquipmentConfig) {
+        self.record(|| MicroscopeEvent::AddEquipment(equipment.clone()));
+        self.configuration
+            .inner_mut()
+            .push_equipment(equipment.clone());
+        let mut mounted = std::mem::take(self.equipment.inner_mut()).into_vec();
+        mounted.push((equipment.build(), ObstructionTier::default()));
+        *self.equipment.inner_mut() = mounted.into_boxed_slice();
+
+        self.invalidate_equipment();
+    }
+
+    fn safe_remove_equipment(&mut self, index: usize) -> bool {
+        self.record(|| MicroscopeEvent::RemoveEquipment(index));
+        if index >= self.equipment.inner().len() {
+            return false;
+        }
+        self.configuration.inner_mut().remove_equipment(index);
+
+        let mut mounted = std::mem::take(self.equipment.inner_mut()).into_vec();
+        mounted.remove(index);
+        *self.equipment.inner_mut() = mounted.into_boxed_slice();
+
+        self.invalidate_equipment();
+        true
+    }
+
+    /// Rebuild the frozen equipment and drop the static assembly containing the old equipment.
+    fn invalidate_equipment(&mut self) {
+        self.freeze_equipment();
+        *self.static_immovable.inner_mut().get_mut().unwrap() = None;
+    }
+
+    fn safe_update_sample_height_map(
+        &mut self,
+        height_map: &[f64],
+        size_x: usize,
+        size_y: usize,
+        real_x: f64,
+        real_y: f64,
+    ) {
+        self.record(|| MicroscopeEvent::UpdateSampleHeightMap {
+            height_map: height_map.to_vec(),
+            size_x,
+            size_y,
+            real_x,
+            real_y,
+        });
+        let real_size =
+            Vector3::new(real_x.map_nan(0.0), real_y.map_nan(0.0), 0.0).scrub(&Vector3::ZERO);
+        let real_size = Vector2::new(real_size.x(), real_size.y());
+        let height_map = height_map
+            .iter()
+            .map(|h| if h.is_finite() { *h } else { 0.0 })
+            .collect::<Vec<_>>();
+        let model = if real_size.x() > 0.0 && real_size.y() > 0.0 {
+            height_map_to_sample_model(&height_map, size_x, size_y, &real_size, 0.0)
+        } else {
+            Vec::new()
+        };
+        let sample = if model.is_empty() {
+            None
+        } else {
+            Some(PrimaryCollider::build(&model))
+        };
+        self.invalidate_immovable_stage();
+        if let Some(h) = self.stage.get_mut().active_holder_mut() {
+            h.swap_sample(sample)
+        }
+    }
+
+    fn movable_stage(&self) -> Arc<dyn Movable<SixAxis>> {
+        self.stage.get_ref().as_movable()
+    }
+
+    fn movable_retract(&self, id: Id) -> Option<Arc<dyn Movable<LinearState>>> {
+        self.retracts
+            .inner()
+            .get(&id)
+            .map(|(r, _, _)| r.get_ref().as_movable())
+    }
+
+    fn extend_with_equipment(
+        &self,
+        mut group: ColliderGroup<PrimaryCollider>,
+    ) -> ColliderGroup<PrimaryCollider> {
+        for (equipment, _) in self.equipment.inner() {
+            group.extend(equipment.get_ref().collider())
+        }
+        group
+    }
+
+    /// Adds only the equipment presented in the given `tier`.
+    fn add_visible_equipment(
+        &self,
+        mut group: ColliderGroup<PrimaryCollider>,
+        tier: ObstructionTier,
+    ) -> ColliderGroup<PrimaryCollider> {
+        for (equipment, visible_up_to) in self.equipment.inner() {
+            if tier <= *visible_up_to {
+                group.extend(equipment.get_ref().collider())
+            }
+        }
+        group
+    }
+
+    fn always_immovable(&self) -> ColliderGroup<PrimaryCollider> {
+        let mut immovable = self.chamber.get_ref().full();
+        match self.frozen_equipment.inner() {
+            Some(frozen) => {
+                immovable.0.push(frozen.clone());
+                immovable
+            }
+            None => self.extend_with_equipment(immovable),
+        }
+    }
+
+    /// Chamber with the equipment, assembled once as neither of them ever moves.
+    fn static_immovable(&self) -> Arc<Immovable> {
+        let mut cache = self.static_immovable.inner().lock().unwrap();
+        cache
+            .get_or_insert_with(|| Arc::new(self.always_immovable()))
+            .clone()
+    }
+
+    fn immovable_without_stage(&self) -> Immovable {
+        let mut immovable = ColliderGroup(self.static_immovable().0.clone());
+        for (r, _, s) in self.retracts.inner().values() {
+            immovable.extend(r.get_ref().move_to(&s.into()));
+        }
+        immovable
+    }
+
+    /// Stage is considered the only relevant part for retracts
+    /// Stage at the current state, shared by all retract queries until the stage changes.
+    fn immovable_stage(&self) -> Arc<Immovable> {
+        let mut cache = self.stage_immovable.inner().lock().unwrap();
+        cache
+            .get_or_insert_with(|| {
+                let state = SixAxis::from(&self.stage_state);
+                Arc::new(self.stage.get_ref().move_to(&state))
+            })
+            .clone()
+    }
+
+    fn invalidate_immovable_stage(&mut self) {
+        *self.stage_immovable.inner_mut().get_mut().unwrap() = None;
+    }
+
+    /// Stage contacts of the configuration at the positions of the parts in the stage
+    /// and in [Microscope::immovable_without_stage], contacts of missing parts are skipped.
+    ///
+    /// The holder is expected at the end of the stage, its body followed by the samples.
+    fn stage_whitelist(&self) -> CollisionWhitelist {
+        let contacts = self.configuration.inner().stage_contacts();
+        if contacts.is_empty() {
+            return CollisionWhitelist::new();
+        }
+
+        let moved = self.immovable_stage().0.len();
+        let holder = self
+            .stage
+            .get_ref()
+            .active_holder()
+            .map_or(0, |h| h.collider().0.len());
+        let body = moved - holder;
+        let stage_part = |part: &StagePart| match part {
+            StagePart::Stage => 0..body,
+            StagePart::Holder => body..(body + holder.min(1)),
+            StagePart::Sample => (body + holder.min(1))..moved,
+        };
+
+        let frozen = usize::from(self.frozen_equipment.inner().is_some());
+        let equipment = if frozen == 0 {
+            self.equipment
+                .inner()
+                .iter()
+                .map(|(e, _)| e.get_ref().collider().0.len())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let chamber = self.static_immovable().0.len() - frozen - equipment.iter().sum::<usize>();
+        let mut obstacles = HashMap::from([(ObstaclePart::Chamber, 0..chamber)]);
+        let mut next = chamber;
+        for (i, len) in equipment.into_iter().enumerate() {
+            obstacles.insert(ObstaclePart::Equipment(i), next..(next + len));
+            next += len;
+        }
+        next += frozen;
+        for (id, (r, _, s)) in self.retracts.inner() {
+            let len = r.get_ref().move_to(&s.into()).0.len();
+            obstacles.insert(ObstaclePart::Retract(*id), next..(next + len));
+            next += len;
+        }
+
+        let mut whitelist = CollisionWhitelist::new();
+        for (part, obstacle) in contacts {
+            let Some(obstacle) = obstacles.get(obstacle) else {
+                continue;
+            };
+            for m in stage_part(part) {
+                for i in obstacle.clone() {
+                    whitelist = whitelist.allow(m, i);
+                }
+            }
+        }
+        whitelist
+    }
+
+    fn update_stage_resolver_state(&mut self, state: &CSixAxis) -> Result<(), StateUpdateError> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+
+        let whitelist = self.stage_whitelist();
+        self.stage_resolver.get_mut().set_whitelist(whitelist);
+        self.stage_resolver.get_mut().update_state(
             &SixAxis::from(state),
             movable.as_ref(),
             &immovable,
+        )?;
+        Ok(())
+    }
+
+    fn update_retract_resolver_state(
+        &mut self,
+        id: Id,
+        state: &CLinearState,
+    ) -> Result<(), StateUpdateError> {
+        let movable = self.movable_retract(id).unwrap();
+        let immovable = self.immovable_stage();
+
+        self.retracts
+            .inner_mut()
+            .get_mut(&id)
+            .unwrap()
+            .1
+            .get_mut()
+            .update_state(&LinearState::from(state), movable.as_ref(), &immovable)?;
+        Ok(())
+    }
+
+    fn safe_update_stage_state(&mut self, state: &CSixAxis) {
+        self.record(|| MicroscopeEvent::UpdateStageState(*state));
+        self.invalidate_immovable_stage();
+        self.stage_state = *state;
+    }
+
+    fn safe_stage_state(&self) -> CSixAxis {
+        self.stage_state
+    }
+
+    fn safe_retract_state(&self, id: Id) -> Option<CLinearState> {
+        self.retracts.inner().get(&id).map(|(_, _, state)| *state)
+    }
+
+    fn safe_snapshot(&self) -> MicroscopeState {
+        let retracts = self
+            .retracts
+            .inner()
+            .iter()
+            .map(|(id, (_, _, state))| (*id, *state))
+            .collect();
+        MicroscopeState::new(self.stage_state, retracts)
+    }
+
+    fn safe_snap_stage_to_grid(&self, state: &CSixAxis) -> CSixAxis {
+        let snapped = self
+            .stage_resolver
+            .get_ref()
+            .snap_to_grid(&SixAxis::from(state), &SixAxis::from(&self.stage_state));
+        CSixAxis::from(&snapped)
+    }
+
+    fn safe_snap_retract_to_grid(&self, id: Id, state: &CLinearState) -> Option<CLinearState> {
+        let (_, resolver, current) = self.retracts.inner().get(&id)?;
+        let snapped = resolver
+            .get_ref()
+            .snap_to_grid(&LinearState::from(state), &LinearState::from(current));
+        Some(CLinearState::from(&snapped))
+    }
+
+    fn safe_update_retract_state(
+        &mut self,
+        id: Id,
+        state: &CLinearState,
+    ) -> Result<(), StateUpdateError> {
+        self.record(|| MicroscopeEvent::UpdateRetractState(id, *state));
+        if !self.is_valid_retract(id) {
+            return Err(StateUpdateError::InvalidId);
+        }
+
+        self.retracts.inner_mut().get_mut(&id).unwrap().2 = *state;
+        Ok(())
+    }
+
+    fn safe_update_resolvers(&mut self) -> Result<(), StateUpdateError> {
+        self.record(|| MicroscopeEvent::UpdateResolvers);
+        self.update_resolver_states()
+    }
+
+    fn update_resolver_states(&mut self) -> Result<(), StateUpdateError> {
+        self.update_stage_resolver_state(&self.stage_state.clone())?;
+
+        let retracts_id_state = self
+            .retracts
+            .inner()
+            .iter()
+            .map(|(id, (_, _, s))| (*id, *s))
+            .collect::<Vec<_>>();
+        for (id, state) in retracts_id_state {
+            self.update_retract_resolver_state(id, &state)?;
+        }
+
+        Ok(())
+    }
+
+    fn safe_home(&mut self) -> Result<(), StateUpdateError> {
+        self.record(|| MicroscopeEvent::Home);
+        self.stage_state = self.stage_home;
+        self.invalidate_immovable_stage();
+        let homes = self.retract_homes.inner();
+        for (id, (_, _, state)) in self.retracts.inner_mut().iter_mut() {
+            *state = homes[id];
+        }
+
+        self.update_resolver_states()
+    }
+
+    fn safe_validate_static_configuration(&self) -> Result<(), StaticCollision> {
+        let home = SixAxis::from(&self.stage_home);
+        let mut parts = vec![(StaticPart::Stage, self.stage.get_ref().move_to(&home))];
+        for (i, (equipment, _)) in self.equipment.inner().iter().enumerate() {
+            parts.push((StaticPart::Equipment(i), equipment.get_ref().collider()));
+        }
+        let homes = self.retract_homes.inner();
+        for (id, (retract, _, _)) in self.retracts.inner() {
+            let home = LinearState::from(&homes[id]);
+            parts.push((StaticPart::Retract(*id), retract.get_ref().move_to(&home)));
+        }
+
+        //  Equipment and retracts are mounted into the chamber walls, touching them is expected,
+        //  so they are checked only against the non-obstructive parts of the chamber
+        let chamber = self.chamber.get_ref();
+        let full = chamber.full();
+        let mounted = chamber.non_obstructive();
+        for (part, collider) in &parts {
+            let against = match part {
+                StaticPart::Stage => &full,
+                _ => &mounted,
+            };
+            if against.collides_with(collider) {
+                return Err(StaticCollision {
+                    first: StaticPart::Chamber,
+                    second: *part,
+                });
+            }
+        }
+
+        for (i, (first, a)) in parts.iter().enumerate() {
+            for (second, b) in &parts[(i + 1)..] {
+                if a.collides_with(b) {
+                    return Err(StaticCollision {
+                        first: *first,
+                        second: *second,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn safe_prepare(&mut self) -> Result<(), StaticCollision> {
+        self.static_immovable();
+        self.safe_validate_static_configuration()
+    }
+
+    fn safe_find_stage_path(&self, state: &CSixAxis) -> CPathResultSixAxis {
+        self.safe_find_stage_path_cancellable(state, &CancelToken::new())
+    }
+
+    fn safe_find_stage_path_cancellable(
+        &self,
+        state: &CSixAxis,
+        cancel: &CancelToken,
+    ) -> CPathResultSixAxis {
+        self.safe_find_stage_path_with_progress(state, cancel, &mut |_| {})
+    }
+
+    fn safe_find_stage_path_with_progress(
+        &self,
+        state: &CSixAxis,
+        cancel: &CancelToken,
+        progress: &mut dyn FnMut(f32),
+    ) -> CPathResultSixAxis {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let from = self.stage_state;
+        let result = self.stage_resolver.get_ref().resolve_path(
+            &SixAxis::from(&from),
+            &SixAxis::from(state),
+            movable.as_ref(),
+            &immovable,
+            cancel,
+            progress,
+        );
+        CPathResultSixAxis::from(result)
+    }
+
+    fn safe_validate_stage_path(&self, path: &[CSixAxis]) -> PathValidation {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let path = path.iter().map(SixAxis::from).collect::<Vec<_>>();
+
+        if let [single] = path.as_slice() {
+            return if whitelist.collides(&immovable, &movable.move_to(single)) {
+                PathValidation::Collision(0)
+            } else {
+                PathValidation::Ok
+            };
+        }
+
+        let step = SixAxis::from(&self.safe_suggest_step_size());
+        let collision = path.windows(2).position(|segment| {
+            !line_of_sight_whitelisted_par(
+                &segment[0],
+                &segment[1],
+                movable.as_ref(),
+                &immovable,
+                &step,
+                &whitelist,
+            )
+        });
+        match collision {
+            Some(i) => PathValidation::Collision(i),
+            None => PathValidation::Ok,
+        }
+    }
+
+    fn safe_swept_volume_bounds(&self, path: &[CSixAxis]) -> Vec<AlignedBoxCollider> {
+        let movable = self.movable_stage();
+        let path = path.iter().map(SixAxis::from).collect::<Vec<_>>();
+        path.windows(2)
+            .map(|segment| {
+                let swept = movable.swept_collider(&segment[0], &segment[1]);
+                AlignedBoxCollider::from_min_max(swept.min(), swept.max())
+            })
+            .collect()
+    }
+
+    fn safe_moving_pair_collides(
+        &self,
+        (stage_from, stage_to): (&CSixAxis, &CSixAxis),
+        retract_id: Id,
+        (retract_from, retract_to): (&CLinearState, &CLinearState),
+    ) -> Option<bool> {
+        let retract = self.movable_retract(retract_id)?;
+        let stage = self.movable_stage();
+        let (stage_from, stage_to) = (SixAxis::from(stage_from), SixAxis::from(stage_to));
+        let (retract_from, retract_to) = (
+            LinearState::from(retract_from),
+            LinearState::from(retract_to),
+        );
+
+        let radius = rotation_radius(
+            &stage.move_to(&stage_from),
+            self.stage.get_ref().rotation_pivot(&stage_from),
+        );
+        let bound = motion_bound(&stage_from, &stage_to, radius)
+            + largest_displacement(
+                &retract.move_to(&retract_from),
+                &retract.move_to(&retract_to),
+            );
+
+        let contact = conservative_advancement_pair(
+            |t| stage.move_to(&stage_from.lerp_t(&stage_to, t)),
+            |t| retract.move_to(&retract_from.lerp(&retract_to, t)),
+            bound,
+            ADVANCE_TOLERANCE,
+        );
+        Some(contact.is_some())
+    }
+
+    fn safe_order_targets(
+        &self,
+        start: &CSixAxis,
+        targets: &[CSixAxis],
+        speed: &CSixAxis,
+    ) -> Vec<usize> {
+        let speed = SixAxis::from(speed);
+        let targets = targets.iter().map(SixAxis::from).collect::<Vec<_>>();
+
+        let mut remaining = (0..targets.len()).collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(targets.len());
+        let mut current = SixAxis::from(start);
+        while !remaining.is_empty() {
+            let (nearest, _) = remaining
+                .iter()
+                .enumerate()
+                .map(|(i, &index)| (i, current.time_to(&targets[index], &speed)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .expect("remaining is not empty");
+            let index = remaining.remove(nearest);
+            current = targets[index];
+            order.push(index);
+        }
+
+        order
+    }
+
+    fn safe_approach_direction(
+        &self,
+        target: &CSixAxis,
+        candidates: &[Vector3],
+        distance: f64,
+    ) -> Option<Vector3> {
+        if !distance.is_finite() {
+            return None;
+        }
+
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let target = SixAxis::from(target);
+        let step = SixAxis::from(&self.safe_suggest_step_size());
+
+        candidates
+            .iter()
+            .filter(|c| c.is_finite() && c.len() > 0.0)
+            .find(|candidate| {
+                let start = SixAxis {
+                    pos: target.pos - candidate.normalize() * distance,
+                    rot: target.rot,
+                };
+                line_of_sight_whitelisted_par(
+                    &start,
+                    &target,
+                    movable.as_ref(),
+                    &immovable,
+                    &step,
+                    &whitelist,
+                )
+            })
+            .copied()
+    }
+
+    fn safe_advance_toward(&self, from: &CSixAxis, goal: &CSixAxis) -> Option<CSixAxis> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let from = SixAxis::from(from);
+        let goal = SixAxis::from(goal);
+        if immovable.collides_with(&movable.move_to(&from)) {
+            return None;
+        }
+
+        let radius = rotation_radius(
+            &movable.move_to(&from),
+            self.stage.get_ref().rotation_pivot(&from),
+        );
+
+        let reached = match conservative_advancement(
+            &from,
+            &goal,
+            movable.as_ref(),
+            &immovable,
+            radius,
+            ADVANCE_TOLERANCE,
+        ) {
+            Advancement::Clear => goal,
+            Advancement::Contact(state) => state,
+        };
+        Some(CSixAxis::from(&reached))
+    }
+
+    fn safe_free_fraction(
+        &self,
+        grid: &CSixAxis,
+        (min, max): (&CSixAxis, &CSixAxis),
+    ) -> Option<f64> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let (grid, min, max) = (
+            six_axis_to_array(grid),
+            six_axis_to_array(min),
+            six_axis_to_array(max),
+        );
+        let counts: [usize; 6] = std::array::from_fn(|i| {
+            let span = max[i] - min[i];
+            if grid[i].is_finite() && grid[i] > 0.0 && span.is_finite() && span > 0.0 {
+                ((span / grid[i]).floor() as usize).saturating_add(1)
+            } else {
+                1
+            }
+        });
+
+        let total = counts
+            .iter()
+            .try_fold(1_usize, |total, &count| total.checked_mul(count))
+            .filter(|&total| total <= MAX_FREE_FRACTION_STATES)?;
+
+        let free = (0..total)
+            .filter(|&index| {
+                let mut rest = index;
+                let state: [f64; 6] = std::array::from_fn(|i| {
+                    let step = rest % counts[i];
+                    rest /= counts[i];
+                    min[i] + step as f64 * grid[i]
+                });
+                !whitelist.collides(&immovable, &movable.move_to(&six_axis_from_array(&state)))
+            })
+            .count();
+        Some(free as f64 / total as f64)
+    }
+
+    fn safe_filter_valid_poses(&self, candidates: &[CSixAxis]) -> Vec<bool> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        candidates
+            .iter()
+            .map(|c| !whitelist.collides(&immovable, &movable.move_to(&SixAxis::from(c))))
+            .collect()
+    }
+
+    fn safe_nearest_valid_pose(
+        &self,
+        desired: &CSixAxis,
+        search_radius: &CSixAxis,
+    ) -> Option<CSixAxis> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let is_free = |s: &[f64; 6]| {
+            !whitelist.collides(&immovable, &movable.move_to(&six_axis_from_array(s)))
+        };
+
+        let desired = six_axis_to_array(desired);
+        if is_free(&desired) {
+            return Some(CSixAxis::from(&six_axis_from_array(&desired)));
+        }
+
+        let radius = six_axis_to_array(search_radius);
+        if radius.iter().any(|r| !(r.is_finite() && *r >= 0.0)) {
+            return None;
+        }
+        let axes = (0..6).filter(|&i| radius[i] > 0.0).collect::<Vec<_>>();
+        if axes.is_empty() {
+            return None;
+        }
+
+        let at = |offset: &[i32], t: f64| {
+            let mut state = desired;
+            for (axis, o) in axes.iter().zip(offset) {
+                state[*axis] += radius[*axis] * t * *o as f64 / NEAREST_RINGS as f64;
+            }
+            state
+        };
+
+        let origin = six_axis_from_array(&desired);
+        let speed = six_axis_from_array(&radius.map(|r| if r > 0.0 { r } else { 1.0 }));
+        let mut checks = 0;
+        let free = (1..=NEAREST_RINGS as i32).find_map(|ring| {
+            if checks >= NEAREST_MAX_CHECKS {
+                return None;
+            }
+
+            //  Rings grow in the time, ties are broken by the geometrically closer offset
+            let mut candidates = ring_offsets(axes.len(), ring)
+                .into_iter()
+                .map(|o| {
+                    let time = origin.time_to(&six_axis_from_array(&at(&o, 1.0)), &speed);
+                    let length = o.iter().map(|i| i * i).sum::<i32>();
+                    (time, length, o)
+                })
+                .collect::<Vec<_>>();
+            candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            candidates.into_iter().find_map(|(_, _, o)| {
+                if checks >= NEAREST_MAX_CHECKS {
+                    return None;
+                }
+                checks += 1;
+                is_free(&at(&o, 1.0)).then_some(o)
+            })
+        })?;
+
+        let (mut blocked, mut clear) = (0.0, 1.0);
+        while clear - blocked > NEAREST_TOLERANCE {
+            let middle = (blocked + clear) / 2.0;
+            if is_free(&at(&free, middle)) {
+                clear = middle;
+            } else {
+                blocked = middle;
+            }
+        }
+        Some(CSixAxis::from(&six_axis_from_array(&at(&free, clear))))
+    }
+
+    fn safe_nudge_to_free(&self, state: &CSixAxis, max_distance: f64) -> Option<CSixAxis> {
+        let separation = self.safe_separation_vector(state)?;
+        if separation.len() > max_distance.max(0.0) {
+            return None;
+        }
+
+        let state = SixAxis::from(state);
+        Some(CSixAxis::from(&SixAxis {
+            pos: state.pos + separation,
+            rot: state.rot,
+        }))
+    }
+
+    fn safe_separation_vector(&self, state: &CSixAxis) -> Option<Vector3> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let state = SixAxis::from(state);
+
+        let mut offset = Vector3::ZERO;
+        for _ in 0..SEPARATION_ITERATIONS {
+            let moved = movable.move_to(&SixAxis {
+                pos: state.pos + offset,
+                rot: state.rot,
+            });
+            if !whitelist.collides(&immovable, &moved) {
+                return Some(offset);
+            }
+
+            //  Deepest pair is separated first, the following pairs from the moved stage
+            let mut deepest: Option<(Vector3, f64)> = None;
+            for (m, moved) in moved.0.iter().enumerate() {
+                for (i, obstacle) in immovable.0.iter().enumerate() {
+                    if whitelist.is_allowed(m, i) {
+                        continue;
+                    }
+                    if let Some((direction, depth)) = obstacle.deepest_penetration(moved) {
+                        if deepest.is_none_or(|(_, d)| depth > d) {
+                            deepest = Some((direction, depth));
+                        }
+                    }
+                }
+            }
+
+            let (direction, depth) = deepest?;
+            offset += direction * (depth + SEPARATION_MARGIN);
+        }
+
+        None
+    }
+
+    fn safe_rotate_to_free(
+        &self,
+        state: &CSixAxis,
+        axis: CAxis,
+        max_angle: f64,
+    ) -> Option<CSixAxis> {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let state = SixAxis::from(state);
+        let is_free = |s: &SixAxis| !whitelist.collides(&immovable, &movable.move_to(s));
+
+        if is_free(&state) {
+            return Some(CSixAxis::from(&state));
+        }
+        if !(max_angle.is_finite() && max_angle > 0.0) {
+            return None;
+        }
+
+        let unit = match axis {
+            CAxis::X => Vector3::new(1.0, 0.0, 0.0),
+            CAxis::Y => Vector3::new(0.0, 1.0, 0.0),
+            CAxis::Z => Vector3::new(0.0, 0.0, 1.0),
+        };
+        (1..=ROTATE_STEPS)
+            .map(|i| max_angle * i as f64 / ROTATE_STEPS as f64)
+            .flat_map(|angle| [angle, -angle])
+            .map(|angle| SixAxis {
+                pos: state.pos,
+                rot: state.rot + unit * angle,
+            })
+            .find(is_free)
+            .map(|free| CSixAxis::from(&free))
+    }
+
+    fn safe_reachable_extents(&self) -> (CSixAxis, CSixAxis) {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let is_free = |s: &SixAxis| !whitelist.collides(&immovable, &movable.move_to(s));
+
+        let state = SixAxis::from(&self.stage_state);
+        if !is_free(&state) {
+            return (self.stage_state, self.stage_state);
+        }
+
+        let step = self.resolver_step();
+        let bounds = group_bounds(&immovable);
+        let reach =
+            (bounds.max() - bounds.min()).len() + 2.0 * bounding_radius(&movable.move_to(&state));
+
+        let mut min = state;
+        let mut max = state;
+        for dof in Dof::ALL {
+            let (unit, step, limit) = match dof {
+                Dof::Px => (Vector3::new(1.0, 0.0, 0.0), step.pos.x(), reach),
+                Dof::Py => (Vector3::new(0.0, 1.0, 0.0), step.pos.y(), reach),
+                Dof::Pz => (Vector3::new(0.0, 0.0, 1.0), step.pos.z(), reach),
+                Dof::Rx => (Vector3::new(1.0, 0.0, 0.0), step.rot.x(), consts::PI),
+                Dof::Ry => (Vector3::new(0.0, 1.0, 0.0), step.rot.y(), consts::PI),
+                Dof::Rz => (Vector3::new(0.0, 0.0, 1.0), step.rot.z(), consts::PI),
+            };
+            let along = |distance: f64| match dof {
+                Dof::Px | Dof::Py | Dof::Pz => SixAxis {
+                    pos: state.pos + unit * distance,
+                    rot: state.rot,
+                },
+                Dof::Rx | Dof::Ry | Dof::Rz => SixAxis {
+                    pos: state.pos,
+                    rot: state.rot + unit * distance,
+                },
+            };
+
+            let up = free_travel(|d| is_free(&along(d)), step, limit);
+            let down = free_travel(|d| is_free(&along(-d)), step, limit);
+            max = SixAxis {
+                pos: max.pos.maximized(&along(up).pos),
+                rot: max.rot.maximized(&along(up).rot),
+            };
+            min = SixAxis {
+                pos: min.pos.minimized(&along(-down).pos),
+                rot: min.rot.minimized(&along(-down).rot),
+            };
+        }
+
+        (CSixAxis::from(&min), CSixAxis::from(&max))
+    }
+
+    fn safe_estimate_difficulty(&self, from: &CSixAxis, to: &CSixAxis) -> Difficulty {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let is_blocked = |s: &SixAxis| whitelist.collides(&immovable, &movable.move_to(s));
+
+        let from = SixAxis::from(from);
+        let to = SixAxis::from(to);
+        if is_blocked(&from) || is_blocked(&to) {
+            return Difficulty::Hard;
+        }
+
+        let step = self.resolver_step();
+        let steps = from.stepping(&to, &step).max(1);
+        let blocked = (1..steps)
+            .map(|i| from.lerp_t(&to, i as f64 / steps as f64))
+            .filter(is_blocked)
+            .count();
+
+        if blocked == 0 {
+            Difficulty::Trivial
+        } else if (blocked as f64) < DIFFICULTY_HARD_BLOCKED * steps as f64 {
+            Difficulty::Moderate
+        } else {
+            Difficulty::Hard
+        }
+    }
+
+    fn safe_path_min_clearance(&self, path: &[CSixAxis]) -> f64 {
+        let step = self.resolver_step();
+        self.path_min_clearance_with_step(path, &step)
+    }
+
+    fn path_min_clearance_with_step(&self, path: &[CSixAxis], step: &SixAxis) -> f64 {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let clearance = |state: &SixAxis| {
+            let moved = movable.move_to(state);
+            if immovable.collides_with(&moved) {
+                0.0
+            } else {
+                immovable.distance_to(&moved)
+            }
+        };
+
+        let path = path.iter().map(SixAxis::from).collect::<Vec<_>>();
+        let first = path.first().map(clearance).unwrap_or(f64::INFINITY);
+        path.windows(2)
+            .flat_map(|segment| {
+                let steps = segment[0].stepping(&segment[1], step).max(1);
+                (1..=steps).map(move |i| segment[0].lerp_t(&segment[1], i as f64 / steps as f64))
+            })
+            .map(|state| clearance(&state))
+            .fold(first, f64::min)
+    }
+
+    fn safe_replan_from(
+        &self,
+        current: &CSixAxis,
+        goal: &CSixAxis,
+        previous: &[SixAxis],
+        cancel: &CancelToken,
+    ) -> CPathResultSixAxis {
+        let movable = self.movable_stage();
+        let immovable = self.immovable_without_stage();
+        let whitelist = self.stage_whitelist();
+        let current = SixAxis::from(current);
+        let goal = SixAxis::from(goal);
+        if whitelist.collides(&immovable, &movable.move_to(&current)) {
+            return CPathResultSixAxis::from(PathResult::InvalidStart(current));
+        }
+
+        let resolve = |to: &SixAxis| {
+            self.stage_resolver.get_ref().resolve_path(
+                &current,
+                to,
+                movable.as_ref(),
+                &immovable,
+                cancel,
+                &mut |_| {},
+            )
+        };
+
+        //  Tail can be reused only if it still leads to the goal
+        if previous.last() != Some(&goal) {
+            return CPathResultSixAxis::from(resolve(&goal));
+        }
+
+        let step = self.resolver_step();
+        let clear = |from: &SixAxis, to: &SixAxis| {
+            line_of_sight_whitelisted_par(from, to, movable.as_ref(), &immovable, &step, &whitelist)
+        };
+
+        //  Start of the longest collision-free tail
+        let mut start = previous.len() - 1;
+        if whitelist.collides(&immovable, &movable.move_to(&previous[start])) {
+            return CPathResultSixAxis::from(resolve(&goal));
+        }
+        while start > 0 && clear(&previous[start - 1], &previous[start]) {
+            start -= 1;
+        }
+
+        let tail = &previous[start..];
+        let nearest = tail
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| current.euclidean_to(a).total_cmp(&current.euclidean_to(b)))
+            .map(|(i, _)| i)
+            .unwrap();
+        if clear(&current, &tail[nearest]) {
+            let mut path = vec![current];
+            path.extend(tail[nearest..].iter().skip_while(|n| **n == current));
+            return CPathResultSixAxis::from(PathResult::Path(path));
+        }
+
+        match resolve(&tail[0]) {
+            PathResult::Path(mut path) => {
+                path.extend_from_slice(&tail[1..]);
+                CPathResultSixAxis::from(PathResult::Path(path))
+            }
+            _ => CPathResultSixAxis::from(resolve(&goal)),
+        }
+    }
+
+    fn safe_find_retract_path(&self, id: Id, state: &CLinearState) -> CPathResultLinearState {
+        let Some((retract, resolver, from)) = self.retracts.inner().get(&id) else {
+            return CPathResultLinearState::from_vec(PathResultState::InvalidId, vec![]);
+        };
+        let movable = retract.get_ref().as_movable();
+        let immovable = self.immovable_stage();
+        let result = resolver.get_ref().resolve_path(
+            &LinearState::from(from),
+            &LinearState::from(state),
+            movable.as_ref(),
+            &immovable,
+            &CancelToken::new(),
+            &mut |_| {},
+        );
+        CPathResultLinearState::from(result)
+    }
+
+    /// Step of the configured stage resolver, or the suggested one if the resolver has none.
+    fn resolver_step(&self) -> SixAxis {
+        match self.configuration.inner().stage_resolver().step_size() {
+            Some(step) => SixAxis::from(&step),
+            None => SixAxis::from(&self.safe_suggest_step_size()),
+        }
+    }
+
+    fn safe_suggest_step_size(&self) -> CSixAxis {
+        let pivot = self
+            .stage
+            .get_ref()
+            .rotation_pivot(&SixAxis::from(&self.stage_state));
+        let step = step_size_from_geometry(
+            &self.immovable_without_stage(),
+            &self.immovable_stage(),
+            pivot,
+        );
+        CSixAxis::from(&step)
+    }
+
+    fn safe_present_static_full(&self) -> TriangleBufferVec {
+        let chamber = self.chamber.get_ref().full();
+        collider_to_triangle_buffer_per_item(self.extend_with_equipment(chamber))
+    }
+
+    fn safe_present_static_full_chunk_count(&self, chunk_size: usize) -> usize {
+        let chamber = self.chamber.get_ref().full();
+        triangle_chunk_count(&self.extend_with_equipment(chamber), chunk_size)
+    }
+
+    fn safe_present_static_full_chunk(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+    ) -> Vec<CVector3> {
+        let chamber = self.chamber.get_ref().full();
+        triangle_chunk(
+            &self.extend_with_equipment(chamber),
+            chunk_index,
+            chunk_size,
+        )
+    }
+
+    fn safe_present_static_boxes(&self) -> Vec<AlignedBoxCollider> {
+        let chamber = self.chamber.get_ref().full();
+        collider_to_bounding_boxes(&self.extend_with_equipment(chamber))
+    }
+
+    fn safe_present_static_full_with_normals(&self) -> InterleavedBufferVec {
+        let chamber = self.chamber.get_ref().full();
+        collider_to_interleaved_buffer_per_item(self.extend_with_equipment(chamber))
+    }
+
+    fn safe_present_static_full_rotated(
+        &self,
+        rotation: &CSixAxis,
+        pivot: &CVector3,
+    ) -> TriangleBufferVec {
+        let transform = SixAxis::from(rotation);
+        transform_buffer(
+            &self.safe_present_static_full(),
+            &Quaternion::from_euler(&transform.rot),
+            &Vector3::from(pivot),
+            &transform.pos,
+        )
+    }
+
+    fn safe_present_static_less_obstructive(&self) -> TriangleBufferVec {
+        let chamber = self.chamber.get_ref().less_obstructive();
+        let group = self.add_visible_equipment(chamber, ObstructionTier::LessObstructive);
+        collider_to_triangle_buffer_per_item(group)
+    }
+
+    fn safe_present_static_non_obstructive(&self) -> TriangleBufferVec {
+        let chamber = self.chamber.get_ref().non_obstructive();
+        let group = self.add_visible_equipment(chamber, ObstructionTier::NonObstructive);
+        collider_to_triangle_buffer_per_item(group)
+    }
+
+    fn safe_present_stage(&self) -> TriangleBufferVec {
+        let stage = self
+            .stage
+            .get_ref()
+            .move_to(&SixAxis::from(&self.stage_state));
+        collider_to_triangle_buffer_per_item(stage)
+    }
+
+    fn safe_present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
+        let stage = self.stage.get_ref().move_to(&SixAxis::from(state));
+        collider_to_triangle_buffer_per_item(stage)
+    }
+
+    fn safe_stage_bounds_at(&self, state: &CSixAxis) -> (Vector3, Vector3) {
+        let bounds = self.stage.get_ref().bounds_at(&SixAxis::from(state));
+        (bounds.min(), bounds.max())
+    }
+
+    fn safe_dynamic_bounds(&self) -> (Vector3, Vector3) {
+        let (mut min, mut max) = self.safe_stage_bounds_at(&self.stage_state);
+        for (retract, _, state) in self.retracts.inner().values() {
+            let bounds = group_bounds(&retract.get_ref().move_to(&LinearState::from(state)));
+            min = min.minimized(&bounds.min());
+            max = max.maximized(&bounds.max());
+        }
+        (min, max)
+    }
+
+    fn safe_stage_principal_axes(&self) -> [Vector3; 3] {
+        self.immovable_stage().principal_axes()
+    }
+
+    fn safe_present_retract(&self, id: Id) -> TriangleBufferVec {
+        let Some((retract, _, state)) = self.retracts.inner().get(&id) else {
+            return TriangleBufferVec::from_vec(vec![]);
+        };
+        let retracted = retract.get_ref().move_to(&LinearState::from(state));
+        collider_to_triangle_buffer_per_item(retracted)
+    }
+
+    fn safe_present_retract_at(&self, id: Id, state: &CLinearState) -> TriangleBufferVec {
+        let Some((retract, _, _)) = self.retracts.inner().get(&id) else {
+            return TriangleBufferVec::from_vec(vec![]);
+        };
+        let retracted = retract.get_ref().move_to(&LinearState::from(state));
+        collider_to_triangle_buffer_per_item(retracted)
+    }
+}
+
+/// Half of the bounding box diagonal of the whole group, `0` for an empty group.
+fn bounding_radius(group: &Immovable) -> f64 {
+    let corners = group
+        .0
+        .iter()
+        .flat_map(|c| [c.min(), c.max()])
+        .collect::<Vec<_>>();
+    bounds_of(&corners).map_or(0.0, |(min, max)| (max - min).len() / 2.0)
+}
+
+/// Distance of the farthest vertex of the group from the rotation `pivot`,
+/// bounds the distance any point of the group moves per radian of rotation about it.
+fn rotation_radius(group: &Immovable, pivot: Vector3) -> f64 {
+    triangle_vertices(group)
+        .into_iter()
+        .map(|v| (v - pivot).len())
+        .fold(0.0, f64::max)
+}
+
+/// Largest distance between matching vertices of the group at two states,
+/// bounds the distance any point moves between them when the group translates along a straight line.
+///
+/// Groups with different triangles at the two states are bounded by the diagonal of both.
+fn largest_displacement(from: &Immovable, to: &Immovable) -> f64 {
+    let (from, to) = (triangle_vertices(from), triangle_vertices(to));
+    if from.len() != to.len() {
+        let (from, to) = (bounds_of(&from), bounds_of(&to));
+        return match (from, to) {
+            (Some((a, b)), Some((c, d))) => (b.maximized(&d) - a.minimized(&c)).len(),
+            _ => 0.0,
+        };
+    }
+
+    from.iter()
+        .zip(&to)
+        .map(|(a, b)| (*b - *a).len())
+        .fold(0.0, f64::max)
+}
+
+fn triangle_vertices(group: &Immovable) -> Vec<Vector3> {
+    group.0.iter().flat_map(|c| c.triangle_buffer()).collect()
+}
+
+/// Clearance at which advancing towards a goal stops in front of an obstacle.
+const ADVANCE_TOLERANCE: f64 = 1e-6;
+
+/// Precision of the travel limits of [Microscope::reachable_extents].
+const EXTENT_TOLERANCE: f64 = 1e-6;
+
+/// Returns the largest distance up to `limit` for which `is_free` holds,
+/// marching by `step` to find the first blocked distance and bisecting down to it.
+///
+/// Obstacles thinner than `step` may be skipped.
+fn free_travel(is_free: impl Fn(f64) -> bool, step: f64, limit: f64) -> f64 {
+    let step = if step > 0.0 { step.min(limit) } else { limit };
+
+    let mut free = 0.0;
+    let mut blocked = loop {
+        if free >= limit {
+            return limit;
+        }
+        let next = (free + step).min(limit);
+        if !is_free(next) {
+            break next;
+        }
+        free = next;
+    };
+
+    while blocked - free > EXTENT_TOLERANCE {
+        let middle = (free + blocked) / 2.0;
+        if is_free(middle) {
+            free = middle;
+        } else {
+            blocked = middle;
+        }
+    }
+    free
+}
+
+/// Largest number of grid states sampled for the free fraction.
+const MAX_FREE_FRACTION_STATES: usize = 100_000;
+
+/// Fraction of blocked poses along the straight line from which planning is considered hard.
+const DIFFICULTY_HARD_BLOCKED: f64 = 0.5;
+
+/// Colliding pairs separated one after another before the separation vector gives up.
+const SEPARATION_ITERATIONS: usize = 32;
+
+/// Clearance added to every separated pair, so the pair does not stay touching.
+const SEPARATION_MARGIN: f64 = 1e-7;
+
+/// Angles tried when rotating a state out of an overlap, evenly up to the maximal angle.
+const ROTATE_STEPS: usize = 32;
+
+/// Rings of the lattice searched for the nearest valid pose, evenly up to the search radius.
+const NEAREST_RINGS: usize = 4;
+
+/// Collision checks of lattice poses after which the nearest valid pose search gives up.
+const NEAREST_MAX_CHECKS: usize = 2048;
+
+/// Fraction of the offset to the free lattice pose to which the nearest valid pose is refined.
+const NEAREST_TOLERANCE: f64 = 1e-3;
+
+/// Lattice offsets in `dimensions` axes with the largest absolute coordinate equal to `ring`.
+fn ring_offsets(dimensions: usize, ring: i32) -> Vec<Vec<i32>> {
+    let side = 2 * ring + 1;
+    (0..side.pow(dimensions as u32))
+        .map(|index| {
+            (0..dimensions)
+                .map(|d| index / side.pow(d as u32) % side - ring)
+                .collect::<Vec<_>>()
+        })
+        .filter(|o| o.iter().any(|i| i.abs() == ring))
+        .collect()
+}
+
+fn six_axis_to_array(s: &CSixAxis) -> [f64; 6] {
+    [s.x, s.y, s.z, s.rx, s.ry, s.rz]
+}
+
+fn six_axis_from_array(s: &[f64; 6]) -> SixAxis {
+    SixAxis {
+        pos: Vector3::new(s[0], s[1], s[2]),
+        rot: Vector3::new(s[3], s[4], s[5]),
+    }
+}
+
+/// Fraction of the smallest feature used as the positional step, so features cannot be skipped.
+const FEATURE_STEP_FRACTION: f64 = 0.5;
+
+/// Smallest suggested positional step, so a sliver triangle does not stall the searches.
+const MIN_SUGGESTED_STEP: f64 = 1e-5;
+
+/// Largest suggested positional step, also used for a scene without static parts.
+const MAX_SUGGESTED_STEP: f64 = 1e-2;
+
+/// Largest suggested rotational step, also used for a stage without triangles.
+const MAX_SUGGESTED_ROTATION_STEP: f64 = std::f64::consts::PI / 36.0;
+
+fn step_size_from_geometry(immovable: &Immovable, stage: &Immovable, pivot: Vector3) -> SixAxis {
+    let position = immovable
+        .0
+        .iter()
+        .map(|c| c.smallest_feature_size())
+        .min_by(f64::total_cmp)
+        .map_or(MAX_SUGGESTED_STEP, |feature| {
+            (feature * FEATURE_STEP_FRACTION).clamp(MIN_SUGGESTED_STEP, MAX_SUGGESTED_STEP)
+        });
+
+    //  Farthest point of the stage must not move more than the positional step
+    let rotation =
+        max_rotation_step(rotation_radius(stage, pivot), position).min(MAX_SUGGESTED_ROTATION_STEP);
+
+    SixAxis {
+        pos: Vector3::new(position, position, position),
+        rot: Vector3::new(rotation, rotation, rotation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::chamber::ChamberConfig;
+    use crate::configuration::equipment::EquipmentConfig;
+    use crate::configuration::resolver_retract::ResolverRetractConfig;
+    use crate::configuration::resolver_stage::ResolverStageConfig;
+    use crate::configuration::retract::RetractConfig;
+    use crate::configuration::stage::StageConfig;
+    use crate::id::make_id;
+    use crate::presentation::buffer_to_packed_f32;
+    use crate::types::{CLinearState, CSixAxis};
+    use collisions::collider_group;
+    use collisions::primitive::TriangleCollider;
+
+    const STEP: CSixAxis = CSixAxis {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+        rx: 0.4,
+        ry: 0.5,
+        rz: 0.6,
+    };
+
+    #[test]
+    fn moving_pair_collides_between_free_endpoints() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        );
+        let microscope = Microscope::build(&config);
+        let stage = |y| CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, y, 0.08)));
+        let retract = |t| CLinearState { t };
+        let collides_at = |stage_state: &CSixAxis, retract_state: &CLinearState| {
+            let stage = microscope
+                .movable_stage()
+                .move_to(&SixAxis::from(stage_state));
+            let retract = microscope
+                .movable_retract(make_id!(11))
+                .unwrap()
+                .move_to(&LinearState::from(retract_state));
+            stage.collides_with(&retract)
+        };
+
+        //  the stage crosses in front of the inserting retract
+        assert!(!collides_at(&stage(-0.1), &retract(0.9)));
+        assert!(!collides_at(&stage(0.1), &retract(1.0)));
+        assert!(collides_at(&stage(0.0), &retract(0.95)));
+        assert_eq!(
+            Some(true),
+            microscope.safe_moving_pair_collides(
+                (&stage(-0.1), &stage(0.1)),
+                make_id!(11),
+                (&retract(0.9), &retract(1.0))
+            )
+        );
+
+        //  the stage leaves before the retract is inserted far enough to reach it
+        assert!(collides_at(&stage(0.0), &retract(1.0)));
+        assert_eq!(
+            Some(false),
+            microscope.safe_moving_pair_collides(
+                (&stage(0.0), &stage(0.2)),
+                make_id!(11),
+                (&retract(0.5), &retract(1.0))
+            )
+        );
+
+        //  the stage stays below the retract
+        let low = |z| CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, z)));
+        assert_eq!(
+            Some(false),
+            microscope.safe_moving_pair_collides(
+                (&low(0.0), &low(0.02)),
+                make_id!(11),
+                (&retract(0.0), &retract(1.0))
+            )
+        );
+        assert_eq!(
+            None,
+            microscope.safe_moving_pair_collides(
+                (&stage(-0.1), &stage(0.1)),
+                make_id!(12),
+                (&retract(0.9), &retract(1.0))
+            )
+        );
+    }
+
+    #[test]
+    fn build_microscope_without_leak() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![EquipmentConfig::ThesisDetectorAlpha],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        );
+
+        let _microscope = Microscope::build(&config);
+    }
+
+    #[test]
+    fn present_static_full_rotated_matches_transformed_buffer() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![],
+        );
+        let microscope = Microscope::build(&config);
+        let rotation = CSixAxis {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 30_f64.to_radians(),
+        };
+        let pivot = CVector3 {
+            x: 0.0,
+            y: 10.0,
+            z: 0.0,
+        };
+
+        let actual = microscope.safe_present_static_full_rotated(&rotation, &pivot);
+
+        let expected = transform_buffer(
+            &microscope.safe_present_static_full(),
+            &Quaternion::from_euler(&Vector3::new(0.0, 0.0, 30_f64.to_radians())),
+            &Vector3::new(0.0, 10.0, 0.0),
+            &Vector3::new(1.0, 2.0, 3.0),
+        );
+        assert_eq!(expected.data().len(), actual.data().len());
+        for (expected, actual) in expected.data().iter().zip(actual.data()) {
+            assert_eq!(expected.data(), actual.data());
+        }
+    }
+
+    #[test]
+    fn static_full_chunks_reproduce_full_buffer() {
+        let config = validation_config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        let microscope = Microscope::build(&config);
+
+        let expected = microscope
+            .safe_present_static_full()
+            .data()
+            .iter()
+            .flat_map(|b| b.data().to_vec())
+            .collect::<Vec<_>>();
+
+        let chunk_size = 1000;
+        let count = microscope.safe_present_static_full_chunk_count(chunk_size);
+        let mut actual = Vec::with_capacity(expected.len());
+        for index in 0..count {
+            let chunk = microscope.safe_present_static_full_chunk(index, chunk_size);
+            assert!(chunk.len() <= chunk_size * 3);
+            actual.extend(chunk);
+        }
+
+        assert_eq!(expected.len().div_ceil(chunk_size * 3), count);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn static_boxes_enclose_full_buffers() {
+        let config = validation_config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        let microscope = Microscope::build(&config);
+
+        let boxes = microscope.safe_present_static_boxes();
+        let full = microscope.safe_present_static_full();
+
+        assert_eq!(full.data().len(), boxes.len());
+        for (aabb, buffer) in boxes.iter().zip(full.data()) {
+            for vertex in buffer.data() {
+                let vertex = Vector3::from(vertex);
+                let min = aabb.min() - Vector3::new(1e-9, 1e-9, 1e-9);
+                let max = aabb.max() + Vector3::new(1e-9, 1e-9, 1e-9);
+                assert_eq!(vertex, vertex.maximized(&min).minimized(&max));
+            }
+        }
+    }
+
+    #[test]
+    fn stage_bounds_enclose_presented_stage() {
+        let config = validation_config(vec![]);
+        let mut microscope = Microscope::build(&config);
+        microscope.safe_update_holder(&HolderConfig::ThesisHolderCircle);
+
+        let states = [
+            (
+                SixAxis::from_position(Vector3::new(0.01, -0.02, 0.03)),
+                1e-9,
+            ),
+            (
+                SixAxis {
+                    pos: Vector3::new(0.0, 0.01, 0.0),
+                    rot: Vector3::new(0.0, 5_f64.to_radians(), 15_f64.to_radians()),
+                },
+                0.02,
+            ),
+        ];
+        for (state, tolerance) in states {
+            let state = CSixAxis::from(&state);
+            let (min, max) = microscope.safe_stage_bounds_at(&state);
+
+            let vertices = microscope
+                .safe_present_stage_at(&state)
+                .data()
+                .iter()
+                .flat_map(|b| b.data().iter().map(Vector3::from).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let tight_min = vertices.iter().fold(vertices[0], |m, v| m.minimized(v));
+            let tight_max = vertices.iter().fold(vertices[0], |m, v| m.maximized(v));
+
+            let eps = Vector3::new(1e-9, 1e-9, 1e-9);
+            assert_eq!(min, min.minimized(&(tight_min + eps)));
+            assert_eq!(max, max.maximized(&(tight_max - eps)));
+            assert!((tight_min - min).len() < tolerance, "{state:?}");
+            assert!((max - tight_max).len() < tolerance, "{state:?}");
+        }
+    }
+
+    #[test]
+    fn suggested_step_below_smallest_feature() {
+        let immovable = collider_group!(PrimaryCollider::build(&[
+            TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(5.0, 0.0, 0.0),
+                Vector3::new(0.0, 5.0, 0.0),
+            ),
+            TriangleCollider::new(
+                Vector3::new(0.0, 0.0, 1.0),
+                Vector3::new(0.2, 0.0, 1.0),
+                Vector3::new(0.0, 0.1, 1.0),
+            ),
+        ]));
+        let stage = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )]));
+
+        let step = step_size_from_geometry(&immovable, &stage, Vector3::ZERO);
+
+        assert!(step.pos.x() > 0.0 && step.pos.x() < 0.2);
+        assert!(step.pos.y() > 0.0 && step.pos.y() < 0.2);
+        assert!(step.pos.z() > 0.0 && step.pos.z() < 0.2);
+        assert!(step.rot.x() > 0.0 && step.rot.x().is_finite());
+    }
+
+    #[test]
+    fn suggested_step_is_clamped() {
+        let sliver = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1e-9, 0.0, 0.0),
+            Vector3::new(0.0, 1e-9, 0.0),
+        )]));
+        let large = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(0.0, 10.0, 0.0),
+        )]));
+
+        let fine = step_size_from_geometry(&sliver, &large, Vector3::ZERO);
+        assert_eq!(MIN_SUGGESTED_STEP, fine.pos.x());
+        assert!(fine.rot.x() > 0.0);
+
+        let coarse = step_size_from_geometry(&large, &sliver, Vector3::ZERO);
+        assert_eq!(MAX_SUGGESTED_STEP, coarse.pos.x());
+        assert_eq!(MAX_SUGGESTED_ROTATION_STEP, coarse.rot.x());
+    }
+
+    #[test]
+    fn suggested_step_of_empty_scene() {
+        let stage = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )]));
+
+        let step = step_size_from_geometry(&collider_group!(), &stage, Vector3::ZERO);
+        assert_eq!(MAX_SUGGESTED_STEP, step.pos.x());
+        assert!(step.rot.x() > 0.0 && step.rot.x() <= MAX_SUGGESTED_ROTATION_STEP);
+
+        let step = step_size_from_geometry(&collider_group!(), &collider_group!(), Vector3::ZERO);
+        assert_eq!(MAX_SUGGESTED_STEP, step.pos.x());
+        assert_eq!(MAX_SUGGESTED_ROTATION_STEP, step.rot.x());
+    }
+
+    #[test]
+    fn rotation_radius_measured_from_pivot() {
+        let group = collider_group!(PrimaryCollider::build(&[TriangleCollider::new(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+        )]));
+
+        assert_eq!(2.0, rotation_radius(&group, Vector3::ZERO));
+        assert_eq!(1.0, rotation_radius(&group, Vector3::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn dynamic_bounds_enclose_stage_and_retract() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        );
+        let mut microscope = Microscope::build(&config);
+        let stage = CSixAxis {
+            x: 0.01,
+            y: -0.02,
+            z: 0.03,
+            rx: 0.0,
+            ry: 0.2,
+            rz: 0.4,
+        };
+        microscope.safe_update_stage_state(&stage);
+        microscope
+            .safe_update_retract_state(make_id!(11), &CLinearState { t: 0.7 })
+            .unwrap();
+
+        let (min, max) = microscope.safe_dynamic_bounds();
+
+        let (stage_min, stage_max) = microscope.safe_stage_bounds_at(&stage);
+        let (retract, _, state) = &microscope.retracts.inner()[&make_id!(11)];
+        let retract = group_bounds(&retract.get_ref().move_to(&LinearState::from(state)));
+        for (part_min, part_max) in [(stage_min, stage_max), (retract.min(), retract.max())] {
+            assert_eq!(min, min.minimized(&part_min));
+            assert_eq!(max, max.maximized(&part_max));
+        }
+        assert_eq!(min, stage_min.minimized(&retract.min()));
+        assert_eq!(max, stage_max.maximized(&retract.max()));
+    }
+
+    #[test]
+    fn stage_path_progress_is_monotonic() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageBidirectionalResolver {
+                step_size: CSixAxis {
+                    x: 0.005,
+                    y: 0.005,
+                    z: 0.005,
+                    rx: 0.1,
+                    ry: 0.1,
+                    rz: 0.1,
+                },
+                connect_distance: 0.02,
+            },
+            vec![],
+            vec![],
+        );
+        let mut microscope = Microscope::build(&config);
+        let at = |x: f64| CSixAxis {
+            x,
+            y: 0.0,
+            z: 0.05,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+        };
+        microscope.safe_update_stage_state(&at(-0.05));
+        microscope.safe_update_resolvers().unwrap();
+
+        let mut reported = Vec::new();
+        let result = microscope.safe_find_stage_path_with_progress(
+            &at(0.05),
+            &CancelToken::new(),
+            &mut |f| reported.push(f),
+        );
+
+        assert_eq!(PathResultState::Path, result.state());
+        assert!(reported.len() > 2);
+        assert!(reported.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(1.0, *reported.last().unwrap());
+    }
+
+    #[test]
+    fn free_fraction_of_open_and_cluttered_workspace() {
+        let open_config = validation_config(vec![]);
+        let cluttered_config = validation_config(vec![
+            EquipmentConfig::ThesisDetectorAlpha,
+            EquipmentConfig::ThesisDetectorBeta,
+        ]);
+        let (open, cluttered) = (
+            Microscope::build(&open_config),
+            Microscope::build(&cluttered_config),
+        );
+        let pose = |x, y, z| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, y, z)));
+        let grid = pose(0.025, 0.025, 0.025);
+        //  5 x 5 x 3 positions each
+        let below = (pose(-0.05, -0.05, 0.0), pose(0.05, 0.05, 0.05));
+        let above = (pose(-0.05, -0.05, 0.1), pose(0.05, 0.05, 0.15));
+
+        assert_eq!(
+            Some(1.0),
+            open.safe_free_fraction(&grid, (&below.0, &below.1))
+        );
+        let cluttered_above = cluttered
+            .safe_free_fraction(&grid, (&above.0, &above.1))
+            .unwrap();
+        let open_above = open
+            .safe_free_fraction(&grid, (&above.0, &above.1))
+            .unwrap();
+        assert!(cluttered_above < 0.25);
+        assert!(cluttered_above < open_above);
+
+        //  zero spacing samples only the lower bound, which is blocked
+        let single = pose(0.0, 0.0, 0.2);
+        let zero = pose(0.0, 0.0, 0.0);
+        assert_eq!(
+            Some(0.0),
+            open.safe_free_fraction(&zero, (&single, &below.1))
+        );
+
+        //  the number of states overflows
+        let fine = pose(1e-300, 1e-300, 1e-300);
+        let huge = (pose(-1.0, -1.0, -1.0), pose(1.0, 1.0, 1.0));
+        assert_eq!(None, open.safe_free_fraction(&fine, (&huge.0, &huge.1)));
+
+        //  the grid is too large to sample
+        let dense = pose(0.001, 0.001, 0.001);
+        assert_eq!(None, open.safe_free_fraction(&dense, (&huge.0, &huge.1)));
+    }
+
+    #[test]
+    fn filter_valid_poses_masks_blocked_candidates() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let pose =
+            |x: f64, z: f64| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, z)));
+        let candidates = [
+            pose(0.0, 0.05),
+            pose(0.0, 0.1),
+            pose(0.05, 0.06),
+            pose(0.0, 0.08),
+            pose(-0.07, 0.075),
+        ];
+
+        let valid = microscope.safe_filter_valid_poses(&candidates);
+
+        assert_eq!(vec![true, false, true, false, true], valid);
+        assert!(microscope.safe_filter_valid_poses(&[]).is_empty());
+    }
+
+    #[test]
+    fn nearest_valid_pose_outside_obstacle() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let collides = |state: &CSixAxis| {
+            microscope
+                .immovable_without_stage()
+                .collides_with(&microscope.movable_stage().move_to(&SixAxis::from(state)))
+        };
+        let pose = |z: f64| CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, z)));
+        let (mut free, mut colliding) = (0.05, 0.1);
+        while colliding - free > 1e-6 {
+            let middle = (free + colliding) / 2.0;
+            if collides(&pose(middle)) {
+                colliding = middle;
+            } else {
+                free = middle;
+            }
+        }
+        let radius = CSixAxis {
+            x: 0.004,
+            y: 0.004,
+            z: 0.004,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+        };
+
+        let desired = pose(colliding + 0.001);
+        assert!(collides(&desired));
+        let nearest = microscope
+            .safe_nearest_valid_pose(&desired, &radius)
+            .unwrap();
+        assert!(!collides(&nearest));
+        let distance =
+            SixAxis::from(&desired).time_to(&SixAxis::from(&nearest), &SixAxis::from(&radius));
+        assert!(distance <= 0.001 / 0.004 + 1e-3, "too far {distance}");
+        assert_eq!(
+            (desired.rx, desired.ry, desired.rz),
+            (nearest.rx, nearest.ry, nearest.rz)
+        );
+
+        let buried = pose(colliding + 0.02);
+        assert_eq!(None, microscope.safe_nearest_valid_pose(&buried, &radius));
+        let all_axes = CSixAxis {
+            rx: 0.01,
+            ry: 0.01,
+            rz: 0.01,
+            ..radius
+        };
+        assert_eq!(None, microscope.safe_nearest_valid_pose(&buried, &all_axes));
+
+        let free = pose(free);
+        assert_eq!(
+            Some(free),
+            microscope.safe_nearest_valid_pose(&free, &radius)
+        );
+    }
+
+    #[test]
+    fn order_targets_visits_collinear_targets_by_distance() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let pose = |x: f64| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, 0.05)));
+
+        let targets = [pose(0.04), pose(0.01), pose(0.06), pose(0.02), pose(-0.03)];
+        let order = microscope.safe_order_targets(&pose(-0.05), &targets, &STEP);
+
+        assert_eq!(vec![4, 1, 3, 0, 2], order);
+        assert!(microscope
+            .safe_order_targets(&pose(0.0), &[], &STEP)
+            .is_empty());
+    }
+
+    #[test]
+    fn stage_principal_axes_are_orthonormal() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+
+        let [first, second, third] = microscope.safe_stage_principal_axes();
+        for axis in [first, second, third] {
+            assert!((axis.len() - 1.0).abs() < 1e-9);
+        }
+        assert!(first.dot(&second).abs() < 1e-9);
+        assert!((first.cross(&second) - third).len() < 1e-9);
+    }
+
+    #[test]
+    fn approach_direction_skips_blocked_candidates() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let target = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, 0.07)));
+        let from_above = Vector3::new(0.0, 0.0, -1.0);
+        let from_side = Vector3::new(1.0, 0.0, 0.0);
+
+        assert_eq!(
+            Some(from_side),
+            microscope.safe_approach_direction(&target, &[from_above, from_side], 0.03)
+        );
+        assert_eq!(
+            None,
+            microscope.safe_approach_direction(&target, &[from_above, Vector3::ZERO], 0.03)
+        );
+    }
+
+    fn validation_config(equipment: Vec<EquipmentConfig>) -> Configuration {
+        Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            equipment,
+            vec![],
+        )
+    }
+
+    fn fine_step_config() -> Configuration {
+        let step = CSixAxis {
+            x: 0.001,
+            y: 0.001,
+            z: 0.001,
+            rx: 0.01,
+            ry: 0.01,
+            rz: 0.01,
+        };
+        Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: step },
+            vec![],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn validate_clean_configuration() {
+        let config = validation_config(vec![
+            EquipmentConfig::ThesisDetectorAlpha,
+            EquipmentConfig::ThesisDetectorBeta,
+        ]);
+        let microscope = Microscope::build(&config);
+
+        assert_eq!(Ok(()), microscope.safe_validate_static_configuration());
+    }
+
+    #[test]
+    fn validate_overlapping_equipment() {
+        let config = validation_config(vec![
+            EquipmentConfig::ThesisDetectorBeta,
+            EquipmentConfig::ThesisDetectorAlpha,
+            EquipmentConfig::ThesisDetectorAlpha,
+        ]);
+        let microscope = Microscope::build(&config);
+
+        let expected = StaticCollision {
+            first: StaticPart::Equipment(1),
+            second: StaticPart::Equipment(2),
+        };
+        assert_eq!(
+            Err(expected),
+            microscope.safe_validate_static_configuration()
+        );
+    }
+
+    #[test]
+    fn validate_clean_configuration_with_retract() {
+        let mut config = retract_config(&[1]);
+        config.push_equipment(EquipmentConfig::ThesisDetectorAlpha);
+        config.push_equipment(EquipmentConfig::ThesisDetectorBeta);
+        let microscope = Microscope::build(&config);
+
+        assert_eq!(Ok(()), microscope.safe_validate_static_configuration());
+    }
+
+    #[test]
+    fn validate_overlapping_retracts_at_home() {
+        let config = retract_config(&[1, 2]);
+        let microscope = Microscope::build(&config);
+
+        let collision = microscope.safe_validate_static_configuration().unwrap_err();
+        let first = StaticPart::Retract(make_id!(1));
+        let second = StaticPart::Retract(make_id!(2));
+        assert!(
+            (collision.first, collision.second) == (first, second)
+                || (collision.first, collision.second) == (second, first),
+            "{collision:?}"
+        );
+    }
+
+    #[test]
+    fn validate_stage_path_reports_colliding_segment() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+
+        let pose = |x, z| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, z)));
+        let clear = [pose(-0.05, 0.0), pose(0.0, 0.0), pose(0.0, 0.05)];
+        assert_eq!(
+            PathValidation::Ok,
+            microscope.safe_validate_stage_path(&clear)
+        );
+
+        let scripted = [
+            pose(-0.05, 0.0),
+            pose(0.0, 0.0),
+            pose(0.0, 0.1),
+            pose(0.0, 0.05),
+        ];
+        assert_eq!(
+            PathValidation::Collision(1),
+            microscope.safe_validate_stage_path(&scripted)
+        );
+    }
+
+    #[test]
+    fn separation_vector_frees_shallow_intrusion() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+
+        let movable = microscope.movable_stage();
+        let immovable = microscope.immovable_without_stage();
+        let collides = |z: f64| {
+            immovable
+                .collides_with(&movable.move_to(&SixAxis::from_position(Vector3::new(0.0, 0.0, z))))
+        };
+        let (mut free, mut colliding) = (0.05, 0.1);
+        while colliding - free > 1e-6 {
+            let middle = (free + colliding) / 2.0;
+            if collides(middle) {
+                colliding = middle;
+            } else {
+                free = middle;
+            }
+        }
+
+        let shallow = SixAxis::from_position(Vector3::new(0.0, 0.0, colliding + 0.0005));
+        let separation = microscope
+            .safe_separation_vector(&CSixAxis::from(&shallow))
+            .unwrap();
+        let separated = SixAxis {
+            pos: shallow.pos + separation,
+            rot: shallow.rot,
+        };
+        assert!(!immovable.collides_with(&movable.move_to(&separated)));
+        //  moves straight back down below the contact, the bisection above is within 1e-6
+        assert!((separation.z() + 0.0005).abs() <= 2e-6);
+        assert!(separation.x().abs() < 1e-9 && separation.y().abs() < 1e-9);
+
+        let free = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, free)));
+        assert_eq!(
+            Some(Vector3::ZERO),
+            microscope.safe_separation_vector(&free)
+        );
+    }
+
+    #[test]
+    fn nudge_escapes_shallow_overlap_only() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+
+        let movable = microscope.movable_stage();
+        let immovable = microscope.immovable_without_stage();
+        let collides = |z: f64| {
+            immovable
+                .collides_with(&movable.move_to(&SixAxis::from_position(Vector3::new(0.0, 0.0, z))))
+        };
+        let (mut free, mut colliding) = (0.05, 0.1);
+        while colliding - free > 1e-6 {
+            let middle = (free + colliding) / 2.0;
+            if collides(middle) {
+                colliding = middle;
+            } else {
+                free = middle;
+            }
+        }
+
+        let pose = |z| CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, z)));
+        let shallow = pose(colliding + 0.0005);
+        let nudged = microscope.safe_nudge_to_free(&shallow, 0.002).unwrap();
+        let nudged = SixAxis::from(&nudged);
+        assert!(!immovable.collides_with(&movable.move_to(&nudged)));
+        assert!((nudged.pos - SixAxis::from(&shallow).pos).len() <= 0.002 + 1e-12);
+
+        let buried = pose(colliding + 0.02);
+        assert_eq!(None, microscope.safe_nudge_to_free(&buried, 0.002));
+
+        let free = pose(free);
+        assert_eq!(Some(free), microscope.safe_nudge_to_free(&free, 0.002));
+    }
+
+    #[test]
+    fn equipment_hidden_from_non_obstructive_view() {
+        let config = validation_config(Vec::new()).with_visible_equipment(vec![
+            (
+                EquipmentConfig::ThesisDetectorAlpha,
+                ObstructionTier::NonObstructive,
+            ),
+            (
+                EquipmentConfig::ThesisDetectorBeta,
+                ObstructionTier::LessObstructive,
+            ),
+        ]);
+        let without_beta = validation_config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        let (microscope, without_beta) =
+            (Microscope::build(&config), Microscope::build(&without_beta));
+
+        let items = |buffer: TriangleBufferVec| buffer.data().len();
+        assert!(
+            items(microscope.safe_present_static_full())
+                > items(without_beta.safe_present_static_full())
+        );
+        assert!(
+            items(microscope.safe_present_static_less_obstructive())
+                > items(without_beta.safe_present_static_less_obstructive())
+        );
+        assert_eq!(
+            items(without_beta.safe_present_static_non_obstructive()),
+            items(microscope.safe_present_static_non_obstructive())
+        );
+    }
+
+    #[test]
+    fn path_min_clearance_finds_nearest_approach() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+
+        let pose = |x, z| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, z)));
+        let path = [pose(-0.01, 0.04), pose(0.0, 0.05), pose(0.01, 0.04)];
+
+        let coarse = microscope.safe_path_min_clearance(&path);
+        let step = SixAxis::from(&microscope.safe_suggest_step_size());
+        let fine_step = SixAxis {
+            pos: step.pos / 4.0,
+            rot: step.rot / 4.0,
+        };
+        let fine = microscope.path_min_clearance_with_step(&path, &fine_step);
+
+        assert!(coarse.is_finite() && coarse > 0.0);
+        assert!(coarse >= fine);
+        assert!(coarse - fine < step.pos.x());
+        assert_eq!(f64::INFINITY, microscope.safe_path_min_clearance(&[]));
+        assert_eq!(0.0, microscope.safe_path_min_clearance(&[pose(0.0, 0.1)]));
+    }
+
+    #[test]
+    fn prepared_matches_unprepared() {
+        let config = validation_config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        let (mut prepared, unprepared) = (Microscope::build(&config), Microscope::build(&config));
+
+        assert_eq!(Ok(()), prepared.safe_prepare());
+        let assembled = prepared.static_immovable.inner().lock().unwrap().clone();
+        let assembled = assembled.expect("static parts assembled by prepare");
+        assert!(unprepared
+            .static_immovable
+            .inner()
+            .lock()
+            .unwrap()
+            .is_none());
+
+        let target = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.03, 0.0, 0.02)));
+        let expected = unprepared.safe_find_stage_path(&target);
+        let actual = prepared.safe_find_stage_path(&target);
+        assert_eq!(expected.nodes(), actual.nodes());
+        assert!(Arc::ptr_eq(&assembled, &prepared.static_immovable()));
+    }
+
+    #[test]
+    fn frozen_equipment_keeps_collisions() {
+        let config = validation_config(vec![
+            EquipmentConfig::ThesisDetectorAlpha,
+            EquipmentConfig::ThesisDetectorBeta,
+        ]);
+        let mut microscope = Microscope::build(&config);
+
+        let frozen = microscope.frozen_equipment.inner().clone().unwrap();
+        let individual = microscope
+            .equipment
+            .inner()
+            .iter()
+            .map(|(e, _)| e.get_ref().collider().into_bvh().triangle_buffer().len())
+            .sum::<usize>();
+        assert_eq!(individual, frozen.triangle_buffer().len());
+
+        let poses = (-4..=4)
+            .flat_map(|i| (0..=4).map(move |j| (i as f64 * 0.025, j as f64 * 0.025)))
+            .map(|(x, z)| SixAxis {
+                pos: Vector3::new(x, 0.0, z),
+                rot: Vector3::ZERO,
+            })
+            .collect::<Vec<_>>();
+        let check = |microscope: &Microscope| {
+            let immovable = microscope.immovable_without_stage();
+            poses
+                .iter()
+                .map(|p| immovable.collides_with(&microscope.movable_stage().move_to(p)))
+                .collect::<Vec<_>>()
+        };
+
+        let with_frozen = check(&microscope);
+        *microscope.frozen_equipment.inner_mut() = None;
+        let without_frozen = check(&microscope);
+
+        assert_eq!(without_frozen, with_frozen);
+        assert!(with_frozen.contains(&true));
+        assert!(with_frozen.contains(&false));
+    }
+
+    #[test]
+    fn equipment_added_and_removed_live() {
+        let mut microscope = Microscope::build(&validation_config(vec![]));
+        let mounted = Microscope::build(&validation_config(vec![
+            EquipmentConfig::ThesisDetectorAlpha,
+        ]));
+
+        let poses = (-4..=4)
+            .flat_map(|i| (0..=4).map(move |j| (i as f64 * 0.025, j as f64 * 0.025)))
+            .map(|(x, z)| SixAxis {
+                pos: Vector3::new(x, 0.0, z),
+                rot: Vector3::ZERO,
+            })
+            .collect::<Vec<_>>();
+        let check = |microscope: &Microscope| {
+            let immovable = microscope.immovable_without_stage();
+            poses
+                .iter()
+                .map(|p| immovable.collides_with(&microscope.movable_stage().move_to(p)))
+                .collect::<Vec<_>>()
+        };
+        let items = |microscope: &Microscope| microscope.safe_present_static_full().data().len();
+
+        assert_eq!(Ok(()), microscope.safe_prepare());
+        let without = check(&microscope);
+        let without_items = items(&microscope);
+        assert_ne!(without, check(&mounted));
+
+        microscope.safe_add_equipment(&EquipmentConfig::ThesisDetectorAlpha);
+        assert_eq!(check(&mounted), check(&microscope));
+        assert_eq!(items(&mounted), items(&microscope));
+        assert!(items(&microscope) > without_items);
+
+        assert!(!microscope.safe_remove_equipment(1));
+        assert!(microscope.safe_remove_equipment(0));
+        assert_eq!(without, check(&microscope));
+        assert_eq!(without_items, items(&microscope));
+        assert!(microscope.frozen_equipment.inner().is_none());
+    }
+
+    #[test]
+    fn advance_toward_stops_before_obstacle() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let collides = |state: &SixAxis| {
+            microscope
+                .immovable_without_stage()
+                .collides_with(&microscope.movable_stage().move_to(state))
+        };
+
+        let from = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.05));
+        let blocked = SixAxis::from_position(Vector3::new(0.0, 0.0, 0.1));
+        let reached = microscope
+            .safe_advance_toward(&(&from).into(), &(&blocked).into())
+            .unwrap();
+        let reached = SixAxis::from(&reached);
+
+        assert!(!collides(&reached));
+        assert!(reached.pos.z() > from.pos.z());
+        assert!(reached.pos.z() < blocked.pos.z());
+        let beyond = SixAxis::from_position(reached.pos + Vector3::new(0.0, 0.0, 1e-4));
+        assert!(collides(&beyond));
+
+        let free = SixAxis::from_position(Vector3::new(0.03, 0.0, 0.05));
+        let reached = microscope.safe_advance_toward(&(&from).into(), &(&free).into());
+        assert_eq!(Some(CSixAxis::from(&free)), reached);
+
+        let colliding = microscope.safe_advance_toward(&(&blocked).into(), &(&from).into());
+        assert_eq!(None, colliding);
+    }
+
+    #[test]
+    fn height_map_with_non_finite_values() {
+        let config = validation_config(vec![]);
+        let mut microscope = Microscope::build(&config);
+        microscope.safe_update_holder(&HolderConfig::ThesisHolderCircle);
+        let height_map = [
+            1.0,
+            f64::NAN,
+            1.0,
+            f64::INFINITY,
+            2.0,
+            1.0,
+            1.0,
+            f64::NEG_INFINITY,
+            1.0,
+        ];
+
+        microscope.safe_update_sample_height_map(&height_map, 3, 3, 10.0, f64::INFINITY);
+        microscope.safe_update_sample_height_map(&height_map, 3, 3, 10.0, 10.0);
+
+        let stage = microscope.safe_present_stage();
+        for buffer in stage.data() {
+            assert!(buffer
+                .data()
+                .iter()
+                .all(|v| v.x.is_finite() && v.y.is_finite() && v.z.is_finite()));
+        }
+    }
+
+    #[test]
+    fn sample_rotation_rotates_presented_sample() {
+        let config = validation_config(vec![]);
+        let mut microscope = Microscope::build(&config);
+        microscope.safe_update_holder(&HolderConfig::ThesisHolderSquare);
+        microscope.safe_update_sample_height_map(&[0.001; 4], 2, 2, 0.02, 0.004);
+        let extents = |microscope: &Microscope| {
+            let stage = microscope.safe_present_stage();
+            let sample = stage.data().last().unwrap();
+            let points = sample
+                .data()
+                .iter()
+                .map(|v| Vector3::new(v.x, v.y, v.z))
+                .collect::<Vec<_>>();
+            let (min, max) = maths::bounds_of(&points).unwrap();
+            max - min
+        };
+
+        let original = extents(&microscope);
+        microscope.safe_set_sample_rotation(&CVector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 90_f64.to_radians(),
+        });
+        let rotated = extents(&microscope);
+
+        assert!((original.x() - 0.02).abs() < 1e-6);
+        assert!((original.y() - 0.004).abs() < 1e-6);
+        assert!((rotated.x() - original.y()).abs() < 1e-6);
+        assert!((rotated.y() - original.x()).abs() < 1e-6);
+        assert!((rotated.z() - original.z()).abs() < 1e-6);
+    }
+
+    fn retract_config(retracts: &[u64]) -> Configuration {
+        let retracts = retracts
+            .iter()
+            .map(|&i| {
+                (
+                    make_id!(i),
+                    (
+                        RetractConfig::ThesisRetract,
+                        ResolverRetractConfig::RetractLinearResolver {
+                            step_size: CLinearState { t: 0.1 },
+                        },
+                    ),
+                )
+            })
+            .collect();
+        Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            retracts,
+        )
+    }
+
+    fn modified_microscope() -> Microscope {
+        let config = retract_config(&[11]);
+        let mut microscope = Microscope::build(&config);
+        microscope.safe_update_holder(&HolderConfig::ThesisHolderSquare);
+        microscope.safe_update_sample_height_map(&[0.001; 4], 2, 2, 0.01, 0.01);
+        microscope.safe_update_stage_state(&CSixAxis::from(&SixAxis::from_position(Vector3::new(
+            0.01, 0.0, 0.05,
+        ))));
+        microscope
+            .safe_update_retract_state(make_id!(11), &CLinearState { t: 0.4 })
+            .unwrap();
+        microscope
+    }
+
+    #[test]
+    fn with_config_keeps_valid_states() {
+        let microscope = modified_microscope();
+        let config = retract_config(&[11, 12]);
+
+        let (rebuilt, update) = microscope.safe_with_config(&config);
+
+        assert!(update.is_ok());
+        assert_eq!(microscope.safe_stage_state(), rebuilt.safe_stage_state());
+        assert_eq!(
+            Some(CLinearState { t: 0.4 }),
+            rebuilt.safe_retract_state(make_id!(11))
+        );
+        assert_eq!(
+            Some(CLinearState { t: 0.0 }),
+            rebuilt.safe_retract_state(make_id!(12))
+        );
+        assert_eq!(
+            buffer_to_packed_f32(&microscope.safe_present_stage()),
+            buffer_to_packed_f32(&rebuilt.safe_present_stage())
         );
-        CPathResultSixAxis::from(result)
     }
 
-    fn safe_find_retract_path(&self, id: Id, state: &CLinearState) -> CPathResultLinearState {
-        let movable = self.movable_retract(id).unwrap();
-        let immovable = self.immovable_stage();
-        let from = self.retracts.inner()[&id].2;
-        let result = self.retracts.inner()[&id].1.get_ref().resolve_path(
-            &LinearState::from(&from),
-            &LinearState::from(state),
-            movable.as_ref(),
-            &immovable,
+    #[test]
+    fn with_config_reports_states_invalid_in_new_geometry() {
+        let config = validation_config(vec![]);
+        let mut microscope = Microscope::build(&config);
+        let below_detector = SixAxis::from_position(Vector3::new(-0.04, 0.0, 0.1));
+        microscope.safe_update_stage_state(&CSixAxis::from(&below_detector));
+
+        let cluttered = validation_config(vec![EquipmentConfig::ThesisDetectorAlpha]);
+        let (rebuilt, update) = microscope.safe_with_config(&cluttered);
+
+        assert!(matches!(update, Err(StateUpdateError::InvalidState)));
+        assert_eq!(microscope.safe_stage_state(), rebuilt.safe_stage_state());
+    }
+
+    #[test]
+    fn with_config_keeps_live_equipment_changes() {
+        let config = validation_config(vec![EquipmentConfig::ThesisDetectorBeta]);
+        let mut microscope = Microscope::build(&config);
+
+        microscope.safe_add_equipment(&EquipmentConfig::ThesisDetectorAlpha);
+        assert!(microscope.safe_remove_equipment(0));
+        let (rebuilt, _) = microscope.safe_with_config(&microscope.configuration.inner().clone());
+
+        assert_eq!(
+            &[(
+                EquipmentConfig::ThesisDetectorAlpha,
+                ObstructionTier::default()
+            )],
+            rebuilt.configuration.inner().equipment()
         );
-        CPathResultLinearState::from(result)
+        assert_eq!(1, rebuilt.equipment.inner().len());
     }
 
-    fn safe_present_static_full(&self) -> TriangleBufferVec {
-        let chamber = self.chamber.get_ref().full();
-        collider_to_triangle_buffer_per_item(self.add_equipment(chamber))
+    #[cfg(not(feature = "ffi"))]
+    #[test]
+    fn with_modified_config_uses_new_stage_resolver() {
+        let microscope = modified_microscope();
+        let resolver = ResolverStageConfig::StageBidirectionalResolver {
+            step_size: STEP,
+            connect_distance: 0.01,
+        };
+
+        let (modified, update) = microscope
+            .with_modified_config(|builder| {
+                *builder =
+                    std::mem::take(builder).with_stage(StageConfig::ThesisStage, resolver.clone());
+            })
+            .unwrap();
+
+        assert!(update.is_ok());
+        assert_eq!(&resolver, modified.configuration.inner().stage_resolver());
+        assert_eq!(microscope.safe_stage_state(), modified.safe_stage_state());
+        assert_eq!(
+            Some(CLinearState { t: 0.4 }),
+            modified.safe_retract_state(make_id!(11))
+        );
+
+        let duplicate = microscope.with_modified_config(|builder| {
+            *builder = std::mem::take(builder).with_retract(
+                make_id!(11),
+                RetractConfig::ThesisRetract,
+                ResolverRetractConfig::RetractLinearResolver {
+                    step_size: CLinearState { t: 0.1 },
+                },
+            );
+        });
+        assert!(matches!(
+            duplicate,
+            Err(ConfigBuilderResult::DuplicateRetractId)
+        ));
     }
 
-    fn safe_present_static_less_obstructive(&self) -> TriangleBufferVec {
-        let chamber = self.chamber.get_ref().less_obstructive();
-        collider_to_triangle_buffer_per_item(self.add_equipment(chamber))
+    #[test]
+    fn replay_reproduces_recorded_mutations() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        );
+        let mut microscope = Microscope::build(&config);
+        let start = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, 0.05)));
+        let goal = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.03, 0.0, 0.05)));
+
+        microscope.safe_start_recording();
+        microscope.safe_update_holder(&HolderConfig::ThesisHolderSquare);
+        microscope.safe_update_sample_height_map(&[0.001, 0.002, 0.003, 0.004], 2, 2, 0.01, 0.01);
+        microscope.safe_add_equipment(&EquipmentConfig::ThesisDetectorAlpha);
+        assert!(!microscope.safe_remove_equipment(5));
+        microscope.safe_update_stage_state(&start);
+        microscope
+            .safe_update_retract_state(make_id!(11), &CLinearState { t: 0.3 })
+            .unwrap();
+        microscope.safe_update_resolvers().unwrap();
+        let log = microscope.safe_stop_recording();
+
+        assert_eq!(7, log.events().len());
+        assert_eq!(
+            MicroscopeEvent::UpdateHolder(HolderConfig::ThesisHolderSquare),
+            log.events()[0]
+        );
+        assert!(microscope.safe_stop_recording().events().is_empty());
+
+        let replayed = Microscope::safe_replay(&config, log.events());
+
+        assert_eq!(start, replayed.safe_stage_state());
+        assert_eq!(
+            Some(CLinearState { t: 0.3 }),
+            replayed.safe_retract_state(make_id!(11))
+        );
+        assert_eq!(1, replayed.equipment.inner().len());
+        assert_eq!(
+            buffer_to_packed_f32(&microscope.safe_present_stage()),
+            buffer_to_packed_f32(&replayed.safe_present_stage())
+        );
+        let expected = microscope.safe_find_stage_path(&goal);
+        let actual = replayed.safe_find_stage_path(&goal);
+        assert_eq!(expected.state(), actual.state());
+        assert_eq!(expected.nodes(), actual.nodes());
     }
 
-    fn safe_present_static_non_obstructive(&self) -> TriangleBufferVec {
-        let chamber = self.chamber.get_ref().non_obstructive();
-        collider_to_triangle_buffer_per_item(self.add_equipment(chamber))
+    #[test]
+    fn home_restores_configured_states() {
+        let home = CSixAxis {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.1,
+        };
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.1 },
+                    },
+                ),
+            )],
+        )
+        .with_homes(home, vec![(make_id!(11), CLinearState { t: 0.2 })]);
+        let mut microscope = Microscope::build(&config);
+        assert_eq!(home, microscope.stage_state);
+
+        microscope.safe_update_stage_state(&STEP);
+        microscope
+            .safe_update_retract_state(make_id!(11), &CLinearState { t: 0.9 })
+            .unwrap();
+        microscope.safe_home().unwrap();
+
+        assert_eq!(home, microscope.stage_state);
+        assert_eq!(
+            CLinearState { t: 0.2 },
+            microscope.retracts.inner()[&make_id!(11)].2
+        );
     }
 
-    fn safe_present_stage(&self) -> TriangleBufferVec {
-        let stage = self
-            .stage
-            .get_ref()
-            .move_to(&SixAxis::from(&self.stage_state));
-        collider_to_triangle_buffer_per_item(stage)
+    #[test]
+    fn stage_contacts_are_allowed_by_resolver() {
+        let lowered = |x| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, -0.001)));
+        let touching = |contacts: Vec<(StagePart, ObstaclePart)>| {
+            let config = Configuration::new(
+                ChamberConfig::ThesisChamber,
+                StageConfig::ThesisStage,
+                ResolverStageConfig::StageLinearResolver { step_size: STEP },
+                vec![EquipmentConfig::ThesisDetectorAlpha],
+                vec![],
+            )
+            .with_homes(lowered(0.0), vec![])
+            .with_stage_contacts(contacts);
+            Microscope::build(&config)
+        };
+        let all_parts = |obstacle| {
+            vec![
+                (StagePart::Stage, obstacle),
+                (StagePart::Holder, obstacle),
+                (StagePart::Sample, obstacle),
+            ]
+        };
+
+        //  the lowered stage sinks into the chamber floor
+        let mut strict = touching(vec![]);
+        assert!(strict.safe_update_resolvers().is_err());
+        let result = strict.safe_find_stage_path(&lowered(0.01));
+        assert_eq!(PathResultState::InvalidStart, result.state());
+
+        let mut other = touching(all_parts(ObstaclePart::Equipment(0)));
+        assert!(other.safe_update_resolvers().is_err());
+
+        let mut allowed = touching(all_parts(ObstaclePart::Chamber));
+        allowed.safe_update_resolvers().unwrap();
+        let result = allowed.safe_find_stage_path(&lowered(0.01));
+        assert_eq!(PathResultState::Path, result.state());
     }
 
-    fn safe_present_stage_at(&self, state: &CSixAxis) -> TriangleBufferVec {
-        let stage = self.stage.get_ref().move_to(&SixAxis::from(state));
-        collider_to_triangle_buffer_per_item(stage)
+    #[test]
+    fn stage_contacts_are_allowed_by_stage_queries() {
+        let lowered = |x| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, -0.001)));
+        let contacts = [StagePart::Stage, StagePart::Holder, StagePart::Sample]
+            .map(|part| (part, ObstaclePart::Chamber))
+            .to_vec();
+        let config = validation_config(vec![])
+            .with_homes(lowered(0.0), vec![])
+            .with_stage_contacts(contacts);
+        let mut microscope = Microscope::build(&config);
+        microscope.safe_update_resolvers().unwrap();
+
+        let result = microscope.safe_find_stage_path(&lowered(0.01));
+        assert_eq!(PathResultState::Path, result.state());
+        assert_eq!(
+            PathValidation::Ok,
+            microscope.safe_validate_stage_path(result.nodes())
+        );
+        assert_eq!(
+            vec![true],
+            microscope.safe_filter_valid_poses(&[lowered(0.0)])
+        );
+        assert_eq!(
+            Some(lowered(0.0)),
+            microscope.safe_nudge_to_free(&lowered(0.0), 0.01)
+        );
     }
 
-    fn safe_present_retract(&self, id: Id) -> TriangleBufferVec {
-        let (retract, _, state) = &self.retracts.inner()[&id];
-        let retracted = retract.get_ref().move_to(&LinearState::from(state));
-        collider_to_triangle_buffer_per_item(retracted)
+    #[test]
+    fn remove_equipment_reindexes_stage_contacts() {
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![
+                EquipmentConfig::ThesisDetectorAlpha,
+                EquipmentConfig::ThesisDetectorBeta,
+                EquipmentConfig::ThesisDetectorAlpha,
+            ],
+            vec![],
+        )
+        .with_stage_contacts(vec![
+            (StagePart::Stage, ObstaclePart::Equipment(0)),
+            (StagePart::Holder, ObstaclePart::Equipment(1)),
+            (StagePart::Sample, ObstaclePart::Equipment(2)),
+            (StagePart::Stage, ObstaclePart::Chamber),
+        ]);
+        let mut microscope = Microscope::build(&config);
+
+        assert!(microscope.safe_remove_equipment(1));
+
+        let expected = [
+            (StagePart::Stage, ObstaclePart::Equipment(0)),
+            (StagePart::Sample, ObstaclePart::Equipment(1)),
+            (StagePart::Stage, ObstaclePart::Chamber),
+        ];
+        assert_eq!(
+            expected.as_slice(),
+            microscope.configuration.inner().stage_contacts()
+        );
     }
 
-    fn safe_present_retract_at(&self, id: Id, state: &CLinearState) -> TriangleBufferVec {
-        let (retract, _, _) = &self.retracts.inner()[&id];
-        let retracted = retract.get_ref().move_to(&LinearState::from(state));
-        collider_to_triangle_buffer_per_item(retracted)
+    #[test]
+    fn states_snap_to_resolver_grid() {
+        let step = CSixAxis {
+            x: 0.25,
+            y: 0.25,
+            z: 0.25,
+            rx: 0.5,
+            ry: 0.5,
+            rz: 0.5,
+        };
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: step },
+            vec![],
+            vec![(
+                make_id!(11),
+                (
+                    RetractConfig::ThesisRetract,
+                    ResolverRetractConfig::RetractLinearResolver {
+                        step_size: CLinearState { t: 0.25 },
+                    },
+                ),
+            )],
+        );
+        let mut microscope = Microscope::build(&config);
+        microscope.stage_state = CSixAxis::from(&SixAxis::from_position(Vector3::ZERO));
+        microscope
+            .retracts
+            .inner_mut()
+            .get_mut(&make_id!(11))
+            .unwrap()
+            .2 = CLinearState { t: 0.0 };
+
+        let off_grid = CSixAxis {
+            x: 0.74,
+            y: -0.52,
+            z: 1.27,
+            rx: 1.02,
+            ry: -0.49,
+            rz: 0.03,
+        };
+        let on_grid = CSixAxis {
+            x: 0.75,
+            y: -0.5,
+            z: 1.25,
+            rx: 1.0,
+            ry: -0.5,
+            rz: 0.0,
+        };
+        assert_eq!(on_grid, microscope.safe_snap_stage_to_grid(&off_grid));
+        assert_eq!(on_grid, microscope.safe_snap_stage_to_grid(&on_grid));
+
+        let id = make_id!(11);
+        assert_eq!(
+            Some(CLinearState { t: 0.25 }),
+            microscope.safe_snap_retract_to_grid(id, &CLinearState { t: 0.27 })
+        );
+        assert_eq!(
+            Some(CLinearState { t: 0.5 }),
+            microscope.safe_snap_retract_to_grid(id, &CLinearState { t: 0.5 })
+        );
+        assert_eq!(
+            None,
+            microscope.safe_snap_retract_to_grid(make_id!(12), &CLinearState { t: 0.5 })
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::configuration::chamber::ChamberConfig;
-    use crate::configuration::equipment::EquipmentConfig;
-    use crate::configuration::resolver_retract::ResolverRetractConfig;
-    use crate::configuration::resolver_stage::ResolverStageConfig;
-    use crate::configuration::retract::RetractConfig;
-    use crate::configuration::stage::StageConfig;
-    use crate::id::make_id;
-    use crate::types::{CLinearState, CSixAxis};
+    #[test]
+    fn difficulty_of_clear_and_walled_targets() {
+        let config = fine_step_config();
+        let microscope = Microscope::build(&config);
+        let state =
+            |x: f64, z: f64| CSixAxis::from(&SixAxis::from_position(Vector3::new(x, 0.0, z)));
 
-    const STEP: CSixAxis = CSixAxis {
-        x: 0.1,
-        y: 0.2,
-        z: 0.3,
-        rx: 0.4,
-        ry: 0.5,
-        rz: 0.6,
-    };
+        assert_eq!(
+            Difficulty::Trivial,
+            microscope.safe_estimate_difficulty(&state(-0.03, 0.05), &state(0.03, 0.05))
+        );
+        assert_eq!(
+            Difficulty::Hard,
+            microscope.safe_estimate_difficulty(&state(0.0, 0.05), &state(0.0, 0.27))
+        );
+        assert_eq!(
+            Difficulty::Hard,
+            microscope.safe_estimate_difficulty(&state(0.0, 0.05), &state(0.0, 0.1))
+        );
+    }
 
     #[test]
-    fn build_microscope_without_leak() {
+    fn swept_bounds_enclose_segment_ends() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let path = [
+            CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, 0.05))),
+            CSixAxis {
+                x: 0.03,
+                y: -0.01,
+                z: 0.05,
+                rx: 0.0,
+                ry: 0.0,
+                rz: 0.2,
+            },
+            CSixAxis {
+                x: -0.02,
+                y: 0.01,
+                z: 0.04,
+                rx: 0.1,
+                ry: 0.0,
+                rz: 0.2,
+            },
+        ];
+
+        let bounds = microscope.safe_swept_volume_bounds(&path);
+
+        assert_eq!(2, bounds.len());
+        let eps = Vector3::new(1e-9, 1e-9, 1e-9);
+        for (bound, segment) in bounds.iter().zip(path.windows(2)) {
+            for state in segment {
+                let stage =
+                    group_bounds(&microscope.movable_stage().move_to(&SixAxis::from(state)));
+                assert_eq!(bound.min(), bound.min().minimized(&(stage.min() + eps)));
+                assert_eq!(bound.max(), bound.max().maximized(&(stage.max() - eps)));
+            }
+        }
+        assert!(microscope.safe_swept_volume_bounds(&path[..1]).is_empty());
+    }
+
+    #[test]
+    fn unknown_retract_id_is_reported() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let id = make_id!(42);
+        let state = CLinearState { t: 0.5 };
+
+        let path = microscope.safe_find_retract_path(id, &state);
+        assert_eq!(PathResultState::InvalidId, path.state());
+        assert!(path.nodes().is_empty());
+        assert!(microscope.safe_present_retract(id).data().is_empty());
+        assert!(microscope
+            .safe_present_retract_at(id, &state)
+            .data()
+            .is_empty());
+    }
+
+    #[test]
+    fn tilted_state_rotated_to_free() {
+        let config = validation_config(vec![]);
+        let microscope = Microscope::build(&config);
+        let collides = |state: &CSixAxis| {
+            microscope
+                .immovable_without_stage()
+                .collides_with(&microscope.movable_stage().move_to(&SixAxis::from(state)))
+        };
+        let tilted = |ry: f64| CSixAxis {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0795,
+            rx: 0.0,
+            ry,
+            rz: 0.0,
+        };
+
+        let state = tilted(0.1);
+        assert!(collides(&state));
+        let rotated = microscope
+            .safe_rotate_to_free(&state, CAxis::Y, 0.2)
+            .unwrap();
+        assert!(!collides(&rotated));
+        assert!((rotated.ry - state.ry).abs() <= 0.2);
+        assert_eq!(
+            (state.x, state.y, state.z),
+            (rotated.x, rotated.y, rotated.z)
+        );
+        assert_eq!((state.rx, state.rz), (rotated.rx, rotated.rz));
+
+        let state = tilted(0.3);
+        assert!(collides(&state));
+        assert_eq!(None, microscope.safe_rotate_to_free(&state, CAxis::Y, 0.05));
+        assert_eq!(None, microscope.safe_rotate_to_free(&state, CAxis::X, 0.5));
+    }
+
+    #[test]
+    fn reachable_extents_end_at_chamber_walls() {
+        let config = fine_step_config();
+        let mut microscope = Microscope::build(&config);
+        let collides = |microscope: &Microscope, state: &CSixAxis| {
+            microscope
+                .immovable_without_stage()
+                .collides_with(&microscope.movable_stage().move_to(&SixAxis::from(state)))
+        };
+        let start = CSixAxis {
+            x: 0.0,
+            y: 0.0,
+            z: 0.05,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+        };
+        microscope.safe_update_stage_state(&start);
+
+        let (min, max) = microscope.safe_reachable_extents();
+        assert!(min.x <= -0.07 && max.x >= 0.07);
+        assert!(min.z < start.z && max.z > start.z && max.z < 0.08);
+
+        let margin = 1e-4;
+        for (free, blocked) in [
+            (
+                CSixAxis { x: max.x, ..start },
+                CSixAxis {
+                    x: max.x + margin,
+                    ..start
+                },
+            ),
+            (
+                CSixAxis { x: min.x, ..start },
+                CSixAxis {
+                    x: min.x - margin,
+                    ..start
+                },
+            ),
+            (
+                CSixAxis { y: max.y, ..start },
+                CSixAxis {
+                    y: max.y + margin,
+                    ..start
+                },
+            ),
+            (
+                CSixAxis { y: min.y, ..start },
+                CSixAxis {
+                    y: min.y - margin,
+                    ..start
+                },
+            ),
+            (
+                CSixAxis { z: max.z, ..start },
+                CSixAxis {
+                    z: max.z + margin,
+                    ..start
+                },
+            ),
+            (
+                CSixAxis { z: min.z, ..start },
+                CSixAxis {
+                    z: min.z - margin,
+                    ..start
+                },
+            ),
+        ] {
+            assert!(!collides(&microscope, &free));
+            assert!(collides(&microscope, &blocked));
+        }
+
+        let blocked = CSixAxis { z: 0.2, ..start };
+        microscope.safe_update_stage_state(&blocked);
+        let (min, max) = microscope.safe_reachable_extents();
+        assert_eq!((blocked, blocked), (min, max));
+    }
+
+    #[test]
+    fn states_read_back() {
+        let state = CSixAxis {
+            x: 0.001,
+            y: -0.002,
+            z: 0.003,
+            rx: 0.1,
+            ry: 0.0,
+            rz: -0.2,
+        };
         let config = Configuration::new(
             ChamberConfig::ThesisChamber,
             StageConfig::ThesisStage,
-            ResolverStageConfig::StageLinearResolver { step_size: STEP },
-            vec![EquipmentConfig::ThesisDetectorAlpha],
+            ResolverStageConfig::StageLinearResolver { step_size: state },
+            vec![],
             vec![(
                 make_id!(11),
                 (
@@ -599,10 +4537,107 @@ mod tests {
                 ),
             )],
         );
+        let mut microscope = Microscope::build(&config);
+
+        microscope.safe_update_stage_state(&state);
+        microscope
+            .safe_update_retract_state(make_id!(11), &CLinearState { t: 0.4 })
+            .unwrap();
+
+        assert_eq!(state, microscope.safe_stage_state());
+        assert_eq!(
+            Some(CLinearState { t: 0.4 }),
+            microscope.safe_retract_state(make_id!(11))
+        );
+        assert_eq!(None, microscope.safe_retract_state(make_id!(12)));
+    }
+
+    fn replan_microscope() -> Microscope {
+        Microscope::build(&validation_config(vec![]))
+    }
+
+    #[test]
+    fn replan_reuses_valid_tail() {
+        let microscope = replan_microscope();
+        let previous = [
+            SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0)),
+            SixAxis::from_position(Vector3::new(0.02, 0.0, 0.02)),
+            SixAxis::from_position(Vector3::new(0.02, 0.0, 0.04)),
+        ];
+        let current = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.012, 0.0, 0.012)));
+        let goal = CSixAxis::from(&previous[2]);
+
+        let result = microscope.safe_replan_from(&current, &goal, &previous, &CancelToken::new());
+
+        let expected = [
+            current,
+            CSixAxis::from(&previous[1]),
+            CSixAxis::from(&previous[2]),
+        ];
+        assert_eq!(&expected, result.nodes());
+    }
+
+    #[test]
+    fn replan_discards_colliding_tail() {
+        let microscope = replan_microscope();
+        //  Middle node collides with the chamber
+        let previous = [
+            SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0)),
+            SixAxis::from_position(Vector3::new(0.0, 0.0, 0.1)),
+            SixAxis::from_position(Vector3::new(0.02, 0.0, 0.04)),
+        ];
+        let current = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, 0.01)));
+        let goal = CSixAxis::from(&previous[2]);
+
+        let result = microscope.safe_replan_from(&current, &goal, &previous, &CancelToken::new());
+
+        assert_eq!(&[current, goal], result.nodes());
+    }
+
+    #[test]
+    fn replan_rejects_colliding_current() {
+        let microscope = replan_microscope();
+        let previous = [
+            SixAxis::from_position(Vector3::new(0.0, 0.0, 0.0)),
+            SixAxis::from_position(Vector3::new(0.02, 0.0, 0.04)),
+        ];
+        let current = CSixAxis::from(&SixAxis::from_position(Vector3::new(0.0, 0.0, 0.1)));
+        let goal = CSixAxis::from(&previous[1]);
+
+        let result = microscope.safe_replan_from(&current, &goal, &previous, &CancelToken::new());
+
+        assert_eq!(PathResultState::InvalidStart, result.state());
+    }
+
+    #[test]
+    fn retracts_share_stage_immovable() {
+        let resolver = ResolverRetractConfig::RetractLinearResolver {
+            step_size: CLinearState { t: 0.1 },
+        };
+        let config = Configuration::new(
+            ChamberConfig::ThesisChamber,
+            StageConfig::ThesisStage,
+            ResolverStageConfig::StageLinearResolver { step_size: STEP },
+            vec![],
+            vec![
+                (
+                    make_id!(11),
+                    (RetractConfig::ThesisRetract, resolver.clone()),
+                ),
+                (make_id!(12), (RetractConfig::ThesisRetract, resolver)),
+            ],
+        );
+        let mut microscope = Microscope::build(&config);
+        let cached = |m: &Microscope| m.stage_immovable.inner().lock().unwrap().clone();
+        assert!(cached(&microscope).is_none());
+
+        microscope.safe_find_retract_path(make_id!(11), &CLinearState { t: 0.5 });
+        let first = cached(&microscope).unwrap();
+        microscope.safe_find_retract_path(make_id!(12), &CLinearState { t: 0.5 });
+        let second = cached(&microscope).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
 
-        #[cfg(feature = "ffi")]
-        let _microscope = Microscope::microscope_from_config(&config);
-        #[cfg(not(feature = "ffi"))]
-        let _microscope = Microscope::from_config(&config);
+        microscope.safe_update_stage_state(&STEP);
+        assert!(cached(&microscope).is_none());
     }
 }
@@ -1,7 +1,10 @@
 use crate::ffi::ffi_vec_for_type;
 use crate::types::CVector3;
+use collisions::common::Bounded;
 use collisions::complex::group::ColliderGroup;
+use collisions::primitive::AlignedBoxCollider;
 use collisions::PrimaryCollider;
+use maths::{Quaternion, Vector3};
 
 ffi_vec_for_type!(pub, TriangleBuffer, trianglebuffer_drop, CVector3);
 ffi_vec_for_type!(
@@ -10,6 +13,15 @@ ffi_vec_for_type!(
     trianglebuffervec_drop,
     TriangleBuffer
 );
+ffi_vec_for_type!(pub, BoundingBoxBuffer, boundingboxbuffer_drop, CVector3);
+ffi_vec_for_type!(pub, PackedBuffer, packedbuffer_drop, u8);
+ffi_vec_for_type!(pub, InterleavedBuffer, interleavedbuffer_drop, f32);
+ffi_vec_for_type!(
+    pub,
+    InterleavedBufferVec,
+    interleavedbuffervec_drop,
+    InterleavedBuffer
+);
 
 pub fn collider_to_triangle_buffer_per_item(
     group: ColliderGroup<PrimaryCollider>,
@@ -23,9 +35,152 @@ pub fn collider_to_triangle_buffer_per_item(
     TriangleBufferVec::from_vec(vec)
 }
 
+/// Number of chunks of `chunk_size` triangles needed for all the triangles in the group.
+pub fn triangle_chunk_count(group: &ColliderGroup<PrimaryCollider>, chunk_size: usize) -> usize {
+    if chunk_size == 0 {
+        return 0;
+    }
+
+    let triangles = group.0.iter().map(|c| c.triangle_count()).sum::<usize>();
+    triangles.div_ceil(chunk_size)
+}
+
+/// Vertices of the triangles in the chunk at `chunk_index`,
+/// with the items concatenated in the order of [collider_to_triangle_buffer_per_item].
+///
+/// Only the requested chunk is materialized, the last chunk may be shorter.
+pub fn triangle_chunk(
+    group: &ColliderGroup<PrimaryCollider>,
+    chunk_index: usize,
+    chunk_size: usize,
+) -> Vec<CVector3> {
+    let mut skip = chunk_index.saturating_mul(chunk_size);
+    let mut chunk = Vec::new();
+    for collider in &group.0 {
+        let remaining = chunk_size - chunk.len() / 3;
+        if remaining == 0 {
+            break;
+        }
+
+        let count = collider.triangle_count();
+        if skip >= count {
+            skip -= count;
+            continue;
+        }
+
+        chunk.extend(
+            collider
+                .triangle_range(skip, remaining)
+                .iter()
+                .map(CVector3::from),
+        );
+        skip = 0;
+    }
+
+    chunk
+}
+
+/// Bounding box of each item in the group, in the same order as [collider_to_triangle_buffer_per_item].
+pub fn collider_to_bounding_boxes(
+    group: &ColliderGroup<PrimaryCollider>,
+) -> Vec<AlignedBoxCollider> {
+    group
+        .0
+        .iter()
+        .map(|c| AlignedBoxCollider::from_min_max(c.min(), c.max()))
+        .collect()
+}
+
+/// Flatten the boxes into consecutive `min`, `max` pairs.
+pub fn bounding_boxes_to_buffer(boxes: &[AlignedBoxCollider]) -> BoundingBoxBuffer {
+    let data = boxes
+        .iter()
+        .flat_map(|b| [CVector3::from(&b.min()), CVector3::from(&b.max())])
+        .collect();
+    BoundingBoxBuffer::from_vec(data)
+}
+
+#[cfg(feature = "ffi")]
+impl TriangleBufferVec {
+    /// Pack the buffers into bytes, see [buffer_to_packed_f32] for the layout.
+    #[no_mangle]
+    pub extern "C" fn trianglebuffervec_to_packed_f32(&self) -> PackedBuffer {
+        PackedBuffer::from_vec(buffer_to_packed_f32(self))
+    }
+}
+
+/// Packs the buffers into little-endian bytes with vertices as `f32`.
+///
+/// Layout is the number of buffers as `u32`, then for each buffer
+/// the number of vertices as `u32` followed by `x, y, z` of every vertex.
+pub fn buffer_to_packed_f32(buffer: &TriangleBufferVec) -> Vec<u8> {
+    let vertices = buffer.data().iter().map(|b| b.data().len()).sum::<usize>();
+    let mut packed = Vec::with_capacity(4 + buffer.data().len() * 4 + vertices * 12);
+
+    packed.extend_from_slice(&(buffer.data().len() as u32).to_le_bytes());
+    for item in buffer.data() {
+        packed.extend_from_slice(&(item.data().len() as u32).to_le_bytes());
+        for v in item.data() {
+            for c in [v.x, v.y, v.z] {
+                packed.extend_from_slice(&(c as f32).to_le_bytes());
+            }
+        }
+    }
+
+    packed
+}
+
+/// Converts the group into interleaved buffers of `[px, py, pz, nx, ny, nz]` per vertex.
+///
+/// Every vertex carries the face normal of its triangle (flat shading).
+pub fn collider_to_interleaved_buffer_per_item(
+    group: ColliderGroup<PrimaryCollider>,
+) -> InterleavedBufferVec {
+    let vec = group
+        .triangle_buffer_with_normals_per_item(|v, n| {
+            [v.x(), v.y(), v.z(), n.x(), n.y(), n.z()].map(|f| f as f32)
+        })
+        .into_iter()
+        .map(|item| InterleavedBuffer::from_vec(item.concat()))
+        .collect::<Vec<InterleavedBuffer>>();
+
+    InterleavedBufferVec::from_vec(vec)
+}
+
+/// Applies a rigid transformation to every vertex of the buffer.
+///
+/// Vertices are rotated around the `pivot` first and then translated.
+pub fn transform_buffer(
+    buffer: &TriangleBufferVec,
+    rotation: &Quaternion,
+    pivot: &Vector3,
+    translation: &Vector3,
+) -> TriangleBufferVec {
+    let vec = buffer
+        .data()
+        .iter()
+        .map(|item| {
+            let transformed = item
+                .data()
+                .iter()
+                .map(|v| {
+                    let v = Vector3::from(v).rotate_around(rotation, pivot) + translation;
+                    CVector3::from(&v)
+                })
+                .collect::<Vec<CVector3>>();
+            TriangleBuffer::from_vec(transformed)
+        })
+        .collect::<Vec<TriangleBuffer>>();
+
+    TriangleBufferVec::from_vec(vec)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use collisions::collider_group;
+    use collisions::primitive::TriangleCollider;
+    use maths::asserts::{assert_vectors, assert_vectors_close};
 
     #[test]
     fn triangle_buffer_without_leaking() {
@@ -51,4 +206,143 @@ mod tests {
         let actual = TriangleBuffer::from_vec(triangles.clone());
         assert_eq!(expected, actual.data());
     }
+
+    #[test]
+    fn interleaved_buffer_shares_face_normal() {
+        let triangle = TriangleCollider::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+        );
+        let group = collider_group!(PrimaryCollider::build(&[triangle]));
+
+        let buffer = collider_to_interleaved_buffer_per_item(group);
+
+        let expected: [f32; 18] = [
+            0.0, 0.0, 0.0, 0.0, 0.0, -1.0, //
+            0.0, 1.0, 0.0, 0.0, 0.0, -1.0, //
+            1.0, 0.0, 0.0, 0.0, 0.0, -1.0, //
+        ];
+        assert_eq!(1, buffer.data().len());
+        assert_eq!(expected, buffer.data()[0].data());
+    }
+
+    fn packed_f32_to_buffer(packed: &[u8]) -> TriangleBufferVec {
+        let mut words = packed
+            .chunks_exact(4)
+            .map(|w| <[u8; 4]>::try_from(w).unwrap());
+        let mut next_u32 = || u32::from_le_bytes(words.next().unwrap());
+
+        let items = next_u32();
+        let vec = (0..items)
+            .map(|_| {
+                let vertices = next_u32();
+                let vec = (0..vertices)
+                    .map(|_| CVector3 {
+                        x: f32::from_bits(next_u32()) as f64,
+                        y: f32::from_bits(next_u32()) as f64,
+                        z: f32::from_bits(next_u32()) as f64,
+                    })
+                    .collect();
+                TriangleBuffer::from_vec(vec)
+            })
+            .collect();
+
+        TriangleBufferVec::from_vec(vec)
+    }
+
+    #[test]
+    fn packed_buffer_round_trip() {
+        let vertices = [
+            CVector3 {
+                x: 1.0,
+                y: -2.5,
+                z: 0.125,
+            },
+            CVector3 {
+                x: 1e-3,
+                y: 55e-3,
+                z: -0.3,
+            },
+            CVector3 {
+                x: 123.456,
+                y: 0.0,
+                z: 7.0,
+            },
+        ];
+        let buffer = TriangleBufferVec::from_vec(vec![
+            TriangleBuffer::from_vec(vertices.to_vec()),
+            TriangleBuffer::from_vec(vec![]),
+        ]);
+
+        let packed = buffer_to_packed_f32(&buffer);
+        assert_eq!(4 + 4 + 3 * 12 + 4, packed.len());
+
+        let unpacked = packed_f32_to_buffer(&packed);
+        assert_eq!(2, unpacked.data().len());
+        assert!(unpacked.data()[1].data().is_empty());
+        for (expected, actual) in vertices.iter().zip(unpacked.data()[0].data()) {
+            assert_vectors_close(Vector3::from(expected), Vector3::from(actual), 1e-5);
+        }
+    }
+
+    #[test]
+    fn transform_buffer_rotates_and_translates() {
+        let buffer = TriangleBufferVec::from_vec(vec![TriangleBuffer::from_vec(vec![CVector3 {
+            x: 2.0,
+            y: 1.0,
+            z: 0.0,
+        }])]);
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 90_f64.to_radians()));
+        let pivot = Vector3::new(1.0, 1.0, 0.0);
+        let translation = Vector3::new(0.0, 0.0, 1.0);
+
+        let transformed = transform_buffer(&buffer, &rotation, &pivot, &translation);
+
+        let actual = Vector3::from(&transformed.data()[0].data()[0]);
+        assert_vectors(Vector3::new(1.0, 2.0, 1.0), actual);
+    }
+
+    #[test]
+    fn chunks_concatenate_to_full_buffer() {
+        let item = |x: f64, count: usize| {
+            PrimaryCollider::build(
+                &(0..count)
+                    .map(|i| {
+                        let y = i as f64;
+                        TriangleCollider::new(
+                            Vector3::new(x, y, 0.0),
+                            Vector3::new(x + 1.0, y, 0.0),
+                            Vector3::new(x, y + 1.0, 0.0),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let group = collider_group!(item(0.0, 7), item(5.0, 1), item(10.0, 12));
+        let full = collider_to_triangle_buffer_per_item(collider_group!(
+            item(0.0, 7),
+            item(5.0, 1),
+            item(10.0, 12)
+        ));
+        let expected = full
+            .data()
+            .iter()
+            .flat_map(|b| b.data().to_vec())
+            .collect::<Vec<_>>();
+
+        for chunk_size in [1, 3, 8, 20, 64] {
+            let count = triangle_chunk_count(&group, chunk_size);
+            assert_eq!(20_usize.div_ceil(chunk_size), count);
+
+            let chunks = (0..count)
+                .flat_map(|i| triangle_chunk(&group, i, chunk_size))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, chunks, "chunk size {chunk_size}");
+            assert!(triangle_chunk(&group, count, chunk_size).is_empty());
+        }
+        assert_eq!(0, triangle_chunk_count(&group, 0));
+        assert_eq!(1, triangle_chunk_count(&group, usize::MAX));
+        assert_eq!(expected, triangle_chunk(&group, 0, usize::MAX));
+    }
 }
@@ -0,0 +1,242 @@
+use crate::ffi::opaque_ffi_for_type;
+use crate::id::Id;
+use crate::types::{CLinearState, CSixAxis};
+use maths::{Quaternion, Vector3};
+use std::f64::consts::TAU;
+
+opaque_ffi_for_type!(CBoxSliceRetractStates, Box<[(Id, CLinearState)]>);
+
+/// # Microscope State
+/// Snapshot of the stage and retract states of a [crate::microscope::Microscope].
+///
+/// Intended for visualization, e.g. scrubbing through a planned path with [lerp_snapshot].
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct MicroscopeState {
+    stage: CSixAxis,
+    retracts: CBoxSliceRetractStates,
+}
+
+impl MicroscopeState {
+    pub fn new(stage: CSixAxis, retracts: Vec<(Id, CLinearState)>) -> Self {
+        Self {
+            stage,
+            retracts: CBoxSliceRetractStates::from_inner(retracts.into_boxed_slice()),
+        }
+    }
+
+    pub fn stage(&self) -> &CSixAxis {
+        &self.stage
+    }
+
+    pub fn retracts(&self) -> &[(Id, CLinearState)] {
+        self.retracts.inner()
+    }
+
+    /// Returns the state of the retract, `None` if the id is not in the snapshot.
+    pub fn retract(&self, id: Id) -> Option<&CLinearState> {
+        self.retracts
+            .inner()
+            .iter()
+            .find(|(i, _)| *i == id)
+            .map(|(_, state)| state)
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl MicroscopeState {
+    /// Get the stage state of the snapshot.
+    #[no_mangle]
+    pub extern "C" fn microscope_state_stage(&self) -> CSixAxis {
+        self.stage
+    }
+
+    /// Get the state of the retract in the snapshot.
+    ///
+    /// Returns `false` and leaves `state` untouched if the id is not in the snapshot.
+    #[no_mangle]
+    pub extern "C" fn microscope_state_retract(&self, id: Id, state: &mut CLinearState) -> bool {
+        match self.retract(id) {
+            Some(s) => {
+                *state = *s;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Interpolate between two snapshots, see [lerp_snapshot].
+    #[no_mangle]
+    pub extern "C" fn microscope_state_lerp(
+        a: &MicroscopeState,
+        b: &MicroscopeState,
+        t: f64,
+    ) -> MicroscopeState {
+        lerp_snapshot(a, b, t)
+    }
+
+    /// # Safety
+    /// Takes ownership of the snapshot and drops it.
+    #[no_mangle]
+    pub extern "C" fn microscope_state_drop(self) {
+        //  dropped after leaving scope
+    }
+}
+
+/// Interpolates between two snapshots for visualization, `t` is clamped to `[0, 1]`.
+///
+/// Stage position and retracts are interpolated linearly, stage rotation is slerped
+/// and its angles are unwrapped to the nearest turn of the angles of `a`,
+/// the endpoints are returned exactly at `0` and `1`.
+/// Retracts missing in `b` keep their state from `a`, retracts missing in `a` are left out.
+///
+/// The result is not checked for collisions.
+pub fn lerp_snapshot(a: &MicroscopeState, b: &MicroscopeState, t: f64) -> MicroscopeState {
+    let t = t.clamp(0.0, 1.0);
+
+    let stage = if t == 0.0 {
+        *a.stage()
+    } else if t == 1.0 {
+        *b.stage()
+    } else {
+        lerp_stage(a.stage(), b.stage(), t)
+    };
+
+    let retracts = a
+        .retracts()
+        .iter()
+        .map(|(id, from)| {
+            let state = match b.retract(*id) {
+                Some(to) if t == 1.0 => *to,
+                Some(to) => CLinearState {
+                    t: from.t + (to.t - from.t) * t,
+                },
+                None => *from,
+            };
+            (*id, state)
+        })
+        .collect();
+
+    MicroscopeState::new(stage, retracts)
+}
+
+fn lerp_stage(from: &CSixAxis, to: &CSixAxis, t: f64) -> CSixAxis {
+    let position = Vector3::new(from.x, from.y, from.z).lerp(&Vector3::new(to.x, to.y, to.z), t);
+    let rotation = Quaternion::from_euler(&Vector3::new(from.rx, from.ry, from.rz))
+        .slerp(
+            &Quaternion::from_euler(&Vector3::new(to.rx, to.ry, to.rz)),
+            t,
+        )
+        .to_euler();
+
+    CSixAxis {
+        x: position.x(),
+        y: position.y(),
+        z: position.z(),
+        rx: unwrap_angle(rotation.x(), from.rx),
+        ry: unwrap_angle(rotation.y(), from.ry),
+        rz: unwrap_angle(rotation.z(), from.rz),
+    }
+}
+
+/// Returns the `angle` shifted by whole turns to be nearest to the `reference`.
+fn unwrap_angle(angle: f64, reference: f64) -> f64 {
+    angle + TAU * ((reference - angle) / TAU).round()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::make_id;
+
+    fn snapshot(x: f64, rz: f64, t: f64) -> MicroscopeState {
+        MicroscopeState::new(
+            CSixAxis {
+                x,
+                y: 0.01,
+                z: 0.05,
+                rx: 0.0,
+                ry: 0.0,
+                rz,
+            },
+            vec![(make_id!(3), CLinearState { t })],
+        )
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn assert_states(expected: &MicroscopeState, actual: &MicroscopeState) {
+        let (e, a) = (expected.stage(), actual.stage());
+        for (e, a) in [
+            (e.x, a.x),
+            (e.y, a.y),
+            (e.z, a.z),
+            (e.rx, a.rx),
+            (e.ry, a.ry),
+            (e.rz, a.rz),
+        ] {
+            assert_close(e, a);
+        }
+
+        assert_eq!(expected.retracts().len(), actual.retracts().len());
+        for (id, state) in expected.retracts() {
+            assert_close(state.t, actual.retract(*id).unwrap().t);
+        }
+    }
+
+    #[test]
+    fn lerp_recovers_endpoints_and_midpoint() {
+        let a = snapshot(-0.02, 0.2, 0.0);
+        let b = snapshot(0.04, 1.4, 0.8);
+
+        assert_states(&a, &lerp_snapshot(&a, &b, 0.0));
+        assert_states(&b, &lerp_snapshot(&a, &b, 1.0));
+
+        let half = lerp_snapshot(&a, &b, 0.5);
+        assert_states(&snapshot(0.01, 0.8, 0.4), &half);
+
+        let rotation = Quaternion::from_euler(&Vector3::new(0.0, 0.0, 0.2))
+            .slerp(&Quaternion::from_euler(&Vector3::new(0.0, 0.0, 1.4)), 0.5)
+            .to_euler();
+        assert_close(rotation.z(), half.stage().rz);
+    }
+
+    #[test]
+    fn lerp_returns_exact_endpoints() {
+        let a = snapshot(0.1, 7.0, 0.3);
+        let b = snapshot(0.7, -4.0, 0.9);
+
+        let start = lerp_snapshot(&a, &b, 0.0);
+        assert_eq!(a.stage(), start.stage());
+        assert_eq!(0.3, start.retract(make_id!(3)).unwrap().t);
+
+        let end = lerp_snapshot(&a, &b, 1.0);
+        assert_eq!(b.stage(), end.stage());
+        assert_eq!(0.9, end.retract(make_id!(3)).unwrap().t);
+    }
+
+    #[test]
+    fn lerp_unwraps_angles_towards_start() {
+        let a = snapshot(0.0, 3.0, 0.0);
+        let b = snapshot(0.0, 3.4 - TAU, 0.0);
+        assert_close(3.2, lerp_snapshot(&a, &b, 0.5).stage().rz);
+
+        let a = snapshot(0.0, 7.0, 0.0);
+        let b = snapshot(0.0, 7.2, 0.0);
+        assert_close(7.1, lerp_snapshot(&a, &b, 0.5).stage().rz);
+    }
+
+    #[test]
+    fn lerp_keeps_retracts_missing_in_target() {
+        let a = snapshot(0.0, 0.0, 0.3);
+        let b = MicroscopeState::new(*snapshot(0.0, 0.0, 0.0).stage(), vec![]);
+
+        let half = lerp_snapshot(&a, &b, 0.5);
+        assert_eq!(0.3, half.retract(make_id!(3)).unwrap().t);
+        assert_eq!(None, half.retract(make_id!(4)));
+    }
+}
@@ -1,8 +1,11 @@
+use crate::ffi::opaque_ffi_for_type;
 use maths::Vector3;
 use models::position::linear::LinearState;
 use models::position::sixaxis::SixAxis;
+use paths::cancel::CancelToken;
 use paths::path::PathResult;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "ffi", repr(C))]
 pub struct CLinearState {
@@ -21,6 +24,7 @@ impl From<&LinearState> for CLinearState {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "ffi", repr(C))]
 pub struct CVector3 {
@@ -45,6 +49,16 @@ impl From<&Vector3> for CVector3 {
     }
 }
 
+/// Axis of the stage coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ffi", repr(u8))]
+pub enum CAxis {
+    X,
+    Y,
+    Z,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "ffi", repr(C))]
 pub struct CSixAxis {
@@ -84,6 +98,9 @@ pub enum PathResultState {
     InvalidStart,
     Path,
     UnreachableEnd,
+    Cancelled,
+    /// No part with the requested id is configured.
+    InvalidId,
 }
 
 macro_rules! path_result_impl {
@@ -150,6 +167,12 @@ macro_rules! path_result_impl {
             }
         }
 
+        impl $name {
+            pub fn state(&self) -> PathResultState {
+                self.state
+            }
+        }
+
         impl From<PathResult<$base>> for $name {
             fn from(value: PathResult<$base>) -> Self {
                 use paths::path::PathResult;
@@ -159,10 +182,13 @@ macro_rules! path_result_impl {
                     PathResult::InvalidStart(_) => PathResultState::InvalidStart,
                     PathResult::Path(_) => PathResultState::Path,
                     PathResult::UnreachableEnd(_) => PathResultState::UnreachableEnd,
+                    PathResult::Cancelled => PathResultState::Cancelled,
                 };
 
                 let nodes = match value {
-                    PathResult::InvalidStart(_) | PathResult::UnreachableEnd(None) => vec![],
+                    PathResult::InvalidStart(_)
+                    | PathResult::UnreachableEnd(None)
+                    | PathResult::Cancelled => vec![],
                     PathResult::Path(path) => path.iter().map($node::from).collect(),
                     PathResult::UnreachableEnd(Some(path)) => {
                         path.iter().map($node::from).collect()
@@ -188,6 +214,48 @@ path_result_impl!(
     LinearState
 );
 
+opaque_ffi_for_type!(pub, CCancelToken, CancelToken);
+
+#[cfg(feature = "ffi")]
+impl CCancelToken {
+    /// Create a token for cancelling a path query.
+    #[no_mangle]
+    pub extern "C" fn ccanceltoken_new() -> Self {
+        Self::from_inner(CancelToken::new())
+    }
+
+    /// Cancel every path query observing the token.
+    #[no_mangle]
+    pub extern "C" fn ccanceltoken_cancel(&self) {
+        self.inner().cancel();
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ccanceltoken_drop(self) {
+        // Dropped after going out of scope
+    }
+}
+
+#[cfg(not(feature = "ffi"))]
+impl Default for CCancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "ffi"))]
+impl CCancelToken {
+    /// Create a token for cancelling a path query.
+    pub fn new() -> Self {
+        Self::from_inner(CancelToken::new())
+    }
+
+    /// Cancel every path query observing the token.
+    pub fn cancel(&self) {
+        self.inner().cancel();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;